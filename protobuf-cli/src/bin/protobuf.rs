@@ -0,0 +1,111 @@
+extern crate protobuf_cli;
+
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::process;
+
+use protobuf_cli::ConvertError;
+use protobuf_cli::ConvertResult;
+use protobuf_cli::Converter;
+use protobuf_cli::StreamFormat;
+
+
+const USAGE: &'static str = "\
+usage: protobuf --descriptor-set FILE --type NAME [--from FORMAT] [--to FORMAT]
+
+Convert messages from stdin and write them to stdout.
+
+    --descriptor-set FILE  FileDescriptorSet, e. g. created by protoc --descriptor_set_out
+    --type NAME            message type name, e. g. foo.bar.Baz
+    --from FORMAT          input format, default is binary
+    --to FORMAT            output format, default is text
+
+Formats are binary, text and json.
+Formats with -delimited suffix (e. g. binary-delimited) are streams of messages
+prefixed with length.";
+
+struct Args {
+    descriptor_set: String,
+    type_name: String,
+    from: StreamFormat,
+    to: StreamFormat,
+}
+
+fn parse_format(name: &str) -> ConvertResult<StreamFormat> {
+    match StreamFormat::parse(name) {
+        Some(format) => Ok(format),
+        None => Err(ConvertError { message: format!("unknown format: {}", name) }),
+    }
+}
+
+fn parse_args(args: &[String]) -> ConvertResult<Args> {
+    let mut descriptor_set = None;
+    let mut type_name = None;
+    let mut from = parse_format("binary")?;
+    let mut to = parse_format("text")?;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let value = match iter.next() {
+            Some(value) => value,
+            None => return Err(ConvertError { message: format!("{} requires value", arg) }),
+        };
+        match &arg[..] {
+            "--descriptor-set" => descriptor_set = Some(value.clone()),
+            "--type" => type_name = Some(value.clone()),
+            "--from" => from = parse_format(value)?,
+            "--to" => to = parse_format(value)?,
+            _ => return Err(ConvertError { message: format!("unknown argument: {}", arg) }),
+        }
+    }
+
+    match (descriptor_set, type_name) {
+        (Some(descriptor_set), Some(type_name)) => Ok(Args {
+            descriptor_set: descriptor_set,
+            type_name: type_name,
+            from: from,
+            to: to,
+        }),
+        _ => Err(ConvertError { message: "--descriptor-set and --type are required".to_owned() }),
+    }
+}
+
+fn run(args: &Args) -> ConvertResult<()> {
+    let mut descriptor_set = Vec::new();
+    File::open(&args.descriptor_set)?.read_to_end(&mut descriptor_set)?;
+    let converter = Converter::from_descriptor_set_bytes(&descriptor_set, &args.type_name)?;
+
+    let mut input = Vec::new();
+    io::stdin().read_to_end(&mut input)?;
+
+    let output = converter.convert(args.from, args.to, &input)?;
+
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    stdout.write_all(&output)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() || args.iter().any(|a| a == "--help" || a == "-h") {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let args = match parse_args(&args) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = run(&args) {
+        eprintln!("protobuf: {}", e);
+        process::exit(1);
+    }
+}
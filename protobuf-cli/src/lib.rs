@@ -0,0 +1,409 @@
+//! Conversion of messages between binary, text and JSON formats
+//! using message types loaded at runtime from a `FileDescriptorSet`.
+//!
+//! This is the implementation of `protobuf` command.
+
+extern crate protobuf;
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use protobuf::CodedInputStream;
+use protobuf::CodedOutputStream;
+use protobuf::Message;
+use protobuf::ProtobufError;
+use protobuf::descriptor::FileDescriptorSet;
+use protobuf::descriptorx::RootScope;
+use protobuf::descriptorx::WithScope;
+use protobuf::json;
+use protobuf::reflect::MessageDescriptor;
+use protobuf::reflect::dynamic::DescriptorPool;
+use protobuf::reflect::dynamic::DescriptorPoolError;
+use protobuf::reflect::dynamic::DynamicMessage;
+use protobuf::text_format;
+
+
+/// Conversion error
+#[derive(Debug)]
+pub struct ConvertError {
+    pub message: String,
+}
+
+impl ConvertError {
+    fn new<S : Into<String>>(message: S) -> ConvertError {
+        ConvertError { message: message.into() }
+    }
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for ConvertError {
+    fn description(&self) -> &str {
+        &self.message
+    }
+}
+
+impl From<io::Error> for ConvertError {
+    fn from(e: io::Error) -> ConvertError {
+        ConvertError::new(e.to_string())
+    }
+}
+
+impl From<ProtobufError> for ConvertError {
+    fn from(e: ProtobufError) -> ConvertError {
+        ConvertError::new(e.to_string())
+    }
+}
+
+impl From<DescriptorPoolError> for ConvertError {
+    fn from(e: DescriptorPoolError) -> ConvertError {
+        ConvertError::new(e.to_string())
+    }
+}
+
+impl From<text_format::ParseError> for ConvertError {
+    fn from(e: text_format::ParseError) -> ConvertError {
+        ConvertError::new(format!("text format: {}", e))
+    }
+}
+
+impl From<json::ParseError> for ConvertError {
+    fn from(e: json::ParseError) -> ConvertError {
+        ConvertError::new(format!("JSON: {}", e))
+    }
+}
+
+impl From<json::PrintError> for ConvertError {
+    fn from(e: json::PrintError) -> ConvertError {
+        ConvertError::new(format!("JSON: {}", e))
+    }
+}
+
+pub type ConvertResult<T> = Result<T, ConvertError>;
+
+
+/// Encoding of a single message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Binary,
+    Text,
+    Json,
+}
+
+/// Encoding of a stream of messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamFormat {
+    pub format: Format,
+    /// Each message is prefixed with its length encoded as varint
+    pub delimited: bool,
+}
+
+impl StreamFormat {
+    /// Parse format name like `json` or `binary-delimited`.
+    pub fn parse(name: &str) -> Option<StreamFormat> {
+        let (name, delimited) = if name.ends_with("-delimited") {
+            (&name[..name.len() - "-delimited".len()], true)
+        } else {
+            (name, false)
+        };
+        let format = match name {
+            "binary" => Format::Binary,
+            "text" => Format::Text,
+            "json" => Format::Json,
+            _ => return None,
+        };
+        Some(StreamFormat {
+            format: format,
+            delimited: delimited,
+        })
+    }
+}
+
+
+/// Find fully-qualified name of the message type.
+///
+/// Name is either fully qualified (with or without leading dot),
+/// or an unambiguous suffix of fully-qualified name.
+pub fn find_message_name(root: &RootScope, name: &str) -> ConvertResult<String> {
+    let fqn = if name.starts_with(".") {
+        name.to_owned()
+    } else {
+        format!(".{}", name)
+    };
+    if let Some(m) = root.find_message_opt(&fqn) {
+        return Ok(m.name_absolute());
+    }
+
+    let candidates: Vec<String> = root.find_messages()
+        .iter()
+        .map(|m| m.name_absolute())
+        .filter(|n| n.ends_with(&fqn))
+        .collect();
+    match candidates.len() {
+        0 => Err(ConvertError::new(format!("message type not found: {}", name))),
+        1 => Ok(candidates.into_iter().next().unwrap()),
+        _ => Err(ConvertError::new(format!(
+            "ambiguous message type name: {}, candidates: {}",
+            name,
+            candidates.join(", ")
+        ))),
+    }
+}
+
+
+/// Converter of messages of given type.
+pub struct Converter {
+    descriptor: &'static MessageDescriptor,
+    // for `google.protobuf.Any` in JSON
+    all_descriptors: Vec<&'static MessageDescriptor>,
+}
+
+impl Converter {
+    /// Load types from descriptor set and find message type by name.
+    pub fn new(file_descriptor_set: FileDescriptorSet, name: &str) -> ConvertResult<Converter> {
+        let files = file_descriptor_set.file;
+
+        let (full_name, all_names) = {
+            let root = RootScope { file_descriptors: &files };
+            let full_name = find_message_name(&root, name)?;
+            let all_names: Vec<String> =
+                root.find_messages().iter().map(|m| m.name_absolute()).collect();
+            (full_name, all_names)
+        };
+
+        let pool = DescriptorPool::from_files(files)?;
+
+        // map entries are not message types in the pool
+        let all_descriptors = all_names.iter().filter_map(|n| pool.message_by_name(n)).collect();
+
+        Ok(Converter {
+            descriptor: pool.message_by_name(&full_name).expect("found in descriptors"),
+            all_descriptors: all_descriptors,
+        })
+    }
+
+    /// Load types from serialized descriptor set and find message type by name.
+    pub fn from_descriptor_set_bytes(bytes: &[u8], name: &str) -> ConvertResult<Converter> {
+        let file_descriptor_set = protobuf::parse_from_bytes::<FileDescriptorSet>(bytes)?;
+        Converter::new(file_descriptor_set, name)
+    }
+
+    /// Descriptor of the converted message type.
+    pub fn descriptor(&self) -> &'static MessageDescriptor {
+        self.descriptor
+    }
+
+    /// Parse a single message, all required fields must be set.
+    pub fn parse(&self, format: Format, data: &[u8]) -> ConvertResult<DynamicMessage> {
+        let mut m = DynamicMessage::new(self.descriptor);
+        match format {
+            Format::Binary => {
+                m.merge_from_bytes(data)?;
+            }
+            Format::Text => {
+                text_format::parse_from_str_into(&mut m, &utf8(data)?)?;
+            }
+            Format::Json => {
+                let options = json::ParseOptions {
+                    any_types: self.all_descriptors.clone(),
+                    ..Default::default()
+                };
+                json::merge_from_str_with_options(&mut m, &utf8(data)?, &options)?;
+            }
+        }
+        m.check_initialized()?;
+        Ok(m)
+    }
+
+    /// Print a single message.
+    pub fn print(&self, format: Format, m: &DynamicMessage) -> ConvertResult<Vec<u8>> {
+        Ok(match format {
+            Format::Binary => m.write_to_bytes()?,
+            Format::Text => text_format::print_to_string(m).into_bytes(),
+            Format::Json => {
+                let options = json::PrintOptions {
+                    any_types: self.all_descriptors.clone(),
+                    ..Default::default()
+                };
+                json::print_to_string_with_options(m, &options)?.into_bytes()
+            }
+        })
+    }
+
+    /// Parse a stream of messages.
+    ///
+    /// Non-delimited input is always a single message.
+    pub fn parse_stream(
+        &self,
+        format: StreamFormat,
+        data: &[u8],
+    ) -> ConvertResult<Vec<DynamicMessage>> {
+        if !format.delimited {
+            return Ok(vec![self.parse(format.format, data)?]);
+        }
+
+        let mut r = Vec::new();
+        let mut is = CodedInputStream::from_bytes(data);
+        while !is.eof()? {
+            let len = is.read_raw_varint32()?;
+            let bytes = is.read_raw_bytes(len)?;
+            r.push(self.parse(format.format, &bytes)?);
+        }
+        Ok(r)
+    }
+
+    /// Print a stream of messages.
+    ///
+    /// Several non-delimited text or JSON messages are separated by newlines,
+    /// binary format requires exactly one message.
+    pub fn print_stream(
+        &self,
+        format: StreamFormat,
+        messages: &[DynamicMessage],
+    ) -> ConvertResult<Vec<u8>> {
+        let mut r = Vec::new();
+        if !format.delimited && format.format == Format::Binary && messages.len() != 1 {
+            return Err(ConvertError::new(format!(
+                "cannot write {} messages in non-delimited binary format",
+                messages.len()
+            )));
+        }
+        for m in messages {
+            let bytes = self.print(format.format, m)?;
+            if format.delimited {
+                let mut os = CodedOutputStream::new(&mut r);
+                os.write_raw_varint32(bytes.len() as u32)?;
+                os.write_raw_bytes(&bytes)?;
+            } else {
+                r.extend(bytes);
+                if format.format != Format::Binary {
+                    r.push(b'\n');
+                }
+            }
+        }
+        Ok(r)
+    }
+
+    /// Convert messages from one format to another.
+    pub fn convert(
+        &self,
+        from: StreamFormat,
+        to: StreamFormat,
+        data: &[u8],
+    ) -> ConvertResult<Vec<u8>> {
+        let messages = self.parse_stream(from, data)?;
+        self.print_stream(to, &messages)
+    }
+}
+
+fn utf8(data: &[u8]) -> ConvertResult<String> {
+    match String::from_utf8(data.to_vec()) {
+        Ok(s) => Ok(s),
+        Err(..) => Err(ConvertError::new("input is not valid UTF-8")),
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use protobuf::descriptor;
+    use protobuf::descriptor::FileDescriptorSet;
+    use protobuf::descriptorx::RootScope;
+
+    use super::*;
+
+    fn descriptor_set() -> FileDescriptorSet {
+        let mut file_descriptor_set = FileDescriptorSet::new();
+        file_descriptor_set.mut_file().push(descriptor::file_descriptor_proto().clone());
+        file_descriptor_set
+    }
+
+    fn converter() -> Converter {
+        Converter::new(descriptor_set(), "google.protobuf.FieldDescriptorProto").unwrap()
+    }
+
+    fn format(name: &str) -> StreamFormat {
+        StreamFormat::parse(name).unwrap()
+    }
+
+    #[test]
+    fn test_stream_format_parse() {
+        assert_eq!(
+            Some(StreamFormat { format: Format::Json, delimited: true }),
+            StreamFormat::parse("json-delimited")
+        );
+        assert_eq!(
+            Some(StreamFormat { format: Format::Binary, delimited: false }),
+            StreamFormat::parse("binary")
+        );
+        assert_eq!(None, StreamFormat::parse("xml"));
+    }
+
+    #[test]
+    fn test_find_message_name() {
+        let files = descriptor_set().file;
+        let root = RootScope { file_descriptors: &files };
+        assert_eq!(
+            ".google.protobuf.FileDescriptorSet",
+            find_message_name(&root, "google.protobuf.FileDescriptorSet").unwrap()
+        );
+        assert_eq!(
+            ".google.protobuf.FileDescriptorSet",
+            find_message_name(&root, ".google.protobuf.FileDescriptorSet").unwrap()
+        );
+        assert_eq!(
+            ".google.protobuf.DescriptorProto.ExtensionRange",
+            find_message_name(&root, "ExtensionRange").unwrap()
+        );
+        assert!(find_message_name(&root, "NoSuchMessage").is_err());
+    }
+
+    #[test]
+    fn test_convert() {
+        let c = converter();
+        let text = "name: \"foo\" number: 3";
+        let binary = c.convert(format("text"), format("binary"), text.as_bytes()).unwrap();
+        assert_eq!(vec![0x0a, 0x03, b'f', b'o', b'o', 0x18, 0x03], binary);
+        let json = c.convert(format("binary"), format("json"), &binary).unwrap();
+        assert_eq!("{\"name\": \"foo\", \"number\": 3}\n", String::from_utf8(json).unwrap());
+        let text2 = c.convert(format("json"), format("text"), b"{\"name\": \"foo\", \"number\": 3}")
+            .unwrap();
+        assert_eq!("name: \"foo\" number: 3\n", String::from_utf8(text2).unwrap());
+    }
+
+    #[test]
+    fn test_convert_delimited() {
+        let c = converter();
+        let input = b"\x02\x18\x01\x02\x18\x02";
+        let text = c.convert(format("binary-delimited"), format("text"), input).unwrap();
+        assert_eq!("number: 1\nnumber: 2\n", String::from_utf8(text).unwrap());
+        let text_delimited =
+            c.convert(format("binary-delimited"), format("text-delimited"), input).unwrap();
+        assert_eq!(b"\x09number: 1\x09number: 2".to_vec(), text_delimited);
+        let binary =
+            c.convert(format("text-delimited"), format("binary-delimited"), &text_delimited)
+                .unwrap();
+        assert_eq!(input.to_vec(), binary);
+        assert!(c.convert(format("binary-delimited"), format("binary"), input).is_err());
+    }
+
+    #[test]
+    fn test_parse_checks_all_formats() {
+        let c = converter();
+        // singular field specified twice
+        assert!(c.parse(Format::Text, b"number: 1 number: 2").is_err());
+
+        let c = Converter::new(descriptor_set(), "google.protobuf.UninterpretedOption.NamePart")
+            .unwrap();
+        assert!(c.parse(Format::Text, b"name_part: \"a\" is_extension: true").is_ok());
+        // `is_extension` is required
+        assert!(c.parse(Format::Binary, b"\x0a\x01a").is_err());
+        assert!(c.parse(Format::Text, b"name_part: \"a\"").is_err());
+        assert!(c.parse(Format::Json, b"{\"namePart\": \"a\"}").is_err());
+    }
+}
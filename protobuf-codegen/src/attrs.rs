@@ -0,0 +1,79 @@
+//! Extra derives and attributes of generated types.
+//!
+//! Derives and attributes from file options (`rustproto.message_derive_all` etc.)
+//! are added to derives and attributes from message, enum or field options.
+
+use protobuf::descriptor::*;
+use protobuf::descriptorx::*;
+use protobuf::rustproto;
+
+use super::code_writer::CodeWriter;
+
+
+// append values which are not in `r` yet
+fn extend_unique(r: &mut Vec<String>, values: Vec<String>) {
+    for v in values {
+        if !r.contains(&v) {
+            r.push(v);
+        }
+    }
+}
+
+fn with_default(default: &[&str], file_values: Vec<String>, values: Vec<String>) -> Vec<String> {
+    let mut r = default.iter().map(|&s| s.to_owned()).collect();
+    extend_unique(&mut r, file_values);
+    extend_unique(&mut r, values);
+    r
+}
+
+/// `default` derives followed by extra derives of the message
+pub fn message_derive(message: &MessageWithScope, default: &[&str]) -> Vec<String> {
+    with_default(
+        default,
+        rustproto::exts::message_derive_all.get(message.get_file_descriptor().get_options()),
+        rustproto::exts::message_derive.get(message.message.get_options()),
+    )
+}
+
+/// Extra attributes of the message
+pub fn message_attrs(message: &MessageWithScope) -> Vec<String> {
+    with_default(
+        &[],
+        rustproto::exts::message_attr_all.get(message.get_file_descriptor().get_options()),
+        rustproto::exts::message_attr.get(message.message.get_options()),
+    )
+}
+
+/// `default` derives followed by extra derives of the enum
+pub fn enum_derive(en: &EnumWithScope, default: &[&str]) -> Vec<String> {
+    with_default(
+        default,
+        rustproto::exts::enum_derive_all.get(en.get_file_descriptor().get_options()),
+        rustproto::exts::enum_derive.get(en.en.get_options()),
+    )
+}
+
+/// Extra attributes of the enum
+pub fn enum_attrs(en: &EnumWithScope) -> Vec<String> {
+    with_default(
+        &[],
+        rustproto::exts::enum_attr_all.get(en.get_file_descriptor().get_options()),
+        rustproto::exts::enum_attr.get(en.en.get_options()),
+    )
+}
+
+/// Extra attributes of the struct field
+pub fn field_attrs(field: &FieldDescriptorProto) -> Vec<String> {
+    rustproto::exts::field_attr.get(field.get_options())
+}
+
+pub fn write_derive(w: &mut CodeWriter, derive: &[String]) {
+    let derive: Vec<&str> = derive.iter().map(|s| &s[..]).collect();
+    w.derive(&derive);
+}
+
+pub fn write_attrs(w: &mut CodeWriter, attrs: &[String]) {
+    for attr in attrs {
+        w.write_line(&format!("#[{}]", attr));
+    }
+}
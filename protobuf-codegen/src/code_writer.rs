@@ -6,9 +6,12 @@ use std::io::Write;
 // TODO: should not use wire_format here
 use protobuf::wire_format;
 
+use no_std::StdCrate;
+
 pub struct CodeWriter<'a> {
     writer: &'a mut (Write + 'a),
     indent: String,
+    std: StdCrate,
 }
 
 impl<'a> CodeWriter<'a> {
@@ -16,9 +19,15 @@ impl<'a> CodeWriter<'a> {
         CodeWriter {
             writer: writer,
             indent: "".to_string(),
+            std: StdCrate::Std,
         }
     }
 
+    /// Crate which standard library paths written by this writer start with
+    pub(crate) fn set_std(&mut self, std: StdCrate) {
+        self.std = std;
+    }
+
     pub fn write_line<S : AsRef<str>>(&mut self, line: S) {
         (if line.as_ref().is_empty() {
              self.writer.write_all("\n".as_bytes())
@@ -80,6 +89,7 @@ impl<'a> CodeWriter<'a> {
         cb(&mut CodeWriter {
             writer: self.writer,
             indent: format!("{}    ", self.indent),
+            std: self.std,
         });
     }
 
@@ -91,6 +101,7 @@ impl<'a> CodeWriter<'a> {
         cb(&mut CodeWriter {
             writer: self.writer,
             indent: format!("// {}", self.indent),
+            std: self.std,
         });
     }
 
@@ -361,7 +372,8 @@ impl<'a> CodeWriter<'a> {
 
     pub fn error_unexpected_wire_type(&mut self, wire_type: &str) {
         self.write_line(&format!(
-            "return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type({}));",
+            "return {}(::protobuf::rt::unexpected_wire_type({}));",
+            self.std.path("result::Result::Err"),
             wire_type
         ));
     }
@@ -4,19 +4,30 @@ use protobuf::descriptor::*;
 use protobuf::descriptorx::*;
 
 use super::code_writer::*;
+use super::serde::*;
+use super::naming::*;
+use super::rustdoc::*;
+use super::attrs::*;
+use super::no_std::StdCrate;
 
 
 #[derive(Clone)]
 pub struct EnumValueGen {
     proto: EnumValueDescriptorProto,
     enum_rust_name: String,
+    variant_name: String,
 }
 
 impl EnumValueGen {
-    fn parse(proto: &EnumValueDescriptorProto, enum_rust_name: &str) -> EnumValueGen {
+    fn parse(
+        en: &EnumWithScope,
+        proto: &EnumValueDescriptorProto,
+        enum_rust_name: &str,
+    ) -> EnumValueGen {
         EnumValueGen {
             proto: proto.clone(),
             enum_rust_name: enum_rust_name.to_string(),
+            variant_name: enum_value_name(en, proto),
         }
     }
 
@@ -27,7 +38,7 @@ impl EnumValueGen {
 
     // name of enum variant in generated rust code
     fn rust_name_inner(&self) -> String {
-        self.proto.rust_name()
+        self.variant_name.clone()
     }
 
     pub fn rust_name_outer(&self) -> String {
@@ -42,38 +53,32 @@ impl EnumValueGen {
 
 pub struct EnumGen<'a> {
     enum_with_scope: &'a EnumWithScope<'a>,
+    // name of the enum in the declaration, or path to it when the enum is referenced
     type_name: String,
     lite_runtime: bool,
+    serde_derive: bool,
+    std: StdCrate,
 }
 
 impl<'a> EnumGen<'a> {
-    pub fn new(
-        enum_with_scope: &'a EnumWithScope<'a>,
-        current_file: &FileDescriptorProto,
-    ) -> EnumGen<'a> {
-        let rust_name = if enum_with_scope.get_scope().get_file_descriptor().get_name() ==
-            current_file.get_name()
-        {
-            // field type is a message or enum declared in the same file
-            enum_with_scope.rust_name()
-        } else {
-            format!(
-                "super::{}::{}",
-                proto_path_to_rust_mod(
-                    enum_with_scope.get_scope().get_file_descriptor().get_name(),
-                ),
-                enum_with_scope.rust_name()
-            )
-        };
+    /// Generator for the enum declaration
+    pub fn new(enum_with_scope: &'a EnumWithScope<'a>) -> EnumGen<'a> {
+        EnumGen::with_type_name(enum_with_scope, type_decl_name(enum_with_scope))
+    }
+
+    /// Enum referenced by given path, e. g. from a field of another message
+    pub fn with_type_name(enum_with_scope: &'a EnumWithScope<'a>, type_name: String) -> EnumGen<'a> {
         EnumGen {
             enum_with_scope: enum_with_scope,
-            type_name: rust_name,
+            type_name: type_name,
             lite_runtime: enum_with_scope
                 .get_scope()
                 .get_file_descriptor()
                 .get_options()
                 .get_optimize_for() ==
                 FileOptions_OptimizeMode::LITE_RUNTIME,
+            serde_derive: serde_derive_enabled(&enum_with_scope.scope, None),
+            std: StdCrate::for_file(enum_with_scope.get_scope().get_file_descriptor()),
         }
     }
 
@@ -84,7 +89,7 @@ impl<'a> EnumGen<'a> {
     fn values_all(&self) -> Vec<EnumValueGen> {
         let mut r = Vec::new();
         for p in self.enum_with_scope.values() {
-            r.push(EnumValueGen::parse(p, &self.type_name));
+            r.push(EnumValueGen::parse(self.enum_with_scope, p, &self.type_name));
         }
         r
     }
@@ -98,14 +103,18 @@ impl<'a> EnumGen<'a> {
             if !used.insert(p.get_number()) {
                 continue;
             }
-            r.push(EnumValueGen::parse(p, &self.type_name));
+            r.push(EnumValueGen::parse(self.enum_with_scope, p, &self.type_name));
         }
         r
     }
 
     // find enum value by name
     pub fn value_by_name(&'a self, name: &str) -> EnumValueGen {
-        EnumValueGen::parse(self.enum_with_scope.value_by_name(name), &self.type_name)
+        EnumValueGen::parse(
+            self.enum_with_scope,
+            self.enum_with_scope.value_by_name(name),
+            &self.type_name,
+        )
     }
 
     pub fn write(&self, w: &mut CodeWriter) {
@@ -129,6 +138,8 @@ impl<'a> EnumGen<'a> {
     }
 
     fn write_struct(&self, w: &mut CodeWriter) {
+        let file = self.enum_with_scope.get_scope().get_file_descriptor();
+        write_doc_comment(w, file, &enum_path(self.enum_with_scope));
         let mut derive = Vec::new();
         derive.push("Clone");
         if !self.allow_alias() {
@@ -143,10 +154,15 @@ impl<'a> EnumGen<'a> {
                 "Note: you cannot use pattern matching for enums with allow_alias option",
             );
         }
-        w.derive(&derive);
+        write_derive(w, &enum_derive(self.enum_with_scope, &derive));
+        if self.serde_derive {
+            write_serde_derive(w);
+        }
+        write_attrs(w, &enum_attrs(self.enum_with_scope));
         let ref type_name = self.type_name;
         w.expr_block(&format!("pub enum {}", type_name), |w| {
-            for value in self.values_all() {
+            for (i, value) in self.values_all().into_iter().enumerate() {
+                write_doc_comment(w, file, &enum_value_path(self.enum_with_scope, i));
                 if self.allow_alias() {
                     w.write_line(&format!(
                         "{}, // {}",
@@ -181,14 +197,14 @@ impl<'a> EnumGen<'a> {
 
             w.write_line("");
             let ref type_name = self.type_name;
-            w.def_fn(&format!("from_i32(value: i32) -> ::std::option::Option<{}>", type_name), |w| {
+            w.def_fn(&format!("from_i32(value: i32) -> {}<{}>", self.std.path("option::Option"), type_name), |w| {
                 w.match_expr("value", |w| {
                     let values = self.values_unique();
                     for value in values {
-                        w.write_line(&format!("{} => ::std::option::Option::Some({}),",
-                            value.number(), value.rust_name_outer()));
+                        w.write_line(&format!("{} => {}({}),",
+                            value.number(), self.std.path("option::Option::Some"), value.rust_name_outer()));
                     }
-                    w.write_line(&format!("_ => ::std::option::Option::None"));
+                    w.write_line(&format!("_ => {}", self.std.path("option::Option::None")));
                 });
             });
 
@@ -207,10 +223,11 @@ impl<'a> EnumGen<'a> {
             if !self.lite_runtime {
                 w.write_line("");
                 let ref type_name = self.type_name;
-                w.def_fn(&format!("enum_descriptor_static(_: ::std::option::Option<{}>) -> &'static ::protobuf::reflect::EnumDescriptor", type_name), |w| {
+                w.def_fn(&format!("enum_descriptor_static(_: {}<{}>) -> &'static ::protobuf::reflect::EnumDescriptor", self.std.path("option::Option"), type_name), |w| {
                     w.lazy_static_decl_get("descriptor", "::protobuf::reflect::EnumDescriptor", |w| {
-                        let ref type_name = self.type_name;
-                        w.write_line(&format!("::protobuf::reflect::EnumDescriptor::new(\"{}\", file_descriptor_proto())", type_name));
+                        // descriptor is looked up by legacy name regardless of naming mode
+                        let rust_name = self.enum_with_scope.rust_name();
+                        w.write_line(&format!("::protobuf::reflect::EnumDescriptor::new(\"{}\", file_descriptor_proto())", rust_name));
                     });
                 });
             }
@@ -226,17 +243,23 @@ impl<'a> EnumGen<'a> {
                         "::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())",
                     )
                 },
-            )
+            );
+            w.write_line("");
+            w.def_fn(
+                &format!("from_value_box(value: ::protobuf::reflect::ProtobufValueBox) -> {}<Self>",
+                    self.std.path("option::Option")),
+                |w| w.write_line("value.into_enum()"),
+            );
         })
     }
 
     fn write_impl_copy(&self, w: &mut CodeWriter) {
-        w.impl_for_block("::std::marker::Copy", &self.type_name, |_w| {});
+        w.impl_for_block(&self.std.path("marker::Copy"), &self.type_name, |_w| {});
     }
 
     fn write_impl_eq(&self, w: &mut CodeWriter) {
         assert!(self.allow_alias());
-        w.impl_for_block("::std::cmp::PartialEq", &self.type_name, |w| {
+        w.impl_for_block(&self.std.path("cmp::PartialEq"), &self.type_name, |w| {
             w.def_fn("eq(&self, other: &Self) -> bool", |w| {
                 w.write_line("self.value() == other.value()");
             });
@@ -245,8 +268,8 @@ impl<'a> EnumGen<'a> {
 
     fn write_impl_hash(&self, w: &mut CodeWriter) {
         assert!(self.allow_alias());
-        w.impl_for_block("::std::hash::Hash", &self.type_name, |w| {
-            w.def_fn("hash<H : ::std::hash::Hasher>(&self, state: &mut H)", |w| {
+        w.impl_for_block(&self.std.path("hash::Hash"), &self.type_name, |w| {
+            w.def_fn(&format!("hash<H : {}>(&self, state: &mut H)", self.std.path("hash::Hasher")), |w| {
                 w.write_line("state.write_i32(self.value())");
             });
         });
@@ -254,12 +277,12 @@ impl<'a> EnumGen<'a> {
 
     fn write_impl_default(&self, w: &mut CodeWriter) {
         assert!(self.enum_with_scope.scope.file_scope.syntax() == Syntax::PROTO3);
-        w.impl_for_block("::std::default::Default", &self.type_name, |w| {
+        w.impl_for_block(&self.std.path("default::Default"), &self.type_name, |w| {
             w.def_fn("default() -> Self", |w| {
                 w.write_line(&format!(
                     "{}::{}",
                     &self.type_name,
-                    &self.enum_with_scope.values()[0].rust_name()
+                    &enum_value_name(self.enum_with_scope, &self.enum_with_scope.values()[0])
                 ))
             });
         });
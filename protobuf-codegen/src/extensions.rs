@@ -2,6 +2,7 @@ use protobuf::descriptor::*;
 use protobuf::descriptorx::*;
 use super::code_writer::CodeWriter;
 use super::rust_types_values::*;
+use super::no_std::StdCrate;
 
 
 struct ExtGen<'a> {
@@ -12,7 +13,7 @@ struct ExtGen<'a> {
 
 impl<'a> ExtGen<'a> {
     fn extendee_rust_name(&self) -> String {
-        type_name_to_rust_relative(self.field.get_extendee(), self.file, true, self.root_scope)
+        type_name_to_rust_relative(self.field.get_extendee(), self.file, 1, self.root_scope)
     }
 
     fn repeated(&self) -> bool {
@@ -30,7 +31,7 @@ impl<'a> ExtGen<'a> {
             let rust_name_relative = type_name_to_rust_relative(
                 self.field.get_type_name(),
                 self.file,
-                true,
+                1,
                 self.root_scope,
             );
             match self.field.get_field_type() {
@@ -61,9 +62,10 @@ impl<'a> ExtGen<'a> {
                 self.return_type_gen().rust_type()
             ),
             &format!(
-                "{} {{ field_number: {}, phantom: ::std::marker::PhantomData }}",
+                "{} {{ field_number: {}, phantom: {} }}",
                 field_type,
-                self.field.get_number()
+                self.field.get_number(),
+                StdCrate::for_file(self.file).path("marker::PhantomData")
             ),
         );
     }
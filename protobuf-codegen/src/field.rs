@@ -2,20 +2,26 @@ use std::borrow::Cow;
 
 use protobuf::descriptor::*;
 use protobuf::descriptorx::*;
-// use protobuf::rustproto; // TODO: should probably live here
+use protobuf::rustproto;
 use protobuf::wire_format;
 use protobuf::rt;
 use protobuf::rust;
 use protobuf::text_format;
-// use protobuf::types::ProtobufType;
-// use protobuf::reflect::ProtobufValue;
+use protobuf::types::ProtobufType;
 
-// use protobuf::ext::ExtFieldOptional;
+use protobuf::ext::ExtFieldOptional;
 
 use super::message::*;
 use super::rust_types_values::*;
 use super::enums::*;
 use super::code_writer::CodeWriter;
+use super::well_known_types::wrapper_value_type;
+use super::serde::write_serde_derive;
+use super::naming::*;
+use super::rustdoc::*;
+use super::attrs::*;
+use super::no_std::no_std;
+use super::no_std::StdCrate;
 
 
 
@@ -29,15 +35,10 @@ fn type_is_copy(field_type: FieldDescriptorProto_Type) -> bool {
 }
 
 trait FieldDescriptorProtoTypeExt {
-    fn read(&self, is: &str) -> String;
     fn is_s_varint(&self) -> bool;
 }
 
 impl FieldDescriptorProtoTypeExt for FieldDescriptorProto_Type {
-    fn read(&self, is: &str) -> String {
-        format!("{}.read_{}()", is, protobuf_name(*self))
-    }
-
     /// True if self is signed integer with zigzag encoding
     fn is_s_varint(&self) -> bool {
         match *self {
@@ -68,7 +69,7 @@ fn field_type_wire_type(field_type: FieldDescriptorProto_Type) -> wire_format::W
         FieldDescriptorProto_Type::TYPE_STRING => WireTypeLengthDelimited,
         FieldDescriptorProto_Type::TYPE_BYTES => WireTypeLengthDelimited,
         FieldDescriptorProto_Type::TYPE_MESSAGE => WireTypeLengthDelimited,
-        FieldDescriptorProto_Type::TYPE_GROUP => WireTypeLengthDelimited, // not true
+        FieldDescriptorProto_Type::TYPE_GROUP => WireTypeStartGroup,
     }
 }
 
@@ -122,16 +123,14 @@ pub enum GenProtobufType {
     Primitive(FieldDescriptorProto_Type, PrimitiveTypeVariant),
     // name, file name
     Message(String, String),
-    // name, file name, default value
-    Enum(String, String, String),
-    Group,
+    // name, file name, default value, open (proto3) enum
+    Enum(String, String, String, bool),
 }
 
 impl GenProtobufType {
     fn proto_type(&self) -> FieldDescriptorProto_Type {
         match *self {
             GenProtobufType::Primitive(t, ..) => t,
-            GenProtobufType::Group => FieldDescriptorProto_Type::TYPE_GROUP,
             GenProtobufType::Message(..) => FieldDescriptorProto_Type::TYPE_MESSAGE,
             GenProtobufType::Enum(..) => FieldDescriptorProto_Type::TYPE_ENUM,
         }
@@ -141,6 +140,14 @@ impl GenProtobufType {
         type_is_copy(self.proto_type())
     }
 
+    /// Open enum, unknown values are stored in the field
+    fn is_enum_or_unknown(&self) -> bool {
+        match *self {
+            GenProtobufType::Enum(_, _, _, open) => open,
+            _ => false,
+        }
+    }
+
     pub fn rust_type(&self) -> RustType {
         match *self {
             GenProtobufType::Primitive(t, PrimitiveTypeVariant::Default) => rust_name(t),
@@ -153,11 +160,13 @@ impl GenProtobufType {
                 PrimitiveTypeVariant::Carllerche,
             ) => RustType::Bytes,
             GenProtobufType::Primitive(.., PrimitiveTypeVariant::Carllerche) => unreachable!(),
-            GenProtobufType::Group => RustType::Group,
             GenProtobufType::Message(ref name, _) => RustType::Message(name.clone()),
-            GenProtobufType::Enum(ref name, _, ref default_value) => {
+            GenProtobufType::Enum(ref name, _, ref default_value, false) => {
                 RustType::Enum(name.clone(), default_value.clone())
             }
+            GenProtobufType::Enum(ref name, _, ref default_value, true) => {
+                RustType::EnumOrUnknown(name.clone(), default_value.clone())
+            }
         }
     }
 
@@ -165,8 +174,31 @@ impl GenProtobufType {
         match *self {
             GenProtobufType::Primitive(t, v) => ProtobufTypeGen::Primitive(t, v),
             GenProtobufType::Message(ref name, ..) => ProtobufTypeGen::Message(name.clone()),
-            GenProtobufType::Enum(ref name, ..) => ProtobufTypeGen::Enum(name.clone()),
-            GenProtobufType::Group => unreachable!(),
+            GenProtobufType::Enum(ref name, _, _, false) => ProtobufTypeGen::Enum(name.clone()),
+            GenProtobufType::Enum(ref name, _, _, true) => {
+                ProtobufTypeGen::EnumOrUnknown(name.clone())
+            }
+        }
+    }
+
+    /// Same type referenced from a module nested in the current module
+    fn from_submodule(&self) -> GenProtobufType {
+        fn relative(name: &str) -> String {
+            if name.starts_with("::") {
+                name.to_owned()
+            } else {
+                format!("super::{}", name)
+            }
+        }
+
+        match *self {
+            GenProtobufType::Primitive(..) => self.clone(),
+            GenProtobufType::Message(ref name, ref file_name) => {
+                GenProtobufType::Message(relative(name), file_name.clone())
+            }
+            GenProtobufType::Enum(ref name, ref file_name, ref default_value, open) => {
+                GenProtobufType::Enum(relative(name), file_name.clone(), default_value.clone(), open)
+            }
         }
     }
 
@@ -204,11 +236,27 @@ impl SingularFieldFlag {
 pub struct SingularField {
     pub flag: SingularFieldFlag,
     pub elem: GenProtobufType,
+    // wrapper well-known type (e. g. `Int32Value`) stored as `Option` of the wrapped value,
+    // `elem` is the type of the wrapped value
+    pub wrapper: Option<String>,
+    // stored as `Option<T>` or `Option<Box<T>>` instead of `SingularField` or `SingularPtrField`
+    pub option: bool,
 }
 
 impl SingularField {
     fn rust_type(&self) -> RustType {
         match self.flag {
+            SingularFieldFlag::WithFlag { .. } if self.wrapper.is_some() => {
+                RustType::Option(Box::new(self.elem.rust_type()))
+            }
+            SingularFieldFlag::WithFlag { .. } if self.option => {
+                match self.elem.proto_type() {
+                    FieldDescriptorProto_Type::TYPE_MESSAGE => RustType::Option(
+                        Box::new(RustType::Uniq(Box::new(self.elem.rust_type()))),
+                    ),
+                    _ => RustType::Option(Box::new(self.elem.rust_type())),
+                }
+            }
             SingularFieldFlag::WithFlag { .. } => {
                 match self.elem.proto_type() {
                     FieldDescriptorProto_Type::TYPE_MESSAGE => RustType::SingularPtrField(
@@ -239,16 +287,12 @@ pub struct OneofField {
 impl OneofField {
     fn parse(
         oneof: &OneofWithContext,
-        _field: &FieldDescriptorProto,
+        field: &FieldDescriptorProto,
         elem: GenProtobufType,
     ) -> OneofField {
         // detecting recursion
-        let boxed = if let &GenProtobufType::Message(ref name, ..) = &elem {
-            if *name == oneof.message.rust_name() {
-                true
-            } else {
-                false
-            }
+        let boxed = if let &GenProtobufType::Message(..) = &elem {
+            field.get_type_name() == oneof.message.name_absolute()
         } else {
             false
         };
@@ -256,7 +300,7 @@ impl OneofField {
         OneofField {
             elem: elem,
             oneof_name: oneof.name().to_string(),
-            oneof_type_name: RustType::Oneof(oneof.rust_name()),
+            oneof_type_name: RustType::Oneof(oneof_path_from_message(oneof)),
             boxed: boxed,
         }
     }
@@ -295,6 +339,15 @@ pub struct MapField {
     name: String,
     key: GenProtobufType,
     value: GenProtobufType,
+    // stored in `BTreeMap` instead of `HashMap`
+    btree: bool,
+}
+
+impl MapField {
+    // `map` or `btree_map`, part of names of runtime functions for the map type
+    fn rt_fn_name_part(&self) -> &'static str {
+        if self.btree { "btree_map" } else { "map" }
+    }
 }
 
 #[derive(Clone)]
@@ -313,9 +366,8 @@ enum FieldElem {
     Primitive(FieldDescriptorProto_Type, PrimitiveTypeVariant),
     // name, file name, entry
     Message(String, String, Option<Box<EntryKeyValue>>),
-    // name, file name, default value
-    Enum(String, String, String),
-    Group,
+    // name, file name, default value, open (proto3) enum
+    Enum(String, String, String, bool),
 }
 
 impl FieldElem {
@@ -327,21 +379,21 @@ impl FieldElem {
             FieldElem::Message(name, file_name, Some(..)) => {
                 GenProtobufType::Message(name, file_name)
             }
-            FieldElem::Enum(name, file_name, default_value) => {
-                GenProtobufType::Enum(name, file_name, default_value)
+            FieldElem::Enum(name, file_name, default_value, open) => {
+                GenProtobufType::Enum(name, file_name, default_value, open)
             }
-            FieldElem::Group => GenProtobufType::Group,
         }
     }
 }
 
-/*
-fn join_field_ext<A : ProtobufValue + Clone, T : ProtobufType<Value = A>>(
+/// Option value from field options, or from options of containing messages,
+/// or from file options.
+fn join_field_ext<T : ProtobufType>(
     source: &FieldWithContext,
     field_ext: ExtFieldOptional<FieldOptions, T>,
     message_ext: ExtFieldOptional<MessageOptions, T>,
     file_ext: ExtFieldOptional<FileOptions, T>,
-) -> Option<A> {
+) -> Option<T::Value> {
     if let Some(v) = field_ext.get(source.field.get_options()) {
         return Some(v);
     }
@@ -350,18 +402,17 @@ fn join_field_ext<A : ProtobufValue + Clone, T : ProtobufType<Value = A>>(
             return Some(v);
         }
     }
-    return file_ext.get(source.message.scope.get_file_descriptor().get_options());
+    file_ext.get(source.message.scope.get_file_descriptor().get_options())
 }
-*/
 
+/// Field element type, `depth` is depth of the module where the field type is referenced
 fn field_elem(
     field: &FieldWithContext,
     root_scope: &RootScope,
     parse_map: bool,
+    depth: usize,
 ) -> (FieldElem, Option<EnumValueGen>) {
-    if field.field.get_field_type() == FieldDescriptorProto_Type::TYPE_GROUP {
-        (FieldElem::Group, None)
-    } else if field.field.has_type_name() {
+    if field.field.has_type_name() {
         let message_or_enum = root_scope.find_message_or_enum(field.field.get_type_name());
         let file_name = message_or_enum
             .get_scope()
@@ -372,20 +423,24 @@ fn field_elem(
         let rust_relative_name = type_name_to_rust_relative(
             field.field.get_type_name(),
             field.message.get_scope().file_scope.file_descriptor,
-            false,
+            depth,
             root_scope,
         );
         match (field.field.get_field_type(), message_or_enum) {
             (
                 FieldDescriptorProto_Type::TYPE_MESSAGE,
                 MessageOrEnumWithScope::Message(message_with_scope),
+            ) |
+            (
+                FieldDescriptorProto_Type::TYPE_GROUP,
+                MessageOrEnumWithScope::Message(message_with_scope),
             ) => {
                 let entry_key_value = if let (true, Some((key, value))) =
                     (parse_map, message_with_scope.map_entry())
                 {
                     Some(Box::new(EntryKeyValue(
-                        field_elem(&key, root_scope, false).0.into_type(),
-                        field_elem(&value, root_scope, false).0.into_type(),
+                        field_elem(&key, root_scope, false, depth).0.into_type(),
+                        field_elem(&value, root_scope, false, depth).0.into_type(),
                     )))
                 } else {
                     None
@@ -399,10 +454,7 @@ fn field_elem(
                 FieldDescriptorProto_Type::TYPE_ENUM,
                 MessageOrEnumWithScope::Enum(enum_with_scope),
             ) => {
-                let e = EnumGen::new(
-                    &enum_with_scope,
-                    field.message.get_scope().get_file_descriptor(),
-                );
+                let e = EnumGen::with_type_name(&enum_with_scope, rust_relative_name.clone());
                 let ev = if field.field.has_default_value() {
                     e.value_by_name(field.field.get_default_value()).clone()
                 } else {
@@ -412,7 +464,8 @@ fn field_elem(
                     FieldElem::Enum(
                         rust_relative_name,
                         file_name,
-                        enum_with_scope.values()[0].get_name().to_owned(),
+                        enum_value_name(&enum_with_scope, &enum_with_scope.values()[0]),
+                        enum_with_scope.get_scope().file_scope.syntax() == Syntax::PROTO3,
                     ),
                     Some(ev),
                 )
@@ -420,18 +473,18 @@ fn field_elem(
             _ => panic!("unknown named type: {:?}", field.field.get_field_type()),
         }
     } else if field.field.has_field_type() {
-        let carllerche_for_bytes = false; /* join_field_ext(
+        let carllerche_for_bytes = join_field_ext(
             field,
             rustproto::exts::carllerche_bytes_for_bytes_field,
             rustproto::exts::carllerche_bytes_for_bytes,
             rustproto::exts::carllerche_bytes_for_bytes_all,
-        ).unwrap_or(false); */
-        let carllerche_for_string = false; /* join_field_ext(
+        ).unwrap_or(false);
+        let carllerche_for_string = join_field_ext(
             field,
             rustproto::exts::carllerche_bytes_for_string_field,
             rustproto::exts::carllerche_bytes_for_string,
             rustproto::exts::carllerche_bytes_for_string_all,
-        ).unwrap_or(false); */
+        ).unwrap_or(false);
 
         let elem = match field.field.get_field_type() {
             FieldDescriptorProto_Type::TYPE_STRING if carllerche_for_string => {
@@ -485,15 +538,36 @@ pub struct FieldGen<'a> {
     pub proto_field: FieldWithContext<'a>,
     // field name in generated code
     pub rust_name: String,
+    // `TYPE_MESSAGE` for groups
     pub proto_type: FieldDescriptorProto_Type,
+    // group is a message encoded with start and end group tags
+    group: bool,
     wire_type: wire_format::WireType,
     enum_default_value: Option<EnumValueGen>,
     pub kind: FieldKind,
+    // generate `with_xxx` setters
+    with_methods: bool,
+    std: StdCrate,
 }
 
 impl<'a> FieldGen<'a> {
     pub fn parse(field: FieldWithContext<'a>, root_scope: &'a RootScope<'a>) -> FieldGen<'a> {
-        let (elem, enum_default_value) = field_elem(&field, root_scope, true);
+        let depth = scope_depth(&field.message.scope);
+        let (elem, enum_default_value) = field_elem(&field, root_scope, true, depth);
+
+        let wrappers_as_option = join_field_ext(
+            &field,
+            rustproto::exts::wrappers_as_option_field,
+            rustproto::exts::wrappers_as_option,
+            rustproto::exts::wrappers_as_option_all,
+        ).unwrap_or(false);
+
+        let singular_field_option = join_field_ext(
+            &field,
+            rustproto::exts::singular_field_option_field,
+            rustproto::exts::singular_field_option,
+            rustproto::exts::singular_field_option_all,
+        ).unwrap_or(false);
 
         let kind = if field.field.get_label() == FieldDescriptorProto_Label::LABEL_REPEATED {
             match (elem, true) {
@@ -502,6 +576,7 @@ impl<'a> FieldGen<'a> {
                     name: name,
                     key: key_value.0.clone(),
                     value: key_value.1.clone(),
+                    btree: no_std(field.message.get_scope().get_file_descriptor()),
                 }),
                 // regular repeated field
                 (elem, _) => FieldKind::Repeated(RepeatedField {
@@ -521,21 +596,62 @@ impl<'a> FieldGen<'a> {
                     required: field.field.get_label() == FieldDescriptorProto_Label::LABEL_REQUIRED,
                 }
             };
-            FieldKind::Singular(SingularField {
-                elem: elem.into_type(),
-                flag: flag,
-            })
+            let value_type = match field.field.get_field_type() {
+                FieldDescriptorProto_Type::TYPE_MESSAGE if wrappers_as_option => {
+                    wrapper_value_type(field.field.get_type_name())
+                }
+                _ => None,
+            };
+            match (elem, value_type) {
+                (FieldElem::Message(name, ..), Some(value_type)) => {
+                    FieldKind::Singular(SingularField {
+                        elem: GenProtobufType::Primitive(
+                            value_type,
+                            PrimitiveTypeVariant::Default,
+                        ),
+                        flag: flag,
+                        wrapper: Some(name),
+                        option: singular_field_option,
+                    })
+                }
+                (elem, _) => FieldKind::Singular(SingularField {
+                    elem: elem.into_type(),
+                    flag: flag,
+                    wrapper: None,
+                    option: singular_field_option,
+                }),
+            }
         };
 
+        let proto_type = match kind {
+            // wrapper is generated as its value
+            FieldKind::Singular(SingularField { wrapper: Some(..), ref elem, .. }) => {
+                elem.proto_type()
+            }
+            _ => match field.field.get_field_type() {
+                FieldDescriptorProto_Type::TYPE_GROUP => FieldDescriptorProto_Type::TYPE_MESSAGE,
+                t => t,
+            },
+        };
+
+        let with_methods = rustproto::exts::with_methods_all
+            .get(field.message.get_scope().get_file_descriptor().get_options())
+            .unwrap_or(false);
+
+        let std = StdCrate::for_file(field.message.get_scope().get_file_descriptor());
+
         FieldGen {
             root_scope: root_scope,
             syntax: field.message.get_scope().file_scope.syntax(),
             rust_name: field.rust_name(),
-            proto_type: field.field.get_field_type(),
+            proto_type: proto_type,
+            group: field.field.get_field_type() == FieldDescriptorProto_Type::TYPE_GROUP,
             wire_type: field_type_wire_type(field.field.get_field_type()),
             enum_default_value: enum_default_value,
             proto_field: field,
             kind: kind,
+            with_methods: with_methods,
+            std: std,
         }
     }
 
@@ -601,9 +717,30 @@ impl<'a> FieldGen<'a> {
         }
     }
 
+    // name of wrapper well-known type if the field is stored as `Option` of wrapped value
+    fn wrapper(&self) -> Option<&str> {
+        match self.kind {
+            FieldKind::Singular(SingularField { wrapper: Some(ref wrapper), .. }) => Some(wrapper),
+            _ => None,
+        }
+    }
+
+    // message field stored as `Option<Box<M>>`
+    fn is_option_boxed(&self) -> bool {
+        match self.kind {
+            FieldKind::Singular(SingularField {
+                flag: SingularFieldFlag::WithFlag { .. },
+                option: true,
+                wrapper: None,
+                ..
+            }) => self.proto_type == FieldDescriptorProto_Type::TYPE_MESSAGE,
+            _ => false,
+        }
+    }
+
     pub fn variant_path(&self) -> String {
         // TODO: should reuse code from OneofVariantGen
-        format!("{}::{}", self.oneof().oneof_type_name, self.rust_name)
+        format!("{}::{}", self.oneof().oneof_type_name.to_code(self.std), self.rust_name)
     }
 
     // TODO: drop it
@@ -620,9 +757,12 @@ impl<'a> FieldGen<'a> {
     pub fn full_storage_type(&self) -> RustType {
         match self.kind {
             FieldKind::Repeated(ref repeated) => repeated.rust_type(),
-            FieldKind::Map(MapField { ref key, ref value, .. }) => {
+            FieldKind::Map(MapField { ref key, ref value, btree: false, .. }) => {
                 RustType::HashMap(Box::new(key.rust_type()), Box::new(value.rust_type()))
             }
+            FieldKind::Map(MapField { ref key, ref value, btree: true, .. }) => {
+                RustType::BTreeMap(Box::new(key.rust_type()), Box::new(value.rust_type()))
+            }
             FieldKind::Singular(ref singular) => singular.rust_type(),
             FieldKind::Oneof(..) => unreachable!(),
         }
@@ -717,6 +857,36 @@ impl<'a> FieldGen<'a> {
         }
     }
 
+    // proto3 enum, stored as `ProtobufEnumOrUnknown`
+    fn is_enum_or_unknown(&self) -> bool {
+        self.is_enum() && self.elem().is_enum_or_unknown()
+    }
+
+    // proto2 enum, unknown values are stored in unknown fields
+    fn is_closed_enum(&self) -> bool {
+        self.is_enum() && !self.elem().is_enum_or_unknown()
+    }
+
+    // suffix of `read_xxx` functions in `CodedInputStream` and `rt`
+    fn read_fn_suffix(&self) -> &'static str {
+        if self.is_enum_or_unknown() {
+            "enum_or_unknown"
+        } else {
+            protobuf_name(self.proto_type)
+        }
+    }
+
+    // expression reading singular value from `CodedInputStream`
+    fn read_proc(&self, is: &str) -> String {
+        if let Some(wrapper) = self.wrapper() {
+            format!("::protobuf::rt::read_wrapper::<{}>({})?", wrapper, is)
+        } else if self.group {
+            format!("{}.read_group({})?", is, self.proto_field.number())
+        } else {
+            format!("{}.read_{}()?", is, self.read_fn_suffix())
+        }
+    }
+
     // elem data is not stored in heap
     pub fn elem_type_is_copy(&self) -> bool {
         type_is_copy(self.proto_type)
@@ -743,7 +913,7 @@ impl<'a> FieldGen<'a> {
         }
 
         match parse_special_float(proto_default) {
-            Some(special) => format!("::std::{}::{}", type_name, special),
+            Some(special) => self.std.path(&format!("{}::{}", type_name, special)),
             // hope it is decimal float
             None => format!("{}{}", proto_default, type_name),
         }
@@ -752,7 +922,12 @@ impl<'a> FieldGen<'a> {
     fn default_value_from_proto(&self) -> Option<String> {
         assert!(self.is_singular() || self.is_oneof());
         if self.enum_default_value.is_some() {
-            Some(self.enum_default_value.as_ref().unwrap().rust_name_outer())
+            let default_value = self.enum_default_value.as_ref().unwrap().rust_name_outer();
+            if self.is_enum_or_unknown() {
+                Some(format!("::protobuf::ProtobufEnumOrUnknown::new({})", default_value))
+            } else {
+                Some(default_value)
+            }
         } else if self.proto_field.field.has_default_value() {
             let proto_default = self.proto_field.field.get_default_value();
             Some(match self.proto_type {
@@ -814,7 +989,7 @@ impl<'a> FieldGen<'a> {
     fn get_xxx_default_value_rust(&self) -> String {
         assert!(self.is_singular() || self.is_oneof());
         self.default_value_from_proto()
-            .unwrap_or_else(|| self.get_xxx_return_type().default_value())
+            .unwrap_or_else(|| self.get_xxx_return_type().default_value(self.std))
     }
 
     // default to be assigned to field
@@ -825,7 +1000,7 @@ impl<'a> FieldGen<'a> {
             self.reconstruct_def()
         );
         self.default_value_from_proto_typed()
-            .unwrap_or_else(|| self.elem().rust_type().default_value_typed())
+            .unwrap_or_else(|| self.elem().rust_type().default_value_typed(self.std))
     }
 
     pub fn reconstruct_def(&self) -> String {
@@ -863,10 +1038,21 @@ impl<'a> FieldGen<'a> {
                     ],
                 }
             }
-            FieldKind::Map(MapField { ref key, ref value, .. }) => {
+            FieldKind::Map(ref map) => {
                 AccessorFn {
-                    name: "make_map_accessor".to_owned(),
-                    type_params: vec![key.lib_protobuf_type(), value.lib_protobuf_type()],
+                    name: format!("make_{}_accessor", map.rt_fn_name_part()),
+                    type_params: vec![map.key.lib_protobuf_type(), map.value.lib_protobuf_type()],
+                    for_reflect_suffix: true,
+                    accessors: vec![
+                        format!("get_{}_for_reflect", self.rust_name),
+                        format!("mut_{}_for_reflect", self.rust_name),
+                    ],
+                }
+            }
+            FieldKind::Singular(SingularField { wrapper: Some(ref wrapper), .. }) => {
+                AccessorFn {
+                    name: "make_option_wrapper_accessor".to_owned(),
+                    type_params: vec![wrapper.clone()],
                     for_reflect_suffix: true,
                     accessors: vec![
                         format!("get_{}_for_reflect", self.rust_name),
@@ -877,6 +1063,7 @@ impl<'a> FieldGen<'a> {
             FieldKind::Singular(SingularField {
                 ref elem,
                 flag: SingularFieldFlag::WithoutFlag,
+                ..
             }) => {
                 if let &GenProtobufType::Message(ref name, _) = elem {
                     // TODO: old style, needed because of default instance
@@ -902,9 +1089,25 @@ impl<'a> FieldGen<'a> {
                     }
                 }
             }
+            FieldKind::Singular(SingularField { ref elem, .. }) if self.is_option_boxed() => {
+                // `ReflectOptional` is not implemented for `Option<Box<M>>`
+                AccessorFn {
+                    name: "make_singular_message_accessor".to_owned(),
+                    type_params: vec![elem.rust_type().to_code(self.std)],
+                    for_reflect_suffix: false,
+                    accessors: vec![
+                        format!("has_{}", self.rust_name),
+                        format!("get_{}", self.rust_name),
+                        format!("set_{}", self.rust_name),
+                        format!("mut_{}", self.rust_name),
+                        format!("clear_{}", self.rust_name),
+                    ],
+                }
+            }
             FieldKind::Singular(SingularField {
                 ref elem,
                 flag: SingularFieldFlag::WithFlag { .. },
+                ..
             }) => {
                 let coll = match self.full_storage_type() {
                     RustType::Option(..) => "option",
@@ -927,12 +1130,13 @@ impl<'a> FieldGen<'a> {
                 // TODO: uses old style
 
                 let suffix = match &self.elem().rust_type() {
-                    t if t.is_primitive() => format!("{}", t),
+                    t if t.is_primitive() => t.to_code(self.std),
                     &RustType::String => "string".to_string(),
                     &RustType::Vec(ref t) if t.is_u8() => "bytes".to_string(),
                     &RustType::Enum(..) => "enum".to_string(),
+                    &RustType::EnumOrUnknown(..) => "enum_or_unknown".to_string(),
                     &RustType::Message(..) => "message".to_string(),
-                    t => panic!("unexpected field type: {}", t),
+                    t => panic!("unexpected field type: {:?}", t),
                 };
 
                 let name = format!("make_singular_{}_accessor", suffix);
@@ -940,20 +1144,27 @@ impl<'a> FieldGen<'a> {
                 let mut type_params = Vec::new();
                 match elem {
                     &GenProtobufType::Message(ref name, _) |
-                    &GenProtobufType::Enum(ref name, _, _) => {
+                    &GenProtobufType::Enum(ref name, ..) => {
                         type_params.push(name.to_owned());
                     }
                     _ => (),
                 }
 
+                let mut accessors = vec![
+                    format!("has_{}", self.rust_name),
+                    format!("get_{}", self.rust_name),
+                    format!("set_{}", self.rust_name),
+                ];
+                if let &GenProtobufType::Message(..) = elem {
+                    accessors.push(format!("mut_{}", self.rust_name));
+                }
+                accessors.push(format!("clear_{}", self.rust_name));
+
                 AccessorFn {
                     name: name,
                     type_params: type_params,
                     for_reflect_suffix: false,
-                    accessors: vec![
-                        format!("has_{}", self.rust_name),
-                        format!("get_{}", self.rust_name),
-                    ],
+                    accessors: accessors,
                 }
             }
         }
@@ -988,9 +1199,10 @@ impl<'a> FieldGen<'a> {
                             t => t.clone(),
                         };
                         format!(
-                            "::protobuf::rt::enum_size({}, {})",
+                            "::protobuf::rt::{}_size({}, {})",
+                            self.read_fn_suffix(),
                             self.proto_field.number(),
-                            var_type.into_target(&param_type, var)
+                            var_type.into_target(&param_type, var, self.std)
                         )
                     }
                     _ => {
@@ -1002,13 +1214,13 @@ impl<'a> FieldGen<'a> {
                             format!(
                                 "::protobuf::rt::value_varint_zigzag_size({}, {})",
                                 self.proto_field.number(),
-                                var_type.into_target(&param_type, var)
+                                var_type.into_target(&param_type, var, self.std)
                             )
                         } else {
                             format!(
                                 "::protobuf::rt::value_size({}, {}, ::protobuf::wire_format::{:?})",
                                 self.proto_field.number(),
-                                var_type.into_target(&param_type, var),
+                                var_type.into_target(&param_type, var, self.std),
                                 self.wire_type
                             )
                         }
@@ -1025,6 +1237,22 @@ impl<'a> FieldGen<'a> {
         };
 
         match self.proto_type {
+            FieldDescriptorProto_Type::TYPE_MESSAGE if self.group => {
+                let number = self.proto_field.number();
+                w.write_line(&format!(
+                    "{}.write_tag({}, ::protobuf::wire_format::{:?})?;",
+                    os,
+                    number,
+                    wire_format::WireTypeStartGroup
+                ));
+                w.write_line(&format!("{}.write_to_with_cached_sizes({})?;", var, os));
+                w.write_line(&format!(
+                    "{}.write_tag({}, ::protobuf::wire_format::{:?})?;",
+                    os,
+                    number,
+                    wire_format::WireTypeEndGroup
+                ));
+            }
             FieldDescriptorProto_Type::TYPE_MESSAGE => {
                 w.write_line(&format!(
                     "{}.write_tag({}, ::protobuf::wire_format::{:?})?;",
@@ -1048,7 +1276,7 @@ impl<'a> FieldGen<'a> {
                     os,
                     os_write_fn_suffix,
                     number,
-                    ty.into_target(&param_type, var)
+                    ty.into_target(&param_type, var, self.std)
                 ));
             }
         }
@@ -1078,10 +1306,13 @@ impl<'a> FieldGen<'a> {
         assert!(self.is_singular());
         match self.full_storage_type() {
             RustType::Option(ref e) if e.is_copy() => RustType::Option(e.clone()),
-            RustType::Option(e) => RustType::Option(Box::new(e.ref_type())),
+            RustType::Option(e) => match *e {
+                RustType::Uniq(ty) => RustType::Option(Box::new(RustType::Ref(ty))),
+                e => RustType::Option(Box::new(e.ref_type())),
+            },
             RustType::SingularField(ty) |
             RustType::SingularPtrField(ty) => RustType::Option(Box::new(RustType::Ref(ty))),
-            x => panic!("cannot convert {} to option", x),
+            x => panic!("cannot convert {:?} to option", x),
         }
     }
 
@@ -1091,6 +1322,7 @@ impl<'a> FieldGen<'a> {
 
         let suffix = match self.full_storage_type() {
             RustType::Option(ref e) if e.is_copy() => "",
+            _ if self.is_option_boxed() => ".as_ref().map(|v| &**v)",
             _ => ".as_ref()",
         };
 
@@ -1108,6 +1340,7 @@ impl<'a> FieldGen<'a> {
             FieldKind::Singular(SingularField {
                 flag: SingularFieldFlag::WithFlag { .. },
                 ref elem,
+                ..
             }) => {
                 let var = "v";
                 let ref_prefix = match elem.rust_type().is_copy() {
@@ -1127,6 +1360,7 @@ impl<'a> FieldGen<'a> {
             FieldKind::Singular(SingularField {
                 flag: SingularFieldFlag::WithoutFlag,
                 ref elem,
+                ..
             }) => {
                 match *elem {
                     GenProtobufType::Primitive(FieldDescriptorProto_Type::TYPE_STRING, ..) |
@@ -1140,7 +1374,7 @@ impl<'a> FieldGen<'a> {
                             format!(
                                 "{} != {}",
                                 self.self_field(),
-                                self.full_storage_type().default_value()
+                                self.full_storage_type().default_value(self.std)
                             ),
                             |w| { cb(&self.self_field(), &self.full_storage_type(), w); },
                         );
@@ -1181,7 +1415,7 @@ impl<'a> FieldGen<'a> {
             }) => {
                 let cond = format!(
                     "Some({}::{}(ref {}))",
-                    oneof_type_name,
+                    oneof_type_name.to_code(self.std),
                     self.rust_name,
                     varn
                 );
@@ -1203,7 +1437,7 @@ impl<'a> FieldGen<'a> {
         let full_storage_type = self.full_storage_type();
         match self.singular() {
             &SingularField { flag: SingularFieldFlag::WithFlag { .. }, .. } => {
-                Cow::Owned(full_storage_type.wrap_value(value))
+                Cow::Owned(full_storage_type.wrap_value(value, self.std))
             }
             &SingularField { flag: SingularFieldFlag::WithoutFlag, .. } => {
                 Cow::Borrowed(value)
@@ -1224,15 +1458,15 @@ impl<'a> FieldGen<'a> {
         match self.kind {
             FieldKind::Repeated(..) |
             FieldKind::Map(..) => {
-                let converted = ty.into_target(&self.full_storage_type(), value);
+                let converted = ty.into_target(&self.full_storage_type(), value, self.std);
                 self.write_self_field_assign(w, &converted);
             }
-            FieldKind::Singular(SingularField { ref elem, ref flag }) => {
-                let converted = ty.into_target(&elem.rust_type(), value);
+            FieldKind::Singular(SingularField { ref elem, ref flag, .. }) => {
+                let converted = ty.into_target(&elem.rust_type(), value, self.std);
                 let wrapped = if *flag == SingularFieldFlag::WithoutFlag {
                     converted
                 } else {
-                    self.full_storage_type().wrap_value(&converted)
+                    self.full_storage_type().wrap_value(&converted, self.std)
                 };
                 self.write_self_field_assign(w, &wrapped);
             }
@@ -1245,13 +1479,14 @@ impl<'a> FieldGen<'a> {
         if self.is_oneof() {
             let self_field_oneof = self.self_field_oneof();
             w.write_line(format!(
-                "{} = ::std::option::Option::Some({}({}))",
+                "{} = {some}({}({}))",
                 self_field_oneof,
                 self.variant_path(),
                 // TODO: default from .proto is not needed here (?)
                 self.element_default_value_rust()
-                    .into_type(self.full_storage_iter_elem_type())
-                    .value
+                    .into_type(self.full_storage_iter_elem_type(), self.std)
+                    .value,
+                some = self.std.path("option::Option::Some"),
             ));
         } else {
             match self.full_storage_type() {
@@ -1261,7 +1496,20 @@ impl<'a> FieldGen<'a> {
                     w.write_line(&format!("{}.set_default();", self_field));
                 }
                 _ => {
-                    self.write_self_field_assign_some(w, &self.element_default_value_rust().value);
+                    let default_value = self.element_default_value_rust();
+                    // default value from `.proto` file is `&str` or `&[u8]`
+                    let default_value = match self.elem() {
+                        &GenProtobufType::Primitive(
+                            FieldDescriptorProto_Type::TYPE_STRING,
+                            PrimitiveTypeVariant::Default,
+                        ) |
+                        &GenProtobufType::Primitive(
+                            FieldDescriptorProto_Type::TYPE_BYTES,
+                            PrimitiveTypeVariant::Default,
+                        ) => default_value.into_type(self.elem().rust_type(), self.std),
+                        _ => default_value,
+                    };
+                    self.write_self_field_assign_some(w, &default_value.value);
                 }
             }
         }
@@ -1279,7 +1527,7 @@ impl<'a> FieldGen<'a> {
     fn self_field_vec_packed_varint_data_size(&self) -> String {
         assert!(!self.is_fixed());
         let fn_name = if self.is_enum() {
-            "vec_packed_enum_data_size".to_string()
+            format!("vec_packed_{}_data_size", self.read_fn_suffix())
         } else {
             let zigzag_suffix = if self.is_zigzag() { "_zigzag" } else { "" };
             format!("vec_packed_varint{}_data_size", zigzag_suffix)
@@ -1310,7 +1558,7 @@ impl<'a> FieldGen<'a> {
         // zero is filtered outside
         assert!(!self.is_fixed());
         let fn_name = if self.is_enum() {
-            "vec_packed_enum_size".to_string()
+            format!("vec_packed_{}_size", self.read_fn_suffix())
         } else {
             let zigzag_suffix = if self.is_zigzag() { "_zigzag" } else { "" };
             format!("vec_packed_varint{}_size", zigzag_suffix)
@@ -1327,47 +1575,194 @@ impl<'a> FieldGen<'a> {
         format!("self.{}", self.oneof().oneof_name)
     }
 
-    pub fn write_read_from_field(&self, w: &mut CodeWriter, is: &str) {
+    // read proto2 enum value into variable `v` and pass it to `cb`,
+    // values unknown to the enum are stored in unknown fields
+    fn write_read_closed_enum<F>(
+        &self,
+        w: &mut CodeWriter,
+        is: &str,
+        unknown_fields: &str,
+        cb: F,
+    ) where
+        F : Fn(&mut CodeWriter),
+    {
+        let read_proc = format!(
+            "::protobuf::rt::read_proto2_enum_with_unknown_fields({}, {}, {})?",
+            is,
+            self.proto_field.number(),
+            unknown_fields
+        );
+        w.if_let_stmt(&format!("{}(v)", self.std.path("option::Option::Some")), &read_proc, cb);
+    }
+
+    fn write_read_repeated_closed_enum(
+        &self,
+        w: &mut CodeWriter,
+        is: &str,
+        target: &str,
+        unknown_fields: &str,
+    ) {
+        w.write_line(&format!("::protobuf::rt::read_repeated_proto2_enum_with_unknown_fields_into(wire_type, {is}, &mut {target}, {number}, {unknown_fields})?;",
+            is = is,
+            target = target,
+            number = self.proto_field.number(),
+            unknown_fields = unknown_fields));
+    }
+
+    pub fn write_merge_from_field(&self, w: &mut CodeWriter, is: &str, unknown_fields: &str) {
         match self.kind {
+            FieldKind::Oneof(..) if self.is_closed_enum() => {
+                w.assert_wire_type(self.wire_type);
+                self.write_read_closed_enum(w, is, unknown_fields, |w| {
+                    w.write_line(&format!("{} = {some}({}(v));",
+                        self.self_field_oneof(),
+                        self.variant_path(), some = self.std.path("option::Option::Some")));
+                });
+            }
             FieldKind::Oneof(ref oneof) => {
-                let read_proc = self.proto_type.read(is) + "?";
+                let read_proc = self.read_proc(is);
 
                 let read_proc = if oneof.boxed {
-                    format!("::std::boxed::Box::new({})", read_proc)
+                    format!("{}({})", self.std.path("boxed::Box::new"), read_proc)
                 } else {
                     read_proc
                 };
 
-                w.write_line(&format!("_field_{oneof_name} = ::std::option::Option::Some(::std::option::Option::Some({variant}({read})));",
-                    oneof_name = oneof.oneof_name,
-                    variant = self.variant_path(),
-                    read = read_proc));
+                let assign = format!("{} = {some}({}({}))",
+                    self.self_field_oneof(),
+                    self.variant_path(),
+                    read_proc, some = self.std.path("option::Option::Some"));
+
+                w.assert_wire_type(self.wire_type);
+                if self.proto_type == FieldDescriptorProto_Type::TYPE_MESSAGE {
+                    let v = if oneof.boxed { "&mut **v" } else { "v" };
+                    let merge = if self.group {
+                        format!("{}.merge_group({}, {})?", is, v, self.proto_field.number())
+                    } else {
+                        format!("{}.merge_message({})?", is, v)
+                    };
+                    w.match_block(self.self_field_oneof(), |w| {
+                        w.case_expr(
+                            format!("{}({}(ref mut v))", self.std.path("option::Option::Some"), self.variant_path()),
+                            &merge,
+                        );
+                        w.case_expr("_", &assign);
+                    });
+                } else {
+                    w.write_line(&format!("{};", assign));
+                }
+            }
+            FieldKind::Singular(SingularField { wrapper: Some(ref wrapper), .. }) => {
+                w.write_line(&format!("::protobuf::rt::merge_wrapper_into::<{wrapper}>(wire_type, {is}, &mut {self_field})?;",
+                    wrapper = wrapper,
+                    is = is,
+                    self_field = self.self_field()));
+            }
+            FieldKind::Singular(..) if self.group && self.is_option_boxed() => {
+                w.write_line(&format!("::protobuf::rt::merge_option_group_into(wire_type, {is}, &mut {self_field}, {number})?;",
+                    is = is,
+                    self_field = self.self_field(),
+                    number = self.proto_field.number()));
+            }
+            FieldKind::Singular(..) if self.is_option_boxed() => {
+                w.write_line(&format!("::protobuf::rt::merge_option_message_into(wire_type, {is}, &mut {self_field})?;",
+                    is = is,
+                    self_field = self.self_field()));
+            }
+            FieldKind::Singular(..) if self.group => {
+                w.write_line(&format!("::protobuf::rt::merge_singular_group_into(wire_type, {is}, &mut {self_field}, {number})?;",
+                    is = is,
+                    self_field = self.self_field(),
+                    number = self.proto_field.number()));
+            }
+            FieldKind::Singular(..) if self.proto_type == FieldDescriptorProto_Type::TYPE_MESSAGE => {
+                w.write_line(&format!("::protobuf::rt::merge_singular_message_into(wire_type, {is}, &mut {self_field})?;",
+                    is = is,
+                    self_field = self.self_field()));
+            }
+            FieldKind::Singular(..) if self.is_closed_enum() => {
+                w.assert_wire_type(self.wire_type);
+                self.write_read_closed_enum(w, is, unknown_fields, |w| {
+                    self.write_self_field_assign_some(w, "v");
+                });
             }
             FieldKind::Singular(..) => {
-                let wire_type = field_type_wire_type(self.proto_type);
-                let read_proc = format!("{}.read_{}()?", is, protobuf_name(self.proto_type));
+                let read_proc = self.read_proc(is);
 
-                w.assert_wire_type(wire_type);
-                w.write_line(&format!("_field_{rust_name} = ::std::option::Option::Some({read_proc});",
-                    rust_name = self.rust_name,
-                    read_proc = self.singular_field_expr(&read_proc)));
+                w.assert_wire_type(self.wire_type);
+                self.write_self_field_assign_some(w, &read_proc);
+            }
+            FieldKind::Repeated(..) if self.is_closed_enum() => {
+                self.write_read_repeated_closed_enum(w, is, &self.self_field(), unknown_fields);
+            }
+            FieldKind::Repeated(..) if self.group => {
+                w.write_line(&format!("::protobuf::rt::read_repeated_group_into(wire_type, {is}, &mut {self_field}, {number})?;",
+                    is = is,
+                    self_field = self.self_field(),
+                    number = self.proto_field.number()));
             }
             FieldKind::Repeated(..) => {
-                w.write_line(&format!("::protobuf::rt::read_repeated_{proto_type}_into(wire_type, {is}, &mut _field_{rust_name})?;",
-                    proto_type = protobuf_name(self.proto_type),
+                w.write_line(&format!("::protobuf::rt::read_repeated_{read_fn_suffix}_into(wire_type, {is}, &mut {self_field})?;",
+                    read_fn_suffix = self.read_fn_suffix(),
                     is = is,
-                    rust_name = self.rust_name));
+                    self_field = self.self_field()));
             }
             FieldKind::Map(ref map) => {
-                w.write_line(&format!("::protobuf::rt::read_map_into::<{k}, {v}>(wire_type, {is}, &mut _field_{rust_name})?;",
+                w.write_line(&format!("::protobuf::rt::read_{map}_into::<{k}, {v}>(wire_type, {is}, &mut {self_field})?;",
+                    map = map.rt_fn_name_part(),
                     k = map.key.lib_protobuf_type(),
                     v = map.value.lib_protobuf_type(),
                     is = is,
-                    rust_name = self.rust_name));
+                    self_field = self.self_field()));
             }
         }
     }
 
+    // merge field of other message of the same type
+    pub fn write_merge_field(&self, w: &mut CodeWriter, other: &str) {
+        let self_field = self.self_field();
+        let other_field = format!("{}.{}", other, self.rust_name);
+        match self.kind {
+            FieldKind::Singular(SingularField { wrapper: Some(ref wrapper), .. }) => {
+                w.write_line(&format!("::protobuf::rt::merge_wrapper::<{}>(&mut {}, &{});",
+                    wrapper, self_field, other_field));
+            }
+            FieldKind::Singular(..) if self.is_option_boxed() => {
+                w.write_line(&format!("::protobuf::rt::merge_option_message(&mut {}, &{});",
+                    self_field, other_field));
+            }
+            FieldKind::Singular(..) if self.proto_type == FieldDescriptorProto_Type::TYPE_MESSAGE => {
+                w.write_line(&format!("::protobuf::rt::merge_singular_message(&mut {}, &{});",
+                    self_field, other_field));
+            }
+            FieldKind::Singular(SingularField { ref flag, ref elem, .. }) => {
+                let cond = match (flag, elem) {
+                    (&SingularFieldFlag::WithFlag { .. }, _) => {
+                        format!("{}.is_some()", other_field)
+                    }
+                    (_, &GenProtobufType::Primitive(FieldDescriptorProto_Type::TYPE_STRING, ..)) |
+                    (_, &GenProtobufType::Primitive(FieldDescriptorProto_Type::TYPE_BYTES, ..)) => {
+                        format!("!{}.is_empty()", other_field)
+                    }
+                    _ => {
+                        format!("{} != {}", other_field, self.full_storage_type().default_value(self.std))
+                    }
+                };
+                w.if_stmt(cond, |w| {
+                    w.write_line(&format!("{} = {}.clone();", self_field, other_field));
+                });
+            }
+            FieldKind::Repeated(..) => {
+                w.write_line(&format!("{}.extend({}.iter().cloned());", self_field, other_field));
+            }
+            FieldKind::Map(..) => {
+                w.write_line(&format!("{}.extend({}.iter().map(|(k, v)| (k.clone(), v.clone())));",
+                    self_field, other_field));
+            }
+            FieldKind::Oneof(..) => unreachable!(),
+        }
+    }
+
     fn self_field_vec_packed_size(&self) -> String {
         match self.kind {
             FieldKind::Repeated(RepeatedField { packed: true, .. }) => {
@@ -1394,6 +1789,15 @@ impl<'a> FieldGen<'a> {
         assert!(!self.is_repeated_packed());
 
         match self.proto_type {
+            // start and end group tags
+            FieldDescriptorProto_Type::TYPE_MESSAGE if self.group => {
+                w.write_line(&format!(
+                    "{} += {} + {}.compute_size();",
+                    sum_var,
+                    self.tag_size() * 2,
+                    item_var
+                ));
+            }
             FieldDescriptorProto_Type::TYPE_MESSAGE => {
                 w.write_line(&format!("let len = {}.compute_size();", item_var));
                 let tag_size = self.tag_size();
@@ -1415,6 +1819,14 @@ impl<'a> FieldGen<'a> {
 
     pub fn write_message_write_field(&self, w: &mut CodeWriter) {
         match self.kind {
+            FieldKind::Singular(SingularField { wrapper: Some(ref wrapper), .. }) => {
+                w.write_line(&format!(
+                    "::protobuf::rt::write_wrapper::<{}>({}, &{}, _os)?;",
+                    wrapper,
+                    self.proto_field.number(),
+                    self.self_field()
+                ));
+            }
             FieldKind::Singular(..) => {
                 self.write_if_let_self_field_is_some(w, |v, v_type, w| {
                     self.write_write_element(w, "_os", v, v_type);
@@ -1442,16 +1854,17 @@ impl<'a> FieldGen<'a> {
                         w.write_line(&format!(
                             "_os.write_{}_no_tag({})?;",
                             os_write_fn_suffix,
-                            v_type.into_target(&param_type, "v")
+                            v_type.into_target(&param_type, "v", self.std)
                         ));
                     });
                 });
             }
-            FieldKind::Map(MapField { ref key, ref value, .. }) => {
+            FieldKind::Map(ref map) => {
                 w.write_line(&format!(
-                    "_os.write_map::<{}, {}>({}, &{})?;",
-                    key.lib_protobuf_type(),
-                    value.lib_protobuf_type(),
+                    "_os.write_{}::<{}, {}>({}, &{})?;",
+                    map.rt_fn_name_part(),
+                    map.key.lib_protobuf_type(),
+                    map.value.lib_protobuf_type(),
                     self.proto_field.number(),
                     self.self_field()
                 ));
@@ -1462,6 +1875,15 @@ impl<'a> FieldGen<'a> {
 
     pub fn write_message_compute_field_size(&self, sum_var: &str, w: &mut CodeWriter) {
         match self.kind {
+            FieldKind::Singular(SingularField { wrapper: Some(ref wrapper), .. }) => {
+                w.write_line(&format!(
+                    "{} += ::protobuf::rt::wrapper_size::<{}>({}, &{});",
+                    sum_var,
+                    wrapper,
+                    self.proto_field.number(),
+                    self.self_field()
+                ));
+            }
             FieldKind::Singular(..) => {
                 self.write_if_let_self_field_is_some(w, |v, v_type, w| {
                     match field_type_size(self.proto_type) {
@@ -1494,12 +1916,13 @@ impl<'a> FieldGen<'a> {
                     }
                 };
             }
-            FieldKind::Map(MapField { ref key, ref value, .. }) => {
+            FieldKind::Map(ref map) => {
                 w.write_line(&format!(
-                    "{} += ::protobuf::rt::compute_map_size::<{}, {}>({}, &{});",
+                    "{} += ::protobuf::rt::compute_{}_size::<{}, {}>({}, &{});",
                     sum_var,
-                    key.lib_protobuf_type(),
-                    value.lib_protobuf_type(),
+                    map.rt_fn_name_part(),
+                    map.key.lib_protobuf_type(),
+                    map.value.lib_protobuf_type(),
                     self.proto_field.number(),
                     self.self_field()
                 ));
@@ -1518,12 +1941,12 @@ impl<'a> FieldGen<'a> {
         let get_xxx_return_type = self.get_xxx_return_type();
 
         if self.proto_type == FieldDescriptorProto_Type::TYPE_MESSAGE {
-            let self_field = self.self_field();
+            let as_option = self.self_field_as_option();
             let ref field_type_name = self.elem().rust_type();
             w.write_line(&format!(
-                "{}.as_ref().unwrap_or_else(|| {}::default_instance())",
-                self_field,
-                field_type_name
+                "{}.unwrap_or_else(|| {}::default_instance())",
+                as_option.value,
+                field_type_name.to_code(self.std)
             ));
         } else {
             let get_xxx_default_value_rust = self.get_xxx_default_value_rust();
@@ -1535,7 +1958,7 @@ impl<'a> FieldGen<'a> {
                         w.match_expr(&as_option.value, |w| {
                             let v_type = as_option.rust_type.elem_type();
                             let r_type = self.get_xxx_return_type();
-                            w.case_expr("Some(v)", v_type.into_target(&r_type, "v"));
+                            w.case_expr("Some(v)", v_type.into_target(&r_type, "v", self.std));
                             let get_xxx_default_value_rust = self.get_xxx_default_value_rust();
                             w.case_expr("None", get_xxx_default_value_rust);
                         });
@@ -1550,7 +1973,7 @@ impl<'a> FieldGen<'a> {
                 &SingularField { flag: SingularFieldFlag::WithoutFlag, .. } => {
                     w.write_line(
                         self.full_storage_type()
-                            .into_target(&get_xxx_return_type, &self_field),
+                            .into_target(&get_xxx_return_type, &self_field, self.std),
                     );
                 }
             }
@@ -1559,7 +1982,11 @@ impl<'a> FieldGen<'a> {
 
     fn write_message_field_get(&self, w: &mut CodeWriter) {
         let get_xxx_return_type = self.get_xxx_return_type();
-        let fn_def = format!("get_{}(&self) -> {}", self.rust_name, get_xxx_return_type);
+        let fn_def = format!(
+            "get_{}(&self) -> {}",
+            self.rust_name,
+            get_xxx_return_type.to_code(self.std)
+        );
 
         w.pub_fn(&fn_def, |w| match self.kind {
             FieldKind::Oneof(OneofField { ref elem, .. }) => {
@@ -1572,11 +1999,12 @@ impl<'a> FieldGen<'a> {
                     };
                     w.case_expr(
                         format!(
-                            "::std::option::Option::Some({}({}))",
+                            "{some}({}({}))",
                             self.variant_path(),
-                            refv
+                            refv,
+                            some = self.std.path("option::Option::Some"),
                         ),
-                        vtype.into_target(&get_xxx_return_type, "v"),
+                        vtype.into_target(&get_xxx_return_type, "v", self.std),
                     );
                     w.case_expr("_", self.get_xxx_default_value_rust());
                 })
@@ -1596,7 +2024,7 @@ impl<'a> FieldGen<'a> {
         let sig = format!(
             "get_{}_for_reflect(&self) -> &{}",
             self.rust_name,
-            self.full_storage_type()
+            self.full_storage_type().to_code(self.std)
         );
         w.def_fn(&sig, |w| w.write_line(&format!("&{}", self.self_field())));
     }
@@ -1605,7 +2033,7 @@ impl<'a> FieldGen<'a> {
         let sig = format!(
             "mut_{}_for_reflect(&mut self) -> &mut {}",
             self.rust_name,
-            self.full_storage_type()
+            self.full_storage_type().to_code(self.std)
         );
         w.def_fn(
             &sig,
@@ -1650,7 +2078,7 @@ impl<'a> FieldGen<'a> {
                 let self_field_oneof = self.self_field_oneof();
                 w.match_expr(self_field_oneof, |w| {
                     w.case_expr(
-                        format!("::std::option::Option::Some({}(..))", self.variant_path()),
+                        format!("{}({}(..))", self.std.path("option::Option::Some"), self.variant_path()),
                         "true",
                     );
                     w.case_expr("_", "false");
@@ -1663,14 +2091,44 @@ impl<'a> FieldGen<'a> {
         let set_xxx_param_type = self.set_xxx_param_type();
         w.comment("Param is passed by value, moved");
         let ref name = self.rust_name;
-        w.pub_fn(&format!("set_{}(&mut self, v: {})", name, set_xxx_param_type), |w| {
+        let sig = format!("set_{}(&mut self, v: {})", name, set_xxx_param_type.to_code(self.std));
+        w.pub_fn(&sig, |w| {
             if !self.is_oneof() {
                 self.write_self_field_assign_value(w, "v", &set_xxx_param_type);
             } else {
                 let self_field_oneof = self.self_field_oneof();
-                let v = set_xxx_param_type.into_target(&self.oneof().rust_type(), "v");
-                w.write_line(&format!("{} = ::std::option::Option::Some({}({}))",
-                    self_field_oneof, self.variant_path(), v));
+                let v = set_xxx_param_type.into_target(&self.oneof().rust_type(), "v", self.std);
+                w.write_line(&format!("{} = {some}({}({}))",
+                    self_field_oneof, self.variant_path(), v,
+                    some = self.std.path("option::Option::Some")));
+            }
+        });
+    }
+
+    fn write_message_field_with(&self, w: &mut CodeWriter) {
+        w.comment("Builder-style setter, same as `set_` but takes and returns `self`");
+        let ref name = self.rust_name;
+        let sig = format!(
+            "with_{}(mut self, v: {}) -> Self",
+            name,
+            self.set_xxx_param_type().to_code(self.std)
+        );
+        w.pub_fn(&sig, |w| {
+            w.write_line(&format!("self.set_{}(v);", name));
+            w.write_line("self");
+        });
+    }
+
+    // needed by reflection: other fields are cleared through `mut_xxx_for_reflect`
+    fn write_message_field_clear(&self, w: &mut CodeWriter) {
+        w.pub_fn(&format!("clear_{}(&mut self)", self.rust_name), |w| {
+            if self.is_oneof() {
+                w.if_stmt(format!("self.{}()", self.has_name()), |w| {
+                    let self_field_oneof = self.self_field_oneof();
+                    w.write_line(&format!("{} = {};", self_field_oneof, self.std.path("option::Option::None")));
+                });
+            } else {
+                w.write_line(&format!("{} = {};", self.self_field(), self.std.path("option::Option::None")));
             }
         });
     }
@@ -1685,9 +2143,9 @@ impl<'a> FieldGen<'a> {
         }
         let fn_def = match mut_xxx_return_type {
             RustType::Ref(ref param) => {
-                format!("mut_{}(&mut self) -> &mut {}", self.rust_name, **param)
+                format!("mut_{}(&mut self) -> &mut {}", self.rust_name, param.to_code(self.std))
             }
-            _ => panic!("not a ref: {}", mut_xxx_return_type),
+            _ => panic!("not a ref: {:?}", mut_xxx_return_type),
         };
         w.pub_fn(&fn_def, |w| {
             match self.kind {
@@ -1714,26 +2172,28 @@ impl<'a> FieldGen<'a> {
 
                     // if oneof does not contain current field
                     w.if_let_else_stmt(&format!(
-                                "::std::option::Option::Some({}(_))",
+                                "{}({}(_))",
+                                self.std.path("option::Option::Some"),
                                 self.variant_path())[..], &self_field_oneof[..],
                     |w|
                     {
                         // initialize it with default value
                         w.write_line(&format!(
-                            "{} = ::std::option::Option::Some({}({}));",
+                            "{} = {some}({}({}));",
                             self_field_oneof,
                             self.variant_path(),
                             self.element_default_value_rust()
-                                .into_type(self.oneof().rust_type())
-                                .value));
+                                .into_type(self.oneof().rust_type(), self.std)
+                                .value, some = self.std.path("option::Option::Some")));
                     });
 
                     // extract field
                     w.match_expr(self_field_oneof, |w| {
                         w.case_expr(
                             format!(
-                                "::std::option::Option::Some({}(ref mut v))",
-                                self.variant_path()
+                                "{some}({}(ref mut v))",
+                                self.variant_path(),
+                                some = self.std.path("option::Option::Some"),
                             ),
                             "v",
                         );
@@ -1753,6 +2213,11 @@ impl<'a> FieldGen<'a> {
         w.write_line("");
         self.write_message_field_set(w);
 
+        if self.with_methods {
+            w.write_line("");
+            self.write_message_field_with(w);
+        }
+
         if self.has_mut() {
             w.write_line("");
             self.write_message_field_mut(w);
@@ -1761,6 +2226,11 @@ impl<'a> FieldGen<'a> {
         w.write_line("");
         self.write_message_field_get(w);
 
+        if self.is_oneof() || self.is_option_boxed() {
+            w.write_line("");
+            self.write_message_field_clear(w);
+        }
+
         if self.accessor_fn().for_reflect_suffix {
             w.write_line("");
             self.write_message_field_get_for_reflect(w);
@@ -1789,11 +2259,16 @@ impl<'a> OneofVariantGen<'a> {
             oneof: oneof,
             variant: variant.clone(),
             field: field.clone(),
-            path: format!("{}::{}", oneof.type_name, field.rust_name),
+            path: format!("{}::{}", oneof.type_name.to_code(field.std), field.rust_name),
             oneof_field: OneofField::parse(
                 variant.oneof,
                 variant.field,
-                field.oneof().elem.clone(),
+                // oneof enum is generated in a module nested in the message module
+                if idiomatic_names(variant.oneof.message.get_file_descriptor()) {
+                    field.oneof().elem.from_submodule()
+                } else {
+                    field.oneof().elem.clone()
+                },
             ),
         }
     }
@@ -1814,16 +2289,18 @@ pub struct OneofGen<'a> {
     oneof: OneofWithContext<'a>,
     type_name: RustType,
     lite_runtime: bool,
+    serde_derive: bool,
 }
 
 impl<'a> OneofGen<'a> {
     pub fn parse(message: &'a MessageGen, oneof: OneofWithContext<'a>) -> OneofGen<'a> {
-        let rust_name = oneof.rust_name();
+        let rust_name = oneof_path_from_message(&oneof);
         OneofGen {
             message: message,
             oneof: oneof,
             type_name: RustType::Oneof(rust_name),
             lite_runtime: message.lite_runtime,
+            serde_derive: message.serde_derive,
         }
     }
 
@@ -1855,18 +2332,32 @@ impl<'a> OneofGen<'a> {
         RustType::Option(Box::new(self.type_name.clone()))
     }
 
+    pub fn write_doc_comment(&self, w: &mut CodeWriter) {
+        write_doc_comment(w, self.oneof.message.get_file_descriptor(), &oneof_path(&self.oneof));
+    }
+
     pub fn write_enum(&self, w: &mut CodeWriter) {
+        self.write_doc_comment(w);
         let mut derive = vec!["Clone", "PartialEq"];
         if self.lite_runtime {
             derive.push("Debug");
         }
-        w.derive(&derive);
-        w.pub_enum(&self.type_name.to_string(), |w| {
+        // oneof enum is a field of the message, so it needs the same derives
+        write_derive(w, &message_derive(self.oneof.message, &derive));
+        if self.serde_derive {
+            write_serde_derive(w);
+        }
+        w.pub_enum(&oneof_decl_name(&self.oneof), |w| {
             for variant in self.variants() {
+                write_doc_comment(
+                    w,
+                    self.oneof.message.get_file_descriptor(),
+                    &field_path(&variant.field.proto_field),
+                );
                 w.write_line(&format!(
                     "{}({}),",
                     variant.field.rust_name,
-                    &variant.rust_type().to_string()
+                    variant.rust_type().to_code(self.message.std)
                 ));
             }
         });
@@ -13,13 +13,23 @@ mod enums;
 mod rust_types_values;
 mod well_known_types;
 mod field;
+mod naming;
 mod extensions;
+mod serde;
+mod service;
+mod mod_tree;
+mod rustdoc;
+mod attrs;
+mod no_std;
 
 pub mod code_writer;
 
 use self::message::*;
 use self::enums::*;
 use self::extensions::*;
+use self::service::ServiceGen;
+use self::mod_tree::*;
+use self::no_std::*;
 use self::code_writer::CodeWriter;
 
 fn escape_byte(s: &mut String, b: u8) {
@@ -98,7 +108,7 @@ fn gen_file(
     let scope = FileScope { file_descriptor: file }.to_scope();
 
     if scope.get_messages().is_empty() && scope.get_enums().is_empty() &&
-        file.get_extension().is_empty()
+        file.get_extension().is_empty() && file.get_service().is_empty()
         {
             // protoc generates empty file descriptors for directories: skip them
             return None;
@@ -108,6 +118,7 @@ fn gen_file(
 
     {
         let mut w = CodeWriter::new(&mut v);
+        w.set_std(StdCrate::for_file(file));
 
         w.write_generated_by("rust-protobuf", env!("CARGO_PKG_VERSION"));
 
@@ -126,19 +137,25 @@ fn gen_file(
         }
         for enum_type in &scope.get_enums() {
             w.write_line("");
-            EnumGen::new(enum_type, file).write(&mut w);
+            EnumGen::new(enum_type).write(&mut w);
         }
 
         write_extensions(file, &root_scope, &mut w);
 
-        if file.get_options().get_optimize_for() != FileOptions_OptimizeMode::LITE_RUNTIME {
+        for service in file.get_service() {
             w.write_line("");
-            write_file_descriptor_data(file, &mut w);
+            ServiceGen::new(service, file, &root_scope).write(&mut w);
         }
     }
 
+    if file.get_options().get_optimize_for() != FileOptions_OptimizeMode::LITE_RUNTIME {
+        let mut w = CodeWriter::new(&mut v);
+        w.write_line("");
+        write_file_descriptor_data(file, &mut w);
+    }
+
     Some(compiler_plugin::GenResult {
-        name: format!("{}.rs", proto_path_to_rust_mod(file.get_name())),
+        name: file_output_name(file),
         content: v,
     })
 }
@@ -158,17 +175,56 @@ pub fn gen(
 
     let all_file_names: Vec<&str> = file_descriptors.iter().map(|f| f.get_name()).collect();
 
+    let mut generated_files: Vec<&FileDescriptorProto> = Vec::new();
+    // output name to the file generated into it
+    let mut output_names: HashMap<String, &str> = HashMap::new();
+
     for file_name in files_to_generate {
         let file = files_map.get(&file_name[..]).expect(&format!(
             "file not found in file descriptors: {:?}, files: {:?}",
             file_name,
             all_file_names
         ));
-        results.extend(gen_file(file, &files_map, &root_scope));
+        if let Some(result) = gen_file(file, &files_map, &root_scope) {
+            if let Some(prev) = output_names.insert(result.name.clone(), file.get_name()) {
+                panic!(
+                    "files {:?} and {:?} are both generated into {:?}, \
+                     they must differ in file name or package",
+                    prev,
+                    file.get_name(),
+                    result.name
+                );
+            }
+            results.push(result);
+            generated_files.push(file);
+        }
     }
+
+    results.extend(gen_mod_rs(&generated_files));
     results
 }
 
 pub fn protoc_gen_rust_main() {
     compiler_plugin::plugin_main(gen);
 }
+
+#[cfg(test)]
+mod test {
+    use protobuf::descriptor::*;
+
+    fn file_with_message(name: &str) -> FileDescriptorProto {
+        let mut message = DescriptorProto::new();
+        message.set_name("Message".to_owned());
+        let mut file = FileDescriptorProto::new();
+        file.set_name(name.to_owned());
+        file.mut_message_type().push(message);
+        file
+    }
+
+    #[test]
+    #[should_panic(expected = "are both generated into \"types.rs\"")]
+    fn test_output_name_collision() {
+        let files = vec![file_with_message("a/types.proto"), file_with_message("b/types.proto")];
+        super::gen(&files, &["a/types.proto".to_owned(), "b/types.proto".to_owned()]);
+    }
+}
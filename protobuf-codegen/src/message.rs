@@ -1,4 +1,3 @@
-use std::collections::HashSet;
 
 use protobuf::descriptor::*;
 use protobuf::descriptorx::*;
@@ -7,7 +6,19 @@ use super::enums::*;
 use super::rust_types_values::*;
 use super::field::*;
 use super::code_writer::*;
-
+use super::serde::*;
+use super::naming::*;
+use super::rustdoc::*;
+use super::attrs::*;
+use super::no_std::StdCrate;
+
+
+/// Module with nested types and oneofs is generated for the message in idiomatic naming mode
+pub fn has_nested_mod(message: &MessageWithScope) -> bool {
+    let scope = message.to_scope();
+    scope.get_messages().into_iter().any(|nested| nested.map_entry().is_none()) ||
+        !scope.get_enums().is_empty() || !message.message.get_oneof_decl().is_empty()
+}
 
 /// Message info for codegen
 pub struct MessageGen<'a> {
@@ -16,6 +27,8 @@ pub struct MessageGen<'a> {
     type_name: String,
     pub fields: Vec<FieldGen<'a>>,
     pub lite_runtime: bool,
+    pub serde_derive: bool,
+    pub std: StdCrate,
 }
 
 impl<'a> MessageGen<'a> {
@@ -28,13 +41,15 @@ impl<'a> MessageGen<'a> {
         MessageGen {
             message: message,
             root_scope: root_scope,
-            type_name: message.rust_name(),
+            type_name: type_decl_name(message),
             fields: fields,
             lite_runtime: message
                 .get_file_descriptor()
                 .get_options()
                 .get_optimize_for() ==
                 FileOptions_OptimizeMode::LITE_RUNTIME,
+            serde_derive: serde_derive_enabled(&message.scope, Some(message.message)),
+            std: StdCrate::for_file(message.get_file_descriptor()),
         }
     }
 
@@ -70,21 +85,6 @@ impl<'a> MessageGen<'a> {
             .collect()
     }
 
-    fn fields_except_group(&'a self) -> Vec<&'a FieldGen> {
-        self.fields
-            .iter()
-            .filter(|f| f.proto_type != FieldDescriptorProto_Type::TYPE_GROUP)
-            .collect()
-    }
-
-    fn fields_except_oneof_and_group(&'a self) -> Vec<&'a FieldGen> {
-        self.fields
-            .iter()
-            .filter(|f| {
-                !f.is_oneof() && f.proto_type != FieldDescriptorProto_Type::TYPE_GROUP
-            })
-            .collect()
-    }
 
 
     fn write_match_each_oneof_variant<F>(&self, w: &mut CodeWriter, cb: F)
@@ -92,7 +92,8 @@ impl<'a> MessageGen<'a> {
         F : Fn(&mut CodeWriter, &OneofVariantGen, &str, &RustType),
     {
         for oneof in self.oneofs() {
-            w.if_let_stmt("::std::option::Option::Some(ref v)", &format!("self.{}", oneof.name())[..], |w| {
+            let some = format!("{}(ref v)", self.std.path("option::Option::Some"));
+            w.if_let_stmt(&some, &format!("self.{}", oneof.name())[..], |w| {
                 w.match_block("v", |w| {
                     for variant in oneof.variants() {
                         let ref field = variant.field;
@@ -114,13 +115,14 @@ impl<'a> MessageGen<'a> {
     fn write_write_to_with_cached_sizes(&self, w: &mut CodeWriter) {
         w.def_fn("write_to_with_cached_sizes(&self, _os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()>", |w| {
             // To have access to its methods but not polute the name space.
-            for f in self.fields_except_oneof_and_group() {
+            for f in self.fields_except_oneof() {
                 f.write_message_write_field(w);
             }
             self.write_match_each_oneof_variant(w, |w, variant, v, v_type| {
                 variant.field.write_write_element(w, "_os", v, v_type);
             });
-            w.write_line("::std::result::Result::Ok(())");
+            w.write_line("_os.write_unknown_fields(&self.unknown_fields)?;");
+            w.write_line(&format!("{}(())", self.std.path("result::Result::Ok")));
         });
     }
 
@@ -143,18 +145,19 @@ impl<'a> MessageGen<'a> {
         w.def_fn("compute_size(&self) -> u32", |w| {
             // To have access to its methods but not polute the name space.
             w.write_line("let mut _size = 0;");
-            for field in self.fields_except_oneof_and_group() {
+            for field in self.fields_except_oneof() {
                 field.write_message_compute_field_size("_size", w);
             }
             self.write_match_each_oneof_variant(w, |w, variant, v, vtype| {
                 variant.field.write_element_size(w, v, vtype, "_size");
             });
+            w.write_line("_size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);");
             w.write_line("_size");
         });
     }
 
     fn write_field_accessors(&self, w: &mut CodeWriter) {
-        for f in self.fields_except_group() {
+        for f in &self.fields {
             w.write_line("");
             let reconstruct_def = f.reconstruct_def();
             w.comment(&(reconstruct_def + ";"));
@@ -166,7 +169,7 @@ impl<'a> MessageGen<'a> {
     fn write_impl_self(&self, w: &mut CodeWriter) {
         w.impl_self_block(&self.type_name, |w| {
             w.pub_fn(&format!("new() -> {}", self.type_name), |w| {
-                w.write_line("::std::default::Default::default()");
+                w.write_line(&format!("{}()", self.std.path("default::Default::default")));
             });
 
             w.write_line("");
@@ -176,82 +179,70 @@ impl<'a> MessageGen<'a> {
     }
 
     fn write_read_from(&self, w: &mut CodeWriter) {
-        w.def_fn(&format!("read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized"), |w| {
-            for f in self.fields_except_oneof_and_group() {
-                match f.kind {
-                    FieldKind::Repeated(..) => {
-                        w.write_line(&format!("let mut _field_{}: {} = ::std::vec::Vec::new();",
-                            f.rust_name, f.full_storage_type().to_string()));
-                    }
-                    FieldKind::Map(..) => {
-                        w.write_line(&format!("let mut _field_{}: {} = ::std::collections::HashMap::new();",
-                            f.rust_name, f.full_storage_type().to_string()));
-                    }
-                    _ => {
-                        w.write_line(&format!("let mut _field_{}: ::std::option::Option<{}> = None;",
-                            f.rust_name, f.full_storage_type().to_string()));
-                    }
-                }
-            }
-
-            for oneof in self.oneofs() {
-                w.write_line(&format!("let mut _field_{}: ::std::option::Option<{}> = None;",
-                    oneof.name(), oneof.full_storage_type().to_string()));
-            }
-
-            w.write_line("");
+        w.def_fn("read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized", |w| {
+            w.write_line(&format!("let mut m = {}::new();", self.type_name));
+            w.write_line("m.merge_from(is)?;");
+            w.write_line("m.check_initialized()?;");
+            w.write_line(&format!("{}(m)", self.std.path("result::Result::Ok")));
+        })
+    }
 
-            w.while_block("!_is.eof()?", |w| {
-                w.write_line(&format!("let (field_number, wire_type) = _is.read_tag_unpack()?;"));
+    fn write_merge_from(&self, w: &mut CodeWriter) {
+        w.def_fn("merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()>", |w| {
+            let cond = format!(
+                "let {}((field_number, wire_type)) = is.read_field_tag_unpack()?",
+                self.std.path("option::Option::Some")
+            );
+            w.while_block(&cond, |w| {
 
                 w.match_block("field_number", |w| {
-                    for f in &self.fields_except_group() {
+                    for f in &self.fields {
                         let number = f.proto_field.number();
 
                         w.case_block(number.to_string(), |w| {
-                            f.write_read_from_field(w, "_is");
+                            f.write_merge_from_field(w, "is", "&mut self.unknown_fields");
                         });
                     }
                     w.case_block("_", |w| {
-                        w.write_line("::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;");
+                        w.write_line("::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;");
                     });
                 });
             });
+            w.write_line(&format!("{}(())", self.std.path("result::Result::Ok")));
+        });
+    }
 
-            w.write_line("");
-
-            w.write_line(&format!("::std::result::Result::Ok({} {{", self.type_name));
-            w.indented(|w| {
-                let mut seen_oneofs = HashSet::new();
-
-                for field in self.fields_except_group() {
-                    match field.kind {
-                        FieldKind::Repeated(..) |
-                        FieldKind::Map(..) => {
-                            w.write_line(format!("{rust_name}: _field_{rust_name},",
-                                rust_name = field.rust_name));
-                        }
-                        FieldKind::Oneof(ref oneof) => {
-                            if seen_oneofs.insert(&oneof.oneof_name) {
-                                w.write_line(format!("{rust_name}: _field_{rust_name}.ok_or_else(|| ::protobuf::ProtobufError::message_not_initialized(\"missing required field: {type_name}::{rust_name}\"))?,",
-                                    rust_name = oneof.oneof_name,
-                                    type_name = self.type_name));
-                            }
-                        }
-                        FieldKind::Singular(ref sing) if sing.flag.is_required() => {
-                            w.write_line(format!("{rust_name}: _field_{rust_name}.ok_or_else(|| ::protobuf::ProtobufError::message_not_initialized(\"missing required field: {type_name}::{rust_name}\"))?,",
-                                rust_name = field.rust_name,
-                                type_name = self.type_name));
-                        }
-                        FieldKind::Singular(_) => {
-                            w.write_line(format!("{rust_name}: _field_{rust_name}.unwrap_or_default(),",
-                                rust_name = field.rust_name));
-                        }
+    fn write_merge(&self, w: &mut CodeWriter) {
+        w.def_fn("merge(&mut self, other: &Self)", |w| {
+            for f in self.fields_except_oneof() {
+                f.write_merge_field(w, "other");
+            }
+            for oneof in self.oneofs() {
+                let variants = oneof.variants();
+                let message_variants: Vec<_> = variants
+                    .iter()
+                    .filter(|v| v.field.proto_type == FieldDescriptorProto_Type::TYPE_MESSAGE)
+                    .collect();
+                let some = self.std.path("option::Option::Some");
+                w.if_let_stmt(&format!("{}(ref v)", some), &format!("other.{}", oneof.name()), |w| {
+                    if message_variants.is_empty() {
+                        w.write_line(&format!("self.{} = {}(v.clone());", oneof.name(), some));
+                        return;
                     }
-                }
-            });
-            w.write_line("})");
-        })
+                    w.match_block(&format!("(&mut self.{}, v)", oneof.name()), |w| {
+                        for variant in &message_variants {
+                            w.case_expr(
+                                format!("(&mut {some}({path}(ref mut m)), &{path}(ref v))",
+                                    some = some, path = variant.path()),
+                                "m.merge(v)",
+                            );
+                        }
+                        w.case_expr("(o, v)", format!("*o = {}(v.clone())", some));
+                    });
+                });
+            }
+            w.write_line("self.unknown_fields.merge(&other.unknown_fields);");
+        });
     }
 
     fn write_descriptor_field(&self, fields_var: &str, field: &FieldGen, w: &mut CodeWriter) {
@@ -271,21 +262,21 @@ impl<'a> MessageGen<'a> {
     }
 
     fn write_descriptor_static(&self, w: &mut CodeWriter) {
-        w.def_fn(&format!("descriptor_static(_: ::std::option::Option<{}>) -> &'static ::protobuf::reflect::MessageDescriptor", self.type_name), |w| {
+        w.def_fn(&format!("descriptor_static(_: {}<{}>) -> &'static ::protobuf::reflect::MessageDescriptor", self.std.path("option::Option"), self.type_name), |w| {
             w.lazy_static_decl_get("descriptor", "::protobuf::reflect::MessageDescriptor", |w| {
-                let fields = self.fields_except_group();
-                if fields.is_empty() {
-                    w.write_line(&format!("let fields = ::std::vec::Vec::new();"));
+                if self.fields.is_empty() {
+                    w.write_line(&format!("let fields = {}::new();", self.std.path("vec::Vec")));
                 } else {
-                    w.write_line(&format!("let mut fields = ::std::vec::Vec::new();"));
+                    w.write_line(&format!("let mut fields = {}::new();", self.std.path("vec::Vec")));
                 }
-                for field in fields {
+                for field in &self.fields {
                     self.write_descriptor_field("fields", field, w);;
                 }
                 w.write_line(&format!(
                     "::protobuf::reflect::MessageDescriptor::new::<{}>(", self.type_name));
                 w.indented(|w| {
-                    w.write_line(&format!("\"{}\",", self.type_name));
+                    // descriptor is looked up by legacy name regardless of naming mode
+                    w.write_line(&format!("\"{}\",", self.message.rust_name()));
                     w.write_line("fields,");
                     w.write_line("file_descriptor_proto()");
                 });
@@ -328,17 +319,31 @@ impl<'a> MessageGen<'a> {
             w.write_line("");
             self.write_read_from(w);
             w.write_line("");
+            self.write_merge_from(w);
+            w.write_line("");
+            self.write_merge(w);
+            w.write_line("");
             self.write_compute_size(w);
             w.write_line("");
             self.write_write_to_with_cached_sizes(w);
             w.write_line("");
-            w.def_fn("as_any(&self) -> &::std::any::Any", |w| {
-                w.write_line("self as &::std::any::Any");
+            w.def_fn("get_unknown_fields(&self) -> &::protobuf::UnknownFields", |w| {
+                w.write_line("&self.unknown_fields");
+            });
+            w.write_line("");
+            w.def_fn("mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields", |w| {
+                w.write_line("&mut self.unknown_fields");
+            });
+            w.write_line("");
+            let any = self.std.path("any::Any");
+            let boxed = self.std.path("boxed::Box");
+            w.def_fn(&format!("as_any(&self) -> &{}", any), |w| {
+                w.write_line(&format!("self as &{}", any));
             });
-            w.def_fn("as_any_mut(&mut self) -> &mut ::std::any::Any", |w| {
-                w.write_line("self as &mut ::std::any::Any");
+            w.def_fn(&format!("as_any_mut(&mut self) -> &mut {}", any), |w| {
+                w.write_line(&format!("self as &mut {}", any));
             });
-            w.def_fn("into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any>", |w| {
+            w.def_fn(&format!("into_any(self: {boxed}<Self>) -> {boxed}<{any}>", boxed = boxed, any = any), |w| {
                 w.write_line("self");
             });
             w.write_line("");
@@ -366,48 +371,100 @@ impl<'a> MessageGen<'a> {
     }
 
     fn write_impl_show(&self, w: &mut CodeWriter) {
-        w.impl_for_block("::std::fmt::Debug", &self.type_name, |w| {
-            w.def_fn("fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result", |w| {
+        w.impl_for_block(self.std.path("fmt::Debug"), &self.type_name, |w| {
+            let sig = format!(
+                "fmt(&self, f: &mut {}) -> {}",
+                self.std.path("fmt::Formatter"),
+                self.std.path("fmt::Result")
+            );
+            w.def_fn(&sig, |w| {
                 w.write_line("::protobuf::text_format::fmt(self, f)");
             });
         });
     }
 
     fn write_struct(&self, w: &mut CodeWriter) {
+        let file = self.message.get_file_descriptor();
+        write_doc_comment(w, file, &message_path(self.message));
         let mut derive = vec!["PartialEq", "Clone", "Default"];
         if self.lite_runtime {
             derive.push("Debug");
         }
-        w.derive(&derive);
+        write_derive(w, &message_derive(self.message, &derive));
+        if self.serde_derive {
+            write_serde_derive(w);
+        }
+        write_attrs(w, &message_attrs(self.message));
         w.pub_struct(&self.type_name, |w| {
             if !self.fields_except_oneof().is_empty() {
                 w.comment("message fields");
                 for field in self.fields_except_oneof() {
-                    if field.proto_type == FieldDescriptorProto_Type::TYPE_GROUP {
-                        w.comment(&format!("{}: <group>", &field.rust_name));
-                    } else {
-                        w.pub_field_decl(
-                            &field.rust_name,
-                            &field.full_storage_type().to_string(),
-                        );
-                    }
+                    write_doc_comment(w, file, &field_path(&field.proto_field));
+                    write_attrs(w, &field_attrs(field.proto_field.field));
+                    w.pub_field_decl(&field.rust_name, &field.full_storage_type().to_code(self.std));
                 }
             }
             if !self.oneofs().is_empty() {
                 w.comment("message oneof groups");
                 for oneof in self.oneofs() {
-                    w.pub_field_decl(oneof.name(), &oneof.full_storage_type().to_string());
+                    oneof.write_doc_comment(w);
+                    w.pub_field_decl(oneof.name(), &oneof.full_storage_type().to_code(self.std));
                 }
             }
+            w.comment("special fields");
+            w.pub_field_decl("unknown_fields", "::protobuf::UnknownFields");
+        });
+    }
+
+    // nested messages except map entries, because they are not used in map fields
+    fn nested_messages(&self) -> Vec<MessageWithScope<'a>> {
+        self.message
+            .to_scope()
+            .get_messages()
+            .into_iter()
+            .filter(|nested| nested.map_entry().is_none())
+            .collect()
+    }
+
+    fn write_nested(&self, w: &mut CodeWriter) {
+        for nested in &self.nested_messages() {
+            w.write_line("");
+            MessageGen::new(nested, self.root_scope).write(w);
+        }
+
+        for enum_type in &self.message.to_scope().get_enums() {
+            w.write_line("");
+            EnumGen::new(enum_type).write(w);
+        }
+    }
+
+    // module with nested types and oneofs in idiomatic naming mode
+    fn write_nested_mod(&self, w: &mut CodeWriter) {
+        if !has_nested_mod(self.message) {
+            return;
+        }
+
+        w.write_line("");
+        w.pub_mod(&message_module_name(self.message.message), |w| {
+            w.write_line("use super::*;");
+            for oneof in self.oneofs() {
+                w.write_line("");
+                oneof.write_enum(w);
+            }
+            self.write_nested(w);
         });
     }
 
     pub fn write(&self, w: &mut CodeWriter) {
+        let idiomatic = idiomatic_names(self.message.get_file_descriptor());
+
         self.write_struct(w);
 
-        for oneof in self.oneofs() {
-            w.write_line("");
-            oneof.write_enum(w);
+        if !idiomatic {
+            for oneof in self.oneofs() {
+                w.write_line("");
+                oneof.write_enum(w);
+            }
         }
 
         w.write_line("");
@@ -421,20 +478,10 @@ impl<'a> MessageGen<'a> {
         w.write_line("");
         self.write_impl_value(w);
 
-        let mut nested_prefix = self.type_name.to_string();
-        nested_prefix.push_str("_");
-
-        for nested in &self.message.to_scope().get_messages() {
-            // ignore map entries, because they are not used in map fields
-            if nested.map_entry().is_none() {
-                w.write_line("");
-                MessageGen::new(nested, self.root_scope).write(w);
-            }
-        }
-
-        for enum_type in &self.message.to_scope().get_enums() {
-            w.write_line("");
-            EnumGen::new(enum_type, self.message.get_scope().get_file_descriptor()).write(w);
+        if idiomatic {
+            self.write_nested_mod(w);
+        } else {
+            self.write_nested(w);
         }
     }
 }
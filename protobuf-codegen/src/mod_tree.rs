@@ -0,0 +1,168 @@
+//! Layout of generated files.
+//!
+//! By default `a/b/c.proto` is generated into `c.rs`, and all generated files
+//! are expected to be sibling modules.
+//!
+//! With `rustproto.package_modules_all` option file `a/b/c.proto` with `package x.y`
+//! is generated into `x/y/c.rs`, and `mod.rs` files are generated for each package:
+//! `mod.rs` declares `x`, `x/mod.rs` declares `y`, `x/y/mod.rs` declares `c`
+//! and reexports its messages, enums and services, so types are accessible as `x::y::TypeName`.
+//! Other items (`file_descriptor_proto`, `exts`) are only accessible through the file module.
+//! Types from other files are referenced relative to the root module.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use protobuf::descriptor::*;
+use protobuf::descriptorx::*;
+use protobuf::rust;
+use protobuf::rustproto;
+use protobuf::compiler_plugin::GenResult;
+
+use super::code_writer::CodeWriter;
+use super::message::has_nested_mod;
+use super::naming::*;
+use super::service::service_type_names;
+
+
+/// Module tree mode is enabled for the file
+pub fn package_modules(file: &FileDescriptorProto) -> bool {
+    rustproto::exts::package_modules_all
+        .get(file.get_options())
+        .unwrap_or(false)
+}
+
+fn package_module_name(component: &str) -> String {
+    if rust::is_rust_keyword(component) {
+        format!("{}_pb", component)
+    } else {
+        component.to_owned()
+    }
+}
+
+/// Modules of the package of the file relative to the root module,
+/// empty when module tree mode is not enabled
+fn package_modules_path(file: &FileDescriptorProto) -> Vec<String> {
+    if !package_modules(file) || file.get_package().is_empty() {
+        return Vec::new();
+    }
+    file.get_package().split('.').map(package_module_name).collect()
+}
+
+/// Path to the file module relative to the root module (`x::y::c`)
+pub fn file_module_path(file: &FileDescriptorProto) -> String {
+    let mut path = package_modules_path(file);
+    path.push(proto_path_to_rust_mod(file.get_name()));
+    path.join("::")
+}
+
+/// Depth of the file module relative to the root module
+pub fn file_module_depth(file: &FileDescriptorProto) -> usize {
+    package_modules_path(file).len() + 1
+}
+
+/// Name of generated file relative to output directory (`x/y/c.rs`)
+pub fn file_output_name(file: &FileDescriptorProto) -> String {
+    let mut path = package_modules_path(file);
+    path.push(format!("{}.rs", proto_path_to_rust_mod(file.get_name())));
+    path.join("/")
+}
+
+/// Names of messages, enums and services declared in the file module
+fn file_type_names(file: &FileDescriptorProto) -> Vec<String> {
+    let file_scope = FileScope { file_descriptor: file };
+    let mut names = Vec::new();
+    if idiomatic_names(file) {
+        for message in file_scope.to_scope().get_messages() {
+            names.push(type_decl_name(&message));
+            // nested types are accessible through the module
+            if has_nested_mod(&message) {
+                names.push(message_module_name(message.message));
+            }
+        }
+        for en in file_scope.to_scope().get_enums() {
+            names.push(type_decl_name(&en));
+        }
+    } else {
+        for message in file_scope.find_messages() {
+            if message.map_entry().is_some() {
+                continue;
+            }
+            names.push(message.rust_name());
+            for oneof in message.oneofs() {
+                names.push(oneof.rust_name());
+            }
+        }
+        for en in file_scope.find_enums() {
+            names.push(en.rust_name());
+        }
+    }
+    for service in file.get_service() {
+        names.extend(service_type_names(service));
+    }
+    names
+}
+
+#[derive(Default)]
+struct PackageModule {
+    packages: BTreeSet<String>,
+    // file module name and names reexported from it
+    files: BTreeMap<String, Vec<String>>,
+}
+
+/// `mod.rs` files for generated files, nothing if module tree mode is not enabled
+pub fn gen_mod_rs(files: &[&FileDescriptorProto]) -> Vec<GenResult> {
+    if !files.iter().any(|f| package_modules(f)) {
+        return Vec::new();
+    }
+
+    let mut packages: BTreeMap<Vec<String>, PackageModule> = BTreeMap::new();
+    packages.insert(Vec::new(), PackageModule::default());
+
+    for file in files {
+        let path = package_modules_path(file);
+        for i in 0..path.len() {
+            packages
+                .entry(path[..i].to_vec())
+                .or_insert_with(Default::default)
+                .packages
+                .insert(path[i].clone());
+        }
+        packages
+            .entry(path)
+            .or_insert_with(Default::default)
+            .files
+            .insert(proto_path_to_rust_mod(file.get_name()), file_type_names(file));
+    }
+
+    packages
+        .into_iter()
+        .map(|(path, module)| {
+            let mut v = Vec::new();
+            {
+                let mut w = CodeWriter::new(&mut v);
+                w.write_generated_by("rust-protobuf", env!("CARGO_PKG_VERSION"));
+                if !module.packages.is_empty() {
+                    w.write_line("");
+                }
+                for package in &module.packages {
+                    w.write_line(&format!("pub mod {};", package));
+                }
+                for (file, names) in &module.files {
+                    w.write_line("");
+                    w.write_line(&format!("pub mod {};", file));
+                    for name in names {
+                        w.write_line(&format!("pub use self::{}::{};", file, name));
+                    }
+                }
+            }
+
+            let mut name = path;
+            name.push("mod.rs".to_owned());
+            GenResult {
+                name: name.join("/"),
+                content: v,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,172 @@
+//! Names of generated types.
+//!
+//! By default nested types are flattened into the file module
+//! (`FileOptions_OptimizeMode`) and enum variants are named like values in `.proto` file.
+//!
+//! With `rustproto.idiomatic_names_all` option types nested in message `FooBar`
+//! are generated in module `foo_bar` (`file_options::OptimizeMode`),
+//! oneof enums are generated in that module too (`value::Kind`),
+//! and enum variants are CamelCase with enum name prefix stripped
+//! (`TYPE_DOUBLE` of enum `Type` is `Double`).
+
+use protobuf::descriptor::*;
+use protobuf::descriptorx::*;
+use protobuf::rust;
+use protobuf::rustproto;
+
+
+/// Idiomatic naming mode is enabled for the file
+pub fn idiomatic_names(file: &FileDescriptorProto) -> bool {
+    rustproto::exts::idiomatic_names_all
+        .get(file.get_options())
+        .unwrap_or(false)
+}
+
+/// Convert `SayHello` or `sayHello` to `say_hello`
+pub fn camel_to_snake(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut r = String::with_capacity(s.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i != 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).map_or(false, |n| n.is_lowercase());
+            // `HTTPRequest` is `http_request`
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_lower) {
+                r.push('_');
+            }
+        }
+        r.extend(c.to_lowercase());
+    }
+    r
+}
+
+/// Convert `TYPE_DOUBLE` or `type_double` to `TypeDouble`
+fn snake_to_camel(s: &str) -> String {
+    let mut r = String::with_capacity(s.len());
+    for word in s.split('_') {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            r.extend(first.to_uppercase());
+            r.extend(chars.flat_map(|c| c.to_lowercase()));
+        }
+    }
+    r
+}
+
+/// Name of the module containing types nested in the message
+pub fn message_module_name(message: &DescriptorProto) -> String {
+    let name = camel_to_snake(message.get_name());
+    if rust::is_rust_keyword(&name) {
+        format!("message_{}", name)
+    } else {
+        name
+    }
+}
+
+/// Depth of the module where types declared in the scope are generated,
+/// relative to the file module
+pub fn scope_depth(scope: &Scope) -> usize {
+    if idiomatic_names(scope.get_file_descriptor()) {
+        scope.path.len()
+    } else {
+        0
+    }
+}
+
+/// `super::super::` for depth 2
+pub fn super_prefix(depth: usize) -> String {
+    "super::".repeat(depth)
+}
+
+/// Name of message or enum in the module where it is declared
+pub fn type_decl_name<'a, W : WithScope<'a>>(t: &W) -> String {
+    if !idiomatic_names(t.get_file_descriptor()) {
+        return t.rust_name();
+    }
+    if rust::is_rust_keyword(t.get_name()) {
+        format!("{}{}", t.escape_prefix(), t.get_name())
+    } else {
+        t.get_name().to_owned()
+    }
+}
+
+/// Path to message or enum relative to the file module
+pub fn type_path_from_file<'a, W : WithScope<'a>>(t: &W) -> String {
+    if !idiomatic_names(t.get_file_descriptor()) {
+        return t.rust_name();
+    }
+    let mut r = String::new();
+    for m in &t.get_scope().path {
+        r.push_str(&message_module_name(m));
+        r.push_str("::");
+    }
+    r.push_str(&type_decl_name(t));
+    r
+}
+
+/// Name of oneof enum in the module where it is declared
+pub fn oneof_decl_name(oneof: &OneofWithContext) -> String {
+    if idiomatic_names(oneof.message.get_file_descriptor()) {
+        snake_to_camel(oneof.oneof.get_name())
+    } else {
+        oneof.rust_name()
+    }
+}
+
+/// Path to oneof enum relative to the module where the message is declared
+pub fn oneof_path_from_message(oneof: &OneofWithContext) -> String {
+    if idiomatic_names(oneof.message.get_file_descriptor()) {
+        format!("{}::{}", message_module_name(oneof.message.message), oneof_decl_name(oneof))
+    } else {
+        oneof.rust_name()
+    }
+}
+
+/// Name of enum variant
+pub fn enum_value_name(en: &EnumWithScope, value: &EnumValueDescriptorProto) -> String {
+    if !idiomatic_names(en.get_file_descriptor()) {
+        return value.rust_name();
+    }
+
+    let prefix = format!("{}_", camel_to_snake(en.en.get_name()).to_uppercase());
+    let name = match value.get_name().starts_with(&prefix) {
+        true => &value.get_name()[prefix.len()..],
+        false => value.get_name(),
+    };
+    // keep the prefix if the name would not be an identifier without it
+    let name = match name.chars().next() {
+        Some(c) if !c.is_numeric() => name,
+        _ => value.get_name(),
+    };
+
+    let name = snake_to_camel(name);
+    if rust::is_rust_keyword(&name) {
+        format!("Value{}", name)
+    } else {
+        name
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::camel_to_snake;
+    use super::snake_to_camel;
+
+    #[test]
+    fn test_camel_to_snake() {
+        assert_eq!("say_hello", camel_to_snake("SayHello"));
+        assert_eq!("say_hello", camel_to_snake("sayHello"));
+        assert_eq!("get_http_request", camel_to_snake("GetHTTPRequest"));
+        assert_eq!("get2_fa", camel_to_snake("Get2Fa"));
+        assert_eq!("list", camel_to_snake("list"));
+    }
+
+    #[test]
+    fn test_snake_to_camel() {
+        assert_eq!("Double", snake_to_camel("DOUBLE"));
+        assert_eq!("LiteRuntime", snake_to_camel("LITE_RUNTIME"));
+        assert_eq!("TestOneof", snake_to_camel("test_oneof"));
+        assert_eq!("Proto3", snake_to_camel("proto3"));
+    }
+}
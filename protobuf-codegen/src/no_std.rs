@@ -0,0 +1,73 @@
+//! Generated code for `no_std` crates.
+//!
+//! With `rustproto.no_std_all` option generated code refers to `core` and `alloc`
+//! instead of `std`, and map fields are stored in `BTreeMap`, because `HashMap`
+//! is not available in `alloc`. Crate including generated code must declare
+//! `extern crate alloc;`, and `protobuf` crate may be used with `std` feature disabled.
+
+use protobuf::descriptor::*;
+use protobuf::rustproto;
+
+
+/// `no_std` mode is enabled for the file
+pub fn no_std(file: &FileDescriptorProto) -> bool {
+    rustproto::exts::no_std_all
+        .get(file.get_options())
+        .unwrap_or(false)
+}
+
+// modules of `std` used by generated code which are reexported from `alloc`,
+// other modules are reexported from `core`
+const ALLOC_MODULES: &[&str] = &["boxed", "collections", "string", "vec"];
+
+/// Crate which standard library paths in generated code start with
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StdCrate {
+    /// `::std`
+    Std,
+    /// `::core` or `::alloc`, in `no_std` mode
+    CoreAlloc,
+}
+
+impl StdCrate {
+    pub fn for_file(file: &FileDescriptorProto) -> StdCrate {
+        if no_std(file) {
+            StdCrate::CoreAlloc
+        } else {
+            StdCrate::Std
+        }
+    }
+
+    /// Absolute path to an item of the standard library,
+    /// e. g. `option::Option` is `::std::option::Option` or `::core::option::Option`
+    pub fn path(self, path: &str) -> String {
+        let krate = match self {
+            StdCrate::Std => "std",
+            StdCrate::CoreAlloc => {
+                let module = path.split("::").next().unwrap();
+                if ALLOC_MODULES.contains(&module) {
+                    "alloc"
+                } else {
+                    "core"
+                }
+            }
+        };
+        format!("::{}::{}", krate, path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::StdCrate;
+
+    #[test]
+    fn test_path() {
+        assert_eq!("::std::vec::Vec", StdCrate::Std.path("vec::Vec"));
+        assert_eq!("::alloc::vec::Vec", StdCrate::CoreAlloc.path("vec::Vec"));
+        assert_eq!("::core::option::Option", StdCrate::CoreAlloc.path("option::Option"));
+        assert_eq!(
+            "::alloc::collections::BTreeMap",
+            StdCrate::CoreAlloc.path("collections::BTreeMap")
+        );
+    }
+}
@@ -1,9 +1,13 @@
-use std::fmt;
 use std::cmp;
 
 use protobuf::descriptor::*;
 use protobuf::descriptorx::*;
 use super::well_known_types::is_well_known_type_full;
+use super::naming::super_prefix;
+use super::naming::type_path_from_file;
+use super::mod_tree::file_module_path;
+use super::mod_tree::file_module_depth;
+use super::no_std::StdCrate;
 
 
 // Represent subset of rust types used in generated code
@@ -16,6 +20,7 @@ pub enum RustType {
     Bool,
     Vec(Box<RustType>),
     HashMap(Box<RustType>, Box<RustType>),
+    BTreeMap(Box<RustType>, Box<RustType>),
     String,
     // [T], not &[T]
     Slice(Box<RustType>),
@@ -33,54 +38,67 @@ pub enum RustType {
     Message(String),
     // protobuf enum, not any enum
     Enum(String, String),
+    // protobuf enum which may hold unknown values: enum name, default value
+    EnumOrUnknown(String, String),
     // oneof enum
     Oneof(String),
     // bytes::Bytes
     Bytes,
     // chars::Chars
     Chars,
-    // group
-    Group,
 }
 
-impl fmt::Display for RustType {
-    #[inline]
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl RustType {
+    /// Type as written in generated code
+    pub fn to_code(&self, std: StdCrate) -> String {
         match *self {
-            RustType::Int(true, bits) => write!(f, "i{}", bits),
-            RustType::Int(false, bits) => write!(f, "u{}", bits),
-            RustType::Float(bits) => write!(f, "f{}", bits),
-            RustType::Bool => write!(f, "bool"),
-            RustType::Vec(ref param) => write!(f, "::std::vec::Vec<{}>", **param),
-            RustType::HashMap(ref key, ref value) => {
-                write!(f, "::std::collections::HashMap<{}, {}>", **key, **value)
+            RustType::Int(true, bits) => format!("i{}", bits),
+            RustType::Int(false, bits) => format!("u{}", bits),
+            RustType::Float(bits) => format!("f{}", bits),
+            RustType::Bool => format!("bool"),
+            RustType::Vec(ref param) |
+            RustType::RepeatedField(ref param) => {
+                format!("{}<{}>", std.path("vec::Vec"), param.to_code(std))
+            }
+            RustType::HashMap(ref key, ref value) => format!(
+                "{}<{}, {}>",
+                std.path("collections::HashMap"),
+                key.to_code(std),
+                value.to_code(std)
+            ),
+            RustType::BTreeMap(ref key, ref value) => format!(
+                "{}<{}, {}>",
+                std.path("collections::BTreeMap"),
+                key.to_code(std),
+                value.to_code(std)
+            ),
+            RustType::String => std.path("string::String"),
+            RustType::Slice(ref param) => format!("[{}]", param.to_code(std)),
+            RustType::Str => format!("str"),
+            RustType::Option(ref param) => {
+                format!("{}<{}>", std.path("option::Option"), param.to_code(std))
             }
-            RustType::String => write!(f, "::std::string::String"),
-            RustType::Slice(ref param) => write!(f, "[{}]", **param),
-            RustType::Str => write!(f, "str"),
-            RustType::Option(ref param) => write!(f, "::std::option::Option<{}>", **param),
             RustType::SingularField(ref param) => {
-                write!(f, "::protobuf::SingularField<{}>", **param)
+                format!("::protobuf::SingularField<{}>", param.to_code(std))
             }
             RustType::SingularPtrField(ref param) => {
-                write!(f, "::protobuf::SingularPtrField<{}>", **param)
+                format!("::protobuf::SingularPtrField<{}>", param.to_code(std))
             }
-            RustType::RepeatedField(ref param) => {
-                write!(f, "::std::vec::Vec<{}>", **param)
+            RustType::Uniq(ref param) => {
+                format!("{}<{}>", std.path("boxed::Box"), param.to_code(std))
             }
-            RustType::Uniq(ref param) => write!(f, "::std::boxed::Box<{}>", **param),
-            RustType::Ref(ref param) => write!(f, "&{}", **param),
+            RustType::Ref(ref param) => format!("&{}", param.to_code(std)),
             RustType::Message(ref name) |
             RustType::Enum(ref name, _) |
-            RustType::Oneof(ref name) => write!(f, "{}", name),
-            RustType::Group => write!(f, "<group>"),
-            RustType::Bytes => write!(f, "::bytes::Bytes"),
-            RustType::Chars => write!(f, "::protobuf::chars::Chars"),
+            RustType::Oneof(ref name) => format!("{}", name),
+            RustType::EnumOrUnknown(ref name, _) => {
+                format!("::protobuf::ProtobufEnumOrUnknown<{}>", name)
+            }
+            RustType::Bytes => format!("::bytes::Bytes"),
+            RustType::Chars => format!("::protobuf::chars::Chars"),
         }
     }
-}
 
-impl RustType {
     pub fn u8() -> RustType {
         RustType::Int(false, 8)
     }
@@ -100,6 +118,8 @@ impl RustType {
             true
         } else if let RustType::Enum(..) = *self {
             true
+        } else if let RustType::EnumOrUnknown(..) = *self {
+            true
         } else {
             false
         }
@@ -140,6 +160,13 @@ impl RustType {
         }
     }
 
+    fn is_enum_or_unknown(&self) -> bool {
+        match *self {
+            RustType::EnumOrUnknown(..) => true,
+            _ => false,
+        }
+    }
+
     pub fn is_u8(&self) -> bool {
         match *self {
             RustType::Int(false, 8) => true,
@@ -155,22 +182,23 @@ impl RustType {
     }
 
     // default value for type
-    pub fn default_value(&self) -> String {
+    pub fn default_value(&self, std: StdCrate) -> String {
         match *self {
             RustType::Ref(ref t) if t.is_str() => "\"\"".to_string(),
             RustType::Ref(ref t) if t.is_slice() => "&[]".to_string(),
             RustType::Int(..) => "0".to_string(),
             RustType::Float(..) => "0.".to_string(),
             RustType::Bool => "false".to_string(),
-            RustType::Vec(..) => "::std::vec::Vec::new()".to_string(),
-            RustType::HashMap(..) => "::std::collections::HashMap::new()".to_string(),
-            RustType::String => "::std::string::String::new()".to_string(),
+            RustType::Vec(..) |
+            RustType::RepeatedField(..) => format!("{}::new()", std.path("vec::Vec")),
+            RustType::HashMap(..) => format!("{}::new()", std.path("collections::HashMap")),
+            RustType::BTreeMap(..) => format!("{}::new()", std.path("collections::BTreeMap")),
+            RustType::String => format!("{}::new()", std.path("string::String")),
             RustType::Bytes => "::bytes::Bytes::new()".to_string(),
             RustType::Chars => "::protobuf::chars::Chars::new()".to_string(),
-            RustType::Option(..) => "::std::option::Option::None".to_string(),
+            RustType::Option(..) => std.path("option::Option::None"),
             RustType::SingularField(..) => "::protobuf::SingularField::none()".to_string(),
             RustType::SingularPtrField(..) => "::protobuf::SingularPtrField::none()".to_string(),
-            RustType::RepeatedField(..) => "::std::vec::Vec::new()".to_string(),
             RustType::Message(ref name) => format!("{}::new()", name),
             RustType::Ref(ref m) if m.is_message() => {
                 match **m {
@@ -180,41 +208,52 @@ impl RustType {
             }
             // Note: default value of enum type may not be equal to default value of field
             RustType::Enum(ref name, ref default) => format!("{}::{}", name, default),
-            _ => panic!("cannot create default value for: {}", *self),
+            RustType::EnumOrUnknown(ref name, ref default) => {
+                format!("::protobuf::ProtobufEnumOrUnknown::new({}::{})", name, default)
+            }
+            _ => panic!("cannot create default value for: {:?}", *self),
         }
     }
 
-    pub fn default_value_typed(self) -> RustValueTyped {
+    pub fn default_value_typed(self, std: StdCrate) -> RustValueTyped {
         RustValueTyped {
-            value: self.default_value(),
+            value: self.default_value(std),
             rust_type: self,
         }
     }
 
     // wrap value in storage type
-    pub fn wrap_value(&self, value: &str) -> String {
+    pub fn wrap_value(&self, value: &str, std: StdCrate) -> String {
         match *self {
-            RustType::Option(..) => format!("::std::option::Option::Some({})", value),
+            RustType::Option(ref t) => match **t {
+                RustType::Uniq(..) => format!(
+                    "{}({}({}))",
+                    std.path("option::Option::Some"),
+                    std.path("boxed::Box::new"),
+                    value
+                ),
+                _ => format!("{}({})", std.path("option::Option::Some"), value),
+            },
             RustType::SingularField(..) => format!("::protobuf::SingularField::some({})", value),
             RustType::SingularPtrField(..) => {
                 format!("::protobuf::SingularPtrField::some({})", value)
             }
-            _ => panic!("not a wrapper type: {}", *self),
+            _ => panic!("not a wrapper type: {:?}", *self),
         }
     }
 
     // expression to convert `v` of type `self` to type `target`
-    pub fn into_target(&self, target: &RustType, v: &str) -> String {
-        self.try_into_target(target, v)
-            .expect(&format!("failed to convert {} into {}", self, target))
+    pub fn into_target(&self, target: &RustType, v: &str, std: StdCrate) -> String {
+        self.try_into_target(target, v, std)
+            .expect(&format!("failed to convert {:?} into {:?}", self, target))
     }
 
-    fn try_into_target(&self, target: &RustType, v: &str) -> Result<String, ()> {
+    fn try_into_target(&self, target: &RustType, v: &str, std: StdCrate) -> Result<String, ()> {
         match (self, target) {
             (x, y) if x == y => return Ok(format!("{}", v)),
             (&RustType::Ref(ref x), y) if **x == *y => return Ok(format!("*{}", v)),
             (x, &RustType::Uniq(ref y)) if *x == **y => {
-                return Ok(format!("::std::boxed::Box::new({})", v))
+                return Ok(format!("{}({})", std.path("boxed::Box::new"), v))
             }
             (&RustType::Uniq(ref x), y) if **x == *y => return Ok(format!("*{}", v)),
             (&RustType::String, &RustType::Ref(ref t)) if **t == RustType::Str => {
@@ -255,11 +294,17 @@ impl RustType {
             (&RustType::Ref(ref t), &RustType::Int(true, 32)) if t.is_enum() => {
                 return Ok(format!("{}.value()", v))
             }
+            (&RustType::EnumOrUnknown(..), &RustType::Int(true, 32)) => {
+                return Ok(format!("{}.value()", v))
+            }
+            (&RustType::Ref(ref t), &RustType::Int(true, 32)) if t.is_enum_or_unknown() => {
+                return Ok(format!("{}.value()", v))
+            }
             _ => (),
         };
 
         if let &RustType::Ref(ref s) = self {
-            if let Ok(conv) = s.try_into_target(target, v) {
+            if let Ok(conv) = s.try_into_target(target, v, std) {
                 return Ok(conv);
             }
         }
@@ -276,14 +321,14 @@ impl RustType {
             &RustType::RepeatedField(ref p) => RustType::Slice(p.clone()),
             &RustType::Bytes => RustType::Slice(Box::new(RustType::u8())),
             &RustType::Message(ref p) => RustType::Message(p.clone()),
-            x => panic!("no ref type for {}", x),
+            x => panic!("no ref type for {:?}", x),
         }))
     }
 
     pub fn elem_type(&self) -> RustType {
         match self {
             &RustType::Option(ref ty) => (**ty).clone(),
-            x => panic!("cannot get elem type of {}", x),
+            x => panic!("cannot get elem type of {:?}", x),
         }
     }
 
@@ -295,7 +340,7 @@ impl RustType {
             &RustType::RepeatedField(ref ty) |
             &RustType::SingularField(ref ty) |
             &RustType::SingularPtrField(ref ty) => RustType::Ref(ty.clone()),
-            x => panic!("cannot iterate {}", x),
+            x => panic!("cannot iterate {:?}", x),
         }
     }
 
@@ -315,8 +360,8 @@ pub struct RustValueTyped {
 }
 
 impl RustValueTyped {
-    pub fn into_type(&self, target: RustType) -> RustValueTyped {
-        let target_value = self.rust_type.into_target(&target, &self.value);
+    pub fn into_type(&self, target: RustType, std: StdCrate) -> RustValueTyped {
+        let target_value = self.rust_type.into_target(&target, &self.value, std);
         RustValueTyped {
             value: target_value,
             rust_type: target,
@@ -396,20 +441,17 @@ fn is_descriptor_proto(file: &FileDescriptorProto) -> bool {
         && file_last_component(file.get_name()) == "descriptor.proto"
 }
 
+/// Path to message or enum referenced from module `depth` levels below the file module
 pub fn type_name_to_rust_relative(
     type_name: &str,
     file: &FileDescriptorProto,
-    subm: bool,
+    depth: usize,
     root_scope: &RootScope,
 ) -> String {
     let message_or_enum = root_scope.find_message_or_enum(type_name);
     if message_or_enum.get_scope().get_file_descriptor().get_name() == file.get_name() {
         // field type is a message or enum declared in the same file
-        if subm {
-            format!("super::{}", message_or_enum.rust_name())
-        } else {
-            format!("{}", message_or_enum.rust_name())
-        }
+        format!("{}{}", super_prefix(depth), type_path_from_file(&message_or_enum))
     } else if let Some(name) = is_well_known_type_full(type_name) {
         // Well-known types are included in rust-protobuf library
         // https://developers.google.com/protocol-buffers/docs/reference/google.protobuf
@@ -418,15 +460,15 @@ pub fn type_name_to_rust_relative(
         // Messages defined in descriptor.proto
         format!("::protobuf::descriptor::{}", message_or_enum.name_to_package())
     } else {
-        if subm {
-            format!("super::super::{}", message_or_enum.rust_fq_name())
-        } else {
-            format!("super::{}", message_or_enum.rust_fq_name())
-        }
+        format!(
+            "{}{}::{}",
+            super_prefix(depth + file_module_depth(file)),
+            file_module_path(message_or_enum.get_file_descriptor()),
+            type_path_from_file(&message_or_enum)
+        )
     }
 }
 
-
 fn capitalize(s: &str) -> String {
     if s.is_empty() {
         return String::new();
@@ -450,6 +492,7 @@ pub enum ProtobufTypeGen {
     Primitive(FieldDescriptorProto_Type, PrimitiveTypeVariant),
     Message(String),
     Enum(String),
+    EnumOrUnknown(String),
 }
 
 impl ProtobufTypeGen {
@@ -476,6 +519,9 @@ impl ProtobufTypeGen {
             &ProtobufTypeGen::Enum(ref name) => {
                 format!("::protobuf::types::ProtobufTypeEnum<{}>", name)
             }
+            &ProtobufTypeGen::EnumOrUnknown(ref name) => {
+                format!("::protobuf::types::ProtobufTypeEnumOrUnknown<{}>", name)
+            }
         }
     }
 }
@@ -0,0 +1,256 @@
+//! Rustdoc of generated code from comments in `.proto` file.
+//!
+//! `protoc` stores comments in `SourceCodeInfo` of the file descriptor
+//! (only if asked with `--include_source_info`, or when invoking a plugin).
+//! Location of a declaration is identified by a path of field numbers and indices
+//! in `FileDescriptorProto`: `[4, 0, 2, 1]` is the second field of the first message.
+
+use std::ptr;
+
+use protobuf::descriptor::*;
+use protobuf::descriptorx::*;
+
+use super::code_writer::CodeWriter;
+
+
+// field numbers from descriptor.proto
+const FILE_MESSAGE_TYPE: i32 = 4;
+const FILE_ENUM_TYPE: i32 = 5;
+const FILE_SERVICE: i32 = 6;
+const MESSAGE_FIELD: i32 = 2;
+const MESSAGE_NESTED_TYPE: i32 = 3;
+const MESSAGE_ENUM_TYPE: i32 = 4;
+const MESSAGE_ONEOF_DECL: i32 = 8;
+const ENUM_VALUE: i32 = 2;
+const SERVICE_METHOD: i32 = 2;
+
+fn index_of<T>(items: &[T], item: &T) -> i32 {
+    items
+        .iter()
+        .position(|i| ptr::eq(i, item))
+        .expect("item is not found in descriptor") as i32
+}
+
+// path to the innermost message of the scope
+fn scope_path(scope: &Scope) -> Vec<i32> {
+    let mut path = Vec::new();
+    let mut messages = scope.get_file_descriptor().get_message_type();
+    let mut field_number = FILE_MESSAGE_TYPE;
+    for message in &scope.path {
+        path.push(field_number);
+        path.push(index_of(messages, *message));
+        messages = message.get_nested_type();
+        field_number = MESSAGE_NESTED_TYPE;
+    }
+    path
+}
+
+pub fn message_path(message: &MessageWithScope) -> Vec<i32> {
+    scope_path(&message.to_scope())
+}
+
+pub fn field_path(field: &FieldWithContext) -> Vec<i32> {
+    let mut path = message_path(&field.message);
+    path.push(MESSAGE_FIELD);
+    path.push(index_of(field.message.message.get_field(), field.field));
+    path
+}
+
+pub fn oneof_path(oneof: &OneofWithContext) -> Vec<i32> {
+    let mut path = message_path(oneof.message);
+    path.push(MESSAGE_ONEOF_DECL);
+    path.push(oneof.index as i32);
+    path
+}
+
+pub fn enum_path(en: &EnumWithScope) -> Vec<i32> {
+    let mut path = scope_path(&en.scope);
+    match en.scope.path.last() {
+        Some(message) => {
+            path.push(MESSAGE_ENUM_TYPE);
+            path.push(index_of(message.get_enum_type(), en.en));
+        }
+        None => {
+            path.push(FILE_ENUM_TYPE);
+            path.push(index_of(en.scope.get_file_descriptor().get_enum_type(), en.en));
+        }
+    }
+    path
+}
+
+pub fn enum_value_path(en: &EnumWithScope, index: usize) -> Vec<i32> {
+    let mut path = enum_path(en);
+    path.push(ENUM_VALUE);
+    path.push(index as i32);
+    path
+}
+
+pub fn service_path(file: &FileDescriptorProto, service: &ServiceDescriptorProto) -> Vec<i32> {
+    vec![FILE_SERVICE, index_of(file.get_service(), service)]
+}
+
+pub fn method_path(
+    file: &FileDescriptorProto,
+    service: &ServiceDescriptorProto,
+    method: &MethodDescriptorProto,
+) -> Vec<i32> {
+    let mut path = service_path(file, service);
+    path.push(SERVICE_METHOD);
+    path.push(index_of(service.get_method(), method));
+    path
+}
+
+fn location<'a>(file: &'a FileDescriptorProto, path: &[i32]) -> Option<&'a SourceCodeInfo_Location> {
+    file.get_source_code_info()
+        .get_location()
+        .iter()
+        .filter(|l| l.get_path() == path)
+        .next()
+}
+
+// comment line without the space after `//`
+fn strip_comment_line(line: &str) -> &str {
+    let line = line.trim_right();
+    if line.starts_with(' ') {
+        &line[1..]
+    } else {
+        line
+    }
+}
+
+fn is_fence(line: &str) -> bool {
+    line.trim_left().starts_with("```")
+}
+
+fn is_indented_code(line: &str) -> bool {
+    line.starts_with("    ") || line.starts_with('\t')
+}
+
+fn dedent_code(line: &str) -> &str {
+    if line.starts_with('\t') {
+        &line[1..]
+    } else if line.starts_with("    ") {
+        &line[4..]
+    } else {
+        line
+    }
+}
+
+// escape characters which rustdoc would interpret as links or HTML,
+// except in code spans
+fn escape_markdown(line: &str) -> String {
+    let mut r = String::with_capacity(line.len());
+    let mut code_span = false;
+    for c in line.chars() {
+        match c {
+            '`' => code_span = !code_span,
+            '\\' | '[' | ']' | '<' if !code_span => r.push('\\'),
+            _ => {}
+        }
+        r.push(c);
+    }
+    r
+}
+
+/// Convert comment from `.proto` file into lines of markdown.
+///
+/// Code blocks are marked as `text`, otherwise rustdoc would compile them as doctests.
+fn comment_to_markdown(comment: &str) -> Vec<String> {
+    let lines: Vec<&str> = comment.lines().map(strip_comment_line).collect();
+
+    let mut r: Vec<String> = Vec::new();
+    let mut fence = false;
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        if fence {
+            fence = !is_fence(line);
+            r.push(line.to_owned());
+        } else if is_fence(line) {
+            fence = true;
+            if line.trim()[3..].is_empty() {
+                r.push(format!("{}text", line));
+            } else {
+                r.push(line.to_owned());
+            }
+        } else if is_indented_code(line) && r.last().map_or(true, |l| l.is_empty()) {
+            // indented code block ends before first non-blank line which is not indented
+            let mut end = i;
+            for j in i..lines.len() {
+                if is_indented_code(lines[j]) {
+                    end = j + 1;
+                } else if !lines[j].is_empty() {
+                    break;
+                }
+            }
+            r.push("```text".to_owned());
+            for code in &lines[i..end] {
+                r.push(dedent_code(code).to_owned());
+            }
+            r.push("```".to_owned());
+            i = end;
+            continue;
+        } else {
+            r.push(escape_markdown(line));
+        }
+        i += 1;
+    }
+    if fence {
+        r.push("```".to_owned());
+    }
+
+    while r.last().map_or(false, |l| l.is_empty()) {
+        r.pop();
+    }
+    let leading_blank = r.iter().take_while(|l| l.is_empty()).count();
+    r.drain(..leading_blank);
+    r
+}
+
+/// Write leading and trailing comments of the declaration as rustdoc
+pub fn write_doc_comment(w: &mut CodeWriter, file: &FileDescriptorProto, path: &[i32]) {
+    let location = match location(file, path) {
+        Some(location) => location,
+        None => return,
+    };
+
+    let mut lines = comment_to_markdown(location.get_leading_comments());
+    let trailing = comment_to_markdown(location.get_trailing_comments());
+    if !lines.is_empty() && !trailing.is_empty() {
+        lines.push(String::new());
+    }
+    lines.extend(trailing);
+
+    for line in &lines {
+        if line.is_empty() {
+            w.write_line("///");
+        } else {
+            w.write_line(&format!("/// {}", line));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::comment_to_markdown;
+
+    #[test]
+    fn test_comment_to_markdown() {
+        assert_eq!(
+            vec!["First line", "second \\[line\\] `[x]`"],
+            comment_to_markdown(" First line\n second [line] `[x]`\n")
+        );
+        assert_eq!(
+            vec!["Example:", "", "```text", "message M {}", "```", "", "Text"],
+            comment_to_markdown(" Example:\n\n     message M {}\n\n Text\n")
+        );
+        assert_eq!(
+            vec!["```text", "a < b", "```"],
+            comment_to_markdown(" ```\n a < b\n ```\n")
+        );
+        assert_eq!(
+            vec!["Vec\\<u8>"],
+            comment_to_markdown("\n Vec<u8>\n\n")
+        );
+    }
+}
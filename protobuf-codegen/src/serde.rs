@@ -0,0 +1,35 @@
+use protobuf::descriptor::*;
+use protobuf::descriptorx::*;
+use protobuf::rustproto;
+
+use super::code_writer::CodeWriter;
+
+
+/// Should `Serialize` and `Deserialize` be derived for types declared
+/// in given scope (`message` is the message itself if the type is a message).
+///
+/// Message option is looked up in the message and containing messages,
+/// then file option is checked.
+pub fn serde_derive_enabled(scope: &Scope, message: Option<&DescriptorProto>) -> bool {
+    for m in message.into_iter().chain(scope.path.iter().rev().cloned()) {
+        if let Some(v) = rustproto::exts::serde_derive.get(m.get_options()) {
+            return v;
+        }
+    }
+
+    let file = scope.get_file_descriptor();
+    if let Some(v) = rustproto::exts::serde_derive_all.get(file.get_options()) {
+        return v;
+    }
+
+    // Types from `google.protobuf` package are generated into `protobuf` crate,
+    // and they are serializable when `protobuf` is built with `with-serde` feature,
+    // so messages referencing them could derive serde too.
+    file.get_package() == "google.protobuf"
+}
+
+/// Write derive attribute; it is active only when `with-serde` feature
+/// of the crate containing generated code is enabled.
+pub fn write_serde_derive(w: &mut CodeWriter) {
+    w.write_line("#[cfg_attr(feature = \"with-serde\", derive(Serialize, Deserialize))]");
+}
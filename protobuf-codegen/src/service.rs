@@ -0,0 +1,325 @@
+use protobuf::descriptor::*;
+use protobuf::descriptorx::*;
+use protobuf::rust;
+
+use super::code_writer::CodeWriter;
+use super::rust_types_values::*;
+use super::no_std::StdCrate;
+use super::naming::camel_to_snake;
+use super::rustdoc::*;
+
+
+struct MethodGen<'a> {
+    proto: &'a MethodDescriptorProto,
+    service_path: &'a str,
+    file: &'a FileDescriptorProto,
+    root_scope: &'a RootScope<'a>,
+}
+
+impl<'a> MethodGen<'a> {
+    fn rust_name(&self) -> String {
+        let name = camel_to_snake(self.proto.get_name());
+        if rust::is_rust_keyword(&name) {
+            format!("rpc_{}", name)
+        } else {
+            name
+        }
+    }
+
+    fn full_path(&self) -> String {
+        format!("/{}/{}", self.service_path, self.proto.get_name())
+    }
+
+    fn method_type(&self) -> &'static str {
+        match (self.proto.get_client_streaming(), self.proto.get_server_streaming()) {
+            (false, false) => "Unary",
+            (true, false) => "ClientStreaming",
+            (false, true) => "ServerStreaming",
+            (true, true) => "BidiStreaming",
+        }
+    }
+
+    fn input(&self) -> String {
+        type_name_to_rust_relative(self.proto.get_input_type(), self.file, 0, self.root_scope)
+    }
+
+    fn output(&self) -> String {
+        type_name_to_rust_relative(self.proto.get_output_type(), self.file, 0, self.root_scope)
+    }
+
+    fn streaming(message: &str) -> String {
+        format!("::protobuf::service::Streaming<{}>", message)
+    }
+
+    // service trait method signature, requests are passed by value
+    fn server_sig(&self) -> String {
+        let input = if self.proto.get_client_streaming() {
+            MethodGen::streaming(&self.input())
+        } else {
+            self.input()
+        };
+        let output = if self.proto.get_server_streaming() {
+            MethodGen::streaming(&self.output())
+        } else {
+            self.output()
+        };
+        format!(
+            "{}(&self, req: {}) -> ::protobuf::service::ServiceResult<{}>",
+            self.rust_name(),
+            input,
+            output
+        )
+    }
+
+    fn client_sig(&self) -> String {
+        let output = if self.proto.get_server_streaming() {
+            MethodGen::streaming(&self.output())
+        } else {
+            self.output()
+        };
+        if self.proto.get_client_streaming() {
+            format!(
+                "{}<I>(&self, req: I) -> ::protobuf::service::ServiceResult<{}> where I : {}<Item = {}>, I::IntoIter : 'static",
+                self.rust_name(),
+                output,
+                StdCrate::for_file(self.file).path("iter::IntoIterator"),
+                self.input()
+            )
+        } else {
+            format!(
+                "{}(&self, req: &{}) -> ::protobuf::service::ServiceResult<{}>",
+                self.rust_name(),
+                self.input(),
+                output
+            )
+        }
+    }
+
+    fn helper_suffix(&self) -> &'static str {
+        match (self.proto.get_client_streaming(), self.proto.get_server_streaming()) {
+            (false, false) => "unary",
+            (true, false) => "client_streaming",
+            (false, true) => "server_streaming",
+            (true, true) => "bidi_streaming",
+        }
+    }
+
+    fn write_descriptor(&self, w: &mut CodeWriter) {
+        w.block("::protobuf::service::MethodDescriptor {", "},", |w| {
+            w.field_entry("name", &format!("\"{}\"", self.proto.get_name()));
+            w.field_entry("full_path", &format!("\"{}\"", self.full_path()));
+            w.field_entry(
+                "method_type",
+                &format!("::protobuf::service::MethodType::{}", self.method_type()),
+            );
+            w.field_entry(
+                "input_type",
+                &format!("\"{}\"", &self.proto.get_input_type()[1..]),
+            );
+            w.field_entry(
+                "output_type",
+                &format!("\"{}\"", &self.proto.get_output_type()[1..]),
+            );
+        });
+    }
+}
+
+fn client_name(proto: &ServiceDescriptorProto) -> String {
+    format!("{}Client", proto.get_name())
+}
+
+fn server_name(proto: &ServiceDescriptorProto) -> String {
+    format!("{}Server", proto.get_name())
+}
+
+/// Names of the trait, the client and the server generated for the service
+pub fn service_type_names(proto: &ServiceDescriptorProto) -> Vec<String> {
+    vec![proto.get_name().to_owned(), client_name(proto), server_name(proto)]
+}
+
+pub struct ServiceGen<'a> {
+    proto: &'a ServiceDescriptorProto,
+    file: &'a FileDescriptorProto,
+    root_scope: &'a RootScope<'a>,
+    // `package.Service`
+    full_name: String,
+}
+
+impl<'a> ServiceGen<'a> {
+    pub fn new(
+        proto: &'a ServiceDescriptorProto,
+        file: &'a FileDescriptorProto,
+        root_scope: &'a RootScope<'a>,
+    ) -> ServiceGen<'a> {
+        let full_name = if file.get_package().is_empty() {
+            proto.get_name().to_owned()
+        } else {
+            format!("{}.{}", file.get_package(), proto.get_name())
+        };
+        ServiceGen {
+            proto: proto,
+            file: file,
+            root_scope: root_scope,
+            full_name: full_name,
+        }
+    }
+
+    fn methods(&'a self) -> Vec<MethodGen<'a>> {
+        self.proto
+            .get_method()
+            .iter()
+            .map(|m| MethodGen {
+                proto: m,
+                service_path: &self.full_name,
+                file: self.file,
+                root_scope: self.root_scope,
+            })
+            .collect()
+    }
+
+    fn trait_name(&self) -> &str {
+        self.proto.get_name()
+    }
+
+    fn client_name(&self) -> String {
+        client_name(self.proto)
+    }
+
+    fn server_name(&self) -> String {
+        server_name(self.proto)
+    }
+
+    fn descriptor_name(&self) -> String {
+        format!("{}_DESCRIPTOR", camel_to_snake(self.proto.get_name()).to_uppercase())
+    }
+
+    fn write_doc_comment(&self, w: &mut CodeWriter) {
+        write_doc_comment(w, self.file, &service_path(self.file, self.proto));
+    }
+
+    fn write_method_doc_comment(&self, method: &MethodGen, w: &mut CodeWriter) {
+        write_doc_comment(w, self.file, &method_path(self.file, self.proto, method.proto));
+    }
+
+    fn write_trait(&self, w: &mut CodeWriter) {
+        self.write_doc_comment(w);
+        w.pub_trait(self.trait_name(), |w| {
+            for method in self.methods() {
+                self.write_method_doc_comment(&method, w);
+                w.fn_def(&method.server_sig());
+            }
+        });
+    }
+
+    fn write_descriptor(&self, w: &mut CodeWriter) {
+        w.write_line(&format!(
+            "pub static {}: ::protobuf::service::ServiceDescriptor = ::protobuf::service::ServiceDescriptor {{",
+            self.descriptor_name()
+        ));
+        w.indented(|w| {
+            w.field_entry("full_name", &format!("\"{}\"", self.full_name));
+            w.write_line("methods: &[");
+            w.indented(|w| for method in self.methods() {
+                method.write_descriptor(w);
+            });
+            w.write_line("],");
+        });
+        w.write_line("};");
+    }
+
+    fn write_client(&self, w: &mut CodeWriter) {
+        let client = self.client_name();
+        self.write_doc_comment(w);
+        w.pub_struct(
+            &format!("{}<C : ::protobuf::service::Channel>", client),
+            |w| {
+                w.field_decl("channel", "C");
+            },
+        );
+        w.write_line("");
+        w.impl_self_block(
+            &format!("<C : ::protobuf::service::Channel> {}<C>", client),
+            |w| {
+                w.pub_fn(&format!("new(channel: C) -> {}<C>", client), |w| {
+                    w.expr_block(&client, |w| {
+                        w.field_entry("channel", "channel");
+                    });
+                });
+                w.write_line("");
+                w.pub_fn("channel(&self) -> &C", |w| {
+                    w.write_line("&self.channel");
+                });
+                for (i, method) in self.methods().iter().enumerate() {
+                    w.write_line("");
+                    self.write_method_doc_comment(method, w);
+                    w.pub_fn(&method.client_sig(), |w| {
+                        w.write_line(&format!(
+                            "::protobuf::service::call_{}(&self.channel, &{}.methods[{}], req)",
+                            method.helper_suffix(),
+                            self.descriptor_name(),
+                            i
+                        ));
+                    });
+                }
+            },
+        );
+    }
+
+    fn write_server(&self, w: &mut CodeWriter) {
+        let server = self.server_name();
+        let bound = format!("S : {}", self.trait_name());
+        w.pub_struct(&format!("{}<{}>", server, bound), |w| {
+            w.field_decl("service", "S");
+        });
+        w.write_line("");
+        w.impl_self_block(&format!("<{}> {}<S>", bound, server), |w| {
+            w.pub_fn(&format!("new(service: S) -> {}<S>", server), |w| {
+                w.expr_block(&server, |w| {
+                    w.field_entry("service", "service");
+                });
+            });
+            w.write_line("");
+            w.pub_fn("service(&self) -> &S", |w| {
+                w.write_line("&self.service");
+            });
+        });
+        w.write_line("");
+        w.impl_for_block(
+            &format!("<{}> ::protobuf::service::ServiceHandler", bound),
+            &format!("{}<S>", server),
+            |w| {
+                w.def_fn("descriptor(&self) -> &'static ::protobuf::service::ServiceDescriptor", |w| {
+                    w.write_line(&format!("&{}", self.descriptor_name()));
+                });
+                w.write_line("");
+                let vec = StdCrate::for_file(self.file).path("vec::Vec");
+                w.def_fn(&format!("call(&self, method: &'static ::protobuf::service::MethodDescriptor, requests: ::protobuf::service::Streaming<{vec}<u8>>) -> ::protobuf::service::ServiceResult<::protobuf::service::Streaming<{vec}<u8>>>", vec = vec), |w| {
+                    w.match_expr("method.full_path", |w| {
+                        for method in self.methods() {
+                            w.case_expr(
+                                &format!("\"{}\"", method.full_path()),
+                                &format!(
+                                    "::protobuf::service::handle_{}(requests, |req| self.service.{}(req))",
+                                    method.helper_suffix(),
+                                    method.rust_name()
+                                ),
+                            );
+                        }
+                        w.case_expr("path", "Err(::protobuf::service::unknown_method(path))");
+                    });
+                });
+            },
+        );
+    }
+
+    pub fn write(&self, w: &mut CodeWriter) {
+        self.write_trait(w);
+        w.write_line("");
+        self.write_descriptor(w);
+        w.write_line("");
+        self.write_client(w);
+        w.write_line("");
+        self.write_server(w);
+    }
+}
+
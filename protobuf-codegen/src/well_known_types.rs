@@ -1,3 +1,5 @@
+use protobuf::descriptor::FieldDescriptorProto_Type;
+
 static NAMES: &'static [&'static str] = &[
     "Any",
     "Api",
@@ -48,6 +50,23 @@ pub fn is_well_known_type_full(name: &str) -> Option<&str> {
     }
 }
 
+/// Type of the `value` field of a wrapper type like `.google.protobuf.Int32Value`,
+/// `None` if the type is not a wrapper type
+pub fn wrapper_value_type(name: &str) -> Option<FieldDescriptorProto_Type> {
+    match is_well_known_type_full(name) {
+        Some("DoubleValue") => Some(FieldDescriptorProto_Type::TYPE_DOUBLE),
+        Some("FloatValue") => Some(FieldDescriptorProto_Type::TYPE_FLOAT),
+        Some("Int64Value") => Some(FieldDescriptorProto_Type::TYPE_INT64),
+        Some("UInt64Value") => Some(FieldDescriptorProto_Type::TYPE_UINT64),
+        Some("Int32Value") => Some(FieldDescriptorProto_Type::TYPE_INT32),
+        Some("UInt32Value") => Some(FieldDescriptorProto_Type::TYPE_UINT32),
+        Some("BoolValue") => Some(FieldDescriptorProto_Type::TYPE_BOOL),
+        Some("StringValue") => Some(FieldDescriptorProto_Type::TYPE_STRING),
+        Some("BytesValue") => Some(FieldDescriptorProto_Type::TYPE_BYTES),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -60,4 +79,14 @@ mod test {
         );
         assert_eq!(None, is_well_known_type_full(".google.protobuf.Fgfg"));
     }
+
+    #[test]
+    fn test_wrapper_value_type() {
+        assert_eq!(
+            Some(FieldDescriptorProto_Type::TYPE_UINT32),
+            wrapper_value_type(".google.protobuf.UInt32Value")
+        );
+        assert_eq!(None, wrapper_value_type(".google.protobuf.Duration"));
+        assert_eq!(None, wrapper_value_type(".foo.Int32Value"));
+    }
 }
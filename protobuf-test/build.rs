@@ -30,6 +30,9 @@ fn clean_old_files() {
     for f in glob_simple("src/**/*_pb.rs") {
         fs::remove_file(f).expect("rm");
     }
+    if path::Path::new("src/v2/test_mod_tree_pb").exists() {
+        fs::remove_dir_all("src/v2/test_mod_tree_pb").expect("rm -r");
+    }
 }
 
 
@@ -112,6 +115,8 @@ fn generate_v_from_common() {
                 out_dir: &format!("src/common/v{}", v),
                 includes: &[&format!("src/common/v{}", v), "../proto"],
                 input: &[&format!("src/common/v{}/{}_pb.proto", v, without_suffix)],
+                include_source_info: true,
+                ..Default::default()
             }).expect("protoc");
         }
     }
@@ -133,11 +138,23 @@ fn generate_pb_rs() {
             out_dir: dir,
             input: &protos.iter().map(|a| a.as_ref()).collect::<Vec<&str>>(),
             includes: &["../proto", dir],
+            include_source_info: true,
+            ..Default::default()
         }).expect("protoc");
     }
 
     gen_v2_v3("src/v2");
 
+    // files in module tree mode must be generated together
+    let protos = glob_simple("src/v2/test_mod_tree/**/*.proto");
+    protoc_rust::run(protoc_rust::Args {
+        out_dir: "src/v2/test_mod_tree_pb",
+        input: &protos.iter().map(|a| a.as_ref()).collect::<Vec<&str>>(),
+        includes: &["../proto", "src/v2/test_mod_tree"],
+        include_source_info: true,
+        ..Default::default()
+    }).expect("protoc");
+
     if protoc::Protoc::from_env_path()
         .version()
         .expect("version")
@@ -150,6 +167,8 @@ fn generate_pb_rs() {
             out_dir: "src/google/protobuf",
             input: &protos.iter().map(|a| a.as_ref()).collect::<Vec<&str>>(),
             includes: &["../proto", "src"],
+            include_source_info: true,
+            ..Default::default()
         }).expect("protoc");
     } else {
         info!("generating stubs in src/v3");
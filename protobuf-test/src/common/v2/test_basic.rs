@@ -120,7 +120,7 @@ fn test_types_singular() {
     message.set_bool_field(true);
     message.set_string_field("thirty two".to_string());
     message.set_bytes_field([33u8, 34].to_vec());
-    message.set_enum_field(TestEnumDescriptor::BLUE);
+    message.set_enum_field(TestEnumDescriptor::BLUE.into());
     test_serialize_deserialize_no_hex(&message);
 }
 
@@ -147,7 +147,7 @@ fn test_types_repeated() {
         vec![[33u8, 34].to_vec(), [35u8].to_vec()]
     );
     message.set_enum_field(
-        [TestEnumDescriptor::BLUE, TestEnumDescriptor::GREEN].to_vec(),
+        vec![TestEnumDescriptor::BLUE.into(), TestEnumDescriptor::GREEN.into()],
     );
     test_serialize_deserialize_no_hex(&message);
 }
@@ -175,7 +175,7 @@ fn test_types_repeated_packed() {
         vec![[33u8, 34].to_vec(), [35u8].to_vec()]
     );
     message.set_enum_field(
-        [TestEnumDescriptor::BLUE, TestEnumDescriptor::GREEN].to_vec(),
+        vec![TestEnumDescriptor::BLUE.into(), TestEnumDescriptor::GREEN.into()],
     );
     test_serialize_deserialize_no_hex(&message);
 }
@@ -0,0 +1,221 @@
+use super::test_dynamic_pb::*;
+
+use std::thread;
+
+use protobuf::Message;
+use protobuf::ProtobufEnum;
+use protobuf::text_format;
+use protobuf::reflect::ProtobufValueBox;
+use protobuf::reflect::ReflectFieldRef;
+use protobuf::reflect::dynamic::DescriptorPool;
+use protobuf::reflect::dynamic::DynamicMessage;
+
+fn pool() -> &'static DescriptorPool {
+    DescriptorPool::from_files(vec![file_descriptor_proto().clone()]).expect("pool")
+}
+
+fn sample() -> Dyn {
+    let mut m = Dyn::new();
+    {
+        let scalars = m.mut_scalars();
+        scalars.set_d(1.5);
+        scalars.set_f(-2.5);
+        scalars.set_i32(-3);
+        scalars.set_i64(4);
+        scalars.set_u32(5);
+        scalars.set_u64(6);
+        scalars.set_s32(-7);
+        scalars.set_s64(-8);
+        scalars.set_fx32(9);
+        scalars.set_fx64(10);
+        scalars.set_sfx32(-11);
+        scalars.set_sfx64(12);
+        scalars.set_b(true);
+        scalars.set_s("str".to_owned());
+        scalars.set_by(b"\x00\x01".to_vec());
+        scalars.set_color(Color::BLUE.into());
+    }
+    m.set_ri(vec![1, -2]);
+    m.set_rp(vec![-3, 4]);
+    m.set_rs(vec!["a".to_owned(), "".to_owned()]);
+    let mut nested = Dyn_Nested::new();
+    nested.set_n(13);
+    m.mut_rn().push(nested.clone());
+    m.mut_mn().insert("k".to_owned(), nested);
+    m.set_os("oneof".to_owned());
+    m
+}
+
+#[test]
+fn test_full_names() {
+    let pool = pool();
+    assert_eq!(
+        "test_dynamic.Dyn.Nested",
+        pool.message_by_name("test_dynamic.Dyn.Nested").unwrap().full_name()
+    );
+    assert!(pool.message_by_name(".test_dynamic.Scalars").is_some());
+    assert!(pool.enum_by_name("test_dynamic.Color").is_some());
+    // map entries are not messages
+    assert!(pool.message_by_name("test_dynamic.Dyn.MnEntry").is_none());
+    assert!(pool.message_by_name("Dyn").is_none());
+}
+
+#[test]
+fn test_round_trip() {
+    let descriptor = pool().message_by_name("test_dynamic.Dyn").unwrap();
+    let m = sample();
+    let bytes = m.write_to_bytes().unwrap();
+
+    let dynamic = DynamicMessage::parse_from_bytes(descriptor, &bytes).unwrap();
+    assert_eq!(m.compute_size(), dynamic.compute_size());
+    assert_eq!(bytes, dynamic.write_to_bytes().unwrap());
+    assert_eq!(text_format::print_to_string(&m), text_format::print_to_string(&dynamic));
+    assert_eq!(format!("{:?}", m), format!("{:?}", dynamic));
+}
+
+#[test]
+fn test_reflect_get() {
+    let descriptor = pool().message_by_name("test_dynamic.Dyn").unwrap();
+    let dynamic =
+        DynamicMessage::parse_from_bytes(descriptor, &sample().write_to_bytes().unwrap()).unwrap();
+
+    let scalars = descriptor.field_by_name("scalars").get_message(&dynamic);
+    assert_eq!("test_dynamic.Scalars", scalars.descriptor().full_name());
+    let scalars_descriptor = scalars.descriptor();
+    assert_eq!(-3, scalars_descriptor.field_by_name("i32").get_i32(scalars));
+    assert_eq!(-8, scalars_descriptor.field_by_name("s64").get_i64(scalars));
+    assert_eq!("str", scalars_descriptor.field_by_name("s").get_str(scalars));
+    assert_eq!("BLUE", scalars_descriptor.field_by_name("color").get_enum(scalars).name());
+
+    assert_eq!(2, descriptor.field_by_name("ri").len_field(&dynamic));
+    match descriptor.field_by_name("rn").get_reflect(&dynamic) {
+        ReflectFieldRef::Repeated(repeated) => assert_eq!(1, repeated.len()),
+        _ => panic!("expecting repeated"),
+    }
+    assert!(!descriptor.field_by_name("on").has_field(&dynamic));
+    assert_eq!("oneof", descriptor.field_by_name("os").get_str(&dynamic));
+
+    // unset message field returns an empty message
+    let empty = DynamicMessage::new(descriptor);
+    let scalars = descriptor.field_by_name("scalars").get_message(&empty);
+    assert_eq!(0, scalars.descriptor().field_by_name("u64").get_u64(scalars));
+}
+
+#[test]
+fn test_reflect_set() {
+    let descriptor = pool().message_by_name("test_dynamic.Dyn").unwrap();
+    let mut dynamic = DynamicMessage::new(descriptor);
+    {
+        let scalars = descriptor.field_by_name("scalars").mut_message(&mut dynamic);
+        let scalars_descriptor = scalars.descriptor();
+        let u32 = ProtobufValueBox::U32(7);
+        scalars_descriptor.field_by_name("u32").set_singular_field(scalars, u32);
+        // values of generated enums are accepted
+        let green = ProtobufValueBox::Enum(Color::GREEN.descriptor());
+        scalars_descriptor.field_by_name("color").set_singular_field(scalars, green);
+    }
+    descriptor.field_by_name("ri").push_repeated(&mut dynamic, ProtobufValueBox::I32(5));
+    let nested_descriptor = descriptor.field_by_name("mn").message_descriptor();
+    assert_eq!("test_dynamic.Dyn.Nested", nested_descriptor.full_name());
+    let mut nested = nested_descriptor.new_instance();
+    nested_descriptor.field_by_name("n").set_singular_field(&mut *nested, ProtobufValueBox::I32(1));
+    descriptor.field_by_name("mn").insert_map(
+        &mut dynamic,
+        ProtobufValueBox::String("k".to_owned()),
+        ProtobufValueBox::Message(nested),
+    );
+    let os = ProtobufValueBox::String("x".to_owned());
+    descriptor.field_by_name("os").set_singular_field(&mut dynamic, os);
+
+    let m: Dyn = ::protobuf::parse_from_bytes(&dynamic.write_to_bytes().unwrap()).unwrap();
+    assert_eq!(7, m.get_scalars().get_u32());
+    assert_eq!(m.get_scalars().get_color(), Color::GREEN);
+    assert_eq!(&[5], m.get_ri());
+    assert_eq!(1, m.get_mn()["k"].get_n());
+    assert_eq!("x", m.get_os());
+}
+
+#[test]
+fn test_oneof() {
+    let descriptor = pool().message_by_name("test_dynamic.Dyn").unwrap();
+    let mut dynamic = DynamicMessage::new(descriptor);
+    let os = ProtobufValueBox::String("x".to_owned());
+    descriptor.field_by_name("os").set_singular_field(&mut dynamic, os);
+    descriptor.field_by_name("on").mut_message(&mut dynamic);
+    assert!(!descriptor.field_by_name("os").has_field(&dynamic));
+    assert!(descriptor.field_by_name("on").has_field(&dynamic));
+}
+
+#[test]
+#[should_panic]
+fn test_set_wrong_type() {
+    let descriptor = pool().message_by_name("test_dynamic.Dyn").unwrap();
+    let mut dynamic = DynamicMessage::new(descriptor);
+    // message of a different type
+    let value = ProtobufValueBox::Message(Box::new(Dyn_Nested::new()));
+    descriptor.field_by_name("on").set_singular_field(&mut dynamic, value);
+}
+
+#[test]
+fn test_unresolved_type() {
+    let mut file = file_descriptor_proto().clone();
+    file.mut_message_type().retain(|m| m.get_name() != "Scalars");
+    let error = DescriptorPool::from_files(vec![file]).err().expect("error");
+    assert_eq!("test_dynamic.Dyn.scalars: type not found: .test_dynamic.Scalars", error.message);
+}
+
+#[test]
+fn test_unknown_fields_preserved() {
+    let descriptor = pool().message_by_name("test_dynamic.Dyn.Nested").unwrap();
+    // field 1 is known, field 15 is not
+    let bytes = [0x08, 0x01, 0x78, 0x02];
+    let dynamic = DynamicMessage::parse_from_bytes(descriptor, &bytes).unwrap();
+    assert_eq!(&[2], &dynamic.get_unknown_fields().get(15).unwrap().varint[..]);
+    assert_eq!(&bytes[..], &dynamic.write_to_bytes().unwrap()[..]);
+}
+
+#[test]
+fn test_generic_parse_fails() {
+    let error = ::protobuf::parse_from_bytes::<DynamicMessage>(&[]).err().expect("error");
+    match error {
+        ::protobuf::ProtobufError::MessageTypeUnknown => {}
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn test_merge() {
+    let descriptor = pool().message_by_name("test_dynamic.Dyn").unwrap();
+    let bytes = sample().write_to_bytes().unwrap();
+
+    let mut expected = sample();
+    expected.merge(&sample());
+
+    let mut dynamic = DynamicMessage::parse_from_bytes(descriptor, &bytes).unwrap();
+    let other = DynamicMessage::parse_from_bytes(descriptor, &bytes).unwrap();
+    dynamic.merge(&other);
+    assert_eq!(expected.write_to_bytes().unwrap(), dynamic.write_to_bytes().unwrap());
+
+    let mut dynamic = DynamicMessage::parse_from_bytes(descriptor, &bytes).unwrap();
+    dynamic.merge_from_bytes(&bytes).unwrap();
+    assert_eq!(expected.write_to_bytes().unwrap(), dynamic.write_to_bytes().unwrap());
+}
+
+#[test]
+fn test_threads() {
+    let descriptor = pool().message_by_name("test_dynamic.Dyn").unwrap();
+    let bytes = sample().write_to_bytes().unwrap();
+    let dynamic = thread::spawn(move || DynamicMessage::parse_from_bytes(descriptor, &bytes))
+        .join()
+        .unwrap()
+        .unwrap();
+    assert_eq!(sample().write_to_bytes().unwrap(), dynamic.write_to_bytes().unwrap());
+
+    // default instance of unset message field is shared by all threads
+    let empty = DynamicMessage::new(descriptor);
+    let handle = thread::spawn(move || {
+        let scalars = descriptor.field_by_name("scalars").get_message(&empty);
+        assert_eq!(0, scalars.compute_size());
+    });
+    handle.join().unwrap();
+}
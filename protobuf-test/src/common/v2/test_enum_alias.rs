@@ -23,6 +23,6 @@ fn test_enum() {
 #[test]
 fn test_enum_in_message() {
     let mut m = TestEnumWithAlias::new();
-    m.set_en(EnumWithAlias::A);
+    m.set_en(EnumWithAlias::A.into());
     test_serialize_deserialize("08 0a", &m);
 }
@@ -0,0 +1,118 @@
+use protobuf::*;
+
+use super::test_merge_pb::*;
+
+
+// zero means unset
+fn inner(a: i32, b: i32, r: &[i32]) -> MergeInner {
+    let mut m = MergeInner::new();
+    if a != 0 {
+        m.set_a(a);
+    }
+    if b != 0 {
+        m.set_b(b);
+    }
+    m.set_r(r.to_vec());
+    m
+}
+
+fn first() -> Merge {
+    let mut m = Merge::new();
+    m.set_i(1);
+    m.set_s("first".to_owned());
+    m.set_inner(inner(1, 0, &[1]));
+    m.set_ri(vec![1]);
+    m.mut_rinner().push(inner(1, 0, &[]));
+    m.mut_m().insert("x".to_owned(), 1);
+    m.set_oinner(inner(1, 0, &[]));
+    m
+}
+
+fn second() -> Merge {
+    let mut m = Merge::new();
+    m.set_i(2);
+    m.set_inner(inner(0, 2, &[2]));
+    m.set_ri(vec![2]);
+    m.mut_rinner().push(inner(2, 0, &[]));
+    m.mut_m().insert("x".to_owned(), 2);
+    m.mut_m().insert("y".to_owned(), 3);
+    m.set_oinner(inner(0, 2, &[]));
+    m
+}
+
+fn check_merged(m: &Merge) {
+    assert_eq!(2, m.get_i());
+    assert_eq!("first", m.get_s());
+    assert_eq!(1, m.get_inner().get_a());
+    assert_eq!(2, m.get_inner().get_b());
+    assert_eq!(&[1, 2], m.get_inner().get_r());
+    assert_eq!(&[1, 2], m.get_ri());
+    assert_eq!(vec![inner(1, 0, &[]), inner(2, 0, &[])], m.get_rinner().to_vec());
+    assert_eq!(2, m.get_m()["x"]);
+    assert_eq!(3, m.get_m()["y"]);
+    assert_eq!(1, m.get_oinner().get_a());
+    assert_eq!(2, m.get_oinner().get_b());
+}
+
+#[test]
+fn test_merge_from_bytes() {
+    let mut m = Merge::new();
+    m.merge_from_bytes(&first().write_to_bytes().unwrap()).unwrap();
+    m.merge_from_bytes(&second().write_to_bytes().unwrap()).unwrap();
+    check_merged(&m);
+}
+
+#[test]
+fn test_merge_from_concatenated() {
+    let mut bytes = first().write_to_bytes().unwrap();
+    bytes.extend(second().write_to_bytes().unwrap());
+    let mut m = Merge::new();
+    m.merge_from(&mut CodedInputStream::from_bytes(&bytes)).unwrap();
+    check_merged(&m);
+}
+
+#[test]
+fn test_merge() {
+    let mut m = Merge::new();
+    m.merge(&first());
+    m.merge(&second());
+    check_merged(&m);
+
+    let mut from_bytes = first();
+    from_bytes.merge_from_bytes(&second().write_to_bytes().unwrap()).unwrap();
+    assert_eq!(from_bytes, m);
+}
+
+#[test]
+fn test_merge_oneof_replaces_other_variant() {
+    let mut os = Merge::new();
+    os.set_os("os".to_owned());
+
+    let mut m = first();
+    m.merge(&os);
+    assert_eq!("os", m.get_os());
+
+    let mut m = first();
+    m.merge_from_bytes(&os.write_to_bytes().unwrap()).unwrap();
+    assert_eq!("os", m.get_os());
+}
+
+#[test]
+fn test_merge_unset_fields_keep_values() {
+    let mut os = Merge::new();
+    os.set_os("os".to_owned());
+    let mut m = os.clone();
+    m.merge(&Merge::new());
+    assert_eq!(os, m);
+}
+
+#[test]
+fn test_merge_unknown_fields() {
+    let mut m = Merge::new();
+    // field 15, varint 1
+    m.merge_from_bytes(&[0x78, 0x01]).unwrap();
+    let mut other = Merge::new();
+    other.merge_from_bytes(&[0x78, 0x02]).unwrap();
+    m.merge(&other);
+    assert_eq!(&[1, 2], &m.get_unknown_fields().get(15).unwrap().varint[..]);
+}
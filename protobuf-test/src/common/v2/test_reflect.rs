@@ -1,6 +1,8 @@
-use super::test_reflect_pb::M;
+use super::test_reflect_pb::*;
 
 use protobuf::Message;
+use protobuf::ProtobufEnum;
+use protobuf::reflect::ProtobufValueBox;
 
 #[ignore] // TODO
 #[test]
@@ -16,3 +18,126 @@ fn test_get_sub_message_via_reflection() {
     assert_eq!("SubM", sub_m.descriptor().full_name());
     assert_eq!(42, sub_m.descriptor().field_by_name("n").get_i32(sub_m));
 }
+
+#[test]
+fn test_set_singular_field() {
+    let mut m = M::new();
+    let descriptor = m.descriptor();
+
+    descriptor.field_by_name("i").set_singular_field(&mut m, ProtobufValueBox::I32(10));
+    let s = ProtobufValueBox::String("ab".to_owned());
+    descriptor.field_by_name("s").set_singular_field(&mut m, s);
+    let one = E::ONE.descriptor();
+    descriptor.field_by_name("e").set_singular_field(&mut m, ProtobufValueBox::Enum(one));
+    assert_eq!(10, m.get_i());
+    assert_eq!("ab", m.get_s());
+    assert_eq!(m.get_e(), E::ONE);
+
+    let mut sub_m = SubM::new();
+    sub_m.set_n(3);
+    let sub_m = ProtobufValueBox::Message(Box::new(sub_m));
+    descriptor.field_by_name("sub_m").set_singular_field(&mut m, sub_m);
+    assert_eq!(3, m.get_sub_m().get_n());
+
+    for name in &["i", "s", "e", "sub_m"] {
+        assert!(descriptor.field_by_name(name).has_field(&m));
+        descriptor.field_by_name(name).clear_field(&mut m);
+        assert!(!descriptor.field_by_name(name).has_field(&m));
+    }
+    assert_eq!(0, m.get_i());
+    assert_eq!("", m.get_s());
+}
+
+#[test]
+#[should_panic]
+fn test_set_singular_field_wrong_type() {
+    let mut m = M::new();
+    let descriptor = m.descriptor();
+    descriptor.field_by_name("i").set_singular_field(&mut m, ProtobufValueBox::I64(10));
+}
+
+#[test]
+fn test_into_enum() {
+    assert_eq!(Some(E::ONE), ProtobufValueBox::Enum(E::ONE.descriptor()).into_enum());
+    // same number, different enum
+    let other_one = ProtobufValueBox::Enum(OtherE::OTHER_ONE.descriptor());
+    assert_eq!(None, other_one.into_enum::<E>());
+}
+
+#[test]
+#[should_panic]
+fn test_set_singular_field_enum_of_other_type() {
+    let mut m = M::new();
+    let descriptor = m.descriptor();
+    let other_one = ProtobufValueBox::Enum(OtherE::OTHER_ONE.descriptor());
+    descriptor.field_by_name("e").set_singular_field(&mut m, other_one);
+}
+
+#[test]
+fn test_mut_message() {
+    let mut m = M::new();
+    let descriptor = m.descriptor();
+    {
+        let sub_m = descriptor.field_by_name("sub_m").mut_message(&mut m);
+        sub_m.descriptor().field_by_name("n").set_singular_field(sub_m, ProtobufValueBox::I32(42));
+    }
+    assert!(m.has_sub_m());
+    assert_eq!(42, m.get_sub_m().get_n());
+}
+
+#[test]
+fn test_repeated_and_map() {
+    let mut m = M::new();
+    let descriptor = m.descriptor();
+
+    descriptor.field_by_name("ri").push_repeated(&mut m, ProtobufValueBox::I32(1));
+    descriptor.field_by_name("ri").push_repeated(&mut m, ProtobufValueBox::I32(2));
+    let sub_m = ProtobufValueBox::Message(Box::new(SubM::new()));
+    descriptor.field_by_name("rm").push_repeated(&mut m, sub_m);
+    descriptor.field_by_name("mi").insert_map(
+        &mut m,
+        ProtobufValueBox::String("k".to_owned()),
+        ProtobufValueBox::I32(5),
+    );
+    assert_eq!(&[1, 2], m.get_ri());
+    assert_eq!(1, m.get_rm().len());
+    assert_eq!(Some(&5), m.get_mi().get("k"));
+
+    for name in &["ri", "rm", "mi"] {
+        descriptor.field_by_name(name).clear_field(&mut m);
+        assert_eq!(0, descriptor.field_by_name(name).len_field(&m));
+    }
+}
+
+#[test]
+fn test_oneof() {
+    let mut m = M::new();
+    let descriptor = m.descriptor();
+
+    descriptor.field_by_name("ou").set_singular_field(&mut m, ProtobufValueBox::U64(7));
+    assert_eq!(7, m.get_ou());
+
+    {
+        let om = descriptor.field_by_name("om").mut_message(&mut m);
+        om.descriptor().field_by_name("n").set_singular_field(om, ProtobufValueBox::I32(8));
+    }
+    assert!(!m.has_ou());
+    assert_eq!(8, m.get_om().get_n());
+
+    // clearing field which is not set does not affect oneof
+    descriptor.field_by_name("ou").clear_field(&mut m);
+    assert!(m.has_om());
+    descriptor.field_by_name("om").clear_field(&mut m);
+    assert!(!m.has_om());
+}
+
+#[test]
+fn test_new_instance() {
+    let descriptor = M::new().descriptor();
+    let mut m = descriptor.new_instance();
+    descriptor.field_by_name("i").set_singular_field(&mut *m, ProtobufValueBox::I32(11));
+    descriptor.field_by_name("ou").set_singular_field(&mut *m, ProtobufValueBox::U64(1));
+
+    let m: &M = m.as_any().downcast_ref().unwrap();
+    assert_eq!(11, m.get_i());
+}
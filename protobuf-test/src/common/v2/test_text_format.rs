@@ -3,11 +3,19 @@ use std::default::Default;
 use super::test_text_format_pb::*;
 
 use protobuf::text_format::print_to_string;
+use protobuf::text_format::parse_from_str;
+use protobuf::text_format::merge_from_str;
+use protobuf::text_format::Loc;
 
 fn t<F : FnMut(&mut TestTypes)>(expected: &str, mut setter: F) {
     let mut m = TestTypes::new();
     setter(&mut m);
     assert_eq!(&*print_to_string(&m), expected);
+    assert_eq!(m, parse_from_str::<TestTypes>(expected).expect("parse"));
+}
+
+fn parse_error_loc(text: &str) -> Loc {
+    parse_from_str::<TestTypes>(text).expect_err("error expected").loc
 }
 
 #[test]
@@ -33,7 +41,7 @@ fn test_singular() {
         m.set_bytes_singular(b"def".to_vec())
     });
     t("test_enum_singular: DARK", |m| {
-        m.set_test_enum_singular(TestEnum::DARK)
+        m.set_test_enum_singular(TestEnum::DARK.into())
     });
     t("test_message_singular {}", |m| {
         m.mut_test_message_singular();
@@ -79,7 +87,7 @@ fn test_repeated_one() {
         m.mut_bytes_repeated().push(b"def".to_vec())
     });
     t("test_enum_repeated: DARK", |m| {
-        m.mut_test_enum_repeated().push(TestEnum::DARK)
+        m.mut_test_enum_repeated().push(TestEnum::DARK.into())
     });
     t("test_message_repeated {}", |m| {
         m.mut_test_message_repeated().push(Default::default());
@@ -143,3 +151,81 @@ fn test_rust_identifier() {
     m.set_field_const(true);
     assert_eq!("const: true", &*format!("{:?}", m));
 }
+
+#[test]
+fn test_parse_pretty() {
+    let mut tm = TestMessage::new();
+    tm.set_value(23);
+    let mut m = TestTypes::new();
+    m.set_test_message_singular(tm);
+    m.set_string_singular("quote\"newline\n\u{44e}".to_string());
+    m.mut_bytes_repeated().push(b"\x00\xff".to_vec());
+    m.mut_test_enum_repeated().push(TestEnum::LIGHT.into());
+    assert_eq!(m, parse_from_str::<TestTypes>(&format!("{:#?}", m)).expect("parse"));
+}
+
+#[test]
+fn test_parse_syntax() {
+    let mut m = TestTypes::new();
+    m.set_int32_singular(-10);
+    m.set_uint64_singular(0x1f);
+    m.set_double_singular(-1.5);
+    m.set_string_singular("abcd".to_string());
+    m.set_test_enum_singular(TestEnum::LIGHT.into());
+    m.mut_test_message_singular().set_value(1);
+    m.mut_int32_repeated().push(1);
+    m.mut_int32_repeated().push(2);
+    m.mut_test_message_repeated().push(Default::default());
+    m.mut_test_message_repeated().push(Default::default());
+    m.mut_test_message_repeated()[1].set_value(3);
+    m.mut_test_enum_repeated().push(TestEnum::DARK.into());
+
+    let text = "
+        # comment
+        int32_singular: -10;
+        uint64_singular: 0x1f,
+        double_singular: -1.5
+        string_singular: 'ab' \"cd\"
+        test_enum_singular: 2
+        test_message_singular: < value: 1 >
+        int32_repeated: [1, 2]
+        test_message_repeated [{}, { value: 3 }]
+        test_enum_repeated: DARK
+    ";
+    assert_eq!(m, parse_from_str::<TestTypes>(text).expect("parse"));
+}
+
+#[test]
+fn test_parse_float_special() {
+    let m = parse_from_str::<TestTypes>("double_singular: -inf float_singular: nan").unwrap();
+    assert_eq!(::std::f64::NEG_INFINITY, m.get_double_singular());
+    assert!(m.get_float_singular().is_nan());
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(Loc { line: 2, col: 3 }, parse_error_loc("int32_singular: 1\n  no_such_field: 2"));
+    assert_eq!(Loc { line: 1, col: 17 }, parse_error_loc("int32_singular: abc"));
+    assert_eq!(Loc { line: 1, col: 17 }, parse_error_loc("int32_singular: 3000000000"));
+    assert_eq!(Loc { line: 1, col: 18 }, parse_error_loc("uint32_singular: -1"));
+    assert_eq!(Loc { line: 1, col: 21 }, parse_error_loc("test_enum_singular: DARKER"));
+    assert_eq!(Loc { line: 1, col: 33 }, parse_error_loc("test_message_singular { value: 1"));
+    assert_eq!(Loc { line: 1, col: 19 }, parse_error_loc("int32_singular: 1 int32_singular: 2"));
+}
+
+#[test]
+fn test_merge_from_str() {
+    let mut m = TestTypes::new();
+    m.set_int32_singular(10);
+    m.set_uint32_singular(20);
+    m.mut_int32_repeated().push(1);
+    m.mut_test_message_singular().set_value(30);
+
+    merge_from_str(&mut m, "int32_singular: 11 int32_singular: 12 int32_repeated: 2").unwrap();
+    merge_from_str(&mut m, "test_message_singular { value: 31 }").unwrap();
+
+    assert_eq!(12, m.get_int32_singular());
+    assert_eq!(20, m.get_uint32_singular());
+    assert_eq!(&[1, 2], m.get_int32_repeated());
+    assert_eq!(31, m.get_test_message_singular().get_value());
+}
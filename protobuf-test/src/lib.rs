@@ -2,9 +2,21 @@
 
 extern crate protobuf;
 
+// used by code generated with `rustproto.no_std_all`
+extern crate alloc;
+extern crate core;
+
 #[cfg(feature = "with-bytes")]
 extern crate bytes;
 
+#[cfg(feature = "with-serde")]
+extern crate serde;
+#[cfg(feature = "with-serde")]
+#[macro_use]
+extern crate serde_derive;
+#[cfg(feature = "with-serde")]
+extern crate serde_json;
+
 mod v2;
 
 // `cfg(proto3)` is emitted by `build.rs`
@@ -1,7 +1,6 @@
 extern crate protobuf;
 
 mod test_default_values_pb;
-mod test_group_pb;
 mod test_import_nested_pb;
 mod test_import_nested_imported_pb;
 mod test_import_pkg_nested_pb;
@@ -21,4 +20,41 @@ mod test_default_values;
 mod test_oneof_default_value_pb;
 mod test_oneof_default_value;
 
+mod test_ext_pb;
+mod test_ext;
+
+mod test_enum_unknown_pb;
+mod test_enum_unknown;
+
+mod test_group_pb;
+mod test_group;
+
+mod test_serde_derive_pb;
+#[cfg(feature = "with-serde")]
+mod test_serde_derive;
+
+mod test_service_pb;
+mod test_service;
+
+mod test_with_methods_pb;
+mod test_with_methods;
+
 mod struct_pb;
+
+mod test_idiomatic_names_pb;
+mod test_idiomatic_names;
+
+mod test_singular_field_option_pb;
+mod test_singular_field_option;
+
+mod test_mod_tree_pb;
+mod test_mod_tree;
+
+mod test_rustdoc_pb;
+mod test_rustdoc;
+
+mod test_derive_pb;
+mod test_derive;
+
+mod test_no_std_pb;
+mod test_no_std;
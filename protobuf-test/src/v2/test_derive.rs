@@ -0,0 +1,46 @@
+use std::collections::HashSet;
+use std::mem;
+
+use protobuf::Message;
+
+use super::test_derive_pb::*;
+
+#[test]
+fn test_message_eq_hash() {
+    let mut a = WithHash::new();
+    a.set_id(1);
+    a.set_name("a".to_owned());
+    a.set_priorities(vec![Priority::HIGH]);
+    a.mut_nested().set_data(vec![1, 2]);
+    a.set_number(10);
+
+    let mut b = a.clone();
+    b.set_text("b".to_owned());
+
+    let mut set = HashSet::new();
+    assert!(set.insert(a.clone()));
+    assert!(set.insert(b));
+    assert!(!set.insert(a));
+    assert_eq!(2, set.len());
+}
+
+#[test]
+fn test_unknown_fields_hash() {
+    let mut a = WithHash::new();
+    a.mut_unknown_fields().add_varint(100, 1);
+    a.mut_unknown_fields().add_varint(101, 2);
+    let mut b = WithHash::new();
+    b.mut_unknown_fields().add_varint(101, 2);
+    b.mut_unknown_fields().add_varint(100, 1);
+
+    let mut set = HashSet::new();
+    set.insert(a);
+    assert!(set.contains(&b));
+}
+
+#[test]
+fn test_enum_ord() {
+    assert!(Priority::LOW < Priority::HIGH);
+    assert_eq!(Some(&Priority::HIGH), [Priority::HIGH, Priority::LOW].iter().max());
+    assert_eq!(mem::size_of::<i32>(), mem::size_of::<Priority>());
+}
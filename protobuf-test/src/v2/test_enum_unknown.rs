@@ -0,0 +1,57 @@
+use protobuf::*;
+use protobuf::reflect::dynamic::DescriptorPool;
+use protobuf::reflect::dynamic::DynamicMessage;
+
+use test::*;
+
+use super::test_enum_unknown_pb::*;
+
+#[test]
+fn test_unknown_values_are_unknown_fields() {
+    let bytes = [0x08, 0x05, 0x12, 0x02, 0x01, 0x07, 0x18, 0x09, 0x18, 0x01];
+    let m = parse_from_bytes::<TestEnumUnknown>(&bytes).unwrap();
+    assert!(!m.has_singular());
+    assert_eq!(&[TestEnumUnknownEnum::ONE], m.get_repeated_packed());
+    assert_eq!(TestEnumUnknownEnum::ONE, m.get_in_oneof());
+
+    let unknown = m.get_unknown_fields();
+    assert_eq!(&[5], unknown.get(1).unwrap().varint.as_slice());
+    assert_eq!(&[7], unknown.get(2).unwrap().varint.as_slice());
+    assert_eq!(&[9], unknown.get(3).unwrap().varint.as_slice());
+
+    test_serialize_deserialize_no_hex(&m);
+}
+
+#[test]
+fn test_known_value_after_unknown() {
+    let m = parse_from_bytes::<TestEnumUnknown>(&[0x08, 0x05, 0x08, 0x01, 0x18, 0x00]).unwrap();
+    assert_eq!(TestEnumUnknownEnum::ONE, m.get_singular());
+    assert_eq!(&[5], m.get_unknown_fields().get(1).unwrap().varint.as_slice());
+}
+
+#[test]
+fn test_unknown_values_dynamic() {
+    let pool = DescriptorPool::from_files(vec![file_descriptor_proto().clone()]).expect("pool");
+    let descriptor = pool.message_by_name("TestEnumUnknown").unwrap();
+    let bytes = [0x08, 0x05, 0x12, 0x02, 0x01, 0x07, 0x18, 0x09, 0x18, 0x01];
+    let dynamic = DynamicMessage::parse_from_bytes(descriptor, &bytes).unwrap();
+    assert!(!descriptor.field_by_name("singular").has_field(&dynamic));
+    assert_eq!(1, descriptor.field_by_name("repeated_packed").len_field(&dynamic));
+    let unknown = dynamic.get_unknown_fields();
+    assert_eq!(&[5], unknown.get(1).unwrap().varint.as_slice());
+    assert_eq!(&[7], unknown.get(2).unwrap().varint.as_slice());
+    assert_eq!(&[9], unknown.get(3).unwrap().varint.as_slice());
+}
+
+#[test]
+fn test_unknown_map_value_dynamic() {
+    let pool = DescriptorPool::from_files(vec![file_descriptor_proto().clone()]).expect("pool");
+    let descriptor = pool.message_by_name("TestEnumUnknown").unwrap();
+    // entries `1: ONE` and `2: 5`, whole entry with unknown value goes to unknown fields
+    let bytes = [0x22, 0x04, 0x08, 0x01, 0x10, 0x01, 0x22, 0x04, 0x08, 0x02, 0x10, 0x05];
+    let dynamic = DynamicMessage::parse_from_bytes(descriptor, &bytes).unwrap();
+    assert_eq!(1, descriptor.field_by_name("map_value").len_field(&dynamic));
+    let unknown = dynamic.get_unknown_fields().get(4).unwrap();
+    assert_eq!(&[vec![0x08, 0x02, 0x10, 0x05]], unknown.length_delimited.as_slice());
+    assert_eq!(&bytes[..], &dynamic.write_to_bytes().unwrap()[..]);
+}
@@ -0,0 +1,161 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use protobuf::*;
+
+use super::test_ext_pb::*;
+use super::test_ext_pb::exts;
+
+fn hash<T : Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn test_get_absent() {
+    let m = ExtendMe::new();
+    assert_eq!(None, exts::int32_ext.get(&m));
+    assert!(!exts::string_ext.has(&m));
+    assert_eq!(Vec::<u32>::new(), exts::repeated_ext.get(&m));
+}
+
+#[test]
+fn test_set_get() {
+    let mut m = ExtendMe::new();
+    exts::int32_ext.set(&mut m, -10);
+    exts::sint64_ext.set(&mut m, -20);
+    exts::fixed32_ext.set(&mut m, 30);
+    exts::double_ext.set(&mut m, 4.5);
+    exts::string_ext.set(&mut m, "str".to_owned());
+    let mut value = ExtValue::new();
+    value.set_s("nested".to_owned());
+    exts::message_ext.set(&mut m, value.clone());
+    exts::enum_ext.set(&mut m, ExtKind::SECOND);
+    exts::repeated_ext.set(&mut m, vec![1, 2]);
+    exts::repeated_ext.push(&mut m, 3);
+
+    assert_eq!(Some(-10), exts::int32_ext.get(&m));
+    assert_eq!(Some(-20), exts::sint64_ext.get(&m));
+    assert_eq!(Some(30), exts::fixed32_ext.get(&m));
+    assert_eq!(Some(4.5), exts::double_ext.get(&m));
+    assert_eq!(Some("str".to_owned()), exts::string_ext.get(&m));
+    assert_eq!(Some(value), exts::message_ext.get(&m));
+    assert_eq!(Some(ExtKind::SECOND), exts::enum_ext.get(&m));
+    assert_eq!(vec![1, 2, 3], exts::repeated_ext.get(&m));
+}
+
+#[test]
+fn test_set_replaces() {
+    let mut m = ExtendMe::new();
+    exts::int32_ext.set(&mut m, 1);
+    exts::int32_ext.set(&mut m, 2);
+    assert_eq!(Some(2), exts::int32_ext.get(&m));
+    exts::repeated_string_ext.push(&mut m, "a".to_owned());
+    exts::repeated_string_ext.set(&mut m, vec!["b".to_owned()]);
+    assert_eq!(vec!["b".to_owned()], exts::repeated_string_ext.get(&m));
+}
+
+#[test]
+fn test_clear() {
+    let mut m = ExtendMe::new();
+    exts::int32_ext.set(&mut m, 1);
+    exts::repeated_ext.push(&mut m, 1);
+    exts::int32_ext.clear(&mut m);
+    exts::repeated_ext.clear(&mut m);
+    assert!(!exts::int32_ext.has(&m));
+    assert_eq!(Vec::<u32>::new(), exts::repeated_ext.get(&m));
+    assert_eq!(ExtendMe::new(), m);
+}
+
+#[test]
+fn test_emptied_unknown_fields_equal_to_new() {
+    let mut m = ExtendMe::new();
+    m.mut_unknown_fields().add_varint(1000, 1);
+    m.mut_unknown_fields().remove(1000);
+    assert_eq!(ExtendMe::new(), m);
+
+    // field 1000, varint 1
+    m.merge_from_bytes(&[0xc0, 0x3e, 0x01]).unwrap();
+    assert!(ExtendMe::new() != m);
+    m.mut_unknown_fields().clear();
+    assert_eq!(ExtendMe::new(), m);
+    assert_eq!(hash(&UnknownFields::new()), hash(m.get_unknown_fields()));
+}
+
+#[test]
+fn test_serialize_parse() {
+    let mut m = ExtendMe::new();
+    m.set_a(1);
+    exts::string_ext.set(&mut m, "str".to_owned());
+    let bytes = m.write_to_bytes().unwrap();
+    assert_eq!(m.compute_size() as usize, bytes.len());
+
+    let parsed: ExtendMe = parse_from_bytes(&bytes).unwrap();
+    assert_eq!(1, parsed.get_a());
+    assert_eq!(Some("str".to_owned()), exts::string_ext.get(&parsed));
+    assert_eq!(m, parsed);
+}
+
+#[test]
+fn test_last_value_wins() {
+    // field 100 twice
+    let m: ExtendMe = parse_from_bytes(&[0xa0, 0x06, 0x01, 0xa0, 0x06, 0x02]).unwrap();
+    assert_eq!(Some(2), exts::int32_ext.get(&m));
+}
+
+#[test]
+fn test_message_occurrences_merged() {
+    // field 105 with `s` set, then field 105 empty
+    let m: ExtendMe = parse_from_bytes(&[0xca, 0x06, 0x03, 0x0a, 0x01, b'a', 0xca, 0x06, 0x00])
+        .unwrap();
+    let mut expected = ExtValue::new();
+    expected.set_s("a".to_owned());
+    assert_eq!(Some(expected), exts::message_ext.get(&m));
+}
+
+#[test]
+fn test_written_in_field_number_order() {
+    let mut m = ExtendMe::new();
+    exts::repeated_ext.push(&mut m, 5);
+    exts::string_ext.set(&mut m, "s".to_owned());
+    exts::int32_ext.set(&mut m, 1);
+    assert_eq!(
+        vec![0xa0, 0x06, 0x01, 0xc2, 0x06, 0x01, b's', 0xd8, 0x06, 0x05],
+        m.write_to_bytes().unwrap()
+    );
+}
+
+#[test]
+fn test_repeated_packed() {
+    // field 107 packed, then field 107 unpacked
+    let m: ExtendMe = parse_from_bytes(&[0xda, 0x06, 0x02, 0x01, 0x02, 0xd8, 0x06, 0x03]).unwrap();
+    let mut values = exts::repeated_ext.get(&m);
+    values.sort();
+    assert_eq!(vec![1, 2, 3], values);
+}
+
+#[test]
+fn test_wrong_wire_type_ignored() {
+    // field 100 as fixed32
+    let m: ExtendMe = parse_from_bytes(&[0xa5, 0x06, 0x01, 0x00, 0x00, 0x00]).unwrap();
+    assert_eq!(None, exts::int32_ext.get(&m));
+}
+
+#[test]
+fn test_custom_options() {
+    let message = file_descriptor_proto()
+        .get_message_type()
+        .iter()
+        .find(|m| m.get_name() == "WithOptions")
+        .unwrap();
+    assert_eq!(Some(true), exts::message_flag.get(message.get_options()));
+
+    let tagged = &message.get_field()[0];
+    assert_eq!(Some("tag".to_owned()), exts::field_tag.get(tagged.get_options()));
+    assert_eq!(vec![1, 2], exts::field_ids.get(tagged.get_options()));
+
+    let plain = &message.get_field()[1];
+    assert_eq!(None, exts::field_tag.get(plain.get_options()));
+}
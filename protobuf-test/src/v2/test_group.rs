@@ -0,0 +1,103 @@
+use protobuf::*;
+use protobuf::reflect::ProtobufValueBox;
+use protobuf::reflect::dynamic::DescriptorPool;
+use protobuf::reflect::dynamic::DynamicMessage;
+
+use test::*;
+
+use super::test_group_pb::*;
+
+fn message_with_groups() -> MessageWithGroup {
+    let mut identifier = MessageWithGroup_Identifier::new();
+    identifier.set_iii(3);
+    identifier.set_sss("x".to_owned());
+    let mut m = MessageWithGroup::new();
+    m.set_aaa("ab".to_owned());
+    m.mut_identifier().push(identifier);
+    m.mut_nested().set_iii(5);
+    m.mut_nested().mut_deeper().set_sss("y".to_owned());
+    m
+}
+
+const MESSAGE_WITH_GROUPS_HEX: &'static str =
+    "0a 02 61 62 93 01 98 01 03 a2 01 01 78 94 01 13 10 05 1b 0a 01 79 1c 14";
+
+#[test]
+fn test_groups() {
+    test_serialize_deserialize(MESSAGE_WITH_GROUPS_HEX, &message_with_groups());
+}
+
+#[test]
+fn test_group_merged() {
+    let mut m = MessageWithGroup::new();
+    m.merge_from_bytes(&[0x13, 0x10, 0x05, 0x14, 0x13, 0x1b, 0x1c, 0x14]).unwrap();
+    assert_eq!(5, m.get_nested().get_iii());
+    assert!(m.get_nested().has_deeper());
+}
+
+#[test]
+fn test_group_in_oneof() {
+    let mut m = MessageWithGroupInOneof::new();
+    m.mut_choice().set_iii(7);
+    test_serialize_deserialize("0b 10 07 0c", &m);
+}
+
+#[test]
+fn test_incorrect_end_group() {
+    // end of group `Deeper` instead of `Nested`
+    assert!(parse_from_bytes::<MessageWithGroup>(&[0x13, 0x10, 0x05, 0x1c]).is_err());
+    // end group outside of group
+    assert!(parse_from_bytes::<MessageWithGroup>(&[0x14]).is_err());
+    // missing end group
+    assert!(parse_from_bytes::<MessageWithGroup>(&[0x13, 0x10, 0x05]).is_err());
+}
+
+#[test]
+fn test_groups_text_format() {
+    let m = message_with_groups();
+    let text = "aaa: \"ab\" Identifier {iii: 3 sss: \"x\"} Nested {iii: 5 Deeper {sss: \"y\"}}";
+    assert_eq!(text, text_format::print_to_string(&m));
+    assert_eq!(m, text_format::parse_from_str::<MessageWithGroup>(text).unwrap());
+    // field name is accepted too
+    let text = "aaa: \"ab\" identifier { iii: 3 sss: \"x\" } nested { iii: 5 Deeper { sss: \"y\" } }";
+    assert_eq!(m, text_format::parse_from_str::<MessageWithGroup>(text).unwrap());
+}
+
+#[test]
+fn test_groups_json() {
+    let m = message_with_groups();
+    let json = json::print_to_string(&m).unwrap();
+    assert_eq!(m, json::parse_from_str::<MessageWithGroup>(&json).unwrap());
+}
+
+#[test]
+fn test_groups_dynamic() {
+    let pool = DescriptorPool::from_files(vec![file_descriptor_proto().clone()]).expect("pool");
+    let descriptor = pool.message_by_name("MessageWithGroup").unwrap();
+    let bytes = message_with_groups().write_to_bytes().unwrap();
+    let dynamic = DynamicMessage::parse_from_bytes(descriptor, &bytes).unwrap();
+    assert_eq!(1, descriptor.field_by_name("identifier").len_field(&dynamic));
+    let nested = descriptor.field_by_name("nested").get_message(&dynamic);
+    assert_eq!(5, nested.descriptor().field_by_name("iii").get_i32(nested));
+    assert_eq!(bytes, dynamic.write_to_bytes().unwrap());
+}
+
+#[test]
+fn test_groups_dynamic_text_format() {
+    let pool = DescriptorPool::from_files(vec![file_descriptor_proto().clone()]).expect("pool");
+    let descriptor = pool.message_by_name("MessageWithGroup").unwrap();
+    let m = message_with_groups();
+    let text = text_format::print_to_string(&m);
+
+    let mut dynamic = DynamicMessage::new(descriptor);
+    text_format::merge_from_str(&mut dynamic, &text).unwrap();
+    assert_eq!(text, text_format::print_to_string(&dynamic));
+    assert_eq!(m.write_to_bytes().unwrap(), dynamic.write_to_bytes().unwrap());
+
+    let nested = descriptor.field_by_name("nested");
+    assert_eq!("MessageWithGroup.Nested", nested.message_descriptor().full_name());
+    let iii = nested.message_descriptor().field_by_name("iii");
+    iii.set_singular_field(nested.mut_message(&mut dynamic), ProtobufValueBox::I32(6));
+    let bytes = dynamic.write_to_bytes().unwrap();
+    assert_eq!(6, parse_from_bytes::<MessageWithGroup>(&bytes).unwrap().get_nested().get_iii());
+}
@@ -0,0 +1,95 @@
+use protobuf::Message;
+use protobuf::ProtobufEnum;
+use protobuf::text_format::print_to_string;
+
+use super::test_idiomatic_names_pb::*;
+use super::test_idiomatic_names_pb::file_options_like::OptimizeMode;
+use super::test_idiomatic_names_pb::file_options_like::Nested;
+use super::test_idiomatic_names_pb::file_options_like::TestOneof;
+use super::test_idiomatic_names_pb::file_options_like::nested::Deep;
+use super::test_idiomatic_names_pb::file_options_like::nested::Type;
+
+#[test]
+fn test_enum_variants() {
+    assert_eq!(0, TopLevelEnum::Unknown.value());
+    assert_eq!(1, TopLevelEnum::FirstValue.value());
+    assert_eq!(2, TopLevelEnum::TopLevelEnum2.value());
+    assert_eq!(3, TopLevelEnum::Other.value());
+    assert_eq!(Some(OptimizeMode::LiteRuntime), OptimizeMode::from_i32(3));
+    assert_eq!(Type::Double, Type::from_i32(1).unwrap());
+}
+
+#[test]
+fn test_defaults() {
+    let m = FileOptionsLike::new();
+    assert_eq!(OptimizeMode::Speed, m.get_optimize_for());
+    assert_eq!(Type::Float, Deep::new().get_deep_type());
+    assert_eq!(Type::Double, Nested::new().get_nested_type());
+}
+
+#[test]
+fn test_nested() {
+    let mut deep = Deep::new();
+    deep.set_deep_type(Type::Double);
+    deep.set_top(TopLevelEnum::Other);
+
+    let mut m = FileOptionsLike::new();
+    m.mut_nested().set_deep(deep.clone());
+    m.mut_nested().mut_parent().set_optimize_for(OptimizeMode::CodeSize);
+    m.mut_nested().mut_parent().set_oneof_type(Type::Double);
+    m.mut_deeps().push(deep.clone());
+    m.mut_nested_map().insert("k".to_owned(), Nested::new());
+    m.set_oneof_nested(Nested::new());
+
+    let mut r = ReferencesNested::new();
+    r.set_deep(deep.clone());
+    r.set_mode(OptimizeMode::CodeSize);
+
+    let bytes = m.write_to_bytes().unwrap();
+    assert_eq!(m, ::protobuf::parse_from_bytes::<FileOptionsLike>(&bytes).unwrap());
+}
+
+#[test]
+fn test_oneof() {
+    let mut m = FileOptionsLike::new();
+    m.set_oneof_type(Type::Float);
+    assert!(Some(TestOneof::oneof_type(Type::Float)) == m.test_oneof);
+
+    m.mut_oneof_recursive().set_oneof_nested(Nested::new());
+    let bytes = m.write_to_bytes().unwrap();
+    let parsed = ::protobuf::parse_from_bytes::<FileOptionsLike>(&bytes).unwrap();
+    match parsed.test_oneof {
+        Some(TestOneof::oneof_recursive(ref r)) => assert!(r.has_oneof_nested()),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_extension() {
+    let mut nested = Nested::new();
+    nested.mut_unknown_fields().add_varint(100, 2);
+    assert_eq!(Some(Type::Float), exts::ext_type.get(&nested));
+}
+
+#[test]
+fn test_reflection_uses_proto_names() {
+    assert_eq!("TopLevelEnum", TopLevelEnum::enum_descriptor_static(None).name());
+    assert_eq!("OptimizeMode", OptimizeMode::enum_descriptor_static(None).name());
+    assert_eq!("TOP_LEVEL_ENUM_FIRST_VALUE", TopLevelEnum::FirstValue.descriptor().name());
+    assert_eq!("Deep", Deep::new().descriptor().name());
+}
+
+#[test]
+fn test_text_format_uses_proto_names() {
+    let mut m = FileOptionsLike::new();
+    m.set_optimize_for(OptimizeMode::LiteRuntime);
+    m.mut_nested().set_nested_type(Type::Double);
+    assert_eq!(
+        "optimize_for: LITE_RUNTIME nested {nested_type: TYPE_DOUBLE}",
+        print_to_string(&m)
+    );
+
+    let mut parsed = FileOptionsLike::new();
+    ::protobuf::text_format::merge_from_str(&mut parsed, &print_to_string(&m)).unwrap();
+    assert_eq!(m, parsed);
+}
@@ -0,0 +1,69 @@
+use protobuf::Message;
+use protobuf::parse_from_bytes;
+
+use super::test_mod_tree_pb::Root;
+use super::test_mod_tree_pb::x;
+use super::test_mod_tree_pb::x::y;
+use super::test_mod_tree_pb::z;
+
+#[test]
+fn test_package_modules() {
+    let mut xy_point = y::Point::new();
+    xy_point.set_x(10);
+    xy_point.set_y(20);
+
+    let mut common = x::Common::new();
+    common.set_name("common".to_owned());
+
+    let mut z_point = z::Point::new();
+    z_point.set_point(xy_point.clone());
+    z_point.set_color(y::Color::GREEN);
+    z_point.set_common(common);
+    z_point.mut_nested().set_point(xy_point.clone());
+
+    let mut root = Root::new();
+    root.set_xy_point(xy_point);
+    root.set_z_point(z_point);
+    root.set_colors(vec![y::Color::RED, y::Color::GREEN]);
+
+    let bytes = root.write_to_bytes().unwrap();
+    let parsed: Root = parse_from_bytes(&bytes).unwrap();
+    assert_eq!(root, parsed);
+    assert_eq!(20, parsed.get_z_point().get_nested().get_point().get_y());
+    assert_eq!("common", parsed.get_z_point().get_common().get_name());
+}
+
+#[test]
+fn test_package_of_two_files() {
+    let mut polygon = y::Polygon::new();
+    polygon.mut_points().push(y::Point::new());
+    assert_eq!(1, polygon.get_points().len());
+    // not reexported from package module, because both files declare it
+    assert_eq!(
+        "a/b/shapes.proto",
+        y::shapes::file_descriptor_proto().get_name()
+    );
+}
+
+#[test]
+fn test_file_modules() {
+    // types are also accessible through file modules
+    let _: x::y::types::Point = y::Point::new();
+    let _: z::types::Point = z::Point::new();
+    let _: x::common::Common = x::Common::new();
+    assert_eq!(
+        "a/b/types.proto",
+        x::y::types::file_descriptor_proto().get_name()
+    );
+    assert_eq!(
+        "c/types.proto",
+        z::types::file_descriptor_proto().get_name()
+    );
+}
+
+#[test]
+fn test_descriptor() {
+    let descriptor = z::Point::new().descriptor();
+    assert_eq!("point", descriptor.field_by_name("point").name());
+    assert_eq!("Point", descriptor.name());
+}
@@ -0,0 +1,62 @@
+use std::collections::BTreeMap;
+
+use protobuf::Message;
+use protobuf::parse_from_bytes;
+use protobuf::reflect::ProtobufValueBox;
+use protobuf::reflect::ReflectFieldRef;
+
+use super::test_no_std_pb::*;
+
+fn nested(name: &str) -> TestNoStdNested {
+    let mut r = TestNoStdNested::new();
+    r.set_name(name.to_owned());
+    r
+}
+
+#[test]
+fn test_write_read() {
+    let mut m = TestNoStd::new();
+    m.set_int32_field(10);
+    m.set_string_field("s".to_owned());
+    m.set_bytes_field(vec![1, 2]);
+    m.set_enum_field(TestNoStdEnum::GREEN);
+    m.set_message_field(nested("n"));
+    m.mut_repeated_field().push("r".to_owned());
+    m.mut_map_field().insert("b".to_owned(), 2);
+    m.mut_map_field().insert("a".to_owned(), 1);
+    m.mut_map_message_field().insert(3, nested("m"));
+    m.set_one_message(nested("o"));
+
+    let bytes = m.write_to_bytes().unwrap();
+    let parsed: TestNoStd = parse_from_bytes(&bytes).unwrap();
+    assert_eq!(m, parsed);
+}
+
+#[test]
+fn test_map_is_btree_map() {
+    let mut m = TestNoStd::new();
+    m.mut_map_field().insert("b".to_owned(), 2);
+    m.mut_map_field().insert("a".to_owned(), 1);
+
+    let map: &BTreeMap<String, i32> = m.get_map_field();
+    let keys: Vec<&str> = map.keys().map(|k| &k[..]).collect();
+    assert_eq!(vec!["a", "b"], keys);
+}
+
+#[test]
+fn test_map_reflect() {
+    let mut m = TestNoStd::new();
+    m.mut_map_field().insert("a".to_owned(), 1);
+
+    let field = m.descriptor().field_by_name("map_field");
+    match field.get_reflect(&m) {
+        ReflectFieldRef::Map(map) => assert_eq!(1, map.len()),
+        _ => panic!("map expected"),
+    }
+    field.insert_map(
+        &mut m,
+        ProtobufValueBox::String("b".to_owned()),
+        ProtobufValueBox::I32(2),
+    );
+    assert_eq!(Some(&2), m.get_map_field().get("b"));
+}
@@ -1,4 +1,5 @@
 use protobuf::*;
+use protobuf::text_format;
 
 use super::test_required_pb::*;
 
@@ -24,3 +25,11 @@ fn test_is_initialized_is_recursive() {
     m.mut_inner().set_b(false);
     assert!(m.is_initialized());
 }
+
+#[test]
+fn test_text_format_missing_required() {
+    assert!(text_format::parse_from_str::<TestRequiredOuter>("inner { b: true }").is_ok());
+    let e = text_format::parse_from_str::<TestRequiredOuter>("inner { }").unwrap_err();
+    assert_eq!(text_format::Loc { line: 1, col: 9 }, e.loc);
+    assert!(text_format::parse_from_str::<TestRequiredOuter>("").is_err());
+}
@@ -0,0 +1,72 @@
+// generated code is checked for doc comments, because rustdoc is not available at runtime
+static GENERATED: &'static str = include_str!("test_rustdoc_pb.rs");
+
+fn assert_doc_before(doc: &str, decl: &str) {
+    let expected = format!("{}\n{}", doc, decl);
+    let normalized: String = GENERATED
+        .lines()
+        .map(|l| l.trim())
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert!(normalized.contains(&expected), "not found:\n{}", expected);
+}
+
+#[test]
+fn test_message() {
+    assert!(!GENERATED.contains("Detached comment"));
+    assert_doc_before(
+        "/// Message with comments.\n\
+         ///\n\
+         /// Example:\n\
+         ///\n\
+         /// ```text\n\
+         /// message Foo {\n\
+         ///     optional int32 x = 1;\n\
+         /// }\n\
+         /// ```\n\
+         ///\n\
+         /// Links like \\[this\\] and tags like \\<b> are escaped.\n\
+         #[derive(PartialEq,Clone,Default)]",
+        "pub struct CommentedMessage {",
+    );
+}
+
+#[test]
+fn test_fields() {
+    assert_doc_before("/// Leading comment of field", "pub leading: ::std::option::Option<i32>,");
+    assert_doc_before("/// Trailing comment of field", "pub trailing: ::std::option::Option<i32>,");
+    assert_doc_before("/// Block comment of field", "pub block: ::std::option::Option<i32>,");
+    assert_doc_before(
+        "/// ```text\n/// fn not_a_doctest() { compile_error!(); }\n/// ```",
+        "pub nested_enum: ",
+    );
+}
+
+#[test]
+fn test_oneof() {
+    assert_doc_before(
+        "/// Leading comment of oneof",
+        "pub one: ::std::option::Option<CommentedMessage_oneof_one>,",
+    );
+    assert_doc_before(
+        "/// Leading comment of oneof\n#[derive(Clone,PartialEq)]",
+        "pub enum CommentedMessage_oneof_one {",
+    );
+    assert_doc_before("/// Leading comment of oneof field", "s(::std::string::String),");
+}
+
+#[test]
+fn test_enum() {
+    assert_doc_before(
+        "/// Leading comment of nested enum\n#[derive(Clone,PartialEq,Eq,Debug,Hash)]",
+        "pub enum CommentedMessage_NestedEnum {",
+    );
+    assert_doc_before("/// Leading comment of enum value", "FIRST = 1,");
+    assert_doc_before("/// Trailing comment of enum value", "SECOND = 2,");
+}
+
+#[test]
+fn test_service() {
+    assert_doc_before("/// Leading comment of service", "pub trait CommentedService {");
+    assert_doc_before("/// Leading comment of method", "fn method(");
+}
@@ -0,0 +1,43 @@
+use serde_json;
+
+use protobuf::Message;
+
+use super::test_serde_derive_pb::*;
+
+#[test]
+fn test_round_trip() {
+    let mut m = TestSerdeDerive::new();
+    m.set_int32_field(10);
+    m.set_string_field("str".to_owned());
+    m.set_bytes_field(vec![1, 2]);
+    m.set_enum_field(TestSerdeDerive_TestSerdeEnum::GREEN);
+    m.mut_message_field().set_value("nested".to_owned());
+    m.mut_repeated_int64_field().push(-3);
+    m.set_one_uint32(17);
+
+    let json = serde_json::to_string(&m).expect("serialize");
+    let parsed: TestSerdeDerive = serde_json::from_str(&json).expect("deserialize");
+    assert_eq!(m, parsed);
+}
+
+#[test]
+fn test_representation() {
+    let mut m = TestSerdeDerive::new();
+    m.set_enum_field(TestSerdeDerive_TestSerdeEnum::RED);
+
+    let json = serde_json::to_value(&m).expect("serialize");
+    // singular fields are serialized as `Option`, closed enums by name
+    assert_eq!(serde_json::Value::Null, json["int32_field"]);
+    assert_eq!(serde_json::Value::Null, json["message_field"]);
+    assert_eq!("RED", json["enum_field"]);
+}
+
+#[test]
+fn test_unknown_fields_preserved() {
+    let mut m = TestSerdeDerive::new();
+    m.mut_unknown_fields().add_varint(100, 7);
+
+    let json = serde_json::to_string(&m).expect("serialize");
+    let parsed: TestSerdeDerive = serde_json::from_str(&json).expect("deserialize");
+    assert_eq!(m.write_to_bytes().unwrap(), parsed.write_to_bytes().unwrap());
+}
@@ -0,0 +1,124 @@
+use protobuf::service::*;
+
+use super::test_service_pb::*;
+
+fn request(value: i32) -> Request {
+    let mut r = Request::new();
+    r.set_value(value);
+    r
+}
+
+fn response(value: i32) -> Response {
+    let mut r = Response::new();
+    r.set_value(value);
+    r
+}
+
+struct TestServiceImpl;
+
+impl TestService for TestServiceImpl {
+    fn unary(&self, req: Request) -> ServiceResult<Response> {
+        if req.get_value() < 0 {
+            return Err(ServiceError::new("negative"));
+        }
+        Ok(response(req.get_value() + 1))
+    }
+
+    fn client_streaming(&self, req: Streaming<Request>) -> ServiceResult<Response> {
+        let mut sum = 0;
+        for r in req {
+            sum += r?.get_value();
+        }
+        Ok(response(sum))
+    }
+
+    fn server_streaming(&self, req: Request) -> ServiceResult<Streaming<Response>> {
+        Ok(Box::new((0..req.get_value()).map(|i| Ok(response(i)))))
+    }
+
+    fn bidi_streaming(&self, req: Streaming<Request>) -> ServiceResult<Streaming<Response>> {
+        Ok(Box::new(req.map(|r| r.map(|r| response(r.get_value() * 2)))))
+    }
+
+    fn rpc_type(&self, req: Request) -> ServiceResult<Response> {
+        Ok(response(-req.get_value()))
+    }
+}
+
+fn client() -> TestServiceClient<InProcessChannel<TestServiceServer<TestServiceImpl>>> {
+    TestServiceClient::new(InProcessChannel::new(TestServiceServer::new(TestServiceImpl)))
+}
+
+fn values(responses: Streaming<Response>) -> Vec<i32> {
+    responses.map(|r| r.unwrap().get_value()).collect()
+}
+
+#[test]
+fn test_descriptor() {
+    assert_eq!("test_service.TestService", TEST_SERVICE_DESCRIPTOR.full_name);
+    let names: Vec<_> = TEST_SERVICE_DESCRIPTOR.methods.iter().map(|m| m.name).collect();
+    assert_eq!(
+        vec!["Unary", "ClientStreaming", "ServerStreaming", "BidiStreaming", "Type"],
+        names
+    );
+
+    let method = TEST_SERVICE_DESCRIPTOR.method_by_name("ServerStreaming").unwrap();
+    assert_eq!("/test_service.TestService/ServerStreaming", method.full_path);
+    assert_eq!(MethodType::ServerStreaming, method.method_type);
+    assert!(!method.method_type.is_client_streaming());
+    assert!(method.method_type.is_server_streaming());
+    assert_eq!("test_service.Request", method.input_type);
+    assert_eq!("test_service.Response", method.output_type);
+
+    let method = TEST_SERVICE_DESCRIPTOR.method_by_path("/test_service.TestService/Type");
+    assert_eq!("Type", method.unwrap().name);
+}
+
+#[test]
+fn test_unary() {
+    let client = client();
+    assert_eq!(11, client.unary(&request(10)).unwrap().get_value());
+    assert_eq!(-3, client.rpc_type(&request(3)).unwrap().get_value());
+    assert_eq!(
+        ServiceError::new("negative"),
+        client.unary(&request(-1)).unwrap_err()
+    );
+}
+
+#[test]
+fn test_streaming() {
+    let client = client();
+    let sum = client.client_streaming(vec![request(1), request(2), request(3)]);
+    assert_eq!(6, sum.unwrap().get_value());
+
+    let responses = client.server_streaming(&request(3)).unwrap();
+    assert_eq!(vec![0, 1, 2], values(responses));
+
+    let responses = client.bidi_streaming(vec![request(1), request(5)]).unwrap();
+    assert_eq!(vec![2, 10], values(responses));
+}
+
+#[test]
+fn test_unknown_method() {
+    static OTHER: MethodDescriptor = MethodDescriptor {
+        name: "Other",
+        full_path: "/test_service.TestService/Other",
+        method_type: MethodType::Unary,
+        input_type: "test_service.Request",
+        output_type: "test_service.Response",
+    };
+    let channel = InProcessChannel::new(TestServiceServer::new(TestServiceImpl));
+    let err = call_unary::<_, _, Response>(&channel, &OTHER, &request(1)).unwrap_err();
+    assert_eq!("unknown method: /test_service.TestService/Other", err.message);
+}
+
+#[test]
+fn test_unary_requires_single_message() {
+    let server = TestServiceServer::new(TestServiceImpl);
+    let method = TEST_SERVICE_DESCRIPTOR.method_by_name("Unary").unwrap();
+    let err = match server.call(method, Box::new(Vec::new().into_iter())) {
+        Ok(..) => panic!("expecting error"),
+        Err(e) => e,
+    };
+    assert_eq!("expecting one message, got none", err.message);
+}
@@ -0,0 +1,143 @@
+use protobuf::Message;
+use protobuf::parse_from_bytes;
+use protobuf::text_format;
+use protobuf::reflect::ProtobufValueBox;
+use protobuf::reflect::ProtobufValueRef;
+use protobuf::reflect::ReflectFieldRef;
+
+use super::test_singular_field_option_pb::*;
+
+fn nested(id: i32) -> TestSingularFieldOptionNested {
+    let mut m = TestSingularFieldOptionNested::new();
+    m.set_id(id);
+    m
+}
+
+fn singular_field() -> TestSingularField {
+    let mut m = TestSingularField::new();
+    m.set_int32_field(1);
+    m.set_string_field("s".to_owned());
+    m.set_bytes_field(vec![2, 3]);
+    m.set_message_field(nested(4));
+    m.mut_recursive().mut_message_field().set_id(5);
+    m.mut_group().set_group_field(6);
+    m
+}
+
+fn singular_field_option() -> TestSingularFieldOption {
+    let mut recursive = TestSingularFieldOption::new();
+    recursive.message_field = Some(Box::new(nested(5)));
+
+    let mut group = TestSingularFieldOption_Group::new();
+    group.group_field = Some(6);
+
+    let mut m = TestSingularFieldOption::new();
+    m.int32_field = Some(1);
+    m.string_field = Some("s".to_owned());
+    m.bytes_field = Some(vec![2, 3]);
+    m.message_field = Some(Box::new(nested(4)));
+    m.recursive = Some(Box::new(recursive));
+    m.group = Some(Box::new(group));
+    m
+}
+
+#[test]
+fn test_accessors() {
+    let mut m = TestSingularFieldOption::new();
+    assert!(!m.has_string_field());
+    assert_eq!("", m.get_string_field());
+    assert_eq!("def", m.get_default_string_field());
+    assert!(!m.has_message_field());
+    assert_eq!(0, m.get_message_field().get_id());
+
+    m.mut_string_field().push_str("ab");
+    m.mut_message_field().set_id(3);
+    m.set_bytes_field(vec![1]);
+    assert_eq!(Some("ab".to_owned()), m.string_field);
+    assert_eq!(Some(Box::new(nested(3))), m.message_field);
+    assert_eq!(Some(vec![1]), m.bytes_field);
+
+    m.clear_message_field();
+    assert_eq!(None, m.message_field);
+
+    match m.string_field {
+        Some(ref s) if s == "ab" => (),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_wire_format_is_the_same() {
+    let bytes = singular_field().write_to_bytes().unwrap();
+    assert_eq!(bytes, singular_field_option().write_to_bytes().unwrap());
+    assert_eq!(bytes.len() as u32, singular_field_option().compute_size());
+
+    assert_eq!(singular_field_option(), parse_from_bytes(&bytes).unwrap());
+    let mut m = TestSingularFieldOption::new();
+    m.merge_from_bytes(&bytes).unwrap();
+    m.merge_from_bytes(&bytes).unwrap();
+    assert_eq!(singular_field_option(), m);
+}
+
+#[test]
+fn test_merge() {
+    let mut m = TestSingularFieldOption::new();
+    m.mut_recursive().int32_field = Some(1);
+
+    let mut other = TestSingularFieldOption::new();
+    other.mut_recursive().string_field = Some("x".to_owned());
+    other.string_field = Some("y".to_owned());
+    m.merge(&other);
+
+    assert_eq!(Some(1), m.get_recursive().int32_field);
+    assert_eq!("x", m.get_recursive().get_string_field());
+    assert_eq!("y", m.get_string_field());
+}
+
+#[test]
+fn test_required() {
+    let mut m = TestSingularFieldOptionRequired::new();
+    m.required_string = Some("s".to_owned());
+    assert!(!m.is_initialized());
+    m.required_message = Some(Box::new(TestSingularFieldOptionNested::new()));
+    assert!(!m.is_initialized());
+    m.mut_required_message().set_id(1);
+    assert!(m.is_initialized());
+
+    let bytes = m.write_to_bytes().unwrap();
+    assert_eq!(m, parse_from_bytes(&bytes).unwrap());
+}
+
+#[test]
+fn test_reflect() {
+    let mut m = TestSingularFieldOption::new();
+    let d = m.descriptor();
+
+    let string_field = d.field_by_name("string_field");
+    assert!(!string_field.has_field(&m));
+    string_field.set_singular_field(&mut m, ProtobufValueBox::String("s".to_owned()));
+    assert_eq!(Some("s".to_owned()), m.string_field);
+    assert_eq!("s", string_field.get_str(&m));
+
+    let message_field = d.field_by_name("message_field");
+    assert!(!message_field.has_field(&m));
+    message_field.set_singular_field(&mut m, ProtobufValueBox::Message(Box::new(nested(1))));
+    assert_eq!(Some(Box::new(nested(1))), m.message_field);
+    match message_field.get_reflect(&m) {
+        ReflectFieldRef::Optional(Some(ProtobufValueRef::Message(v))) => {
+            assert_eq!(Some(&nested(1)), v.as_any().downcast_ref());
+        }
+        _ => panic!("message expected"),
+    }
+
+    message_field.clear_field(&mut m);
+    string_field.clear_field(&mut m);
+    assert_eq!(TestSingularFieldOption::new(), m);
+}
+
+#[test]
+fn test_text_format() {
+    let text = text_format::print_to_string(&singular_field());
+    assert_eq!(text, text_format::print_to_string(&singular_field_option()));
+    assert_eq!(singular_field_option(), text_format::parse_from_str(&text).unwrap());
+}
@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+
+use protobuf::Message;
+
+use super::test_with_methods_pb::*;
+
+fn nested(id: i32) -> TestWithMethodsNested {
+    TestWithMethodsNested::new().with_id(id)
+}
+
+#[test]
+fn test_same_as_setters() {
+    let mut expected = TestWithMethods::new();
+    expected.set_int32_field(10);
+    expected.set_string_field("s".to_owned());
+    expected.set_bytes_field(vec![1, 2]);
+    expected.set_enum_field(TestWithMethodsEnum::TWO);
+    expected.mut_message_field().set_id(1);
+    expected.mut_message_field().set_name("n".to_owned());
+    expected.set_repeated_field(vec![3, 4]);
+    expected.mut_repeated_message_field().push(nested(2));
+    expected.mut_map_field().insert("k".to_owned(), 5);
+    expected.set_one_uint32(6);
+
+    let mut map = HashMap::new();
+    map.insert("k".to_owned(), 5);
+
+    let built = TestWithMethods::new()
+        .with_int32_field(10)
+        .with_string_field("s".to_owned())
+        .with_bytes_field(vec![1, 2])
+        .with_enum_field(TestWithMethodsEnum::TWO)
+        .with_message_field(nested(1).with_name("n".to_owned()))
+        .with_repeated_field(vec![3, 4])
+        .with_repeated_message_field(vec![nested(2)])
+        .with_map_field(map)
+        .with_one_uint32(6);
+
+    assert_eq!(expected, built);
+}
+
+#[test]
+fn test_oneof_last_wins() {
+    let m = TestWithMethods::new()
+        .with_one_uint32(6)
+        .with_one_message(nested(1));
+    assert!(!m.has_one_uint32());
+    assert_eq!(1, m.get_one_message().get_id());
+}
+
+#[test]
+fn test_required_fields_checked() {
+    let m = TestWithMethods::new().with_message_field(TestWithMethodsNested::new());
+    assert!(!m.is_initialized());
+
+    let m = TestWithMethods::new().with_message_field(nested(1));
+    assert!(m.is_initialized());
+}
@@ -8,9 +8,32 @@ mod test_map;
 
 mod test_ident_pb;
 
+mod test_json_pb;
+mod test_json;
+mod test_text_format_time;
+
 mod test_issue_190_pb;
 
+mod test_enum_unknown_pb;
+mod test_enum_unknown;
+
+mod test_any_pb;
+mod test_any;
+
+mod test_field_mask_pb;
+mod test_field_mask;
+
+mod test_wrappers_as_option_pb;
+mod test_wrappers_as_option;
+
+mod test_serde_derive_pb;
+#[cfg(feature = "with-serde")]
+mod test_serde_derive;
+
 #[cfg(feature = "bytes")]
 mod test_map_carllerche;
 #[cfg(feature = "bytes")]
 mod test_map_carllerche_pb;
+
+mod test_idiomatic_names_pb;
+mod test_idiomatic_names;
@@ -0,0 +1,84 @@
+use protobuf::Message;
+use protobuf::text_format;
+use protobuf::well_known_types::Any;
+use protobuf::well_known_types::Duration;
+use protobuf::well_known_types_util;
+
+use super::test_any_pb::*;
+
+fn message_in_any() -> MessageInAny {
+    let mut m = MessageInAny::new();
+    m.set_value(3);
+    m.mut_duration().set_seconds(2);
+    m
+}
+
+#[test]
+fn test_pack_unpack() {
+    let m = message_in_any();
+    let any = Any::pack(&m).unwrap();
+    assert_eq!("type.googleapis.com/test_any.MessageInAny", any.get_type_url());
+    assert!(any.is::<MessageInAny>());
+    assert!(!any.is::<Duration>());
+    assert_eq!(Some(m.clone()), any.unpack::<MessageInAny>().unwrap());
+    assert_eq!(None, any.unpack::<Duration>().unwrap());
+
+    let mut any = any;
+    any.set_value(vec![0xff]);
+    assert!(any.unpack::<MessageInAny>().is_err());
+}
+
+#[test]
+fn test_unpack_dyn() {
+    let m = message_in_any();
+    let any = Any::pack(&m).unwrap();
+    well_known_types_util::register_message::<MessageInAny>();
+    let content = any.unpack_dyn().unwrap().unwrap();
+    assert_eq!(Some(&m), content.as_any().downcast_ref::<MessageInAny>());
+
+    let mut unregistered = UnregisteredInAny::new();
+    unregistered.set_name("n".to_owned());
+    let any = Any::pack(&unregistered).unwrap();
+    assert!(any.unpack_dyn().unwrap().is_none());
+}
+
+#[test]
+fn test_text_format() {
+    well_known_types_util::register_message::<MessageInAny>();
+
+    let mut m = TestAny::new();
+    m.set_any(Any::pack(&message_in_any()).unwrap());
+    let mut duration = Duration::new();
+    duration.set_nanos(1000);
+    m.mut_any_repeated().push(Any::pack(&duration).unwrap());
+    let mut unregistered = UnregisteredInAny::new();
+    unregistered.set_name("n".to_owned());
+    m.mut_any_repeated().push(Any::pack(&unregistered).unwrap());
+
+    let text = "any {[type.googleapis.com/test_any.MessageInAny] {value: 3 duration {seconds: 2}}} \
+                any_repeated {[type.googleapis.com/google.protobuf.Duration] {nanos: 1000}} \
+                any_repeated {type_url: \"type.googleapis.com/test_any.UnregisteredInAny\" value: \"\\n\\001n\"}";
+    assert_eq!(text, text_format::print_to_string(&m));
+    assert_eq!(m, text_format::parse_from_str(text).unwrap());
+
+    let pretty = format!("{:#?}", m);
+    assert!(pretty.contains("  [type.googleapis.com/test_any.MessageInAny] {\n    value: 3\n"));
+    assert_eq!(m, text_format::parse_from_str(&pretty).unwrap());
+}
+
+#[test]
+fn test_text_format_parse_errors() {
+    let parse = |text| text_format::parse_from_str::<TestAny>(text).unwrap_err().message;
+    assert_eq!(
+        "unknown type in Any: type.googleapis.com/test_any.UnregisteredInAny",
+        parse("any {[type.googleapis.com/test_any.UnregisteredInAny] {name: \"n\"}}")
+    );
+    assert_eq!(
+        "Any content is specified multiple times",
+        parse(
+            "any {[type.googleapis.com/google.protobuf.Empty] {} \
+             [type.googleapis.com/google.protobuf.Empty] {}}",
+        )
+    );
+    assert_eq!("extensions are not supported", parse("[x.y]: 1"));
+}
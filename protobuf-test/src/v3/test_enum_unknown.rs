@@ -0,0 +1,66 @@
+use protobuf::*;
+use protobuf::json;
+use protobuf::text_format;
+use protobuf::reflect::dynamic::DescriptorPool;
+use protobuf::reflect::dynamic::DynamicMessage;
+
+use test::*;
+
+use super::test_enum_unknown_pb::*;
+
+#[test]
+fn test_unknown_values_are_preserved() {
+    let m = parse_from_bytes::<TestEnumUnknown>(&[0x08, 0x05, 0x12, 0x02, 0x01, 0x07, 0x18, 0x09])
+        .unwrap();
+    assert_eq!(Err(5), m.get_singular().enum_value());
+    assert_eq!(
+        vec![Ok(TestEnumUnknownEnum::ONE), Err(7)],
+        m.get_repeated_packed().iter().map(|e| e.enum_value()).collect::<Vec<_>>()
+    );
+    assert_eq!(9, m.get_in_oneof().value());
+    assert!(m.get_unknown_fields().is_empty());
+
+    test_serialize_deserialize("08 05 12 02 01 07 18 09", &m);
+}
+
+#[test]
+fn test_unknown_values_set() {
+    let mut m = TestEnumUnknown::new();
+    m.set_singular(ProtobufEnumOrUnknown::from_i32(100));
+    m.set_in_oneof(ProtobufEnumOrUnknown::from_i32(-1));
+    assert_eq!(100, m.get_singular().value());
+    assert!(m.get_singular() != TestEnumUnknownEnum::ZERO);
+    test_serialize_deserialize("08 64 18 ff ff ff ff ff ff ff ff ff 01", &m);
+}
+
+#[test]
+fn test_unknown_values_text_format() {
+    let mut m = TestEnumUnknown::new();
+    m.set_singular(ProtobufEnumOrUnknown::from_i32(100));
+    m.mut_repeated_packed().push(TestEnumUnknownEnum::ONE.into());
+    m.set_in_oneof(TestEnumUnknownEnum::ZERO.into());
+    let text = "singular: 100 repeated_packed: ONE in_oneof: ZERO";
+    assert_eq!(text, text_format::print_to_string(&m));
+    assert_eq!(m, text_format::parse_from_str(text).unwrap());
+}
+
+#[test]
+fn test_unknown_values_json() {
+    let mut m = TestEnumUnknown::new();
+    m.set_singular(ProtobufEnumOrUnknown::from_i32(100));
+    m.set_in_oneof(TestEnumUnknownEnum::ONE.into());
+    let json = "{\"singular\": 100, \"inOneof\": \"ONE\"}";
+    assert_eq!(json, json::print_to_string(&m).unwrap());
+    assert_eq!(m, json::parse_from_str(json).unwrap());
+}
+
+#[test]
+fn test_unknown_values_dynamic() {
+    let pool = DescriptorPool::from_files(vec![file_descriptor_proto().clone()]).expect("pool");
+    let descriptor = pool.message_by_name("TestEnumUnknown").unwrap();
+    let bytes = [0x08, 0x05, 0x12, 0x02, 0x01, 0x07, 0x18, 0x09];
+    let dynamic = DynamicMessage::parse_from_bytes(descriptor, &bytes).unwrap();
+    assert_eq!(5, descriptor.field_by_name("singular").get_i32(&dynamic));
+    assert!(dynamic.get_unknown_fields().is_empty());
+    assert_eq!(&bytes[..], &dynamic.write_to_bytes().unwrap()[..]);
+}
@@ -0,0 +1,120 @@
+use protobuf::Message;
+use protobuf::well_known_types::FieldMask;
+use protobuf::well_known_types_util;
+use protobuf::well_known_types_util::MergeOptions;
+
+use super::test_field_mask_pb::*;
+
+fn nested(a: i32, b: &str, c: &[i32]) -> Nested {
+    let mut r = Nested::new();
+    r.set_a(a);
+    r.set_b(b.to_owned());
+    r.set_c(c.to_vec());
+    r
+}
+
+fn message() -> TestFieldMask {
+    let mut m = TestFieldMask::new();
+    m.set_scalar(1);
+    m.set_text("t".to_owned());
+    m.set_nested(nested(2, "b", &[3]));
+    m.mut_repeated_nested().push(nested(4, "", &[]));
+    m.mut_map().insert("k".to_owned(), 5);
+    m.mut_strings().push("s".to_owned());
+    m
+}
+
+fn mask(paths: &[&str]) -> FieldMask {
+    FieldMask::from_paths(paths.iter().cloned())
+}
+
+#[test]
+fn test_validate() {
+    let d = TestFieldMask::descriptor_static(None);
+    assert!(mask(&["scalar", "nested.a", "repeated_nested", "map"]).validate(d).is_ok());
+    assert_eq!(
+        "incorrect path \"nested.x\": message test_field_mask.Nested has no field \"x\"",
+        mask(&["nested.x"]).validate(d).unwrap_err().message
+    );
+    assert_eq!(
+        "incorrect path \"repeated_nested.a\": field repeated_nested is not a singular message field",
+        mask(&["repeated_nested.a"]).validate(d).unwrap_err().message
+    );
+    assert!(mask(&["scalar.a"]).validate(d).is_err());
+    assert!(mask(&["nested."]).validate(d).is_err());
+}
+
+#[test]
+fn test_trim() {
+    let mut m = message();
+    well_known_types_util::trim(&mut m, &mask(&["text", "nested.b", "map", "unknown"]));
+
+    let mut expected = TestFieldMask::new();
+    expected.set_text("t".to_owned());
+    expected.set_nested(nested(0, "b", &[]));
+    expected.mut_map().insert("k".to_owned(), 5);
+    assert_eq!(expected, m);
+}
+
+#[test]
+fn test_merge_with_mask() {
+    let src = message();
+    let mut dst = TestFieldMask::new();
+    dst.set_scalar(10);
+    dst.set_text("dst".to_owned());
+    dst.set_nested(nested(20, "dst", &[30]));
+    dst.mut_repeated_nested().push(nested(40, "", &[]));
+    dst.mut_map().insert("dst".to_owned(), 50);
+
+    let mut m = dst.clone();
+    let paths = &["scalar", "nested", "repeated_nested", "map", "strings"];
+    well_known_types_util::merge_with_mask(&src, &mut m, &mask(paths), &MergeOptions::default());
+    let mut expected = dst.clone();
+    expected.set_scalar(1);
+    expected.set_nested(nested(2, "b", &[30, 3]));
+    expected.mut_repeated_nested().push(nested(4, "", &[]));
+    expected.mut_map().insert("k".to_owned(), 5);
+    expected.mut_strings().push("s".to_owned());
+    assert_eq!(expected, m);
+
+    let mut m = dst.clone();
+    let options = MergeOptions {
+        replace_message_fields: true,
+        replace_repeated_fields: true,
+    };
+    well_known_types_util::merge_with_mask(&src, &mut m, &mask(paths), &options);
+    let mut expected = src.clone();
+    expected.set_text("dst".to_owned());
+    assert_eq!(expected, m);
+
+    // subpath, and fields unset in source
+    let mut m = dst.clone();
+    let paths = &["nested.a", "text"];
+    well_known_types_util::merge_with_mask(
+        &TestFieldMask::new(),
+        &mut m,
+        &mask(paths),
+        &MergeOptions::default(),
+    );
+    let mut expected = dst.clone();
+    expected.set_text(String::new());
+    expected.set_nested(nested(0, "dst", &[30]));
+    assert_eq!(expected, m);
+
+    // unset message field is kept unless replaced
+    let mut m = dst.clone();
+    well_known_types_util::merge_with_mask(
+        &TestFieldMask::new(),
+        &mut m,
+        &mask(&["nested"]),
+        &MergeOptions::default(),
+    );
+    assert_eq!(dst, m);
+    well_known_types_util::merge_with_mask(
+        &TestFieldMask::new(),
+        &mut m,
+        &mask(&["nested"]),
+        &options,
+    );
+    assert!(!m.has_nested());
+}
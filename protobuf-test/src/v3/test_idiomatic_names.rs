@@ -0,0 +1,43 @@
+use protobuf::*;
+
+use test::*;
+
+use super::test_idiomatic_names_pb::*;
+use super::test_idiomatic_names_pb::value::Entry;
+use super::test_idiomatic_names_pb::value::Kind;
+
+#[test]
+fn test_open_enum() {
+    assert_eq!(Kind::Null, Kind::default());
+    assert_eq!(Kind::Null, Value::new().get_kind().enum_value_or_default());
+
+    let mut m = Value::new();
+    m.set_kind(ProtobufEnumOrUnknown::new(Kind::Number));
+    m.set_kind_value(ProtobufEnumOrUnknown::from_i32(7));
+    test_serialize_deserialize("08 01 20 07", &m);
+}
+
+#[test]
+fn test_nested() {
+    let mut entry = Entry::new();
+    entry.set_kind(ProtobufEnumOrUnknown::new(Kind::Number));
+
+    let mut m = Value::new();
+    m.mut_entries().push(entry.clone());
+    m.set_entry_value(entry);
+    match m.value {
+        Some(value::Value::entry_value(ref e)) => {
+            assert_eq!(Ok(Kind::Number), e.get_kind().enum_value())
+        }
+        _ => panic!(),
+    }
+    test_serialize_deserialize("12 02 08 01 2a 02 08 01", &m);
+}
+
+#[test]
+fn test_text_format_uses_proto_names() {
+    let mut m = Value::new();
+    m.set_kind(ProtobufEnumOrUnknown::new(Kind::Number));
+    m.set_kind_value(ProtobufEnumOrUnknown::new(Kind::Null));
+    assert_eq!("kind: KIND_NUMBER kind_value: KIND_NULL", text_format::print_to_string(&m));
+}
@@ -0,0 +1,359 @@
+use std::f64;
+
+use protobuf::Message;
+use protobuf::json;
+use protobuf::json::ParseOptions;
+use protobuf::json::PrintOptions;
+use protobuf::well_known_types::Any;
+use protobuf::well_known_types::Duration;
+use protobuf::well_known_types::Empty;
+use protobuf::well_known_types::ListValue;
+use protobuf::well_known_types::NullValue;
+use protobuf::well_known_types::Struct;
+use protobuf::well_known_types::Timestamp;
+use protobuf::well_known_types::Value;
+
+use super::test_json_pb::*;
+
+fn t<M : Message + PartialEq>(expected: &str, m: &M) {
+    assert_eq!(expected, json::print_to_string(m).expect("print"));
+    assert_eq!(*m, json::parse_from_str::<M>(expected).expect("parse"));
+}
+
+fn parse_error<M : Message>(json: &str) -> String {
+    json::parse_from_str::<M>(json).expect_err("error expected").message
+}
+
+#[test]
+fn test_empty() {
+    t("{}", &TestTypes::new());
+    t("{}", &TestWellKnownTypes::new());
+}
+
+#[test]
+fn test_scalars() {
+    let mut m = TestTypes::new();
+    m.set_double_singular(1.5);
+    m.set_float_singular(-2.25);
+    m.set_int32_singular(-3);
+    m.set_int64_singular(-9223372036854775808);
+    m.set_uint32_singular(4294967295);
+    m.set_uint64_singular(18446744073709551615);
+    m.set_sint32_singular(7);
+    m.set_sint64_singular(-8);
+    m.set_fixed32_singular(9);
+    m.set_fixed64_singular(10);
+    m.set_sfixed32_singular(-11);
+    m.set_sfixed64_singular(12);
+    m.set_bool_singular(true);
+    m.set_string_singular("quote\"newline\n\u{44e}\x01".to_owned());
+    m.set_bytes_singular(b"\x00\xff\xfe".to_vec());
+    m.set_test_enum_singular(TestEnum::LIGHT.into());
+    m.mut_test_message_singular().set_value(17);
+    t(
+        "{\"doubleSingular\": 1.5, \"floatSingular\": -2.25, \"int32Singular\": -3, \
+         \"int64Singular\": \"-9223372036854775808\", \"uint32Singular\": 4294967295, \
+         \"uint64Singular\": \"18446744073709551615\", \"sint32Singular\": 7, \
+         \"sint64Singular\": \"-8\", \"fixed32Singular\": 9, \"fixed64Singular\": \"10\", \
+         \"sfixed32Singular\": -11, \"sfixed64Singular\": \"12\", \"boolSingular\": true, \
+         \"stringSingular\": \"quote\\\"newline\\n\u{44e}\\u0001\", \"bytesSingular\": \"AP/+\", \
+         \"testEnumSingular\": \"LIGHT\", \"testMessageSingular\": {\"value\": 17}}",
+        &m,
+    );
+}
+
+#[test]
+fn test_float_exponent() {
+    let mut m = TestTypes::new();
+    m.set_double_singular(1e300);
+    m.set_float_singular(-1.5e-10);
+    t("{\"doubleSingular\": 1e300, \"floatSingular\": -1.5e-10}", &m);
+
+    m.set_double_singular(-2.5e-300);
+    m.set_float_singular(3e30);
+    t("{\"doubleSingular\": -2.5e-300, \"floatSingular\": 3e30}", &m);
+
+    m.set_double_singular(1e20);
+    m.set_float_singular(0.000001);
+    t("{\"doubleSingular\": 100000000000000000000, \"floatSingular\": 0.000001}", &m);
+}
+
+#[test]
+fn test_float_special() {
+    let mut m = TestTypes::new();
+    m.set_double_singular(f64::NEG_INFINITY);
+    m.set_float_singular(::std::f32::INFINITY);
+    t("{\"doubleSingular\": \"-Infinity\", \"floatSingular\": \"Infinity\"}", &m);
+
+    m.set_double_singular(f64::NAN);
+    assert_eq!(
+        "{\"doubleSingular\": \"NaN\", \"floatSingular\": \"Infinity\"}",
+        json::print_to_string(&m).unwrap()
+    );
+    let parsed = json::parse_from_str::<TestTypes>("{\"doubleSingular\": \"NaN\"}").unwrap();
+    assert!(parsed.get_double_singular().is_nan());
+}
+
+#[test]
+fn test_repeated_and_maps() {
+    let mut m = TestTypes::new();
+    m.mut_int64_repeated().push(1);
+    m.mut_int64_repeated().push(-2);
+    m.mut_test_enum_repeated().push(TestEnum::DARK.into());
+    m.mut_test_message_repeated().push(TestMessage::new());
+    m.mut_map_string_int32().insert("b".to_owned(), 2);
+    m.mut_map_string_int32().insert("a".to_owned(), 1);
+    let mut message = TestMessage::new();
+    message.set_value(5);
+    m.mut_map_int64_message().insert(-10, message);
+    m.mut_map_bool_enum().insert(true, TestEnum::LIGHT.into());
+    m.set_field_with_json_name("x".to_owned());
+    t(
+        "{\"int64Repeated\": [\"1\", \"-2\"], \"testEnumRepeated\": [\"DARK\"], \
+         \"testMessageRepeated\": [{}], \"mapStringInt32\": {\"a\": 1, \"b\": 2}, \
+         \"mapInt64Message\": {\"-10\": {\"value\": 5}}, \"mapBoolEnum\": {\"true\": \"LIGHT\"}, \
+         \"renamed\": \"x\"}",
+        &m,
+    );
+}
+
+#[test]
+fn test_print_options() {
+    let mut m = TestTypes::new();
+    m.set_int32_singular(1);
+    m.set_test_enum_singular(TestEnum::DARK.into());
+
+    let options = PrintOptions {
+        proto_field_name: true,
+        enum_values_int: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        "{\"int32_singular\": 1, \"test_enum_singular\": 1}",
+        json::print_to_string_with_options(&m, &options).unwrap()
+    );
+
+    let options = PrintOptions {
+        always_output_default_values: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        "{\"doubleSingular\": 0, \"floatSingular\": 0, \"int32Singular\": 1, \
+         \"int64Singular\": \"0\", \"uint32Singular\": 0, \"uint64Singular\": \"0\", \
+         \"sint32Singular\": 0, \"sint64Singular\": \"0\", \"fixed32Singular\": 0, \
+         \"fixed64Singular\": \"0\", \"sfixed32Singular\": 0, \"sfixed64Singular\": \"0\", \
+         \"boolSingular\": false, \"stringSingular\": \"\", \"bytesSingular\": \"\", \
+         \"testEnumSingular\": \"DARK\", \"int64Repeated\": [], \"testEnumRepeated\": [], \
+         \"testMessageRepeated\": [], \"mapStringInt32\": {}, \"mapInt64Message\": {}, \
+         \"mapBoolEnum\": {}, \"renamed\": \"\"}",
+        json::print_to_string_with_options(&m, &options).unwrap()
+    );
+}
+
+#[test]
+fn test_parse_alternative_forms() {
+    let mut m = TestTypes::new();
+    m.set_int32_singular(100);
+    m.set_int64_singular(-5);
+    m.set_uint32_singular(3);
+    m.set_float_singular(1.5);
+    m.set_bytes_singular(b"\x00\xff\xfe".to_vec());
+    m.set_test_enum_singular(TestEnum::LIGHT.into());
+    m.set_field_with_json_name("y".to_owned());
+    m.mut_int64_repeated().push(7);
+
+    let parsed = json::parse_from_str::<TestTypes>(
+        "{
+            \"int32_singular\": 1e2,
+            \"int64Singular\": -5,
+            \"uint32Singular\": \"3\",
+            \"floatSingular\": \"1.5\",
+            \"bytesSingular\": \"AP_-\",
+            \"testEnumSingular\": 2,
+            \"field_with_json_name\": \"y\",
+            \"int64Repeated\": [7.0],
+            \"doubleSingular\": null,
+            \"testMessageSingular\": null
+        }",
+    ).unwrap();
+    assert_eq!(m, parsed);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        "message test_json.TestTypes has no field noSuchField",
+        parse_error::<TestTypes>("{\"noSuchField\": 1}")
+    );
+    assert_eq!(
+        "field int32_singular is specified multiple times",
+        parse_error::<TestTypes>("{\"int32Singular\": 1, \"int32_singular\": 2}")
+    );
+    assert_eq!(
+        "int32Singular: integer is out of range: 2147483648",
+        parse_error::<TestTypes>("{\"int32Singular\": 2147483648}")
+    );
+    assert_eq!(
+        "int32Singular: invalid integer: 1.5",
+        parse_error::<TestTypes>("{\"int32Singular\": 1.5}")
+    );
+    assert_eq!(
+        "boolSingular: expecting bool, got string",
+        parse_error::<TestTypes>("{\"boolSingular\": \"true\"}")
+    );
+    assert_eq!(
+        "testMessageSingular: value: expecting number, got array",
+        parse_error::<TestTypes>("{\"testMessageSingular\": {\"value\": []}}")
+    );
+    assert_eq!(
+        "testEnumSingular: unknown value BRIGHT of enum TestEnum",
+        parse_error::<TestTypes>("{\"testEnumSingular\": \"BRIGHT\"}")
+    );
+    assert_eq!(
+        "expecting ':' at 1:18",
+        parse_error::<TestTypes>("{\"int32Singular\" 1}")
+    );
+    assert!(json::parse_from_str::<TestTypes>("[]").is_err());
+    assert!(json::parse_from_str::<TestTypes>("{\"bytesSingular\": \"*\"}").is_err());
+    assert!(json::parse_from_str::<TestTypes>("{\"int64Repeated\": [null]}").is_err());
+}
+
+#[test]
+fn test_parse_ignore_unknown_fields() {
+    let options = ParseOptions {
+        ignore_unknown_fields: true,
+        ..Default::default()
+    };
+    let parsed = json::parse_from_str_with_options::<TestTypes>(
+        "{\"noSuchField\": {\"a\": [1]}, \"int32Singular\": 1, \"testEnumSingular\": \"BRIGHT\"}",
+        &options,
+    ).unwrap();
+    let mut m = TestTypes::new();
+    m.set_int32_singular(1);
+    assert_eq!(m, parsed);
+}
+
+#[test]
+fn test_merge_from_str() {
+    let mut m = TestTypes::new();
+    m.set_int32_singular(1);
+    m.set_uint32_singular(2);
+    m.mut_int64_repeated().push(3);
+    json::merge_from_str(&mut m, "{\"int32Singular\": 10, \"int64Repeated\": [\"4\"]}").unwrap();
+    assert_eq!(10, m.get_int32_singular());
+    assert_eq!(2, m.get_uint32_singular());
+    assert_eq!(&[3, 4], m.get_int64_repeated());
+}
+
+#[test]
+fn test_timestamp_duration() {
+    let mut m = TestWellKnownTypes::new();
+    let mut timestamp = Timestamp::new();
+    timestamp.set_seconds(63108020);
+    timestamp.set_nanos(21000000);
+    m.set_timestamp(timestamp);
+    let mut duration = Duration::new();
+    duration.set_seconds(-1);
+    duration.set_nanos(-500000000);
+    m.set_duration(duration);
+    t("{\"timestamp\": \"1972-01-01T10:00:20.021Z\", \"duration\": \"-1.500s\"}", &m);
+
+    assert_eq!(
+        m,
+        json::parse_from_str(
+            "{\"timestamp\": \"1972-01-01T12:00:20.021+02:00\", \"duration\": \"-1.5s\"}",
+        ).unwrap()
+    );
+    assert!(json::parse_from_str::<TestWellKnownTypes>("{\"timestamp\": \"1972\"}").is_err());
+    assert!(json::parse_from_str::<TestWellKnownTypes>("{\"duration\": \"1\"}").is_err());
+
+    m.mut_timestamp().set_seconds(253402300800);
+    assert!(json::print_to_string(&m).is_err());
+}
+
+#[test]
+fn test_field_mask_wrappers_empty() {
+    let mut m = TestWellKnownTypes::new();
+    m.mut_field_mask().mut_paths().push("foo_bar.baz".to_owned());
+    m.mut_field_mask().mut_paths().push("qux".to_owned());
+    m.mut_int64_value().set_value(10);
+    m.mut_string_value().set_value("s".to_owned());
+    m.mut_bytes_value().set_value(b"a".to_vec());
+    m.set_empty(Empty::new());
+    t(
+        "{\"fieldMask\": \"fooBar.baz,qux\", \"int64Value\": \"10\", \"stringValue\": \"s\", \
+         \"bytesValue\": \"YQ==\", \"empty\": {}}",
+        &m,
+    );
+
+    // wrapper with default value is printed
+    let mut m = TestWellKnownTypes::new();
+    m.mut_string_value();
+    m.set_field_mask(Default::default());
+    t("{\"fieldMask\": \"\", \"stringValue\": \"\"}", &m);
+}
+
+#[test]
+fn test_struct() {
+    let mut list = ListValue::new();
+    list.mut_values().push(Value::new());
+    list.mut_values()[0].set_number_value(1.5);
+    list.mut_values().push(Value::new());
+    list.mut_values()[1].set_null_value(NullValue::NULL_VALUE.into());
+
+    let mut nested = Struct::new();
+    nested.mut_fields().insert("b".to_owned(), Value::new());
+    nested.mut_fields().get_mut("b").unwrap().set_bool_value(true);
+
+    let mut m = TestWellKnownTypes::new();
+    m.mut_struct_value().mut_fields().insert("s".to_owned(), Value::new());
+    m.mut_struct_value().mut_fields().get_mut("s").unwrap().set_string_value("x".to_owned());
+    m.mut_struct_value().mut_fields().insert("n".to_owned(), Value::new());
+    m.mut_struct_value().mut_fields().get_mut("n").unwrap().set_struct_value(nested);
+    m.mut_struct_value().mut_fields().insert("l".to_owned(), Value::new());
+    m.mut_struct_value().mut_fields().get_mut("l").unwrap().set_list_value(list.clone());
+    m.mut_value().set_null_value(NullValue::NULL_VALUE.into());
+    m.set_list_value(list);
+    t(
+        "{\"structValue\": {\"l\": [1.5, null], \"n\": {\"b\": true}, \"s\": \"x\"}, \
+         \"value\": null, \"listValue\": [1.5, null]}",
+        &m,
+    );
+}
+
+#[test]
+fn test_any() {
+    let mut message = TestMessage::new();
+    message.set_value(3);
+    let mut any = Any::new();
+    any.set_type_url("type.googleapis.com/test_json.TestMessage".to_owned());
+    any.set_value(message.write_to_bytes().unwrap());
+
+    let mut duration = Duration::new();
+    duration.set_seconds(2);
+    let mut any_duration = Any::new();
+    any_duration.set_type_url("type.googleapis.com/google.protobuf.Duration".to_owned());
+    any_duration.set_value(duration.write_to_bytes().unwrap());
+
+    let mut m = TestWellKnownTypes::new();
+    m.set_any(any);
+    m.mut_any_repeated().push(any_duration);
+
+    let expected = "{\"any\": {\"@type\": \"type.googleapis.com/test_json.TestMessage\", \
+                    \"value\": 3}, \"anyRepeated\": [{\"@type\": \
+                    \"type.googleapis.com/google.protobuf.Duration\", \"value\": \"2s\"}]}";
+
+    assert!(json::print_to_string(&m).is_err());
+    assert!(json::parse_from_str::<TestWellKnownTypes>(expected).is_err());
+
+    let print_options = PrintOptions {
+        any_types: vec![TestMessage::descriptor_static(None), Duration::descriptor_static(None)],
+        ..Default::default()
+    };
+    let parse_options = ParseOptions {
+        any_types: print_options.any_types.clone(),
+        ..Default::default()
+    };
+    assert_eq!(expected, json::print_to_string_with_options(&m, &print_options).unwrap());
+    assert_eq!(m, json::parse_from_str_with_options(expected, &parse_options).unwrap());
+}
@@ -1,4 +1,5 @@
 use protobuf::text_format::print_to_string;
+use protobuf::text_format::parse_from_str;
 
 use super::test_map_pb::*;
 
@@ -46,3 +47,22 @@ fn text_format() {
 
     assert_eq!(&*print_to_string(&map), "m {key: \"two\" value: 2}")
 }
+
+#[test]
+fn text_format_parse() {
+    let mut map = TestMap::new();
+    map.mut_m().insert("two".to_owned(), 2);
+    map.mut_m().insert("three".to_owned(), 3);
+    let mut entry = TestMapEntry::new();
+    entry.set_v(10);
+    map.mut_mm().insert("map".to_owned(), entry);
+
+    assert_eq!(map, parse_from_str::<TestMap>(&print_to_string(&map)).expect("parse"));
+    assert_eq!(
+        map,
+        parse_from_str::<TestMap>(
+            "m { key: 'two' value: 2 } m [{ key: 'three' value: 3 }] mm { key: 'map' value { v: 10 } }",
+        ).expect("parse")
+    );
+    assert!(parse_from_str::<TestMap>("m { key: 'two' val: 2 }").is_err());
+}
@@ -0,0 +1,42 @@
+use serde_json;
+
+use protobuf::ProtobufEnumOrUnknown;
+
+use super::test_serde_derive_pb::*;
+
+fn sample() -> TestSerdeDerive {
+    let mut m = TestSerdeDerive::new();
+    m.set_int32_field(10);
+    m.set_string_field("str".to_owned());
+    m.set_bytes_field(vec![1, 2]);
+    m.set_enum_field(ProtobufEnumOrUnknown::new(TestSerdeEnum::GREEN));
+    m.mut_message_field().set_value("nested".to_owned());
+    m.mut_repeated_int64_field().push(-3);
+    m.mut_map_field().insert("k".to_owned(), TestSerdeDerive_TestSerdeNested::new());
+    m.mut_timestamp_field().set_seconds(1_500_000_000);
+    m.mut_value_field().set_string_value("v".to_owned());
+    m.mut_one_message().set_value("in oneof".to_owned());
+    m
+}
+
+#[test]
+fn test_round_trip() {
+    let m = sample();
+
+    let json = serde_json::to_string(&m).expect("serialize");
+    let parsed: TestSerdeDerive = serde_json::from_str(&json).expect("deserialize");
+    assert_eq!(m, parsed);
+}
+
+#[test]
+fn test_open_enum_unknown_value() {
+    let mut m = TestSerdeDerive::new();
+    m.enum_field = ProtobufEnumOrUnknown::from_i32(20);
+
+    let json = serde_json::to_value(&m).expect("serialize");
+    // open enums are serialized as numbers
+    assert_eq!(20, json["enum_field"]);
+
+    let parsed: TestSerdeDerive = serde_json::from_value(json).expect("deserialize");
+    assert_eq!(20, parsed.enum_field.value());
+}
@@ -0,0 +1,58 @@
+use protobuf::text_format;
+use protobuf::text_format::PrintOptions;
+use protobuf::well_known_types::Duration;
+use protobuf::well_known_types::Timestamp;
+
+use super::test_json_pb::TestWellKnownTypes;
+
+fn human_readable() -> PrintOptions {
+    PrintOptions { human_readable_time: true, ..Default::default() }
+}
+
+#[test]
+fn test_human_readable_time() {
+    let mut m = TestWellKnownTypes::new();
+    m.set_timestamp(Timestamp::from_seconds_nanos(63108020, 21000000).unwrap());
+    m.set_duration(Duration::from_seconds_nanos(-1, -500000000).unwrap());
+
+    let text = "timestamp: \"1972-01-01T10:00:20.021Z\" duration: \"-1.500s\"";
+    assert_eq!(text, text_format::print_to_string_with_options(&m, &human_readable()));
+    assert_eq!(m, text_format::parse_from_str(text).unwrap());
+
+    // default is message form, which is still accepted by parser
+    let text = "timestamp {seconds: 63108020 nanos: 21000000} duration {seconds: -1 nanos: -500000000}";
+    assert_eq!(text, text_format::print_to_string(&m));
+    assert_eq!(m, text_format::parse_from_str(text).unwrap());
+
+    let text = "timestamp: \"1972-01-01T12:00:20.021+02:00\" duration: \"-1.5s\"";
+    assert_eq!(m, text_format::parse_from_str(text).unwrap());
+}
+
+#[test]
+fn test_human_readable_time_out_of_range() {
+    let mut m = TestWellKnownTypes::new();
+    m.mut_timestamp().set_seconds(253402300800);
+    // printed as message
+    assert_eq!(
+        "timestamp {seconds: 253402300800}",
+        text_format::print_to_string_with_options(&m, &human_readable())
+    );
+}
+
+#[test]
+fn test_parse_time_errors() {
+    assert!(text_format::parse_from_str::<TestWellKnownTypes>("timestamp: \"1972\"").is_err());
+    assert!(text_format::parse_from_str::<TestWellKnownTypes>("duration: \"1\"").is_err());
+    assert!(text_format::parse_from_str::<TestWellKnownTypes>("empty: \"1s\"").is_err());
+}
+
+#[test]
+fn test_rfc3339_and_seconds_string() {
+    let t = Timestamp::parse_rfc3339("1972-01-01T10:00:20.021Z").unwrap();
+    assert_eq!(63108020, t.get_seconds());
+    assert_eq!(21000000, t.get_nanos());
+    assert_eq!("1972-01-01T10:00:20.021Z", t.to_rfc3339().unwrap());
+
+    let d = Duration::parse_seconds_string("1.5s").unwrap();
+    assert_eq!("1.500s", d.to_seconds_string().unwrap());
+}
@@ -0,0 +1,167 @@
+use protobuf::Message;
+use protobuf::json;
+use protobuf::text_format;
+use protobuf::parse_from_bytes;
+use protobuf::reflect::ProtobufValueBox;
+use protobuf::reflect::ReflectFieldRef;
+use protobuf::well_known_types::*;
+use protobuf::well_known_types_util::merge_with_mask;
+use protobuf::well_known_types_util::MergeOptions;
+use protobuf::well_known_types_util::WrapperMessage;
+
+use super::test_wrappers_as_option_pb::*;
+
+fn wrappers() -> TestWrappers {
+    let mut m = TestWrappers::new();
+    m.mut_double_value().set_value(1.5);
+    m.mut_float_value().set_value(-2.5);
+    m.mut_int64_value().set_value(-3);
+    m.mut_uint64_value().set_value(4);
+    // default values are stored too
+    m.mut_int32_value();
+    m.mut_uint32_value().set_value(6);
+    m.mut_bool_value().set_value(true);
+    m.mut_string_value().set_value("s".to_owned());
+    m.mut_bytes_value();
+    m.mut_repeated_int32_value().push(Int32Value::new());
+    m
+}
+
+fn wrappers_as_option() -> TestWrappersAsOption {
+    let mut m = TestWrappersAsOption::new();
+    m.double_value = Some(1.5);
+    m.float_value = Some(-2.5);
+    m.int64_value = Some(-3);
+    m.uint64_value = Some(4);
+    m.int32_value = Some(0);
+    m.set_uint32_value(6);
+    m.set_bool_value(true);
+    m.set_string_value("s".to_owned());
+    m.bytes_value = Some(Vec::new());
+    m.mut_repeated_int32_value().push(Int32Value::new());
+    m
+}
+
+#[test]
+fn test_accessors() {
+    let mut m = TestWrappersAsOption::new();
+    assert!(!m.has_string_value());
+    assert_eq!("", m.get_string_value());
+    assert_eq!(0, m.get_int32_value());
+
+    m.mut_string_value().push_str("ab");
+    m.set_int32_value(10);
+    assert_eq!(Some("ab".to_owned()), m.string_value);
+    assert_eq!("ab", m.get_string_value());
+    assert_eq!(Some(10), m.int32_value);
+}
+
+#[test]
+fn test_wire_format_is_the_same() {
+    let bytes = wrappers().write_to_bytes().unwrap();
+    assert_eq!(bytes, wrappers_as_option().write_to_bytes().unwrap());
+    assert_eq!(bytes.len() as u32, wrappers_as_option().compute_size());
+
+    assert_eq!(wrappers_as_option(), parse_from_bytes(&bytes).unwrap());
+    let mut m = TestWrappersAsOption::new();
+    m.merge_from_bytes(&bytes).unwrap();
+    assert_eq!(wrappers_as_option(), m);
+
+    assert_eq!(TestWrappersAsOption::new(), parse_from_bytes(&[]).unwrap());
+}
+
+#[test]
+fn test_merge() {
+    let mut first = TestWrappers::new();
+    first.mut_int32_value().set_value(5);
+    first.mut_string_value().set_value("a".to_owned());
+    let mut second = TestWrappers::new();
+    second.mut_int32_value();
+    second.mut_uint32_value();
+
+    // wrapper messages are merged, default values are not written
+    let mut m = TestWrappersAsOption::new();
+    m.merge_from_bytes(&first.write_to_bytes().unwrap()).unwrap();
+    m.merge_from_bytes(&second.write_to_bytes().unwrap()).unwrap();
+    assert_eq!(Some(5), m.int32_value);
+    assert_eq!(Some(0), m.uint32_value);
+    assert_eq!(Some("a".to_owned()), m.string_value);
+
+    let mut other = TestWrappersAsOption::new();
+    other.int32_value = Some(0);
+    other.float_value = Some(1.0);
+    m.merge(&other);
+    assert_eq!(Some(5), m.int32_value);
+    assert_eq!(Some(1.0), m.float_value);
+}
+
+#[test]
+fn test_reflect() {
+    let mut m = TestWrappersAsOption::new();
+    let d = m.descriptor();
+    let field = d.field_by_name("int32_value");
+    assert_eq!("google.protobuf.Int32Value", field.message_descriptor().full_name());
+    assert!(!field.has_field(&m));
+
+    m.int32_value = Some(7);
+    assert!(field.has_field(&m));
+    match field.get_reflect(&m) {
+        ReflectFieldRef::Wrapper(Some(v)) => {
+            assert_eq!(Some(&Int32Value::from_value(7)), v.as_any().downcast_ref());
+        }
+        _ => panic!("wrapper expected"),
+    }
+
+    let value = Box::new(StringValue::from_value("x".to_owned()));
+    d.field_by_name("string_value").set_singular_field(&mut m, ProtobufValueBox::Message(value));
+    assert_eq!(Some("x".to_owned()), m.string_value);
+
+    field.clear_field(&mut m);
+    assert_eq!(None, m.int32_value);
+
+    // wrapper is constructed and stored back
+    field.modify_message(&mut m, |w| {
+        w.descriptor().field_by_name("value").set_singular_field(w, ProtobufValueBox::I32(9))
+    });
+    assert_eq!(Some(9), m.int32_value);
+}
+
+#[test]
+#[should_panic(expected = "use modify_message")]
+fn test_reflect_mut_message() {
+    let mut m = TestWrappersAsOption::new();
+    m.descriptor().field_by_name("int32_value").mut_message(&mut m);
+}
+
+#[test]
+fn test_text_format_and_json() {
+    let text = text_format::print_to_string(&wrappers());
+    assert_eq!(text, text_format::print_to_string(&wrappers_as_option()));
+    assert_eq!(wrappers_as_option(), text_format::parse_from_str(&text).unwrap());
+
+    let json = json::print_to_string(&wrappers()).unwrap();
+    assert_eq!(json, json::print_to_string(&wrappers_as_option()).unwrap());
+    assert_eq!(wrappers_as_option(), json::parse_from_str(&json).unwrap());
+}
+
+#[test]
+fn test_field_mask() {
+    let mut dst = wrappers_as_option();
+    let mut src = TestWrappersAsOption::new();
+    src.int32_value = Some(3);
+    let mask = FieldMask::from_paths(vec!["int32_value", "string_value.value"]);
+    merge_with_mask(&src, &mut dst, &mask, &MergeOptions::default());
+    assert_eq!(Some(3), dst.int32_value);
+    assert_eq!(Some("".to_owned()), dst.string_value);
+}
+
+#[test]
+fn test_field_option() {
+    let mut m = TestWrappersAsOptionField::new();
+    m.as_option = Some(1);
+    m.mut_as_message().set_value(1);
+    assert_eq!(
+        Int32Value::from_value(1).write_to_bytes().unwrap(),
+        m.get_as_message().write_to_bytes().unwrap()
+    );
+}
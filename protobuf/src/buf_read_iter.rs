@@ -1,6 +1,10 @@
+#[cfg(feature = "std")]
 use std::cmp;
+#[cfg(feature = "std")]
 use std::io::Read;
+#[cfg(feature = "std")]
 use std::io::BufRead;
+#[cfg(feature = "std")]
 use std::io::BufReader;
 use std::mem;
 use std::u64;
@@ -19,6 +23,7 @@ use error::WireError;
 
 // If an input stream is constructed with a `Read`, we create a
 // `BufReader` with an internal buffer of this size.
+#[cfg(feature = "std")]
 const INPUT_STREAM_BUFFER_SIZE: usize = 4096;
 
 const USE_UNSAFE_FOR_SPEED: bool = true;
@@ -28,7 +33,9 @@ const NO_LIMIT: u64 = u64::MAX;
 
 /// Hold all possible combinations of input source
 enum InputSource<'a> {
+    #[cfg(feature = "std")]
     BufRead(&'a mut BufRead),
+    #[cfg(feature = "std")]
     Read(BufReader<&'a mut Read>),
     Slice(&'a [u8]),
     #[cfg(feature = "bytes")]
@@ -61,7 +68,9 @@ pub struct BufReadIter<'a> {
 impl<'a> Drop for BufReadIter<'a> {
     fn drop(&mut self) {
         match self.input_source {
+            #[cfg(feature = "std")]
             InputSource::BufRead(ref mut buf_read) => buf_read.consume(self.pos_within_buf),
+            #[cfg(feature = "std")]
             InputSource::Read(_) => {
                 // Nothing to flush, because we own BufReader
             }
@@ -71,6 +80,7 @@ impl<'a> Drop for BufReadIter<'a> {
 }
 
 impl<'ignore> BufReadIter<'ignore> {
+    #[cfg(feature = "std")]
     pub fn from_read<'a>(read: &'a mut Read) -> BufReadIter<'a> {
         BufReadIter {
             input_source: InputSource::Read(
@@ -84,6 +94,7 @@ impl<'ignore> BufReadIter<'ignore> {
         }
     }
 
+    #[cfg(feature = "std")]
     pub fn from_buf_read<'a>(buf_read: &'a mut BufRead) -> BufReadIter<'a> {
         BufReadIter {
             input_source: InputSource::BufRead(buf_read),
@@ -243,17 +254,20 @@ impl<'ignore> BufReadIter<'ignore> {
         }
     }
 
+    #[cfg(feature = "std")]
     pub fn read(&mut self, buf: &mut [u8]) -> ProtobufResult<usize> {
         self.fill_buf()?;
 
         let rem = &self.buf[self.pos_within_buf..self.limit_within_buf];
 
         let len = cmp::min(rem.len(), buf.len());
-        &mut buf[..len].copy_from_slice(&rem[..len]);
+        buf[..len].copy_from_slice(&rem[..len]);
         self.pos_within_buf += len;
         Ok((len))
     }
 
+    // without std all the sources are in memory, so refill never reads
+    #[cfg_attr(not(feature = "std"), allow(unreachable_code))]
     pub fn read_exact(&mut self, buf: &mut [u8]) -> ProtobufResult<()> {
         if self.remaining_in_buf_len() >= buf.len() {
             let buf_len = buf.len();
@@ -268,6 +282,7 @@ impl<'ignore> BufReadIter<'ignore> {
             return Err(ProtobufError::WireError(WireError::UnexpectedEof));
         }
 
+        #[cfg(feature = "std")]
         let consume = self.pos_within_buf;
         self.pos_of_buf_start += self.pos_within_buf as u64;
         self.pos_within_buf = 0;
@@ -275,10 +290,12 @@ impl<'ignore> BufReadIter<'ignore> {
         self.limit_within_buf = 0;
 
         match self.input_source {
+            #[cfg(feature = "std")]
             InputSource::Read(ref mut buf_read) => {
                 buf_read.consume(consume);
                 buf_read.read_exact(buf)?;
             }
+            #[cfg(feature = "std")]
             InputSource::BufRead(ref mut buf_read) => {
                 buf_read.consume(consume);
                 buf_read.read_exact(buf)?;
@@ -295,6 +312,8 @@ impl<'ignore> BufReadIter<'ignore> {
         Ok(())
     }
 
+    // without std all the sources are in memory, so refill never reads
+    #[cfg_attr(not(feature = "std"), allow(unreachable_code))]
     fn do_fill_buf(&mut self) -> ProtobufResult<()> {
         debug_assert!(self.pos_within_buf == self.limit_within_buf);
 
@@ -304,6 +323,7 @@ impl<'ignore> BufReadIter<'ignore> {
             return Ok(());
         }
 
+        #[cfg(feature = "std")]
         let consume = self.buf.len();
         self.pos_of_buf_start += self.buf.len() as u64;
         self.buf = &[];
@@ -311,10 +331,12 @@ impl<'ignore> BufReadIter<'ignore> {
         self.limit_within_buf = 0;
 
         match self.input_source {
+            #[cfg(feature = "std")]
             InputSource::Read(ref mut buf_read) => {
                 buf_read.consume(consume);
                 self.buf = unsafe { mem::transmute(buf_read.fill_buf()?) };
             }
+            #[cfg(feature = "std")]
             InputSource::BufRead(ref mut buf_read) => {
                 buf_read.consume(consume);
                 self.buf = unsafe { mem::transmute(buf_read.fill_buf()?) };
@@ -387,7 +409,7 @@ mod test_bytes {
 
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod test {
     use super::*;
     use std::io::BufRead;
@@ -4,6 +4,9 @@ use std::sync::atomic::Ordering;
 
 use types::ProtobufType;
 
+#[cfg(feature = "with-serde")]
+use serde;
+
 /// Cached size field used in generated code.
 /// It is always equal to itself to simplify generated code.
 /// (Generated code can use `#[derive(Eq)]`).
@@ -38,6 +41,22 @@ impl PartialEq<CachedSize> for CachedSize {
 
 impl Eq for CachedSize {}
 
+/// Cached size is not data, so it is serialized as unit
+/// and reset on deserialization.
+#[cfg(feature = "with-serde")]
+impl serde::Serialize for CachedSize {
+    fn serialize<S : serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_unit()
+    }
+}
+
+#[cfg(feature = "with-serde")]
+impl<'de> serde::Deserialize<'de> for CachedSize {
+    fn deserialize<D : serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <() as serde::Deserialize>::deserialize(deserializer).map(|()| CachedSize::default())
+    }
+}
+
 pub struct SizeCache {
     sizes: HashMap<*const (), usize>,
 }
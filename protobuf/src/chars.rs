@@ -4,6 +4,9 @@ use std::fmt;
 
 use bytes::Bytes;
 
+#[cfg(feature = "with-serde")]
+use serde;
+
 /// Thin wrapper around `Bytes` which guarantees that bytes are valid UTF-8 string.
 #[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Chars(Bytes);
@@ -71,6 +74,21 @@ impl fmt::Debug for Chars {
     }
 }
 
+/// Serialized as string.
+#[cfg(feature = "with-serde")]
+impl serde::Serialize for Chars {
+    fn serialize<S : serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self)
+    }
+}
+
+#[cfg(feature = "with-serde")]
+impl<'de> serde::Deserialize<'de> for Chars {
+    fn deserialize<D : serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <String as serde::Deserialize>::deserialize(deserializer).map(Chars::from)
+    }
+}
+
 
 #[cfg(test)]
 mod test {
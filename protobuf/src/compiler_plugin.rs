@@ -1,6 +1,9 @@
 // TODO: move into separate crate
 #![doc(hidden)]
 
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
 use std::io::stdin;
 use std::io::stdout;
 use std::str;
@@ -21,10 +21,15 @@
 use protobuf::Message as Message_imported_for_functions;
 use protobuf::ProtobufEnum as ProtobufEnum_imported_for_functions;
 
+/// The protocol compiler can output a FileDescriptorSet containing the .proto
+/// files it parses.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct FileDescriptorSet {
     // message fields
     pub file: ::std::vec::Vec<FileDescriptorProto>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl FileDescriptorSet {
@@ -78,24 +83,30 @@ impl ::protobuf::Message for FileDescriptorSet {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_file: ::std::vec::Vec<FileDescriptorProto> = ::std::vec::Vec::new();
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = FileDescriptorSet::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_file)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.file)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(FileDescriptorSet {
-            file: _field_file,
-        })
+    fn merge(&mut self, other: &Self) {
+        self.file.extend(other.file.iter().cloned());
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -106,6 +117,7 @@ impl ::protobuf::Message for FileDescriptorSet {
             let len = value.compute_size();
             _size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -115,16 +127,25 @@ impl ::protobuf::Message for FileDescriptorSet {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         };
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -171,21 +192,38 @@ impl ::protobuf::reflect::ProtobufValue for FileDescriptorSet {
     }
 }
 
+/// Describes a complete .proto file.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct FileDescriptorProto {
     // message fields
+    /// file name, relative to root of source tree
     pub name: ::protobuf::SingularField<::std::string::String>,
+    /// e.g. "foo", "foo.bar", etc.
     pub package: ::protobuf::SingularField<::std::string::String>,
+    /// Names of files imported by this file.
     pub dependency: ::std::vec::Vec<::std::string::String>,
+    /// Indexes of the public imported files in the dependency list above.
     pub public_dependency: ::std::vec::Vec<i32>,
+    /// Indexes of the weak imported files in the dependency list.
+    /// For Google-internal migration only. Do not use.
     pub weak_dependency: ::std::vec::Vec<i32>,
+    /// All top-level definitions in this file.
     pub message_type: ::std::vec::Vec<DescriptorProto>,
     pub enum_type: ::std::vec::Vec<EnumDescriptorProto>,
     pub service: ::std::vec::Vec<ServiceDescriptorProto>,
     pub extension: ::std::vec::Vec<FieldDescriptorProto>,
     pub options: ::protobuf::SingularPtrField<FileOptions>,
+    /// This field contains optional information about the original source code.
+    /// You may safely remove this entire field without harming runtime
+    /// functionality of the descriptors -- the information is needed only by
+    /// development tools.
     pub source_code_info: ::protobuf::SingularPtrField<SourceCodeInfo>,
+    /// The syntax of the proto file.
+    /// The supported values are "proto2" and "proto3".
     pub syntax: ::protobuf::SingularField<::std::string::String>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl FileDescriptorProto {
@@ -588,94 +626,89 @@ impl ::protobuf::Message for FileDescriptorProto {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_name: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_package: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_dependency: ::std::vec::Vec<::std::string::String> = ::std::vec::Vec::new();
-        let mut _field_public_dependency: ::std::vec::Vec<i32> = ::std::vec::Vec::new();
-        let mut _field_weak_dependency: ::std::vec::Vec<i32> = ::std::vec::Vec::new();
-        let mut _field_message_type: ::std::vec::Vec<DescriptorProto> = ::std::vec::Vec::new();
-        let mut _field_enum_type: ::std::vec::Vec<EnumDescriptorProto> = ::std::vec::Vec::new();
-        let mut _field_service: ::std::vec::Vec<ServiceDescriptorProto> = ::std::vec::Vec::new();
-        let mut _field_extension: ::std::vec::Vec<FieldDescriptorProto> = ::std::vec::Vec::new();
-        let mut _field_options: ::std::option::Option<::protobuf::SingularPtrField<FileOptions>> = None;
-        let mut _field_source_code_info: ::std::option::Option<::protobuf::SingularPtrField<SourceCodeInfo>> = None;
-        let mut _field_syntax: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = FileDescriptorProto::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_name = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.name = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_package = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.package = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 3 => {
-                    ::protobuf::rt::read_repeated_string_into(wire_type, _is, &mut _field_dependency)?;
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.dependency)?;
                 },
                 10 => {
-                    ::protobuf::rt::read_repeated_int32_into(wire_type, _is, &mut _field_public_dependency)?;
+                    ::protobuf::rt::read_repeated_int32_into(wire_type, is, &mut self.public_dependency)?;
                 },
                 11 => {
-                    ::protobuf::rt::read_repeated_int32_into(wire_type, _is, &mut _field_weak_dependency)?;
+                    ::protobuf::rt::read_repeated_int32_into(wire_type, is, &mut self.weak_dependency)?;
                 },
                 4 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_message_type)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.message_type)?;
                 },
                 5 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_enum_type)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.enum_type)?;
                 },
                 6 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_service)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.service)?;
                 },
                 7 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_extension)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.extension)?;
                 },
                 8 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    _field_options = ::std::option::Option::Some(::protobuf::SingularPtrField::some(_is.read_message()?));
+                    ::protobuf::rt::merge_singular_message_into(wire_type, is, &mut self.options)?;
                 },
                 9 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    _field_source_code_info = ::std::option::Option::Some(::protobuf::SingularPtrField::some(_is.read_message()?));
+                    ::protobuf::rt::merge_singular_message_into(wire_type, is, &mut self.source_code_info)?;
                 },
                 12 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_syntax = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.syntax = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(FileDescriptorProto {
-            name: _field_name.unwrap_or_default(),
-            package: _field_package.unwrap_or_default(),
-            dependency: _field_dependency,
-            public_dependency: _field_public_dependency,
-            weak_dependency: _field_weak_dependency,
-            message_type: _field_message_type,
-            enum_type: _field_enum_type,
-            service: _field_service,
-            extension: _field_extension,
-            options: _field_options.unwrap_or_default(),
-            source_code_info: _field_source_code_info.unwrap_or_default(),
-            syntax: _field_syntax.unwrap_or_default(),
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.name.is_some() {
+            self.name = other.name.clone();
+        }
+        if other.package.is_some() {
+            self.package = other.package.clone();
+        }
+        self.dependency.extend(other.dependency.iter().cloned());
+        self.public_dependency.extend(other.public_dependency.iter().cloned());
+        self.weak_dependency.extend(other.weak_dependency.iter().cloned());
+        self.message_type.extend(other.message_type.iter().cloned());
+        self.enum_type.extend(other.enum_type.iter().cloned());
+        self.service.extend(other.service.iter().cloned());
+        self.extension.extend(other.extension.iter().cloned());
+        ::protobuf::rt::merge_singular_message(&mut self.options, &other.options);
+        ::protobuf::rt::merge_singular_message(&mut self.source_code_info, &other.source_code_info);
+        if other.syntax.is_some() {
+            self.syntax = other.syntax.clone();
+        }
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -724,6 +757,7 @@ impl ::protobuf::Message for FileDescriptorProto {
         if let Some(ref v) = self.syntax.as_ref() {
             _size += ::protobuf::rt::string_size(12, &v);
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -776,16 +810,25 @@ impl ::protobuf::Message for FileDescriptorProto {
         if let Some(ref v) = self.syntax.as_ref() {
             _os.write_string(12, &v)?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -887,7 +930,9 @@ impl ::protobuf::reflect::ProtobufValue for FileDescriptorProto {
     }
 }
 
+/// Describes a message type.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct DescriptorProto {
     // message fields
     pub name: ::protobuf::SingularField<::std::string::String>,
@@ -899,7 +944,11 @@ pub struct DescriptorProto {
     pub oneof_decl: ::std::vec::Vec<OneofDescriptorProto>,
     pub options: ::protobuf::SingularPtrField<MessageOptions>,
     pub reserved_range: ::std::vec::Vec<DescriptorProto_ReservedRange>,
+    /// Reserved field names, which may not be used by fields in the same message.
+    /// A given name may only be reserved once.
     pub reserved_name: ::std::vec::Vec<::std::string::String>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl DescriptorProto {
@@ -1232,75 +1281,71 @@ impl ::protobuf::Message for DescriptorProto {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_name: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_field: ::std::vec::Vec<FieldDescriptorProto> = ::std::vec::Vec::new();
-        let mut _field_extension: ::std::vec::Vec<FieldDescriptorProto> = ::std::vec::Vec::new();
-        let mut _field_nested_type: ::std::vec::Vec<DescriptorProto> = ::std::vec::Vec::new();
-        let mut _field_enum_type: ::std::vec::Vec<EnumDescriptorProto> = ::std::vec::Vec::new();
-        let mut _field_extension_range: ::std::vec::Vec<DescriptorProto_ExtensionRange> = ::std::vec::Vec::new();
-        let mut _field_oneof_decl: ::std::vec::Vec<OneofDescriptorProto> = ::std::vec::Vec::new();
-        let mut _field_options: ::std::option::Option<::protobuf::SingularPtrField<MessageOptions>> = None;
-        let mut _field_reserved_range: ::std::vec::Vec<DescriptorProto_ReservedRange> = ::std::vec::Vec::new();
-        let mut _field_reserved_name: ::std::vec::Vec<::std::string::String> = ::std::vec::Vec::new();
-
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = DescriptorProto::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_name = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.name = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 2 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_field)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.field)?;
                 },
                 6 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_extension)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.extension)?;
                 },
                 3 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_nested_type)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.nested_type)?;
                 },
                 4 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_enum_type)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.enum_type)?;
                 },
                 5 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_extension_range)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.extension_range)?;
                 },
                 8 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_oneof_decl)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.oneof_decl)?;
                 },
                 7 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    _field_options = ::std::option::Option::Some(::protobuf::SingularPtrField::some(_is.read_message()?));
+                    ::protobuf::rt::merge_singular_message_into(wire_type, is, &mut self.options)?;
                 },
                 9 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_reserved_range)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.reserved_range)?;
                 },
                 10 => {
-                    ::protobuf::rt::read_repeated_string_into(wire_type, _is, &mut _field_reserved_name)?;
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.reserved_name)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(DescriptorProto {
-            name: _field_name.unwrap_or_default(),
-            field: _field_field,
-            extension: _field_extension,
-            nested_type: _field_nested_type,
-            enum_type: _field_enum_type,
-            extension_range: _field_extension_range,
-            oneof_decl: _field_oneof_decl,
-            options: _field_options.unwrap_or_default(),
-            reserved_range: _field_reserved_range,
-            reserved_name: _field_reserved_name,
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.name.is_some() {
+            self.name = other.name.clone();
+        }
+        self.field.extend(other.field.iter().cloned());
+        self.extension.extend(other.extension.iter().cloned());
+        self.nested_type.extend(other.nested_type.iter().cloned());
+        self.enum_type.extend(other.enum_type.iter().cloned());
+        self.extension_range.extend(other.extension_range.iter().cloned());
+        self.oneof_decl.extend(other.oneof_decl.iter().cloned());
+        ::protobuf::rt::merge_singular_message(&mut self.options, &other.options);
+        self.reserved_range.extend(other.reserved_range.iter().cloned());
+        self.reserved_name.extend(other.reserved_name.iter().cloned());
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -1345,6 +1390,7 @@ impl ::protobuf::Message for DescriptorProto {
         for value in &self.reserved_name {
             _size += ::protobuf::rt::string_size(10, &value);
         };
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -1395,16 +1441,25 @@ impl ::protobuf::Message for DescriptorProto {
         for v in &self.reserved_name {
             _os.write_string(10, &v)?;
         };
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -1497,10 +1552,13 @@ impl ::protobuf::reflect::ProtobufValue for DescriptorProto {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct DescriptorProto_ExtensionRange {
     // message fields
     pub start: ::std::option::Option<i32>,
     pub end: ::std::option::Option<i32>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl DescriptorProto_ExtensionRange {
@@ -1572,35 +1630,44 @@ impl ::protobuf::Message for DescriptorProto_ExtensionRange {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_start: ::std::option::Option<::std::option::Option<i32>> = None;
-        let mut _field_end: ::std::option::Option<::std::option::Option<i32>> = None;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = DescriptorProto_ExtensionRange::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_start = ::std::option::Option::Some(::std::option::Option::Some(_is.read_int32()?));
+                    self.start = ::std::option::Option::Some(is.read_int32()?);
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_end = ::std::option::Option::Some(::std::option::Option::Some(_is.read_int32()?));
+                    self.end = ::std::option::Option::Some(is.read_int32()?);
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(DescriptorProto_ExtensionRange {
-            start: _field_start.unwrap_or_default(),
-            end: _field_end.unwrap_or_default(),
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.start.is_some() {
+            self.start = other.start.clone();
+        }
+        if other.end.is_some() {
+            self.end = other.end.clone();
+        }
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -1613,6 +1680,7 @@ impl ::protobuf::Message for DescriptorProto_ExtensionRange {
         if let Some(v) = self.end {
             _size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -1623,16 +1691,25 @@ impl ::protobuf::Message for DescriptorProto_ExtensionRange {
         if let Some(v) = self.end {
             _os.write_int32(2, v)?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -1684,11 +1761,19 @@ impl ::protobuf::reflect::ProtobufValue for DescriptorProto_ExtensionRange {
     }
 }
 
+/// Range of reserved tag numbers. Reserved tag numbers may not be used by
+/// fields or extension ranges in the same message. Reserved ranges may
+/// not overlap.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct DescriptorProto_ReservedRange {
     // message fields
+    /// Inclusive.
     pub start: ::std::option::Option<i32>,
+    /// Exclusive.
     pub end: ::std::option::Option<i32>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl DescriptorProto_ReservedRange {
@@ -1760,35 +1845,44 @@ impl ::protobuf::Message for DescriptorProto_ReservedRange {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_start: ::std::option::Option<::std::option::Option<i32>> = None;
-        let mut _field_end: ::std::option::Option<::std::option::Option<i32>> = None;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = DescriptorProto_ReservedRange::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_start = ::std::option::Option::Some(::std::option::Option::Some(_is.read_int32()?));
+                    self.start = ::std::option::Option::Some(is.read_int32()?);
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_end = ::std::option::Option::Some(::std::option::Option::Some(_is.read_int32()?));
+                    self.end = ::std::option::Option::Some(is.read_int32()?);
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(DescriptorProto_ReservedRange {
-            start: _field_start.unwrap_or_default(),
-            end: _field_end.unwrap_or_default(),
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.start.is_some() {
+            self.start = other.start.clone();
+        }
+        if other.end.is_some() {
+            self.end = other.end.clone();
+        }
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -1801,6 +1895,7 @@ impl ::protobuf::Message for DescriptorProto_ReservedRange {
         if let Some(v) = self.end {
             _size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -1811,16 +1906,25 @@ impl ::protobuf::Message for DescriptorProto_ReservedRange {
         if let Some(v) = self.end {
             _os.write_int32(2, v)?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -1872,19 +1976,43 @@ impl ::protobuf::reflect::ProtobufValue for DescriptorProto_ReservedRange {
     }
 }
 
+/// Describes a field within a message.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct FieldDescriptorProto {
     // message fields
     pub name: ::protobuf::SingularField<::std::string::String>,
     pub number: ::std::option::Option<i32>,
     pub label: ::std::option::Option<FieldDescriptorProto_Label>,
+    /// If type_name is set, this need not be set.  If both this and type_name
+    /// are set, this must be one of TYPE_ENUM, TYPE_MESSAGE or TYPE_GROUP.
     pub field_type: ::std::option::Option<FieldDescriptorProto_Type>,
+    /// For message and enum types, this is the name of the type.  If the name
+    /// starts with a '.', it is fully-qualified.  Otherwise, C++-like scoping
+    /// rules are used to find the type (i.e. first the nested types within this
+    /// message are searched, then within the parent, on up to the root
+    /// namespace).
     pub type_name: ::protobuf::SingularField<::std::string::String>,
+    /// For extensions, this is the name of the type being extended.  It is
+    /// resolved in the same manner as type_name.
     pub extendee: ::protobuf::SingularField<::std::string::String>,
+    /// For numeric types, contains the original text representation of the value.
+    /// For booleans, "true" or "false".
+    /// For strings, contains the default text contents (not escaped in any way).
+    /// For bytes, contains the C escaped value.  All bytes >= 128 are escaped.
+    /// TODO(kenton):  Base-64 encode?
     pub default_value: ::protobuf::SingularField<::std::string::String>,
+    /// If set, gives the index of a oneof in the containing type's oneof_decl
+    /// list.  This field is a member of that oneof.
     pub oneof_index: ::std::option::Option<i32>,
+    /// JSON name of this field. The value is set by protocol compiler. If the
+    /// user has set a "json_name" option on this field, that option's value
+    /// will be used. Otherwise, it's deduced from the field's name by converting
+    /// it to camelCase.
     pub json_name: ::protobuf::SingularField<::std::string::String>,
     pub options: ::protobuf::SingularPtrField<FieldOptions>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl FieldDescriptorProto {
@@ -2222,99 +2350,115 @@ impl ::protobuf::Message for FieldDescriptorProto {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_name: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_number: ::std::option::Option<::std::option::Option<i32>> = None;
-        let mut _field_label: ::std::option::Option<::std::option::Option<FieldDescriptorProto_Label>> = None;
-        let mut _field_field_type: ::std::option::Option<::std::option::Option<FieldDescriptorProto_Type>> = None;
-        let mut _field_type_name: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_extendee: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_default_value: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_oneof_index: ::std::option::Option<::std::option::Option<i32>> = None;
-        let mut _field_json_name: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_options: ::std::option::Option<::protobuf::SingularPtrField<FieldOptions>> = None;
-
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = FieldDescriptorProto::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_name = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.name = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 3 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_number = ::std::option::Option::Some(::std::option::Option::Some(_is.read_int32()?));
+                    self.number = ::std::option::Option::Some(is.read_int32()?);
                 },
                 4 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_label = ::std::option::Option::Some(::std::option::Option::Some(_is.read_enum()?));
+                    if let ::std::option::Option::Some(v) = ::protobuf::rt::read_proto2_enum_with_unknown_fields(is, 4, &mut self.unknown_fields)? {
+                        self.label = ::std::option::Option::Some(v);
+                    }
                 },
                 5 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_field_type = ::std::option::Option::Some(::std::option::Option::Some(_is.read_enum()?));
+                    if let ::std::option::Option::Some(v) = ::protobuf::rt::read_proto2_enum_with_unknown_fields(is, 5, &mut self.unknown_fields)? {
+                        self.field_type = ::std::option::Option::Some(v);
+                    }
                 },
                 6 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_type_name = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.type_name = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_extendee = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.extendee = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 7 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_default_value = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.default_value = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 9 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_oneof_index = ::std::option::Option::Some(::std::option::Option::Some(_is.read_int32()?));
+                    self.oneof_index = ::std::option::Option::Some(is.read_int32()?);
                 },
                 10 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_json_name = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.json_name = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 8 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    _field_options = ::std::option::Option::Some(::protobuf::SingularPtrField::some(_is.read_message()?));
+                    ::protobuf::rt::merge_singular_message_into(wire_type, is, &mut self.options)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(FieldDescriptorProto {
-            name: _field_name.unwrap_or_default(),
-            number: _field_number.unwrap_or_default(),
-            label: _field_label.unwrap_or_default(),
-            field_type: _field_field_type.unwrap_or_default(),
-            type_name: _field_type_name.unwrap_or_default(),
-            extendee: _field_extendee.unwrap_or_default(),
-            default_value: _field_default_value.unwrap_or_default(),
-            oneof_index: _field_oneof_index.unwrap_or_default(),
-            json_name: _field_json_name.unwrap_or_default(),
-            options: _field_options.unwrap_or_default(),
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.name.is_some() {
+            self.name = other.name.clone();
+        }
+        if other.number.is_some() {
+            self.number = other.number.clone();
+        }
+        if other.label.is_some() {
+            self.label = other.label.clone();
+        }
+        if other.field_type.is_some() {
+            self.field_type = other.field_type.clone();
+        }
+        if other.type_name.is_some() {
+            self.type_name = other.type_name.clone();
+        }
+        if other.extendee.is_some() {
+            self.extendee = other.extendee.clone();
+        }
+        if other.default_value.is_some() {
+            self.default_value = other.default_value.clone();
+        }
+        if other.oneof_index.is_some() {
+            self.oneof_index = other.oneof_index.clone();
+        }
+        if other.json_name.is_some() {
+            self.json_name = other.json_name.clone();
+        }
+        ::protobuf::rt::merge_singular_message(&mut self.options, &other.options);
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -2352,6 +2496,7 @@ impl ::protobuf::Message for FieldDescriptorProto {
             let len = v.compute_size();
             _size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -2388,16 +2533,25 @@ impl ::protobuf::Message for FieldDescriptorProto {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -2490,24 +2644,36 @@ impl ::protobuf::reflect::ProtobufValue for FieldDescriptorProto {
 }
 
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub enum FieldDescriptorProto_Type {
+    /// 0 is reserved for errors.
+    /// Order is weird for historical reasons.
     TYPE_DOUBLE = 1,
     TYPE_FLOAT = 2,
+    /// Not ZigZag encoded.  Negative numbers take 10 bytes.  Use TYPE_SINT64 if
+    /// negative values are likely.
     TYPE_INT64 = 3,
     TYPE_UINT64 = 4,
+    /// Not ZigZag encoded.  Negative numbers take 10 bytes.  Use TYPE_SINT32 if
+    /// negative values are likely.
     TYPE_INT32 = 5,
     TYPE_FIXED64 = 6,
     TYPE_FIXED32 = 7,
     TYPE_BOOL = 8,
     TYPE_STRING = 9,
+    /// Tag-delimited aggregate.
     TYPE_GROUP = 10,
+    /// Length-delimited aggregate.
     TYPE_MESSAGE = 11,
+    /// New in version 2.
     TYPE_BYTES = 12,
     TYPE_UINT32 = 13,
     TYPE_ENUM = 14,
     TYPE_SFIXED32 = 15,
     TYPE_SFIXED64 = 16,
+    /// Uses ZigZag encoding.
     TYPE_SINT32 = 17,
+    /// Uses ZigZag encoding.
     TYPE_SINT64 = 18,
 }
 
@@ -2584,12 +2750,19 @@ impl ::protobuf::reflect::ProtobufValue for FieldDescriptorProto_Type {
     fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
         ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
     }
+
+    fn from_value_box(value: ::protobuf::reflect::ProtobufValueBox) -> ::std::option::Option<Self> {
+        value.into_enum()
+    }
 }
 
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub enum FieldDescriptorProto_Label {
+    /// 0 is reserved for errors
     LABEL_OPTIONAL = 1,
     LABEL_REQUIRED = 2,
+    /// TODO(sanjay): Should we add LABEL_MAP?
     LABEL_REPEATED = 3,
 }
 
@@ -2636,13 +2809,21 @@ impl ::protobuf::reflect::ProtobufValue for FieldDescriptorProto_Label {
     fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
         ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
     }
+
+    fn from_value_box(value: ::protobuf::reflect::ProtobufValueBox) -> ::std::option::Option<Self> {
+        value.into_enum()
+    }
 }
 
+/// Describes a oneof.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct OneofDescriptorProto {
     // message fields
     pub name: ::protobuf::SingularField<::std::string::String>,
     pub options: ::protobuf::SingularPtrField<OneofOptions>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl OneofDescriptorProto {
@@ -2740,35 +2921,39 @@ impl ::protobuf::Message for OneofDescriptorProto {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_name: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_options: ::std::option::Option<::protobuf::SingularPtrField<OneofOptions>> = None;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = OneofDescriptorProto::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_name = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.name = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 2 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    _field_options = ::std::option::Option::Some(::protobuf::SingularPtrField::some(_is.read_message()?));
+                    ::protobuf::rt::merge_singular_message_into(wire_type, is, &mut self.options)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(OneofDescriptorProto {
-            name: _field_name.unwrap_or_default(),
-            options: _field_options.unwrap_or_default(),
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.name.is_some() {
+            self.name = other.name.clone();
+        }
+        ::protobuf::rt::merge_singular_message(&mut self.options, &other.options);
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -2782,6 +2967,7 @@ impl ::protobuf::Message for OneofDescriptorProto {
             let len = v.compute_size();
             _size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -2794,16 +2980,25 @@ impl ::protobuf::Message for OneofDescriptorProto {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -2855,12 +3050,16 @@ impl ::protobuf::reflect::ProtobufValue for OneofDescriptorProto {
     }
 }
 
+/// Describes an enum type.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct EnumDescriptorProto {
     // message fields
     pub name: ::protobuf::SingularField<::std::string::String>,
     pub value: ::std::vec::Vec<EnumValueDescriptorProto>,
     pub options: ::protobuf::SingularPtrField<EnumOptions>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl EnumDescriptorProto {
@@ -2988,40 +3187,43 @@ impl ::protobuf::Message for EnumDescriptorProto {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_name: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_value: ::std::vec::Vec<EnumValueDescriptorProto> = ::std::vec::Vec::new();
-        let mut _field_options: ::std::option::Option<::protobuf::SingularPtrField<EnumOptions>> = None;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = EnumDescriptorProto::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_name = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.name = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 2 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_value)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.value)?;
                 },
                 3 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    _field_options = ::std::option::Option::Some(::protobuf::SingularPtrField::some(_is.read_message()?));
+                    ::protobuf::rt::merge_singular_message_into(wire_type, is, &mut self.options)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(EnumDescriptorProto {
-            name: _field_name.unwrap_or_default(),
-            value: _field_value,
-            options: _field_options.unwrap_or_default(),
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.name.is_some() {
+            self.name = other.name.clone();
+        }
+        self.value.extend(other.value.iter().cloned());
+        ::protobuf::rt::merge_singular_message(&mut self.options, &other.options);
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -3039,6 +3241,7 @@ impl ::protobuf::Message for EnumDescriptorProto {
             let len = v.compute_size();
             _size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -3056,16 +3259,25 @@ impl ::protobuf::Message for EnumDescriptorProto {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -3122,12 +3334,16 @@ impl ::protobuf::reflect::ProtobufValue for EnumDescriptorProto {
     }
 }
 
+/// Describes a value within an enum.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct EnumValueDescriptorProto {
     // message fields
     pub name: ::protobuf::SingularField<::std::string::String>,
     pub number: ::std::option::Option<i32>,
     pub options: ::protobuf::SingularPtrField<EnumValueOptions>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl EnumValueDescriptorProto {
@@ -3249,43 +3465,48 @@ impl ::protobuf::Message for EnumValueDescriptorProto {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_name: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_number: ::std::option::Option<::std::option::Option<i32>> = None;
-        let mut _field_options: ::std::option::Option<::protobuf::SingularPtrField<EnumValueOptions>> = None;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = EnumValueDescriptorProto::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_name = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.name = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_number = ::std::option::Option::Some(::std::option::Option::Some(_is.read_int32()?));
+                    self.number = ::std::option::Option::Some(is.read_int32()?);
                 },
                 3 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    _field_options = ::std::option::Option::Some(::protobuf::SingularPtrField::some(_is.read_message()?));
+                    ::protobuf::rt::merge_singular_message_into(wire_type, is, &mut self.options)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(EnumValueDescriptorProto {
-            name: _field_name.unwrap_or_default(),
-            number: _field_number.unwrap_or_default(),
-            options: _field_options.unwrap_or_default(),
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.name.is_some() {
+            self.name = other.name.clone();
+        }
+        if other.number.is_some() {
+            self.number = other.number.clone();
+        }
+        ::protobuf::rt::merge_singular_message(&mut self.options, &other.options);
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -3302,6 +3523,7 @@ impl ::protobuf::Message for EnumValueDescriptorProto {
             let len = v.compute_size();
             _size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -3317,16 +3539,25 @@ impl ::protobuf::Message for EnumValueDescriptorProto {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -3383,12 +3614,16 @@ impl ::protobuf::reflect::ProtobufValue for EnumValueDescriptorProto {
     }
 }
 
+/// Describes a service.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct ServiceDescriptorProto {
     // message fields
     pub name: ::protobuf::SingularField<::std::string::String>,
     pub method: ::std::vec::Vec<MethodDescriptorProto>,
     pub options: ::protobuf::SingularPtrField<ServiceOptions>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl ServiceDescriptorProto {
@@ -3516,40 +3751,43 @@ impl ::protobuf::Message for ServiceDescriptorProto {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_name: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_method: ::std::vec::Vec<MethodDescriptorProto> = ::std::vec::Vec::new();
-        let mut _field_options: ::std::option::Option<::protobuf::SingularPtrField<ServiceOptions>> = None;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = ServiceDescriptorProto::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_name = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.name = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 2 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_method)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.method)?;
                 },
                 3 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    _field_options = ::std::option::Option::Some(::protobuf::SingularPtrField::some(_is.read_message()?));
+                    ::protobuf::rt::merge_singular_message_into(wire_type, is, &mut self.options)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(ServiceDescriptorProto {
-            name: _field_name.unwrap_or_default(),
-            method: _field_method,
-            options: _field_options.unwrap_or_default(),
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.name.is_some() {
+            self.name = other.name.clone();
+        }
+        self.method.extend(other.method.iter().cloned());
+        ::protobuf::rt::merge_singular_message(&mut self.options, &other.options);
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -3567,6 +3805,7 @@ impl ::protobuf::Message for ServiceDescriptorProto {
             let len = v.compute_size();
             _size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -3584,16 +3823,25 @@ impl ::protobuf::Message for ServiceDescriptorProto {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -3650,15 +3898,23 @@ impl ::protobuf::reflect::ProtobufValue for ServiceDescriptorProto {
     }
 }
 
+/// Describes a method of a service.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct MethodDescriptorProto {
     // message fields
     pub name: ::protobuf::SingularField<::std::string::String>,
+    /// Input and output type names.  These are resolved in the same way as
+    /// FieldDescriptorProto.type_name, but must refer to a message type.
     pub input_type: ::protobuf::SingularField<::std::string::String>,
     pub output_type: ::protobuf::SingularField<::std::string::String>,
     pub options: ::protobuf::SingularPtrField<MethodOptions>,
+    /// Identifies if client streams multiple client messages
     pub client_streaming: ::std::option::Option<bool>,
+    /// Identifies if server streams multiple server messages
     pub server_streaming: ::std::option::Option<bool>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl MethodDescriptorProto {
@@ -3876,67 +4132,75 @@ impl ::protobuf::Message for MethodDescriptorProto {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_name: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_input_type: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_output_type: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_options: ::std::option::Option<::protobuf::SingularPtrField<MethodOptions>> = None;
-        let mut _field_client_streaming: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_server_streaming: ::std::option::Option<::std::option::Option<bool>> = None;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = MethodDescriptorProto::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_name = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.name = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_input_type = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.input_type = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 3 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_output_type = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.output_type = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 4 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    _field_options = ::std::option::Option::Some(::protobuf::SingularPtrField::some(_is.read_message()?));
+                    ::protobuf::rt::merge_singular_message_into(wire_type, is, &mut self.options)?;
                 },
                 5 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_client_streaming = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.client_streaming = ::std::option::Option::Some(is.read_bool()?);
                 },
                 6 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_server_streaming = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.server_streaming = ::std::option::Option::Some(is.read_bool()?);
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(MethodDescriptorProto {
-            name: _field_name.unwrap_or_default(),
-            input_type: _field_input_type.unwrap_or_default(),
-            output_type: _field_output_type.unwrap_or_default(),
-            options: _field_options.unwrap_or_default(),
-            client_streaming: _field_client_streaming.unwrap_or_default(),
-            server_streaming: _field_server_streaming.unwrap_or_default(),
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.name.is_some() {
+            self.name = other.name.clone();
+        }
+        if other.input_type.is_some() {
+            self.input_type = other.input_type.clone();
+        }
+        if other.output_type.is_some() {
+            self.output_type = other.output_type.clone();
+        }
+        ::protobuf::rt::merge_singular_message(&mut self.options, &other.options);
+        if other.client_streaming.is_some() {
+            self.client_streaming = other.client_streaming.clone();
+        }
+        if other.server_streaming.is_some() {
+            self.server_streaming = other.server_streaming.clone();
+        }
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -3962,6 +4226,7 @@ impl ::protobuf::Message for MethodDescriptorProto {
         if let Some(v) = self.server_streaming {
             _size += 2;
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -3986,16 +4251,25 @@ impl ::protobuf::Message for MethodDescriptorProto {
         if let Some(v) = self.server_streaming {
             _os.write_bool(6, v)?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -4068,23 +4342,73 @@ impl ::protobuf::reflect::ProtobufValue for MethodDescriptorProto {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct FileOptions {
     // message fields
+    /// Sets the Java package where classes generated from this .proto will be
+    /// placed.  By default, the proto package is used, but this is often
+    /// inappropriate because proto packages do not normally start with backwards
+    /// domain names.
     pub java_package: ::protobuf::SingularField<::std::string::String>,
+    /// If set, all the classes from the .proto file are wrapped in a single
+    /// outer class with the given name.  This applies to both Proto1
+    /// (equivalent to the old "--one_java_file" option) and Proto2 (where
+    /// a .proto always translates to a single class, but you may want to
+    /// explicitly choose the class name).
     pub java_outer_classname: ::protobuf::SingularField<::std::string::String>,
+    /// If set true, then the Java code generator will generate a separate .java
+    /// file for each top-level message, enum, and service defined in the .proto
+    /// file.  Thus, these types will *not* be nested inside the outer class
+    /// named by java_outer_classname.  However, the outer class will still be
+    /// generated to contain the file's getDescriptor() method as well as any
+    /// top-level extensions defined in the file.
     pub java_multiple_files: ::std::option::Option<bool>,
+    /// This option does nothing.
     pub java_generate_equals_and_hash: ::std::option::Option<bool>,
+    /// If set true, then the Java2 code generator will generate code that
+    /// throws an exception whenever an attempt is made to assign a non-UTF-8
+    /// byte sequence to a string field.
+    /// Message reflection will do the same.
+    /// However, an extension field still accepts non-UTF-8 byte sequences.
+    /// This option has no effect on when used with the lite runtime.
     pub java_string_check_utf8: ::std::option::Option<bool>,
     pub optimize_for: ::std::option::Option<FileOptions_OptimizeMode>,
+    /// Sets the Go package where structs generated from this .proto will be
+    /// placed. If omitted, the Go package will be derived from the following:
+    ///   - The basename of the package import path, if provided.
+    ///   - Otherwise, the package statement in the .proto file, if present.
+    ///   - Otherwise, the basename of the .proto file, without extension.
     pub go_package: ::protobuf::SingularField<::std::string::String>,
+    /// Should generic services be generated in each language?  "Generic" services
+    /// are not specific to any particular RPC system.  They are generated by the
+    /// main code generators in each language (without additional plugins).
+    /// Generic services were the only kind of service generation supported by
+    /// early versions of google.protobuf.
+    ///
+    /// Generic services are now considered deprecated in favor of using plugins
+    /// that generate code specific to your particular RPC system.  Therefore,
+    /// these default to false.  Old code which depends on generic services should
+    /// explicitly set them to true.
     pub cc_generic_services: ::std::option::Option<bool>,
     pub java_generic_services: ::std::option::Option<bool>,
     pub py_generic_services: ::std::option::Option<bool>,
+    /// Is this file deprecated?
+    /// Depending on the target platform, this can emit Deprecated annotations
+    /// for everything in the file, or it will be completely ignored; in the very
+    /// least, this is a formalization for deprecating files.
     pub deprecated: ::std::option::Option<bool>,
+    /// Enables the use of arenas for the proto messages in this file. This applies
+    /// only to generated classes for C++.
     pub cc_enable_arenas: ::std::option::Option<bool>,
+    /// Sets the objective c class prefix which is prepended to all objective c
+    /// generated classes from this .proto. There is no default.
     pub objc_class_prefix: ::protobuf::SingularField<::std::string::String>,
+    /// Namespace for generated classes; defaults to the package.
     pub csharp_namespace: ::protobuf::SingularField<::std::string::String>,
+    /// The parser stores options it doesn't recognize here. See above.
     pub uninterpreted_option: ::std::vec::Vec<UninterpretedOption>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl FileOptions {
@@ -4534,136 +4858,158 @@ impl ::protobuf::Message for FileOptions {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_java_package: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_java_outer_classname: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_java_multiple_files: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_java_generate_equals_and_hash: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_java_string_check_utf8: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_optimize_for: ::std::option::Option<::std::option::Option<FileOptions_OptimizeMode>> = None;
-        let mut _field_go_package: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_cc_generic_services: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_java_generic_services: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_py_generic_services: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_deprecated: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_cc_enable_arenas: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_objc_class_prefix: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_csharp_namespace: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_uninterpreted_option: ::std::vec::Vec<UninterpretedOption> = ::std::vec::Vec::new();
-
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = FileOptions::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_java_package = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.java_package = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 8 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_java_outer_classname = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.java_outer_classname = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 10 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_java_multiple_files = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.java_multiple_files = ::std::option::Option::Some(is.read_bool()?);
                 },
                 20 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_java_generate_equals_and_hash = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.java_generate_equals_and_hash = ::std::option::Option::Some(is.read_bool()?);
                 },
                 27 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_java_string_check_utf8 = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.java_string_check_utf8 = ::std::option::Option::Some(is.read_bool()?);
                 },
                 9 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_optimize_for = ::std::option::Option::Some(::std::option::Option::Some(_is.read_enum()?));
+                    if let ::std::option::Option::Some(v) = ::protobuf::rt::read_proto2_enum_with_unknown_fields(is, 9, &mut self.unknown_fields)? {
+                        self.optimize_for = ::std::option::Option::Some(v);
+                    }
                 },
                 11 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_go_package = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.go_package = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 16 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_cc_generic_services = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.cc_generic_services = ::std::option::Option::Some(is.read_bool()?);
                 },
                 17 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_java_generic_services = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.java_generic_services = ::std::option::Option::Some(is.read_bool()?);
                 },
                 18 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_py_generic_services = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.py_generic_services = ::std::option::Option::Some(is.read_bool()?);
                 },
                 23 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_deprecated = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.deprecated = ::std::option::Option::Some(is.read_bool()?);
                 },
                 31 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_cc_enable_arenas = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.cc_enable_arenas = ::std::option::Option::Some(is.read_bool()?);
                 },
                 36 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_objc_class_prefix = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.objc_class_prefix = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 37 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_csharp_namespace = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.csharp_namespace = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 999 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_uninterpreted_option)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(FileOptions {
-            java_package: _field_java_package.unwrap_or_default(),
-            java_outer_classname: _field_java_outer_classname.unwrap_or_default(),
-            java_multiple_files: _field_java_multiple_files.unwrap_or_default(),
-            java_generate_equals_and_hash: _field_java_generate_equals_and_hash.unwrap_or_default(),
-            java_string_check_utf8: _field_java_string_check_utf8.unwrap_or_default(),
-            optimize_for: _field_optimize_for.unwrap_or_default(),
-            go_package: _field_go_package.unwrap_or_default(),
-            cc_generic_services: _field_cc_generic_services.unwrap_or_default(),
-            java_generic_services: _field_java_generic_services.unwrap_or_default(),
-            py_generic_services: _field_py_generic_services.unwrap_or_default(),
-            deprecated: _field_deprecated.unwrap_or_default(),
-            cc_enable_arenas: _field_cc_enable_arenas.unwrap_or_default(),
-            objc_class_prefix: _field_objc_class_prefix.unwrap_or_default(),
-            csharp_namespace: _field_csharp_namespace.unwrap_or_default(),
-            uninterpreted_option: _field_uninterpreted_option,
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.java_package.is_some() {
+            self.java_package = other.java_package.clone();
+        }
+        if other.java_outer_classname.is_some() {
+            self.java_outer_classname = other.java_outer_classname.clone();
+        }
+        if other.java_multiple_files.is_some() {
+            self.java_multiple_files = other.java_multiple_files.clone();
+        }
+        if other.java_generate_equals_and_hash.is_some() {
+            self.java_generate_equals_and_hash = other.java_generate_equals_and_hash.clone();
+        }
+        if other.java_string_check_utf8.is_some() {
+            self.java_string_check_utf8 = other.java_string_check_utf8.clone();
+        }
+        if other.optimize_for.is_some() {
+            self.optimize_for = other.optimize_for.clone();
+        }
+        if other.go_package.is_some() {
+            self.go_package = other.go_package.clone();
+        }
+        if other.cc_generic_services.is_some() {
+            self.cc_generic_services = other.cc_generic_services.clone();
+        }
+        if other.java_generic_services.is_some() {
+            self.java_generic_services = other.java_generic_services.clone();
+        }
+        if other.py_generic_services.is_some() {
+            self.py_generic_services = other.py_generic_services.clone();
+        }
+        if other.deprecated.is_some() {
+            self.deprecated = other.deprecated.clone();
+        }
+        if other.cc_enable_arenas.is_some() {
+            self.cc_enable_arenas = other.cc_enable_arenas.clone();
+        }
+        if other.objc_class_prefix.is_some() {
+            self.objc_class_prefix = other.objc_class_prefix.clone();
+        }
+        if other.csharp_namespace.is_some() {
+            self.csharp_namespace = other.csharp_namespace.clone();
+        }
+        self.uninterpreted_option.extend(other.uninterpreted_option.iter().cloned());
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -4716,6 +5062,7 @@ impl ::protobuf::Message for FileOptions {
             let len = value.compute_size();
             _size += 2 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -4767,16 +5114,25 @@ impl ::protobuf::Message for FileOptions {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         };
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -4893,10 +5249,17 @@ impl ::protobuf::reflect::ProtobufValue for FileOptions {
     }
 }
 
+/// Generated classes can be optimized for speed or code size.
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub enum FileOptions_OptimizeMode {
+    /// Generate complete code for parsing, serialization,
     SPEED = 1,
+    /// etc.
+    ///
+    /// Use ReflectionOps to implement these methods.
     CODE_SIZE = 2,
+    /// Generate code using MessageLite and the lite runtime.
     LITE_RUNTIME = 3,
 }
 
@@ -4943,16 +5306,70 @@ impl ::protobuf::reflect::ProtobufValue for FileOptions_OptimizeMode {
     fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
         ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
     }
+
+    fn from_value_box(value: ::protobuf::reflect::ProtobufValueBox) -> ::std::option::Option<Self> {
+        value.into_enum()
+    }
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct MessageOptions {
     // message fields
+    /// Set true to use the old proto1 MessageSet wire format for extensions.
+    /// This is provided for backwards-compatibility with the MessageSet wire
+    /// format.  You should not use this for any other reason:  It's less
+    /// efficient, has fewer features, and is more complicated.
+    ///
+    /// The message must be defined exactly as follows:
+    ///   message Foo {
+    ///     option message_set_wire_format = true;
+    ///     extensions 4 to max;
+    ///   }
+    /// Note that the message cannot have any defined fields; MessageSets only
+    /// have extensions.
+    ///
+    /// All extensions of your type must be singular messages; e.g. they cannot
+    /// be int32s, enums, or repeated messages.
+    ///
+    /// Because this is an option, the above two restrictions are not enforced by
+    /// the protocol compiler.
     pub message_set_wire_format: ::std::option::Option<bool>,
+    /// Disables the generation of the standard "descriptor()" accessor, which can
+    /// conflict with a field of the same name.  This is meant to make migration
+    /// from proto1 easier; new code should avoid fields named "descriptor".
     pub no_standard_descriptor_accessor: ::std::option::Option<bool>,
+    /// Is this message deprecated?
+    /// Depending on the target platform, this can emit Deprecated annotations
+    /// for the message, or it will be completely ignored; in the very least,
+    /// this is a formalization for deprecating messages.
     pub deprecated: ::std::option::Option<bool>,
+    /// Whether the message is an automatically generated map entry type for the
+    /// maps field.
+    ///
+    /// For maps fields:
+    ///     map\<KeyType, ValueType> map_field = 1;
+    /// The parsed descriptor looks like:
+    ///     message MapFieldEntry {
+    ///         option map_entry = true;
+    ///         optional KeyType key = 1;
+    ///         optional ValueType value = 2;
+    ///     }
+    ///     repeated MapFieldEntry map_field = 1;
+    ///
+    /// Implementations may choose not to generate the map_entry=true message, but
+    /// use a native map in the target language to hold the keys and values.
+    /// The reflection APIs in such implementions still need to work as
+    /// if the field is a repeated message field.
+    ///
+    /// NOTE: Do not set the option in .proto files. Always use the maps syntax
+    /// instead. The option should only be implicitly set by the proto compiler
+    /// parser.
     pub map_entry: ::std::option::Option<bool>,
+    /// The parser stores options it doesn't recognize here. See above.
     pub uninterpreted_option: ::std::vec::Vec<UninterpretedOption>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl MessageOptions {
@@ -5102,56 +5519,66 @@ impl ::protobuf::Message for MessageOptions {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_message_set_wire_format: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_no_standard_descriptor_accessor: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_deprecated: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_map_entry: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_uninterpreted_option: ::std::vec::Vec<UninterpretedOption> = ::std::vec::Vec::new();
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = MessageOptions::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_message_set_wire_format = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.message_set_wire_format = ::std::option::Option::Some(is.read_bool()?);
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_no_standard_descriptor_accessor = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.no_standard_descriptor_accessor = ::std::option::Option::Some(is.read_bool()?);
                 },
                 3 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_deprecated = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.deprecated = ::std::option::Option::Some(is.read_bool()?);
                 },
                 7 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_map_entry = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.map_entry = ::std::option::Option::Some(is.read_bool()?);
                 },
                 999 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_uninterpreted_option)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(MessageOptions {
-            message_set_wire_format: _field_message_set_wire_format.unwrap_or_default(),
-            no_standard_descriptor_accessor: _field_no_standard_descriptor_accessor.unwrap_or_default(),
-            deprecated: _field_deprecated.unwrap_or_default(),
-            map_entry: _field_map_entry.unwrap_or_default(),
-            uninterpreted_option: _field_uninterpreted_option,
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.message_set_wire_format.is_some() {
+            self.message_set_wire_format = other.message_set_wire_format.clone();
+        }
+        if other.no_standard_descriptor_accessor.is_some() {
+            self.no_standard_descriptor_accessor = other.no_standard_descriptor_accessor.clone();
+        }
+        if other.deprecated.is_some() {
+            self.deprecated = other.deprecated.clone();
+        }
+        if other.map_entry.is_some() {
+            self.map_entry = other.map_entry.clone();
+        }
+        self.uninterpreted_option.extend(other.uninterpreted_option.iter().cloned());
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -5174,6 +5601,7 @@ impl ::protobuf::Message for MessageOptions {
             let len = value.compute_size();
             _size += 2 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -5195,16 +5623,25 @@ impl ::protobuf::Message for MessageOptions {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         };
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -5272,15 +5709,70 @@ impl ::protobuf::reflect::ProtobufValue for MessageOptions {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct FieldOptions {
     // message fields
+    /// The ctype option instructs the C++ code generator to use a different
+    /// representation of the field than it normally would.  See the specific
+    /// options below.  This option is not yet implemented in the open source
+    /// release -- sorry, we'll try to include it in a future version!
     pub ctype: ::std::option::Option<FieldOptions_CType>,
+    /// The packed option can be enabled for repeated primitive fields to enable
+    /// a more efficient representation on the wire. Rather than repeatedly
+    /// writing the tag and type for each element, the entire array is encoded as
+    /// a single length-delimited blob. In proto3, only explicit setting it to
+    /// false will avoid using packed encoding.
     pub packed: ::std::option::Option<bool>,
+    /// The jstype option determines the JavaScript type used for values of the
+    /// field.  The option is permitted only for 64 bit integral and fixed types
+    /// (int64, uint64, sint64, fixed64, sfixed64).  By default these types are
+    /// represented as JavaScript strings.  This avoids loss of precision that can
+    /// happen when a large value is converted to a floating point JavaScript
+    /// numbers.  Specifying JS_NUMBER for the jstype causes the generated
+    /// JavaScript code to use the JavaScript "number" type instead of strings.
+    /// This option is an enum to permit additional types to be added,
+    /// e.g. goog.math.Integer.
     pub jstype: ::std::option::Option<FieldOptions_JSType>,
+    /// Should this field be parsed lazily?  Lazy applies only to message-type
+    /// fields.  It means that when the outer message is initially parsed, the
+    /// inner message's contents will not be parsed but instead stored in encoded
+    /// form.  The inner message will actually be parsed when it is first accessed.
+    ///
+    /// This is only a hint.  Implementations are free to choose whether to use
+    /// eager or lazy parsing regardless of the value of this option.  However,
+    /// setting this option true suggests that the protocol author believes that
+    /// using lazy parsing on this field is worth the additional bookkeeping
+    /// overhead typically needed to implement it.
+    ///
+    /// This option does not affect the public interface of any generated code;
+    /// all method signatures remain the same.  Furthermore, thread-safety of the
+    /// interface is not affected by this option; const methods remain safe to
+    /// call from multiple threads concurrently, while non-const methods continue
+    /// to require exclusive access.
+    ///
+    ///
+    /// Note that implementations may choose not to check required fields within
+    /// a lazy sub-message.  That is, calling IsInitialized() on the outer message
+    /// may return true even if the inner message has missing required fields.
+    /// This is necessary because otherwise the inner message would have to be
+    /// parsed in order to perform the check, defeating the purpose of lazy
+    /// parsing.  An implementation which chooses not to check required fields
+    /// must be consistent about it.  That is, for any particular sub-message, the
+    /// implementation must either *always* check its required fields, or *never*
+    /// check its required fields, regardless of whether or not the message has
+    /// been parsed.
     pub lazy: ::std::option::Option<bool>,
+    /// Is this field deprecated?
+    /// Depending on the target platform, this can emit Deprecated annotations
+    /// for accessors, or it will be completely ignored; in the very least, this
+    /// is a formalization for deprecating fields.
     pub deprecated: ::std::option::Option<bool>,
+    /// For Google-internal migration only. Do not use.
     pub weak: ::std::option::Option<bool>,
+    /// The parser stores options it doesn't recognize here. See above.
     pub uninterpreted_option: ::std::vec::Vec<UninterpretedOption>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl FieldOptions {
@@ -5478,72 +5970,88 @@ impl ::protobuf::Message for FieldOptions {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_ctype: ::std::option::Option<::std::option::Option<FieldOptions_CType>> = None;
-        let mut _field_packed: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_jstype: ::std::option::Option<::std::option::Option<FieldOptions_JSType>> = None;
-        let mut _field_lazy: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_deprecated: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_weak: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_uninterpreted_option: ::std::vec::Vec<UninterpretedOption> = ::std::vec::Vec::new();
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = FieldOptions::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_ctype = ::std::option::Option::Some(::std::option::Option::Some(_is.read_enum()?));
+                    if let ::std::option::Option::Some(v) = ::protobuf::rt::read_proto2_enum_with_unknown_fields(is, 1, &mut self.unknown_fields)? {
+                        self.ctype = ::std::option::Option::Some(v);
+                    }
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_packed = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.packed = ::std::option::Option::Some(is.read_bool()?);
                 },
                 6 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_jstype = ::std::option::Option::Some(::std::option::Option::Some(_is.read_enum()?));
+                    if let ::std::option::Option::Some(v) = ::protobuf::rt::read_proto2_enum_with_unknown_fields(is, 6, &mut self.unknown_fields)? {
+                        self.jstype = ::std::option::Option::Some(v);
+                    }
                 },
                 5 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_lazy = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.lazy = ::std::option::Option::Some(is.read_bool()?);
                 },
                 3 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_deprecated = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.deprecated = ::std::option::Option::Some(is.read_bool()?);
                 },
                 10 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_weak = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.weak = ::std::option::Option::Some(is.read_bool()?);
                 },
                 999 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_uninterpreted_option)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(FieldOptions {
-            ctype: _field_ctype.unwrap_or_default(),
-            packed: _field_packed.unwrap_or_default(),
-            jstype: _field_jstype.unwrap_or_default(),
-            lazy: _field_lazy.unwrap_or_default(),
-            deprecated: _field_deprecated.unwrap_or_default(),
-            weak: _field_weak.unwrap_or_default(),
-            uninterpreted_option: _field_uninterpreted_option,
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.ctype.is_some() {
+            self.ctype = other.ctype.clone();
+        }
+        if other.packed.is_some() {
+            self.packed = other.packed.clone();
+        }
+        if other.jstype.is_some() {
+            self.jstype = other.jstype.clone();
+        }
+        if other.lazy.is_some() {
+            self.lazy = other.lazy.clone();
+        }
+        if other.deprecated.is_some() {
+            self.deprecated = other.deprecated.clone();
+        }
+        if other.weak.is_some() {
+            self.weak = other.weak.clone();
+        }
+        self.uninterpreted_option.extend(other.uninterpreted_option.iter().cloned());
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -5572,6 +6080,7 @@ impl ::protobuf::Message for FieldOptions {
             let len = value.compute_size();
             _size += 2 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -5599,16 +6108,25 @@ impl ::protobuf::Message for FieldOptions {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         };
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -5686,7 +6204,9 @@ impl ::protobuf::reflect::ProtobufValue for FieldOptions {
 }
 
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub enum FieldOptions_CType {
+    /// Default mode.
     STRING = 0,
     CORD = 1,
     STRING_PIECE = 2,
@@ -5735,12 +6255,20 @@ impl ::protobuf::reflect::ProtobufValue for FieldOptions_CType {
     fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
         ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
     }
+
+    fn from_value_box(value: ::protobuf::reflect::ProtobufValueBox) -> ::std::option::Option<Self> {
+        value.into_enum()
+    }
 }
 
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub enum FieldOptions_JSType {
+    /// Use the default type.
     JS_NORMAL = 0,
+    /// Use JavaScript strings.
     JS_STRING = 1,
+    /// Use JavaScript numbers.
     JS_NUMBER = 2,
 }
 
@@ -5787,12 +6315,20 @@ impl ::protobuf::reflect::ProtobufValue for FieldOptions_JSType {
     fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
         ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
     }
+
+    fn from_value_box(value: ::protobuf::reflect::ProtobufValueBox) -> ::std::option::Option<Self> {
+        value.into_enum()
+    }
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct OneofOptions {
     // message fields
+    /// The parser stores options it doesn't recognize here. See above.
     pub uninterpreted_option: ::std::vec::Vec<UninterpretedOption>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl OneofOptions {
@@ -5846,24 +6382,30 @@ impl ::protobuf::Message for OneofOptions {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_uninterpreted_option: ::std::vec::Vec<UninterpretedOption> = ::std::vec::Vec::new();
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = OneofOptions::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 999 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_uninterpreted_option)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(OneofOptions {
-            uninterpreted_option: _field_uninterpreted_option,
-        })
+    fn merge(&mut self, other: &Self) {
+        self.uninterpreted_option.extend(other.uninterpreted_option.iter().cloned());
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -5874,6 +6416,7 @@ impl ::protobuf::Message for OneofOptions {
             let len = value.compute_size();
             _size += 2 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -5883,16 +6426,25 @@ impl ::protobuf::Message for OneofOptions {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         };
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -5940,11 +6492,21 @@ impl ::protobuf::reflect::ProtobufValue for OneofOptions {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct EnumOptions {
     // message fields
+    /// Set this option to true to allow mapping different tag names to the same
+    /// value.
     pub allow_alias: ::std::option::Option<bool>,
+    /// Is this enum deprecated?
+    /// Depending on the target platform, this can emit Deprecated annotations
+    /// for the enum, or it will be completely ignored; in the very least, this
+    /// is a formalization for deprecating enums.
     pub deprecated: ::std::option::Option<bool>,
+    /// The parser stores options it doesn't recognize here. See above.
     pub uninterpreted_option: ::std::vec::Vec<UninterpretedOption>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl EnumOptions {
@@ -6046,40 +6608,48 @@ impl ::protobuf::Message for EnumOptions {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_allow_alias: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_deprecated: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_uninterpreted_option: ::std::vec::Vec<UninterpretedOption> = ::std::vec::Vec::new();
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = EnumOptions::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_allow_alias = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.allow_alias = ::std::option::Option::Some(is.read_bool()?);
                 },
                 3 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_deprecated = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.deprecated = ::std::option::Option::Some(is.read_bool()?);
                 },
                 999 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_uninterpreted_option)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(EnumOptions {
-            allow_alias: _field_allow_alias.unwrap_or_default(),
-            deprecated: _field_deprecated.unwrap_or_default(),
-            uninterpreted_option: _field_uninterpreted_option,
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.allow_alias.is_some() {
+            self.allow_alias = other.allow_alias.clone();
+        }
+        if other.deprecated.is_some() {
+            self.deprecated = other.deprecated.clone();
+        }
+        self.uninterpreted_option.extend(other.uninterpreted_option.iter().cloned());
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -6096,6 +6666,7 @@ impl ::protobuf::Message for EnumOptions {
             let len = value.compute_size();
             _size += 2 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -6111,16 +6682,25 @@ impl ::protobuf::Message for EnumOptions {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         };
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -6178,10 +6758,18 @@ impl ::protobuf::reflect::ProtobufValue for EnumOptions {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct EnumValueOptions {
     // message fields
+    /// Is this enum value deprecated?
+    /// Depending on the target platform, this can emit Deprecated annotations
+    /// for the enum value, or it will be completely ignored; in the very least,
+    /// this is a formalization for deprecating enum values.
     pub deprecated: ::std::option::Option<bool>,
+    /// The parser stores options it doesn't recognize here. See above.
     pub uninterpreted_option: ::std::vec::Vec<UninterpretedOption>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl EnumValueOptions {
@@ -6259,32 +6847,39 @@ impl ::protobuf::Message for EnumValueOptions {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_deprecated: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_uninterpreted_option: ::std::vec::Vec<UninterpretedOption> = ::std::vec::Vec::new();
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = EnumValueOptions::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_deprecated = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.deprecated = ::std::option::Option::Some(is.read_bool()?);
                 },
                 999 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_uninterpreted_option)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(EnumValueOptions {
-            deprecated: _field_deprecated.unwrap_or_default(),
-            uninterpreted_option: _field_uninterpreted_option,
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.deprecated.is_some() {
+            self.deprecated = other.deprecated.clone();
+        }
+        self.uninterpreted_option.extend(other.uninterpreted_option.iter().cloned());
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -6298,6 +6893,7 @@ impl ::protobuf::Message for EnumValueOptions {
             let len = value.compute_size();
             _size += 2 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -6310,16 +6906,25 @@ impl ::protobuf::Message for EnumValueOptions {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         };
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -6372,10 +6977,18 @@ impl ::protobuf::reflect::ProtobufValue for EnumValueOptions {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct ServiceOptions {
     // message fields
+    /// Is this service deprecated?
+    /// Depending on the target platform, this can emit Deprecated annotations
+    /// for the service, or it will be completely ignored; in the very least,
+    /// this is a formalization for deprecating services.
     pub deprecated: ::std::option::Option<bool>,
+    /// The parser stores options it doesn't recognize here. See above.
     pub uninterpreted_option: ::std::vec::Vec<UninterpretedOption>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl ServiceOptions {
@@ -6453,32 +7066,39 @@ impl ::protobuf::Message for ServiceOptions {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_deprecated: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_uninterpreted_option: ::std::vec::Vec<UninterpretedOption> = ::std::vec::Vec::new();
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = ServiceOptions::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 33 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_deprecated = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.deprecated = ::std::option::Option::Some(is.read_bool()?);
                 },
                 999 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_uninterpreted_option)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(ServiceOptions {
-            deprecated: _field_deprecated.unwrap_or_default(),
-            uninterpreted_option: _field_uninterpreted_option,
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.deprecated.is_some() {
+            self.deprecated = other.deprecated.clone();
+        }
+        self.uninterpreted_option.extend(other.uninterpreted_option.iter().cloned());
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -6492,6 +7112,7 @@ impl ::protobuf::Message for ServiceOptions {
             let len = value.compute_size();
             _size += 2 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -6504,16 +7125,25 @@ impl ::protobuf::Message for ServiceOptions {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         };
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -6566,10 +7196,18 @@ impl ::protobuf::reflect::ProtobufValue for ServiceOptions {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct MethodOptions {
     // message fields
+    /// Is this method deprecated?
+    /// Depending on the target platform, this can emit Deprecated annotations
+    /// for the method, or it will be completely ignored; in the very least,
+    /// this is a formalization for deprecating methods.
     pub deprecated: ::std::option::Option<bool>,
+    /// The parser stores options it doesn't recognize here. See above.
     pub uninterpreted_option: ::std::vec::Vec<UninterpretedOption>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl MethodOptions {
@@ -6647,32 +7285,39 @@ impl ::protobuf::Message for MethodOptions {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_deprecated: ::std::option::Option<::std::option::Option<bool>> = None;
-        let mut _field_uninterpreted_option: ::std::vec::Vec<UninterpretedOption> = ::std::vec::Vec::new();
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = MethodOptions::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 33 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_deprecated = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.deprecated = ::std::option::Option::Some(is.read_bool()?);
                 },
                 999 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_uninterpreted_option)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(MethodOptions {
-            deprecated: _field_deprecated.unwrap_or_default(),
-            uninterpreted_option: _field_uninterpreted_option,
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.deprecated.is_some() {
+            self.deprecated = other.deprecated.clone();
+        }
+        self.uninterpreted_option.extend(other.uninterpreted_option.iter().cloned());
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -6686,6 +7331,7 @@ impl ::protobuf::Message for MethodOptions {
             let len = value.compute_size();
             _size += 2 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -6698,16 +7344,25 @@ impl ::protobuf::Message for MethodOptions {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         };
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -6759,16 +7414,27 @@ impl ::protobuf::reflect::ProtobufValue for MethodOptions {
     }
 }
 
+/// A message representing a option the parser does not recognize. This only
+/// appears in options protos created by the compiler::Parser class.
+/// DescriptorPool resolves these when building Descriptor objects. Therefore,
+/// options protos in descriptor objects (e.g. returned by Descriptor::options(),
+/// or produced by Descriptor::CopyTo()) will never have UninterpretedOptions
+/// in them.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct UninterpretedOption {
     // message fields
     pub name: ::std::vec::Vec<UninterpretedOption_NamePart>,
+    /// The value of the uninterpreted option, in whatever type the tokenizer
+    /// identified it as during parsing. Exactly one of these should be set.
     pub identifier_value: ::protobuf::SingularField<::std::string::String>,
     pub positive_int_value: ::std::option::Option<u64>,
     pub negative_int_value: ::std::option::Option<i64>,
     pub double_value: ::std::option::Option<f64>,
     pub string_value: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     pub aggregate_value: ::protobuf::SingularField<::std::string::String>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl UninterpretedOption {
@@ -7002,72 +7668,84 @@ impl ::protobuf::Message for UninterpretedOption {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_name: ::std::vec::Vec<UninterpretedOption_NamePart> = ::std::vec::Vec::new();
-        let mut _field_identifier_value: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_positive_int_value: ::std::option::Option<::std::option::Option<u64>> = None;
-        let mut _field_negative_int_value: ::std::option::Option<::std::option::Option<i64>> = None;
-        let mut _field_double_value: ::std::option::Option<::std::option::Option<f64>> = None;
-        let mut _field_string_value: ::std::option::Option<::protobuf::SingularField<::std::vec::Vec<u8>>> = None;
-        let mut _field_aggregate_value: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = UninterpretedOption::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 2 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_name)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.name)?;
                 },
                 3 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_identifier_value = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.identifier_value = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 4 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_positive_int_value = ::std::option::Option::Some(::std::option::Option::Some(_is.read_uint64()?));
+                    self.positive_int_value = ::std::option::Option::Some(is.read_uint64()?);
                 },
                 5 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_negative_int_value = ::std::option::Option::Some(::std::option::Option::Some(_is.read_int64()?));
+                    self.negative_int_value = ::std::option::Option::Some(is.read_int64()?);
                 },
                 6 => {
                     if wire_type != ::protobuf::wire_format::WireTypeFixed64 {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_double_value = ::std::option::Option::Some(::std::option::Option::Some(_is.read_double()?));
+                    self.double_value = ::std::option::Option::Some(is.read_double()?);
                 },
                 7 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_string_value = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_bytes()?));
+                    self.string_value = ::protobuf::SingularField::some(is.read_bytes()?);
                 },
                 8 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_aggregate_value = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.aggregate_value = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(UninterpretedOption {
-            name: _field_name,
-            identifier_value: _field_identifier_value.unwrap_or_default(),
-            positive_int_value: _field_positive_int_value.unwrap_or_default(),
-            negative_int_value: _field_negative_int_value.unwrap_or_default(),
-            double_value: _field_double_value.unwrap_or_default(),
-            string_value: _field_string_value.unwrap_or_default(),
-            aggregate_value: _field_aggregate_value.unwrap_or_default(),
-        })
+    fn merge(&mut self, other: &Self) {
+        self.name.extend(other.name.iter().cloned());
+        if other.identifier_value.is_some() {
+            self.identifier_value = other.identifier_value.clone();
+        }
+        if other.positive_int_value.is_some() {
+            self.positive_int_value = other.positive_int_value.clone();
+        }
+        if other.negative_int_value.is_some() {
+            self.negative_int_value = other.negative_int_value.clone();
+        }
+        if other.double_value.is_some() {
+            self.double_value = other.double_value.clone();
+        }
+        if other.string_value.is_some() {
+            self.string_value = other.string_value.clone();
+        }
+        if other.aggregate_value.is_some() {
+            self.aggregate_value = other.aggregate_value.clone();
+        }
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -7096,6 +7774,7 @@ impl ::protobuf::Message for UninterpretedOption {
         if let Some(ref v) = self.aggregate_value.as_ref() {
             _size += ::protobuf::rt::string_size(8, &v);
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -7123,16 +7802,25 @@ impl ::protobuf::Message for UninterpretedOption {
         if let Some(ref v) = self.aggregate_value.as_ref() {
             _os.write_string(8, &v)?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -7209,11 +7897,19 @@ impl ::protobuf::reflect::ProtobufValue for UninterpretedOption {
     }
 }
 
+/// The name of the uninterpreted option.  Each string represents a segment in
+/// a dot-separated name.  is_extension is true iff a segment represents an
+/// extension (denoted with parentheses in options specs in .proto files).
+/// E.g.,{ \["foo", false\], \["bar.baz", true\], \["qux", false\] } represents
+/// "foo.(bar.baz).qux".
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct UninterpretedOption_NamePart {
     // message fields
     pub name_part: ::protobuf::SingularField<::std::string::String>,
     pub is_extension: ::std::option::Option<bool>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl UninterpretedOption_NamePart {
@@ -7303,35 +7999,44 @@ impl ::protobuf::Message for UninterpretedOption_NamePart {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_name_part: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_is_extension: ::std::option::Option<::std::option::Option<bool>> = None;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = UninterpretedOption_NamePart::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_name_part = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.name_part = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_is_extension = ::std::option::Option::Some(::std::option::Option::Some(_is.read_bool()?));
+                    self.is_extension = ::std::option::Option::Some(is.read_bool()?);
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(UninterpretedOption_NamePart {
-            name_part: _field_name_part.ok_or_else(|| ::protobuf::ProtobufError::message_not_initialized("missing required field: UninterpretedOption_NamePart::name_part"))?,
-            is_extension: _field_is_extension.ok_or_else(|| ::protobuf::ProtobufError::message_not_initialized("missing required field: UninterpretedOption_NamePart::is_extension"))?,
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.name_part.is_some() {
+            self.name_part = other.name_part.clone();
+        }
+        if other.is_extension.is_some() {
+            self.is_extension = other.is_extension.clone();
+        }
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -7344,6 +8049,7 @@ impl ::protobuf::Message for UninterpretedOption_NamePart {
         if let Some(v) = self.is_extension {
             _size += 2;
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -7354,16 +8060,25 @@ impl ::protobuf::Message for UninterpretedOption_NamePart {
         if let Some(v) = self.is_extension {
             _os.write_bool(2, v)?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -7415,10 +8130,58 @@ impl ::protobuf::reflect::ProtobufValue for UninterpretedOption_NamePart {
     }
 }
 
+/// Encapsulates information about the original source file from which a
+/// FileDescriptorProto was generated.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct SourceCodeInfo {
     // message fields
+    /// A Location identifies a piece of source code in a .proto file which
+    /// corresponds to a particular definition.  This information is intended
+    /// to be useful to IDEs, code indexers, documentation generators, and similar
+    /// tools.
+    ///
+    /// For example, say we have a file like:
+    ///   message Foo {
+    ///     optional string foo = 1;
+    ///   }
+    /// Let's look at just the field definition:
+    ///   optional string foo = 1;
+    ///   ^       ^^     ^^  ^  ^^^
+    ///   a       bc     de  f  ghi
+    /// We have the following locations:
+    ///   span   path               represents
+    ///   \[a,i)  \[ 4, 0, 2, 0 \]     The whole field definition.
+    ///   \[a,b)  \[ 4, 0, 2, 0, 4 \]  The label (optional).
+    ///   \[c,d)  \[ 4, 0, 2, 0, 5 \]  The type (string).
+    ///   \[e,f)  \[ 4, 0, 2, 0, 1 \]  The name (foo).
+    ///   \[g,h)  \[ 4, 0, 2, 0, 3 \]  The number (1).
+    ///
+    /// Notes:
+    /// - A location may refer to a repeated field itself (i.e. not to any
+    ///   particular index within it).  This is used whenever a set of elements are
+    ///   logically enclosed in a single code segment.  For example, an entire
+    ///   extend block (possibly containing multiple extension definitions) will
+    ///   have an outer location whose path refers to the "extensions" repeated
+    ///   field without an index.
+    /// - Multiple locations may have the same path.  This happens when a single
+    ///   logical declaration is spread out across multiple places.  The most
+    ///   obvious example is the "extend" block again -- there may be multiple
+    ///   extend blocks in the same scope, each of which will have the same path.
+    /// - A location's span is not always a subset of its parent's span.  For
+    ///   example, the "extendee" of an extension declaration appears at the
+    ///   beginning of the "extend" block and is shared by all extensions within
+    ///   the block.
+    /// - Just because a location's span is a subset of some other location's span
+    ///   does not mean that it is a descendent.  For example, a "group" defines
+    ///   both a type and a field in a single declaration.  Thus, the locations
+    ///   corresponding to the type and field and their components will overlap.
+    /// - Code which tries to interpret locations should probably be designed to
+    ///   ignore those that it doesn't understand, as more types of locations could
+    ///   be recorded in the future.
     pub location: ::std::vec::Vec<SourceCodeInfo_Location>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl SourceCodeInfo {
@@ -7472,24 +8235,30 @@ impl ::protobuf::Message for SourceCodeInfo {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_location: ::std::vec::Vec<SourceCodeInfo_Location> = ::std::vec::Vec::new();
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = SourceCodeInfo::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_location)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.location)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(SourceCodeInfo {
-            location: _field_location,
-        })
+    fn merge(&mut self, other: &Self) {
+        self.location.extend(other.location.iter().cloned());
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -7500,6 +8269,7 @@ impl ::protobuf::Message for SourceCodeInfo {
             let len = value.compute_size();
             _size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -7509,16 +8279,25 @@ impl ::protobuf::Message for SourceCodeInfo {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         };
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -7566,13 +8345,91 @@ impl ::protobuf::reflect::ProtobufValue for SourceCodeInfo {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct SourceCodeInfo_Location {
     // message fields
+    /// Identifies which part of the FileDescriptorProto was defined at this
+    /// location.
+    ///
+    /// Each element is a field number or an index.  They form a path from
+    /// the root FileDescriptorProto to the place where the definition.  For
+    /// example, this path:
+    ///   \[ 4, 3, 2, 7, 1 \]
+    /// refers to:
+    ///   file.message_type(3)  // 4, 3
+    ///       .field(7)         // 2, 7
+    ///       .name()           // 1
+    /// This is because FileDescriptorProto.message_type has field number 4:
+    ///   repeated DescriptorProto message_type = 4;
+    /// and DescriptorProto.field has field number 2:
+    ///   repeated FieldDescriptorProto field = 2;
+    /// and FieldDescriptorProto.name has field number 1:
+    ///   optional string name = 1;
+    ///
+    /// Thus, the above path gives the location of a field name.  If we removed
+    /// the last element:
+    ///   \[ 4, 3, 2, 7 \]
+    /// this path refers to the whole field declaration (from the beginning
+    /// of the label to the terminating semicolon).
     pub path: ::std::vec::Vec<i32>,
+    /// Always has exactly three or four elements: start line, start column,
+    /// end line (optional, otherwise assumed same as start line), end column.
+    /// These are packed into a single field for efficiency.  Note that line
+    /// and column numbers are zero-based -- typically you will want to add
+    /// 1 to each before displaying to a user.
     pub span: ::std::vec::Vec<i32>,
+    /// If this SourceCodeInfo represents a complete declaration, these are any
+    /// comments appearing before and after the declaration which appear to be
+    /// attached to the declaration.
+    ///
+    /// A series of line comments appearing on consecutive lines, with no other
+    /// tokens appearing on those lines, will be treated as a single comment.
+    ///
+    /// leading_detached_comments will keep paragraphs of comments that appear
+    /// before (but not connected to) the current element. Each paragraph,
+    /// separated by empty lines, will be one comment element in the repeated
+    /// field.
+    ///
+    /// Only the comment content is provided; comment markers (e.g. //) are
+    /// stripped out.  For block comments, leading whitespace and an asterisk
+    /// will be stripped from the beginning of each line other than the first.
+    /// Newlines are included in the output.
+    ///
+    /// Examples:
+    ///
+    ///   optional int32 foo = 1;  // Comment attached to foo.
+    ///   // Comment attached to bar.
+    ///   optional int32 bar = 2;
+    ///
+    ///   optional string baz = 3;
+    ///   // Comment attached to baz.
+    ///   // Another line attached to baz.
+    ///
+    ///   // Comment attached to qux.
+    ///   //
+    ///   // Another line attached to qux.
+    ///   optional double qux = 4;
+    ///
+    ///   // Detached comment for corge. This is not leading or trailing comments
+    ///   // to qux or corge because there are blank lines separating it from
+    ///   // both.
+    ///
+    ///   // Detached comment for corge paragraph 2.
+    ///
+    ///   optional string corge = 5;
+    ///   /* Block comment attached
+    ///    * to corge.  Leading asterisks
+    ///    * will be removed. */
+    ///   /* Block comment attached to
+    ///    * grault. */
+    ///   optional int32 grault = 6;
+    ///
+    ///   // ignored detached comments.
     pub leading_comments: ::protobuf::SingularField<::std::string::String>,
     pub trailing_comments: ::protobuf::SingularField<::std::string::String>,
     pub leading_detached_comments: ::std::vec::Vec<::std::string::String>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl SourceCodeInfo_Location {
@@ -7743,50 +8600,56 @@ impl ::protobuf::Message for SourceCodeInfo_Location {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_path: ::std::vec::Vec<i32> = ::std::vec::Vec::new();
-        let mut _field_span: ::std::vec::Vec<i32> = ::std::vec::Vec::new();
-        let mut _field_leading_comments: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_trailing_comments: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_leading_detached_comments: ::std::vec::Vec<::std::string::String> = ::std::vec::Vec::new();
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = SourceCodeInfo_Location::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_repeated_int32_into(wire_type, _is, &mut _field_path)?;
+                    ::protobuf::rt::read_repeated_int32_into(wire_type, is, &mut self.path)?;
                 },
                 2 => {
-                    ::protobuf::rt::read_repeated_int32_into(wire_type, _is, &mut _field_span)?;
+                    ::protobuf::rt::read_repeated_int32_into(wire_type, is, &mut self.span)?;
                 },
                 3 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_leading_comments = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.leading_comments = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 4 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_trailing_comments = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.trailing_comments = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 6 => {
-                    ::protobuf::rt::read_repeated_string_into(wire_type, _is, &mut _field_leading_detached_comments)?;
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.leading_detached_comments)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(SourceCodeInfo_Location {
-            path: _field_path,
-            span: _field_span,
-            leading_comments: _field_leading_comments.unwrap_or_default(),
-            trailing_comments: _field_trailing_comments.unwrap_or_default(),
-            leading_detached_comments: _field_leading_detached_comments,
-        })
+    fn merge(&mut self, other: &Self) {
+        self.path.extend(other.path.iter().cloned());
+        self.span.extend(other.span.iter().cloned());
+        if other.leading_comments.is_some() {
+            self.leading_comments = other.leading_comments.clone();
+        }
+        if other.trailing_comments.is_some() {
+            self.trailing_comments = other.trailing_comments.clone();
+        }
+        self.leading_detached_comments.extend(other.leading_detached_comments.iter().cloned());
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -7808,6 +8671,7 @@ impl ::protobuf::Message for SourceCodeInfo_Location {
         for value in &self.leading_detached_comments {
             _size += ::protobuf::rt::string_size(6, &value);
         };
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -7837,16 +8701,25 @@ impl ::protobuf::Message for SourceCodeInfo_Location {
         for v in &self.leading_detached_comments {
             _os.write_string(6, &v)?;
         };
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -7913,10 +8786,18 @@ impl ::protobuf::reflect::ProtobufValue for SourceCodeInfo_Location {
     }
 }
 
+/// Describes the relationship between generated code and its original source
+/// file. A GeneratedCodeInfo message is associated with only one generated
+/// source file, but may contain references to different source .proto files.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct GeneratedCodeInfo {
     // message fields
+    /// An Annotation connects some span of text in generated code to an element
+    /// of its generating .proto file.
     pub annotation: ::std::vec::Vec<GeneratedCodeInfo_Annotation>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl GeneratedCodeInfo {
@@ -7970,24 +8851,30 @@ impl ::protobuf::Message for GeneratedCodeInfo {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_annotation: ::std::vec::Vec<GeneratedCodeInfo_Annotation> = ::std::vec::Vec::new();
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = GeneratedCodeInfo::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_annotation)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.annotation)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(GeneratedCodeInfo {
-            annotation: _field_annotation,
-        })
+    fn merge(&mut self, other: &Self) {
+        self.annotation.extend(other.annotation.iter().cloned());
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -7998,6 +8885,7 @@ impl ::protobuf::Message for GeneratedCodeInfo {
             let len = value.compute_size();
             _size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -8007,16 +8895,25 @@ impl ::protobuf::Message for GeneratedCodeInfo {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         };
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -8064,12 +8961,23 @@ impl ::protobuf::reflect::ProtobufValue for GeneratedCodeInfo {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct GeneratedCodeInfo_Annotation {
     // message fields
+    /// Identifies the element in the original source .proto file. This field
+    /// is formatted the same as SourceCodeInfo.Location.path.
     pub path: ::std::vec::Vec<i32>,
+    /// Identifies the filesystem path to the original source .proto.
     pub source_file: ::protobuf::SingularField<::std::string::String>,
+    /// Identifies the starting offset in bytes in the generated code
+    /// that relates to the identified object.
     pub begin: ::std::option::Option<i32>,
+    /// Identifies the ending offset in bytes in the generated code that
+    /// relates to the identified offset. The end offset should be one past
+    /// the last relevant byte (so the length of the text = end - begin).
     pub end: ::std::option::Option<i32>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl GeneratedCodeInfo_Annotation {
@@ -8202,48 +9110,57 @@ impl ::protobuf::Message for GeneratedCodeInfo_Annotation {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_path: ::std::vec::Vec<i32> = ::std::vec::Vec::new();
-        let mut _field_source_file: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_begin: ::std::option::Option<::std::option::Option<i32>> = None;
-        let mut _field_end: ::std::option::Option<::std::option::Option<i32>> = None;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = GeneratedCodeInfo_Annotation::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_repeated_int32_into(wire_type, _is, &mut _field_path)?;
+                    ::protobuf::rt::read_repeated_int32_into(wire_type, is, &mut self.path)?;
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_source_file = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.source_file = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 3 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_begin = ::std::option::Option::Some(::std::option::Option::Some(_is.read_int32()?));
+                    self.begin = ::std::option::Option::Some(is.read_int32()?);
                 },
                 4 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_end = ::std::option::Option::Some(::std::option::Option::Some(_is.read_int32()?));
+                    self.end = ::std::option::Option::Some(is.read_int32()?);
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(GeneratedCodeInfo_Annotation {
-            path: _field_path,
-            source_file: _field_source_file.unwrap_or_default(),
-            begin: _field_begin.unwrap_or_default(),
-            end: _field_end.unwrap_or_default(),
-        })
+    fn merge(&mut self, other: &Self) {
+        self.path.extend(other.path.iter().cloned());
+        if other.source_file.is_some() {
+            self.source_file = other.source_file.clone();
+        }
+        if other.begin.is_some() {
+            self.begin = other.begin.clone();
+        }
+        if other.end.is_some() {
+            self.end = other.end.clone();
+        }
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -8262,6 +9179,7 @@ impl ::protobuf::Message for GeneratedCodeInfo_Annotation {
         if let Some(v) = self.end {
             _size += ::protobuf::rt::value_size(4, v, ::protobuf::wire_format::WireTypeVarint);
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -8283,16 +9201,25 @@ impl ::protobuf::Message for GeneratedCodeInfo_Annotation {
         if let Some(v) = self.end {
             _os.write_int32(4, v)?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -1,5 +1,8 @@
 /// utilities to work with descriptor
 
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
 use descriptor::FileDescriptorProto;
 use descriptor::DescriptorProto;
 use descriptor::EnumDescriptorProto;
@@ -76,8 +79,22 @@ impl<'a> RootScope<'a> {
         }
     }
 
+    // find message by fully qualified name, `None` if not found
+    pub fn find_message_opt(&'a self, fqn: &str) -> Option<MessageWithScope<'a>> {
+        match self.find_message_or_enum_opt(fqn) {
+            Some(MessageOrEnumWithScope::Message(m)) => Some(m),
+            _ => None,
+        }
+    }
+
     // find message or enum by fully qualified name
     pub fn find_message_or_enum(&'a self, fqn: &str) -> MessageOrEnumWithScope<'a> {
+        self.find_message_or_enum_opt(fqn)
+            .expect(&format!("enum not found by name: {}", fqn))
+    }
+
+    // find message or enum by fully qualified name, `None` if not found
+    pub fn find_message_or_enum_opt(&'a self, fqn: &str) -> Option<MessageOrEnumWithScope<'a>> {
         assert!(fqn.starts_with("."), "name must start with dot: {}", fqn);
         let fqn1 = &fqn[1..];
         self.packages()
@@ -93,7 +110,11 @@ impl<'a> RootScope<'a> {
                  }).into_iter()
             })
             .next()
-            .expect(&format!("enum not found by name: {}", fqn))
+    }
+
+    // all messages in all files, including nested messages
+    pub fn find_messages(&'a self) -> Vec<MessageWithScope<'a>> {
+        self.packages().iter().flat_map(|p| p.find_messages()).collect()
     }
 }
 
@@ -308,6 +329,18 @@ pub trait WithScope<'a> {
         r
     }
 
+    // fully-qualified protobuf name of this type, starting with dot
+    fn name_absolute(&self) -> String {
+        let package = self.get_file_descriptor().get_package();
+        let mut r = ".".to_string();
+        if !package.is_empty() {
+            r.push_str(package);
+            r.push_str(".");
+        }
+        r.push_str(&self.name_to_package());
+        r
+    }
+
     // rust type name of this descriptor
     fn rust_name(&self) -> String {
         let mut r = self.get_scope().rust_prefix();
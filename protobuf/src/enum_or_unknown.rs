@@ -0,0 +1,147 @@
+//! Enum value which may be not known to generated code.
+
+use std::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::marker;
+
+use message::ProtobufEnum;
+
+#[cfg(feature = "with-serde")]
+use serde;
+
+/// Value of an open (proto3) enum field.
+///
+/// Proto3 enums are open: a field may contain a number which does not
+/// correspond to any enum value known at compile time (e. g. a value added
+/// to the enum by a newer version of the `.proto` file).
+/// Such values are preserved as is and written back on serialization.
+pub struct ProtobufEnumOrUnknown<E : ProtobufEnum> {
+    value: i32,
+    _marker: marker::PhantomData<E>,
+}
+
+impl<E : ProtobufEnum> ProtobufEnumOrUnknown<E> {
+    /// Construct from known enum value.
+    pub fn new(e: E) -> ProtobufEnumOrUnknown<E> {
+        ProtobufEnumOrUnknown::from_i32(e.value())
+    }
+
+    /// Construct from any `i32` value, known or not.
+    pub fn from_i32(value: i32) -> ProtobufEnumOrUnknown<E> {
+        ProtobufEnumOrUnknown {
+            value: value,
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Get `i32` value of the enum.
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    /// Get the enum value, or `Err` with the number if it is not known.
+    pub fn enum_value(&self) -> Result<E, i32> {
+        E::from_i32(self.value).ok_or(self.value)
+    }
+
+    /// Get the enum value, or the enum default if the number is not known.
+    pub fn enum_value_or_default(&self) -> E
+    where
+        E : Default,
+    {
+        self.enum_value().unwrap_or_default()
+    }
+}
+
+impl<E : ProtobufEnum> From<E> for ProtobufEnumOrUnknown<E> {
+    fn from(e: E) -> Self {
+        ProtobufEnumOrUnknown::new(e)
+    }
+}
+
+impl<E : ProtobufEnum> Clone for ProtobufEnumOrUnknown<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E : ProtobufEnum> Copy for ProtobufEnumOrUnknown<E> {}
+
+impl<E : ProtobufEnum> PartialEq for ProtobufEnumOrUnknown<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<E : ProtobufEnum> Eq for ProtobufEnumOrUnknown<E> {}
+
+impl<E : ProtobufEnum> PartialEq<E> for ProtobufEnumOrUnknown<E> {
+    fn eq(&self, other: &E) -> bool {
+        self.value == other.value()
+    }
+}
+
+impl<E : ProtobufEnum> Hash for ProtobufEnumOrUnknown<E> {
+    fn hash<H : Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
+/// Default is the enum value with number zero, which is
+/// the first value of any proto3 enum.
+impl<E : ProtobufEnum> Default for ProtobufEnumOrUnknown<E> {
+    fn default() -> Self {
+        ProtobufEnumOrUnknown::from_i32(0)
+    }
+}
+
+impl<E : ProtobufEnum + fmt::Debug> fmt::Debug for ProtobufEnumOrUnknown<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.enum_value() {
+            Ok(e) => e.fmt(f),
+            Err(v) => v.fmt(f),
+        }
+    }
+}
+
+/// Serialized as enum number, so unknown values survive serialization
+/// and the representation is the same regardless of whether value is known.
+#[cfg(feature = "with-serde")]
+impl<E : ProtobufEnum> serde::Serialize for ProtobufEnumOrUnknown<E> {
+    fn serialize<S : serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i32(self.value)
+    }
+}
+
+#[cfg(feature = "with-serde")]
+impl<'de, E : ProtobufEnum> serde::Deserialize<'de> for ProtobufEnumOrUnknown<E> {
+    fn deserialize<D : serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <i32 as serde::Deserialize>::deserialize(deserializer).map(ProtobufEnumOrUnknown::from_i32)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use descriptor::FieldDescriptorProto_Type;
+
+    #[test]
+    fn known() {
+        let e = ProtobufEnumOrUnknown::new(FieldDescriptorProto_Type::TYPE_STRING);
+        assert_eq!(9, e.value());
+        assert_eq!(Ok(FieldDescriptorProto_Type::TYPE_STRING), e.enum_value());
+        assert!(e == FieldDescriptorProto_Type::TYPE_STRING);
+        assert_eq!("TYPE_STRING", format!("{:?}", e));
+    }
+
+    #[test]
+    fn unknown() {
+        let e = ProtobufEnumOrUnknown::<FieldDescriptorProto_Type>::from_i32(100);
+        assert_eq!(100, e.value());
+        assert_eq!(Err(100), e.enum_value());
+        assert_eq!("100", format!("{:?}", e));
+    }
+}
@@ -1,4 +1,9 @@
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+#[cfg(feature = "std")]
 use std::io;
+#[cfg(feature = "std")]
 use std::error::Error;
 use std::fmt;
 use std::str;
@@ -24,10 +29,13 @@ pub enum WireError {
 
 #[derive(Debug)]
 pub enum ProtobufError {
+    #[cfg(feature = "std")]
     IoError(io::Error),
     WireError(WireError),
     Utf8(str::Utf8Error),
     MessageNotInitialized { message: &'static str },
+    /// Message type is not known statically, e. g. generic parse of `DynamicMessage`
+    MessageTypeUnknown,
 }
 
 impl ProtobufError {
@@ -42,6 +50,7 @@ impl fmt::Display for ProtobufError {
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for ProtobufError {
     fn description(&self) -> &str {
         match self {
@@ -62,6 +71,7 @@ impl Error for ProtobufError {
             }
             &ProtobufError::Utf8(ref e) => &e.description(),
             &ProtobufError::MessageNotInitialized { .. } => "not all message fields set",
+            &ProtobufError::MessageTypeUnknown => "message type is unknown",
         }
     }
 
@@ -71,10 +81,12 @@ impl Error for ProtobufError {
             &ProtobufError::Utf8(ref e) => Some(e),
             &ProtobufError::WireError(..) => None,
             &ProtobufError::MessageNotInitialized { .. } => None,
+            &ProtobufError::MessageTypeUnknown => None,
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl From<io::Error> for ProtobufError {
     fn from(err: io::Error) -> Self {
         ProtobufError::IoError(err)
@@ -87,6 +99,7 @@ impl From<str::Utf8Error> for ProtobufError {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<ProtobufError> for io::Error {
     fn from(err: ProtobufError) -> Self {
         match err {
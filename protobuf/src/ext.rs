@@ -1,7 +1,19 @@
+//! Extension fields.
+//!
+//! Extensions are not known to the extended message type,
+//! so extension values are stored in unknown fields of the message,
+//! and decoded when accessed with `ExtFieldOptional` or `ExtFieldRepeated`.
+
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
 use std::marker::PhantomData;
 
-use core::Message;
+use message::Message;
+use stream::CodedInputStream;
+use stream::with_coded_output_stream_to_bytes;
 use types::ProtobufType;
+use unknown::UnknownFields;
 
 /// Optional ext field
 pub struct ExtFieldOptional<M : Message, T : ProtobufType> {
@@ -16,13 +28,72 @@ pub struct ExtFieldRepeated<M : Message, T : ProtobufType> {
 }
 
 impl<M : Message, T : ProtobufType> ExtFieldOptional<M, T> {
+    /// Get the extension value, `None` if the extension is not set.
+    ///
+    /// If the field occurs in the message multiple times, the last value is returned,
+    /// except for message-typed extensions, where all the occurrences are merged.
     pub fn get(&self, m: &M) -> Option<T::Value> {
-        None
+        m.get_unknown_fields().get(self.field_number).and_then(T::get_from_unknown)
+    }
+
+    /// True iff the extension is set.
+    pub fn has(&self, m: &M) -> bool {
+        self.get(m).is_some()
+    }
+
+    /// Replace the extension value.
+    pub fn set(&self, m: &mut M, value: T::Value) {
+        self.clear(m);
+        add_value::<T>(m.mut_unknown_fields(), self.field_number, &value);
+    }
+
+    /// Remove the extension from the message.
+    pub fn clear(&self, m: &mut M) {
+        m.mut_unknown_fields().remove(self.field_number);
     }
 }
 
 impl<M : Message, T : ProtobufType> ExtFieldRepeated<M, T> {
-    pub fn get(&self, _m: &M) -> Vec<T::Value> {
-        unimplemented!()
+    /// Get all the extension values, both packed and unpacked.
+    pub fn get(&self, m: &M) -> Vec<T::Value> {
+        match m.get_unknown_fields().get(self.field_number) {
+            Some(values) => T::get_repeated_from_unknown(values),
+            None => Vec::new(),
+        }
     }
+
+    /// Append a value to the extension.
+    pub fn push(&self, m: &mut M, value: T::Value) {
+        add_value::<T>(m.mut_unknown_fields(), self.field_number, &value);
+    }
+
+    /// Replace all the extension values.
+    ///
+    /// Values are written unpacked.
+    pub fn set(&self, m: &mut M, values: Vec<T::Value>) {
+        self.clear(m);
+        for value in &values {
+            add_value::<T>(m.mut_unknown_fields(), self.field_number, value);
+        }
+    }
+
+    /// Remove all the extension values from the message.
+    pub fn clear(&self, m: &mut M) {
+        m.mut_unknown_fields().remove(self.field_number);
+    }
+}
+
+fn add_value<T : ProtobufType>(
+    unknown_fields: &mut UnknownFields,
+    field_number: u32,
+    value: &T::Value,
+) {
+    // encode the value and decode it back as unknown value
+    let bytes = with_coded_output_stream_to_bytes(|os| {
+        T::write_with_cached_size(field_number, value, os)
+    }).expect("write to vec");
+    let mut is = CodedInputStream::from_bytes(&bytes);
+    let (_, wire_type) = is.read_tag_unpack().expect("tag");
+    let unknown = is.read_unknown(wire_type).expect("value");
+    unknown_fields.add_value(field_number, unknown);
 }
@@ -1,5 +1,8 @@
 // hex encoder and decoder used by rust-protobuf unittests
 
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
 use std::char;
 
 fn decode_hex_digit(digit: char) -> u8 {
@@ -52,13 +55,15 @@ pub fn encode_hex(bytes: &[u8]) -> String {
 
 #[cfg(test)]
 mod test {
+    #[cfg(not(feature = "std"))]
+    use std::prelude::v1::*;
 
     use super::decode_hex;
     use super::encode_hex;
 
     #[test]
     fn test_decode_hex() {
-        assert_eq!(decode_hex(""), [].to_vec());
+        assert_eq!(decode_hex(""), Vec::<u8>::new());
         assert_eq!(decode_hex("00"), [0x00u8].to_vec());
         assert_eq!(decode_hex("ff"), [0xffu8].to_vec());
         assert_eq!(decode_hex("AB"), [0xabu8].to_vec());
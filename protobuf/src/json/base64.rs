@@ -0,0 +1,93 @@
+//! Base64 codec for `bytes` fields.
+
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode with standard alphabet and padding
+pub fn encode(input: &[u8]) -> String {
+    let mut r = String::with_capacity((input.len() + 2) / 3 * 4);
+    for chunk in input.chunks(3) {
+        let b0 = chunk[0] as u32;
+        let b1 = chunk.get(1).map_or(0, |&b| b as u32);
+        let b2 = chunk.get(2).map_or(0, |&b| b as u32);
+        let n = (b0 << 16) | (b1 << 8) | b2;
+
+        r.push(ALPHABET[(n >> 18) as usize & 0x3f] as char);
+        r.push(ALPHABET[(n >> 12) as usize & 0x3f] as char);
+        if chunk.len() > 1 {
+            r.push(ALPHABET[(n >> 6) as usize & 0x3f] as char);
+        } else {
+            r.push('=');
+        }
+        if chunk.len() > 2 {
+            r.push(ALPHABET[n as usize & 0x3f] as char);
+        } else {
+            r.push('=');
+        }
+    }
+    r
+}
+
+fn decode_char(c: u8) -> Option<u32> {
+    let v = match c {
+        b'A'..=b'Z' => c - b'A',
+        b'a'..=b'z' => c - b'a' + 26,
+        b'0'..=b'9' => c - b'0' + 52,
+        b'+' | b'-' => 62,
+        b'/' | b'_' => 63,
+        _ => return None,
+    };
+    Some(v as u32)
+}
+
+/// Decode standard or URL-safe base64, padding is optional
+pub fn decode(input: &str) -> Result<Vec<u8>, ()> {
+    let input = input.trim_end_matches('=').as_bytes();
+    if input.len() % 4 == 1 {
+        return Err(());
+    }
+
+    let mut r = Vec::with_capacity(input.len() * 3 / 4);
+    for chunk in input.chunks(4) {
+        let mut n = 0;
+        for (i, &c) in chunk.iter().enumerate() {
+            n |= decode_char(c).ok_or(())? << (18 - 6 * i);
+        }
+        r.push((n >> 16) as u8);
+        if chunk.len() > 2 {
+            r.push((n >> 8) as u8);
+        }
+        if chunk.len() > 3 {
+            r.push(n as u8);
+        }
+    }
+    Ok(r)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        assert_eq!("", encode(b""));
+        assert_eq!("YQ==", encode(b"a"));
+        assert_eq!("YWI=", encode(b"ab"));
+        assert_eq!("YWJj", encode(b"abc"));
+        assert_eq!("AP/+", encode(b"\x00\xff\xfe"));
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(Ok(b"".to_vec()), decode(""));
+        assert_eq!(Ok(b"a".to_vec()), decode("YQ=="));
+        assert_eq!(Ok(b"a".to_vec()), decode("YQ"));
+        assert_eq!(Ok(b"abc".to_vec()), decode("YWJj"));
+        assert_eq!(Ok(b"\x00\xff\xfe".to_vec()), decode("AP/+"));
+        assert_eq!(Ok(b"\x00\xff\xfe".to_vec()), decode("AP_-"));
+        assert_eq!(Err(()), decode("Y"));
+        assert_eq!(Err(()), decode("YW*j"));
+    }
+}
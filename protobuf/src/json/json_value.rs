@@ -0,0 +1,314 @@
+//! Generic JSON value and its parser.
+
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+use std::char;
+
+use super::ParseError;
+use super::ParseResult;
+
+
+/// Parsed JSON value
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    /// Number as written in input, so 64-bit integers are not rounded
+    Number(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    /// Object members in input order
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// Name of the value type for error messages
+    pub fn type_name(&self) -> &'static str {
+        match *self {
+            JsonValue::Null => "null",
+            JsonValue::Bool(..) => "bool",
+            JsonValue::Number(..) => "number",
+            JsonValue::String(..) => "string",
+            JsonValue::Array(..) => "array",
+            JsonValue::Object(..) => "object",
+        }
+    }
+}
+
+/// Maximum nesting of arrays and objects
+const RECURSION_LIMIT: u32 = 100;
+
+struct Reader<'a> {
+    input: &'a str,
+    pos: usize,
+    depth: u32,
+}
+
+impl<'a> Reader<'a> {
+    fn rem(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn error<T, S : Into<String>>(&self, message: S) -> ParseResult<T> {
+        // position is reported as line and column
+        let before = &self.input[..self.pos];
+        let line = before.matches('\n').count() + 1;
+        let col = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+        Err(ParseError::new(
+            format!("{} at {}:{}", message.into(), line, col),
+        ))
+    }
+
+    fn skip_ws(&mut self) {
+        let rem = self.rem();
+        let trimmed = rem.trim_start_matches(|c| c == ' ' || c == '\t' || c == '\n' || c == '\r');
+        self.pos += rem.len() - trimmed.len();
+    }
+
+    fn peek_char(&self) -> Option<char> {
+        self.rem().chars().next()
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.peek_char()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn next_str_if_eq(&mut self, s: &str) -> bool {
+        if self.rem().starts_with(s) {
+            self.pos += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_char(&mut self, c: char) -> ParseResult<()> {
+        self.skip_ws();
+        if self.peek_char() != Some(c) {
+            return self.error(format!("expecting '{}'", c));
+        }
+        self.next_char();
+        Ok(())
+    }
+
+    fn read_hex4(&mut self) -> ParseResult<u32> {
+        let mut r = 0;
+        for _ in 0..4 {
+            match self.next_char().and_then(|c| c.to_digit(16)) {
+                Some(d) => r = r * 16 + d,
+                None => return self.error("expecting 4 hex digits"),
+            }
+        }
+        Ok(r)
+    }
+
+    fn read_string(&mut self) -> ParseResult<String> {
+        self.expect_char('"')?;
+        let mut r = String::new();
+        loop {
+            let c = match self.next_char() {
+                Some(c) => c,
+                None => return self.error("unterminated string"),
+            };
+            match c {
+                '"' => return Ok(r),
+                '\\' => {
+                    let c = match self.next_char() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\x08',
+                        Some('f') => '\x0c',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => {
+                            let mut code = self.read_hex4()?;
+                            if code >= 0xd800 && code < 0xdc00 {
+                                // surrogate pair
+                                if !self.next_str_if_eq("\\u") {
+                                    return self.error("expecting low surrogate");
+                                }
+                                let low = self.read_hex4()?;
+                                if low < 0xdc00 || low >= 0xe000 {
+                                    return self.error("expecting low surrogate");
+                                }
+                                code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                            }
+                            match char::from_u32(code) {
+                                Some(c) => c,
+                                None => return self.error("invalid unicode escape"),
+                            }
+                        }
+                        _ => return self.error("invalid escape"),
+                    };
+                    r.push(c);
+                }
+                c if (c as u32) < 0x20 => return self.error("control character in string"),
+                c => r.push(c),
+            }
+        }
+    }
+
+    fn read_number(&mut self) -> ParseResult<String> {
+        let rem = self.rem();
+        let len = rem.find(|c: char| {
+            !(c.is_ascii_digit() || c == '-' || c == '+' || c == '.' || c == 'e' || c == 'E')
+        }).unwrap_or(rem.len());
+        let number = &rem[..len];
+        if number.parse::<f64>().is_err() || number.starts_with('+') ||
+            number.starts_with('.')
+        {
+            return self.error(format!("invalid number: {}", number));
+        }
+        self.pos += len;
+        Ok(number.to_owned())
+    }
+
+    fn read_value(&mut self) -> ParseResult<JsonValue> {
+        self.skip_ws();
+        let c = match self.peek_char() {
+            Some(c) => c,
+            None => return self.error("unexpected end of input"),
+        };
+        match c {
+            '{' | '[' => {
+                if self.depth >= RECURSION_LIMIT {
+                    return self.error("recursion limit exceeded");
+                }
+                self.depth += 1;
+                let r = if c == '{' {
+                    self.read_object()?
+                } else {
+                    self.read_array()?
+                };
+                self.depth -= 1;
+                Ok(r)
+            }
+            '"' => Ok(JsonValue::String(self.read_string()?)),
+            '-' | '0'..='9' => Ok(JsonValue::Number(self.read_number()?)),
+            _ => {
+                if self.next_str_if_eq("null") {
+                    Ok(JsonValue::Null)
+                } else if self.next_str_if_eq("true") {
+                    Ok(JsonValue::Bool(true))
+                } else if self.next_str_if_eq("false") {
+                    Ok(JsonValue::Bool(false))
+                } else {
+                    self.error(format!("unexpected character '{}'", c))
+                }
+            }
+        }
+    }
+
+    fn read_array(&mut self) -> ParseResult<JsonValue> {
+        self.expect_char('[')?;
+        let mut r = Vec::new();
+        self.skip_ws();
+        if self.next_str_if_eq("]") {
+            return Ok(JsonValue::Array(r));
+        }
+        loop {
+            r.push(self.read_value()?);
+            self.skip_ws();
+            if self.next_str_if_eq("]") {
+                return Ok(JsonValue::Array(r));
+            }
+            self.expect_char(',')?;
+        }
+    }
+
+    fn read_object(&mut self) -> ParseResult<JsonValue> {
+        self.expect_char('{')?;
+        let mut r = Vec::new();
+        self.skip_ws();
+        if self.next_str_if_eq("}") {
+            return Ok(JsonValue::Object(r));
+        }
+        loop {
+            self.skip_ws();
+            let key = self.read_string()?;
+            self.expect_char(':')?;
+            let value = self.read_value()?;
+            r.push((key, value));
+            self.skip_ws();
+            if self.next_str_if_eq("}") {
+                return Ok(JsonValue::Object(r));
+            }
+            self.expect_char(',')?;
+        }
+    }
+}
+
+/// Parse JSON document
+pub fn parse(input: &str) -> ParseResult<JsonValue> {
+    let mut reader = Reader {
+        input: input,
+        pos: 0,
+        depth: 0,
+    };
+    let value = reader.read_value()?;
+    reader.skip_ws();
+    if reader.peek_char().is_some() {
+        return reader.error("trailing data");
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn s(s: &str) -> JsonValue {
+        JsonValue::String(s.to_owned())
+    }
+
+    fn n(s: &str) -> JsonValue {
+        JsonValue::Number(s.to_owned())
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Ok(JsonValue::Null), parse(" null "));
+        assert_eq!(Ok(JsonValue::Bool(true)), parse("true"));
+        assert_eq!(Ok(n("-1.5e3")), parse("-1.5e3"));
+        assert_eq!(Ok(n("9223372036854775807")), parse("9223372036854775807"));
+        assert_eq!(
+            Ok(JsonValue::Array(vec![n("1"), JsonValue::Array(Vec::new()), s("a")])),
+            parse("[1, [], \"a\"]")
+        );
+        assert_eq!(
+            Ok(JsonValue::Object(vec![
+                ("a".to_owned(), n("1")),
+                ("b".to_owned(), JsonValue::Object(Vec::new())),
+            ])),
+            parse("{\"a\": 1, \"b\": {}}")
+        );
+    }
+
+    #[test]
+    fn test_parse_string() {
+        assert_eq!(Ok(s("a\"\\/\n\t")), parse(r#""a\"\\\/\n\t""#));
+        assert_eq!(Ok(s("ю")), parse(r#""ю""#));
+        assert_eq!(Ok(s("ю")), parse(r#""\u044e""#));
+        assert_eq!(Ok(s("\u{1f600}")), parse(r#""\ud83d\ude00""#));
+        assert!(parse(r#""\ud83d""#).is_err());
+        assert!(parse("\"abc").is_err());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!(parse("").is_err());
+        assert!(parse("[1,]").is_err());
+        assert!(parse("{\"a\" 1}").is_err());
+        assert!(parse("1 2").is_err());
+        assert!(parse("+1").is_err());
+        assert_eq!(
+            Err(ParseError::new("unexpected character 'x' at 2:3")),
+            parse("[1,\n  x]")
+        );
+    }
+}
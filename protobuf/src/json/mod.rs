@@ -0,0 +1,143 @@
+//! JSON printer and parser.
+//!
+//! Implementation of [proto3 JSON mapping](https://developers.google.com/protocol-buffers/docs/proto3#json),
+//! including special representation of well-known types.
+
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+#[cfg(feature = "std")]
+use std::error::Error;
+use std::fmt;
+
+use message::Message;
+use message::parse_from_bytes;
+use reflect::MessageDescriptor;
+
+mod base64;
+mod json_value;
+mod parse;
+mod print;
+mod well_known;
+
+use self::parse::Parser;
+use self::print::Printer;
+
+
+/// JSON printer options
+#[derive(Default, Clone)]
+pub struct PrintOptions {
+    /// Print enum values as numbers instead of names
+    pub enum_values_int: bool,
+    /// Use field names from `.proto` files instead of `lowerCamelCase` names
+    pub proto_field_name: bool,
+    /// Print singular fields with default values, and empty repeated and map fields
+    pub always_output_default_values: bool,
+    /// Message types which may be packed into `google.protobuf.Any`,
+    /// in addition to types in the global registry (see `well_known_types_util::register_type`)
+    pub any_types: Vec<&'static MessageDescriptor>,
+}
+
+/// JSON parser options
+#[derive(Default, Clone)]
+pub struct ParseOptions {
+    /// Skip unknown fields and unknown enum value names instead of returning an error
+    pub ignore_unknown_fields: bool,
+    /// Message types which may be packed into `google.protobuf.Any`,
+    /// in addition to types in the global registry (see `well_known_types_util::register_type`)
+    pub any_types: Vec<&'static MessageDescriptor>,
+}
+
+/// Message cannot be represented as JSON,
+/// e. g. `Timestamp` is out of range or type of `Any` content is unknown
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrintError {
+    pub message: String,
+}
+
+impl fmt::Display for PrintError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for PrintError {
+    fn description(&self) -> &str {
+        &self.message
+    }
+}
+
+pub type PrintResult<T> = Result<T, PrintError>;
+
+/// JSON parse error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+}
+
+impl ParseError {
+    fn new<S : Into<String>>(message: S) -> ParseError {
+        ParseError { message: message.into() }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ParseError {
+    fn description(&self) -> &str {
+        &self.message
+    }
+}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// Print message as JSON with default options
+pub fn print_to_string(m: &Message) -> PrintResult<String> {
+    print_to_string_with_options(m, &PrintOptions::default())
+}
+
+/// Print message as JSON
+pub fn print_to_string_with_options(m: &Message, options: &PrintOptions) -> PrintResult<String> {
+    let mut printer = Printer::new(options);
+    printer.print_message(m)?;
+    Ok(printer.into_string())
+}
+
+/// Parse message from JSON with default options
+pub fn parse_from_str<M : Message>(json: &str) -> ParseResult<M> {
+    parse_from_str_with_options(json, &ParseOptions::default())
+}
+
+/// Parse message from JSON
+pub fn parse_from_str_with_options<M : Message>(
+    json: &str,
+    options: &ParseOptions,
+) -> ParseResult<M> {
+    let bytes = Parser::new(options).parse_message(M::descriptor_static(None), json)?;
+    parse_from_bytes(&bytes).map_err(|e| ParseError::new(e.to_string()))
+}
+
+/// Parse JSON and merge it into given message with default options
+pub fn merge_from_str(m: &mut Message, json: &str) -> ParseResult<()> {
+    merge_from_str_with_options(m, json, &ParseOptions::default())
+}
+
+/// Parse JSON and merge it into given message.
+///
+/// Fields specified in JSON replace (or, for repeated fields, are appended to)
+/// fields of the message, same as when reading concatenated binary messages.
+pub fn merge_from_str_with_options(
+    m: &mut Message,
+    json: &str,
+    options: &ParseOptions,
+) -> ParseResult<()> {
+    let d = m.descriptor();
+    let bytes = Parser::new(options).parse_message(d, json)?;
+    m.merge_from_bytes(&bytes).map_err(|e| ParseError::new(e.to_string()))
+}
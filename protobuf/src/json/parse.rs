@@ -0,0 +1,510 @@
+//! JSON parser.
+//!
+//! Like text format parser, it is driven by message descriptors and emits
+//! serialized message, which is then read with `Message::read_from`.
+
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+use std::collections::HashSet;
+use std::f32;
+use std::f64;
+use std::i32;
+use std::u32;
+
+use descriptor::FieldDescriptorProto;
+use descriptor::FieldDescriptorProto_Label;
+use descriptor::FieldDescriptorProto_Type;
+use reflect::MessageDescriptor;
+use reflect::field_type::FieldType;
+use reflect::field_type::MessageType;
+use stream::CodedOutputStream;
+use strx;
+use well_known_types;
+
+use super::base64;
+use super::json_value;
+use super::json_value::JsonValue;
+use super::well_known;
+use super::ParseError;
+use super::ParseOptions;
+use super::ParseResult;
+
+
+fn expecting<T>(what: &str, json: &JsonValue) -> ParseResult<T> {
+    Err(ParseError::new(format!("expecting {}, got {}", what, json.type_name())))
+}
+
+fn json_name(field: &FieldDescriptorProto) -> String {
+    if field.has_json_name() {
+        field.get_json_name().to_owned()
+    } else {
+        strx::to_lower_camel_case(field.get_name())
+    }
+}
+
+/// Fields for which JSON `null` is a value rather than absence of value
+fn accepts_null(field: &FieldDescriptorProto) -> bool {
+    field.get_type_name() == ".google.protobuf.Value" ||
+        field.get_type_name() == ".google.protobuf.NullValue"
+}
+
+/// Serialize nested message written by `f`
+fn nested<F>(f: F) -> ParseResult<Vec<u8>>
+where
+    F : FnOnce(&mut CodedOutputStream) -> ParseResult<()>,
+{
+    let mut r = Vec::new();
+    {
+        let mut os = CodedOutputStream::vec(&mut r);
+        f(&mut os)?;
+    }
+    Ok(r)
+}
+
+/// Text of JSON number or of number in quotes
+fn number_text(json: &JsonValue) -> ParseResult<&str> {
+    match *json {
+        JsonValue::Number(ref s) |
+        JsonValue::String(ref s) => Ok(s),
+        ref json => expecting("number", json),
+    }
+}
+
+fn read_i64(json: &JsonValue) -> ParseResult<i64> {
+    let s = number_text(json)?;
+    if let Ok(v) = s.parse::<i64>() {
+        return Ok(v);
+    }
+    // integers can be written with exponent or fraction, e. g. `1e3` or `1.0`
+    // (`f64::fract` is not available without `std`, so fraction is checked by round trip)
+    match s.parse::<f64>() {
+        Ok(v) if v >= -9223372036854775808.0 && v < 9223372036854775808.0 && v as i64 as f64 == v => {
+            Ok(v as i64)
+        }
+        _ => Err(ParseError::new(format!("invalid integer: {}", s))),
+    }
+}
+
+fn read_u64(json: &JsonValue) -> ParseResult<u64> {
+    let s = number_text(json)?;
+    if let Ok(v) = s.parse::<u64>() {
+        return Ok(v);
+    }
+    match s.parse::<f64>() {
+        Ok(v) if v >= 0.0 && v < 18446744073709551616.0 && v as u64 as f64 == v => Ok(v as u64),
+        _ => Err(ParseError::new(format!("invalid unsigned integer: {}", s))),
+    }
+}
+
+fn read_i32(json: &JsonValue) -> ParseResult<i32> {
+    let v = read_i64(json)?;
+    if v < i32::MIN as i64 || v > i32::MAX as i64 {
+        return Err(ParseError::new(format!("integer is out of range: {}", v)));
+    }
+    Ok(v as i32)
+}
+
+fn read_u32(json: &JsonValue) -> ParseResult<u32> {
+    let v = read_u64(json)?;
+    if v > u32::MAX as u64 {
+        return Err(ParseError::new(format!("integer is out of range: {}", v)));
+    }
+    Ok(v as u32)
+}
+
+fn read_f64(json: &JsonValue) -> ParseResult<f64> {
+    let s = match *json {
+        JsonValue::String(ref s) if s == "NaN" => return Ok(f64::NAN),
+        JsonValue::String(ref s) if s == "Infinity" => return Ok(f64::INFINITY),
+        JsonValue::String(ref s) if s == "-Infinity" => return Ok(f64::NEG_INFINITY),
+        ref json => number_text(json)?,
+    };
+    match s.parse::<f64>() {
+        Ok(v) if v.is_finite() => Ok(v),
+        _ => Err(ParseError::new(format!("invalid number: {}", s))),
+    }
+}
+
+fn read_f32(json: &JsonValue) -> ParseResult<f32> {
+    let v = read_f64(json)?;
+    if v.is_finite() && (v > f32::MAX as f64 || v < f32::MIN as f64) {
+        return Err(ParseError::new(format!("float is out of range: {}", v)));
+    }
+    Ok(v as f32)
+}
+
+fn read_bool(json: &JsonValue) -> ParseResult<bool> {
+    match *json {
+        JsonValue::Bool(v) => Ok(v),
+        ref json => expecting("bool", json),
+    }
+}
+
+fn read_string(json: &JsonValue) -> ParseResult<&str> {
+    match *json {
+        JsonValue::String(ref s) => Ok(s),
+        ref json => expecting("string", json),
+    }
+}
+
+fn read_array(json: &JsonValue) -> ParseResult<&[JsonValue]> {
+    match *json {
+        JsonValue::Array(ref values) => Ok(values),
+        ref json => expecting("array", json),
+    }
+}
+
+fn read_object(json: &JsonValue) -> ParseResult<&[(String, JsonValue)]> {
+    match *json {
+        JsonValue::Object(ref members) => Ok(members),
+        ref json => expecting("object", json),
+    }
+}
+
+pub struct Parser<'a> {
+    options: &'a ParseOptions,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(options: &'a ParseOptions) -> Parser<'a> {
+        Parser { options: options }
+    }
+
+    /// Parse JSON document as message of given type, return serialized message
+    pub fn parse_message(
+        &self,
+        d: &'static MessageDescriptor,
+        json: &str,
+    ) -> ParseResult<Vec<u8>> {
+        let json = json_value::parse(json)?;
+        self.merge_message(d, &json)
+    }
+
+    /// Return `None` if value is unknown enum name and unknown fields are ignored
+    fn read_enum(&self, field: &FieldType, json: &JsonValue) -> ParseResult<Option<i32>> {
+        let d = field.enum_descriptor.expect("enum descriptor");
+        match *json {
+            JsonValue::Null => Ok(Some(0)),
+            JsonValue::String(ref name) => {
+                match d.values().iter().find(|v| v.name() == name) {
+                    Some(v) => Ok(Some(v.value())),
+                    None if self.options.ignore_unknown_fields => Ok(None),
+                    None => Err(ParseError::new(
+                        format!("unknown value {} of enum {}", name, d.name()),
+                    )),
+                }
+            }
+            ref json => {
+                let number = read_i32(json)?;
+                if !d.is_open() && !d.values().iter().any(|v| v.value() == number) {
+                    return Err(ParseError::new(
+                        format!("unknown value {} of enum {}", number, d.name()),
+                    ));
+                }
+                Ok(Some(number))
+            }
+        }
+    }
+
+    fn merge_value(
+        &self,
+        field: &FieldType,
+        json: &JsonValue,
+        os: &mut CodedOutputStream,
+    ) -> ParseResult<()> {
+        let number = field.proto.get_number() as u32;
+        let r = match field.proto.get_field_type() {
+            FieldDescriptorProto_Type::TYPE_MESSAGE | FieldDescriptorProto_Type::TYPE_GROUP => {
+                let d = match field.message {
+                    Some(MessageType::Message(d)) => d,
+                    _ => unreachable!(),
+                };
+                let bytes = self.merge_message(d, json)?;
+                field.write_message_bytes(&bytes, os)
+            }
+            FieldDescriptorProto_Type::TYPE_DOUBLE => os.write_double(number, read_f64(json)?),
+            FieldDescriptorProto_Type::TYPE_FLOAT => os.write_float(number, read_f32(json)?),
+            FieldDescriptorProto_Type::TYPE_INT64 => os.write_int64(number, read_i64(json)?),
+            FieldDescriptorProto_Type::TYPE_UINT64 => os.write_uint64(number, read_u64(json)?),
+            FieldDescriptorProto_Type::TYPE_INT32 => os.write_int32(number, read_i32(json)?),
+            FieldDescriptorProto_Type::TYPE_FIXED64 => os.write_fixed64(number, read_u64(json)?),
+            FieldDescriptorProto_Type::TYPE_FIXED32 => os.write_fixed32(number, read_u32(json)?),
+            FieldDescriptorProto_Type::TYPE_BOOL => os.write_bool(number, read_bool(json)?),
+            FieldDescriptorProto_Type::TYPE_STRING => os.write_string(number, read_string(json)?),
+            FieldDescriptorProto_Type::TYPE_BYTES => {
+                let s = read_string(json)?;
+                let bytes = match base64::decode(s) {
+                    Ok(bytes) => bytes,
+                    Err(()) => return Err(ParseError::new(format!("invalid base64: {}", s))),
+                };
+                os.write_bytes(number, &bytes)
+            }
+            FieldDescriptorProto_Type::TYPE_UINT32 => os.write_uint32(number, read_u32(json)?),
+            FieldDescriptorProto_Type::TYPE_ENUM => {
+                match self.read_enum(field, json)? {
+                    Some(v) => os.write_enum(number, v),
+                    None => Ok(()),
+                }
+            }
+            FieldDescriptorProto_Type::TYPE_SFIXED32 => {
+                os.write_sfixed32(number, read_i32(json)?)
+            }
+            FieldDescriptorProto_Type::TYPE_SFIXED64 => {
+                os.write_sfixed64(number, read_i64(json)?)
+            }
+            FieldDescriptorProto_Type::TYPE_SINT32 => os.write_sint32(number, read_i32(json)?),
+            FieldDescriptorProto_Type::TYPE_SINT64 => os.write_sint64(number, read_i64(json)?),
+        };
+        r.expect("write to vec");
+        Ok(())
+    }
+
+    fn merge_map_field(
+        &self,
+        field: &FieldType,
+        entry: MessageType,
+        json: &JsonValue,
+        os: &mut CodedOutputStream,
+    ) -> ParseResult<()> {
+        let key_field = entry.field_by_name("key").expect("key");
+        let value_field = entry.field_by_name("value").expect("value");
+
+        for &(ref key, ref value) in read_object(json)? {
+            // map keys are always strings in JSON
+            let key = match key_field.proto.get_field_type() {
+                FieldDescriptorProto_Type::TYPE_BOOL => {
+                    match &key[..] {
+                        "true" => JsonValue::Bool(true),
+                        "false" => JsonValue::Bool(false),
+                        _ => return Err(ParseError::new(format!("invalid bool map key: {}", key))),
+                    }
+                }
+                _ => JsonValue::String(key.clone()),
+            };
+            if *value == JsonValue::Null && !accepts_null(value_field.proto) {
+                return Err(ParseError::new("map value must not be null"));
+            }
+
+            let bytes = nested(|entry_os| {
+                self.merge_value(&key_field, &key, entry_os)?;
+                self.merge_value(&value_field, value, entry_os)
+            })?;
+            os.write_bytes(field.proto.get_number() as u32, &bytes)
+                .expect("write to vec");
+        }
+        Ok(())
+    }
+
+    fn merge_field(
+        &self,
+        field: &FieldType,
+        json: &JsonValue,
+        os: &mut CodedOutputStream,
+    ) -> ParseResult<()> {
+        if let Some(entry @ MessageType::MapEntry(..)) = field.message {
+            return self.merge_map_field(field, entry, json, os);
+        }
+
+        if field.proto.get_label() == FieldDescriptorProto_Label::LABEL_REPEATED {
+            for value in read_array(json)? {
+                if *value == JsonValue::Null && !accepts_null(field.proto) {
+                    return Err(ParseError::new("repeated field element must not be null"));
+                }
+                self.merge_value(field, value, os)?;
+            }
+            Ok(())
+        } else {
+            self.merge_value(field, json, os)
+        }
+    }
+
+    fn merge_fields(
+        &self,
+        message: MessageType,
+        members: &[(String, JsonValue)],
+        os: &mut CodedOutputStream,
+    ) -> ParseResult<()> {
+        let mut seen_fields = HashSet::new();
+        let mut seen_oneofs = HashSet::new();
+
+        for &(ref name, ref value) in members {
+            let proto = message.field_protos().iter().find(|f| {
+                f.get_name() == name || json_name(f) == *name
+            });
+            let proto = match proto {
+                Some(proto) => proto,
+                None if self.options.ignore_unknown_fields => continue,
+                None => {
+                    return Err(ParseError::new(
+                        format!("message {} has no field {}", message.name(), name),
+                    ));
+                }
+            };
+
+            if !seen_fields.insert(proto.get_number()) {
+                return Err(ParseError::new(
+                    format!("field {} is specified multiple times", proto.get_name()),
+                ));
+            }
+
+            // null means default value
+            if *value == JsonValue::Null && !accepts_null(proto) {
+                continue;
+            }
+
+            if proto.has_oneof_index() && !seen_oneofs.insert(proto.get_oneof_index()) {
+                return Err(ParseError::new(format!(
+                    "field {} is a member of oneof which is already set",
+                    proto.get_name()
+                )));
+            }
+
+            let field = message.field_by_name(proto.get_name()).expect("field");
+            self.merge_field(&field, value, os).map_err(|e| {
+                ParseError::new(format!("{}: {}", name, e.message))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Write fields of `Timestamp` or `Duration`, zeros are omitted like in generated code
+    fn merge_seconds_nanos(&self, seconds: i64, nanos: i32, os: &mut CodedOutputStream) {
+        if seconds != 0 {
+            os.write_int64(1, seconds).expect("write to vec");
+        }
+        if nanos != 0 {
+            os.write_int32(2, nanos).expect("write to vec");
+        }
+    }
+
+    fn merge_timestamp(&self, json: &JsonValue, os: &mut CodedOutputStream) -> ParseResult<()> {
+        let s = read_string(json)?;
+        let (seconds, nanos) = match well_known_types::parse_timestamp(s) {
+            Ok(r) => r,
+            Err(()) => return Err(ParseError::new(format!("invalid timestamp: {}", s))),
+        };
+        self.merge_seconds_nanos(seconds, nanos, os);
+        Ok(())
+    }
+
+    fn merge_duration(&self, json: &JsonValue, os: &mut CodedOutputStream) -> ParseResult<()> {
+        let s = read_string(json)?;
+        let (seconds, nanos) = match well_known_types::parse_duration(s) {
+            Ok(r) => r,
+            Err(()) => return Err(ParseError::new(format!("invalid duration: {}", s))),
+        };
+        self.merge_seconds_nanos(seconds, nanos, os);
+        Ok(())
+    }
+
+    fn merge_field_mask(&self, json: &JsonValue, os: &mut CodedOutputStream) -> ParseResult<()> {
+        let s = read_string(json)?;
+        if !s.is_empty() {
+            for path in s.split(',') {
+                os.write_string(1, &strx::to_snake_case(path))
+                    .expect("write to vec");
+            }
+        }
+        Ok(())
+    }
+
+    fn merge_struct(&self, json: &JsonValue, os: &mut CodedOutputStream) -> ParseResult<()> {
+        for &(ref key, ref value) in read_object(json)? {
+            let value = nested(|os| self.merge_struct_value(value, os))?;
+            let entry = nested(|os| {
+                os.write_string(1, key).expect("write to vec");
+                os.write_bytes(2, &value).expect("write to vec");
+                Ok(())
+            })?;
+            os.write_bytes(1, &entry).expect("write to vec");
+        }
+        Ok(())
+    }
+
+    fn merge_struct_value(&self, json: &JsonValue, os: &mut CodedOutputStream) -> ParseResult<()> {
+        let r = match *json {
+            JsonValue::Null => os.write_enum(1, 0),
+            JsonValue::Number(..) => os.write_double(2, read_f64(json)?),
+            JsonValue::String(ref s) => os.write_string(3, s),
+            JsonValue::Bool(v) => os.write_bool(4, v),
+            JsonValue::Object(..) => {
+                let bytes = nested(|os| self.merge_struct(json, os))?;
+                os.write_bytes(5, &bytes)
+            }
+            JsonValue::Array(..) => {
+                let bytes = nested(|os| self.merge_list_value(json, os))?;
+                os.write_bytes(6, &bytes)
+            }
+        };
+        r.expect("write to vec");
+        Ok(())
+    }
+
+    fn merge_list_value(&self, json: &JsonValue, os: &mut CodedOutputStream) -> ParseResult<()> {
+        for value in read_array(json)? {
+            let bytes = nested(|os| self.merge_struct_value(value, os))?;
+            os.write_bytes(1, &bytes).expect("write to vec");
+        }
+        Ok(())
+    }
+
+    fn merge_any(&self, json: &JsonValue, os: &mut CodedOutputStream) -> ParseResult<()> {
+        let members = read_object(json)?;
+        if members.is_empty() {
+            return Ok(());
+        }
+
+        let type_url = match members.iter().find(|m| m.0 == "@type") {
+            Some(&(_, ref type_url)) => read_string(type_url)?,
+            None => return Err(ParseError::new("Any must have @type")),
+        };
+        let content_type = match well_known::find_any_type(&self.options.any_types, type_url) {
+            Some(content_type) => content_type,
+            None => return Err(ParseError::new(format!("unknown type in Any: {}", type_url))),
+        };
+
+        let content = if well_known::is_special_well_known_type(content_type.full_name()) {
+            match members.iter().find(|m| m.0 == "value") {
+                Some(&(_, ref value)) => self.merge_message(content_type, value)?,
+                None => return Err(ParseError::new("Any must have value")),
+            }
+        } else {
+            let content_members: Vec<_> = members
+                .iter()
+                .filter(|m| m.0 != "@type")
+                .cloned()
+                .collect();
+            self.merge_message(content_type, &JsonValue::Object(content_members))?
+        };
+
+        os.write_string(1, type_url).expect("write to vec");
+        os.write_bytes(2, &content).expect("write to vec");
+        Ok(())
+    }
+
+    fn merge_message(&self, d: &'static MessageDescriptor, json: &JsonValue) -> ParseResult<Vec<u8>> {
+        nested(|os| match d.full_name() {
+            "google.protobuf.Any" => self.merge_any(json, os),
+            "google.protobuf.Timestamp" => self.merge_timestamp(json, os),
+            "google.protobuf.Duration" => self.merge_duration(json, os),
+            "google.protobuf.FieldMask" => self.merge_field_mask(json, os),
+            "google.protobuf.Struct" => self.merge_struct(json, os),
+            "google.protobuf.Value" => self.merge_struct_value(json, os),
+            "google.protobuf.ListValue" => self.merge_list_value(json, os),
+            "google.protobuf.DoubleValue" |
+            "google.protobuf.FloatValue" |
+            "google.protobuf.Int64Value" |
+            "google.protobuf.UInt64Value" |
+            "google.protobuf.Int32Value" |
+            "google.protobuf.UInt32Value" |
+            "google.protobuf.BoolValue" |
+            "google.protobuf.StringValue" |
+            "google.protobuf.BytesValue" => {
+                let field = MessageType::Message(d).field_by_name("value").expect("value");
+                self.merge_value(&field, json, os)
+            }
+            _ => self.merge_fields(MessageType::Message(d), read_object(json)?, os),
+        })
+    }
+}
@@ -0,0 +1,451 @@
+//! JSON printer.
+
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+use std::f64;
+use std::fmt::Write;
+
+use message::Message;
+use descriptor::FieldDescriptorProto_Type;
+use reflect::FieldDescriptor;
+use reflect::ProtobufValueRef;
+use reflect::ReflectFieldRef;
+use strx;
+use text_format::unescape_string;
+use well_known_types;
+
+use super::base64;
+use super::well_known;
+use super::PrintError;
+use super::PrintOptions;
+use super::PrintResult;
+
+
+fn print_json_string(s: &str, buf: &mut String) {
+    buf.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            '\x08' => buf.push_str("\\b"),
+            '\x0c' => buf.push_str("\\f"),
+            c if (c as u32) < 0x20 => write!(buf, "\\u{:04x}", c as u32).unwrap(),
+            c => buf.push(c),
+        }
+    }
+    buf.push('"');
+}
+
+fn print_map_key(key: ProtobufValueRef, buf: &mut String) {
+    match key {
+        ProtobufValueRef::String(s) => print_json_string(s, buf),
+        ProtobufValueRef::I32(v) => write!(buf, "\"{}\"", v).unwrap(),
+        ProtobufValueRef::I64(v) => write!(buf, "\"{}\"", v).unwrap(),
+        ProtobufValueRef::U32(v) => write!(buf, "\"{}\"", v).unwrap(),
+        ProtobufValueRef::U64(v) => write!(buf, "\"{}\"", v).unwrap(),
+        ProtobufValueRef::Bool(v) => write!(buf, "\"{}\"", v).unwrap(),
+        _ => panic!("wrong map key type"),
+    }
+}
+
+pub struct Printer<'a> {
+    buf: String,
+    options: &'a PrintOptions,
+}
+
+/// Plain `{}` formatting of very large or very small numbers produces
+/// hundreds of digits, so switch to exponent notation outside of the
+/// range where plain notation is short (same bounds as JavaScript).
+fn use_exponent(v: f64) -> bool {
+    v != 0.0 && (v >= 1e21 || v <= -1e21 || (v < 1e-7 && v > -1e-7))
+}
+
+impl<'a> Printer<'a> {
+    pub fn new(options: &'a PrintOptions) -> Printer<'a> {
+        Printer {
+            buf: String::new(),
+            options: options,
+        }
+    }
+
+    pub fn into_string(self) -> String {
+        self.buf
+    }
+
+    fn print_f64(&mut self, v: f64) {
+        if v.is_nan() {
+            self.buf.push_str("\"NaN\"");
+        } else if v == f64::INFINITY {
+            self.buf.push_str("\"Infinity\"");
+        } else if v == f64::NEG_INFINITY {
+            self.buf.push_str("\"-Infinity\"");
+        } else if use_exponent(v) {
+            write!(self.buf, "{:e}", v).unwrap();
+        } else {
+            write!(self.buf, "{}", v).unwrap();
+        }
+    }
+
+    fn print_f32(&mut self, v: f32) {
+        if !v.is_finite() {
+            self.print_f64(v as f64);
+        } else if use_exponent(v as f64) {
+            write!(self.buf, "{:e}", v).unwrap();
+        } else {
+            write!(self.buf, "{}", v).unwrap();
+        }
+    }
+
+    fn print_value(&mut self, value: ProtobufValueRef) -> PrintResult<()> {
+        match value {
+            ProtobufValueRef::Message(m) => return self.print_message(m),
+            ProtobufValueRef::Enum(e) => {
+                if self.options.enum_values_int {
+                    write!(self.buf, "{}", e.value()).unwrap();
+                } else {
+                    print_json_string(e.name(), &mut self.buf);
+                }
+            }
+            ProtobufValueRef::String(s) => print_json_string(s, &mut self.buf),
+            ProtobufValueRef::Bytes(b) => print_json_string(&base64::encode(b), &mut self.buf),
+            // 64-bit integers are printed as strings, because
+            // JavaScript numbers cannot represent them precisely
+            ProtobufValueRef::I64(v) => write!(self.buf, "\"{}\"", v).unwrap(),
+            ProtobufValueRef::U64(v) => write!(self.buf, "\"{}\"", v).unwrap(),
+            ProtobufValueRef::I32(v) => write!(self.buf, "{}", v).unwrap(),
+            ProtobufValueRef::U32(v) => write!(self.buf, "{}", v).unwrap(),
+            ProtobufValueRef::Bool(v) => write!(self.buf, "{}", v).unwrap(),
+            ProtobufValueRef::F32(v) => self.print_f32(v),
+            ProtobufValueRef::F64(v) => self.print_f64(v),
+        }
+        Ok(())
+    }
+
+    /// Print value of unset singular field
+    fn print_default_value(&mut self, field: &FieldDescriptor) -> PrintResult<()> {
+        let proto = field.proto();
+        let default = proto.get_default_value();
+        let has_default = proto.has_default_value();
+        match proto.get_field_type() {
+            FieldDescriptorProto_Type::TYPE_MESSAGE |
+            FieldDescriptorProto_Type::TYPE_GROUP => self.buf.push_str("null"),
+            FieldDescriptorProto_Type::TYPE_ENUM => {
+                let d = field.enum_descriptor();
+                let value = if has_default {
+                    d.values().iter().find(|v| v.name() == default)
+                } else {
+                    d.values().iter().find(|v| v.value() == 0)
+                };
+                let value = value.unwrap_or(&d.values()[0]);
+                self.print_value(ProtobufValueRef::Enum(value))?;
+            }
+            FieldDescriptorProto_Type::TYPE_STRING => print_json_string(default, &mut self.buf),
+            FieldDescriptorProto_Type::TYPE_BYTES => {
+                print_json_string(&base64::encode(&unescape_string(default)), &mut self.buf)
+            }
+            FieldDescriptorProto_Type::TYPE_BOOL => {
+                self.buf.push_str(if default == "true" { "true" } else { "false" })
+            }
+            FieldDescriptorProto_Type::TYPE_INT64 |
+            FieldDescriptorProto_Type::TYPE_UINT64 |
+            FieldDescriptorProto_Type::TYPE_FIXED64 |
+            FieldDescriptorProto_Type::TYPE_SFIXED64 |
+            FieldDescriptorProto_Type::TYPE_SINT64 => {
+                write!(self.buf, "\"{}\"", if has_default { default } else { "0" }).unwrap()
+            }
+            FieldDescriptorProto_Type::TYPE_INT32 |
+            FieldDescriptorProto_Type::TYPE_UINT32 |
+            FieldDescriptorProto_Type::TYPE_FIXED32 |
+            FieldDescriptorProto_Type::TYPE_SFIXED32 |
+            FieldDescriptorProto_Type::TYPE_SINT32 => {
+                self.buf.push_str(if has_default { default } else { "0" })
+            }
+            FieldDescriptorProto_Type::TYPE_DOUBLE |
+            FieldDescriptorProto_Type::TYPE_FLOAT => {
+                let v = match default {
+                    "" => 0.0,
+                    "inf" => f64::INFINITY,
+                    "-inf" => f64::NEG_INFINITY,
+                    "nan" => f64::NAN,
+                    s => s.parse().unwrap_or(0.0),
+                };
+                self.print_f64(v);
+            }
+        }
+        Ok(())
+    }
+
+    fn print_fields(&mut self, m: &Message, first: &mut bool) -> PrintResult<()> {
+        for field in m.descriptor().fields() {
+            self.print_field(m, field, first)?;
+        }
+        Ok(())
+    }
+
+    fn print_field(
+        &mut self,
+        m: &Message,
+        field: &FieldDescriptor,
+        first: &mut bool,
+    ) -> PrintResult<()> {
+        let reflect = field.get_reflect(m);
+        let print_default = match reflect {
+            ReflectFieldRef::Map(map) => map.len() == 0,
+            ReflectFieldRef::Repeated(repeated) => repeated.len() == 0,
+            ReflectFieldRef::Optional(ref v) => {
+                // unset message fields and oneof members are never printed
+                let message = match field.proto().get_field_type() {
+                    FieldDescriptorProto_Type::TYPE_MESSAGE |
+                    FieldDescriptorProto_Type::TYPE_GROUP => true,
+                    _ => false,
+                };
+                if v.is_none() && (message || field.proto().has_oneof_index()) {
+                    return Ok(());
+                }
+                v.is_none()
+            }
+            // unset like other message fields
+            ReflectFieldRef::Wrapper(None) => return Ok(()),
+            ReflectFieldRef::Wrapper(Some(..)) => false,
+        };
+        if print_default && !self.options.always_output_default_values {
+            return Ok(());
+        }
+
+        if !*first {
+            self.buf.push_str(", ");
+        }
+        *first = false;
+
+        if self.options.proto_field_name {
+            print_json_string(field.name(), &mut self.buf);
+        } else {
+            print_json_string(field.json_name(), &mut self.buf);
+        }
+        self.buf.push_str(": ");
+
+        match reflect {
+            ReflectFieldRef::Map(map) => {
+                // sort entries to make output stable
+                let mut entries: Vec<_> = map.into_iter()
+                    .map(|(k, v)| {
+                        let mut key = String::new();
+                        print_map_key(k.as_ref(), &mut key);
+                        (key, v)
+                    })
+                    .collect();
+                entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+                self.buf.push('{');
+                for (i, (key, value)) in entries.into_iter().enumerate() {
+                    if i != 0 {
+                        self.buf.push_str(", ");
+                    }
+                    self.buf.push_str(&key);
+                    self.buf.push_str(": ");
+                    self.print_value(value.as_ref())?;
+                }
+                self.buf.push('}');
+            }
+            ReflectFieldRef::Repeated(repeated) => {
+                self.buf.push('[');
+                for (i, value) in repeated.into_iter().enumerate() {
+                    if i != 0 {
+                        self.buf.push_str(", ");
+                    }
+                    self.print_value(value.as_ref())?;
+                }
+                self.buf.push(']');
+            }
+            ReflectFieldRef::Optional(Some(value)) => self.print_value(value)?,
+            ReflectFieldRef::Optional(None) => self.print_default_value(field)?,
+            ReflectFieldRef::Wrapper(Some(w)) => self.print_message(&*w)?,
+            ReflectFieldRef::Wrapper(None) => unreachable!(),
+        }
+        Ok(())
+    }
+
+    fn print_timestamp(&mut self, m: &Message) -> PrintResult<()> {
+        let d = m.descriptor();
+        let seconds = d.field_by_name("seconds").get_i64(m);
+        let nanos = d.field_by_name("nanos").get_i32(m);
+        let s = well_known_types::format_timestamp(seconds, nanos)
+            .map_err(|message| PrintError { message: message })?;
+        print_json_string(&s, &mut self.buf);
+        Ok(())
+    }
+
+    fn print_duration(&mut self, m: &Message) -> PrintResult<()> {
+        let d = m.descriptor();
+        let seconds = d.field_by_name("seconds").get_i64(m);
+        let nanos = d.field_by_name("nanos").get_i32(m);
+        let s = well_known_types::format_duration(seconds, nanos)
+            .map_err(|message| PrintError { message: message })?;
+        print_json_string(&s, &mut self.buf);
+        Ok(())
+    }
+
+    fn print_field_mask(&mut self, m: &Message) -> PrintResult<()> {
+        let mut paths = Vec::new();
+        if let ReflectFieldRef::Repeated(repeated) =
+            m.descriptor().field_by_name("paths").get_reflect(m)
+        {
+            for path in repeated {
+                match path.as_ref() {
+                    ProtobufValueRef::String(path) => paths.push(strx::to_lower_camel_case(path)),
+                    _ => panic!("wrong type"),
+                }
+            }
+        }
+        print_json_string(&paths.join(","), &mut self.buf);
+        Ok(())
+    }
+
+    fn print_struct(&mut self, m: &Message) -> PrintResult<()> {
+        match m.descriptor().field_by_name("fields").get_reflect(m) {
+            ReflectFieldRef::Map(map) => {
+                let mut entries: Vec<_> = map.into_iter()
+                    .map(|(k, v)| match k.as_ref() {
+                        ProtobufValueRef::String(k) => (k, v),
+                        _ => panic!("wrong type"),
+                    })
+                    .collect();
+                entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+                self.buf.push('{');
+                for (i, (key, value)) in entries.into_iter().enumerate() {
+                    if i != 0 {
+                        self.buf.push_str(", ");
+                    }
+                    print_json_string(key, &mut self.buf);
+                    self.buf.push_str(": ");
+                    self.print_value(value.as_ref())?;
+                }
+                self.buf.push('}');
+            }
+            _ => panic!("wrong type"),
+        }
+        Ok(())
+    }
+
+    fn print_struct_value(&mut self, m: &Message) -> PrintResult<()> {
+        let d = m.descriptor();
+        let field = match d.fields().iter().find(|f| f.has_field(m)) {
+            Some(field) => field,
+            None => {
+                return Err(PrintError { message: format!("{} kind is not set", d.full_name()) });
+            }
+        };
+        match field.name() {
+            "null_value" => self.buf.push_str("null"),
+            "number_value" => {
+                let v = field.get_f64(m);
+                if !v.is_finite() {
+                    return Err(PrintError {
+                        message: format!("{} cannot be represented as JSON number", v),
+                    });
+                }
+                self.print_f64(v);
+            }
+            "string_value" => print_json_string(field.get_str(m), &mut self.buf),
+            "bool_value" => self.print_value(ProtobufValueRef::Bool(field.get_bool(m)))?,
+            _ => self.print_message(field.get_message(m))?,
+        }
+        Ok(())
+    }
+
+    fn print_list_value(&mut self, m: &Message) -> PrintResult<()> {
+        match m.descriptor().field_by_name("values").get_reflect(m) {
+            ReflectFieldRef::Repeated(repeated) => {
+                self.buf.push('[');
+                for (i, value) in repeated.into_iter().enumerate() {
+                    if i != 0 {
+                        self.buf.push_str(", ");
+                    }
+                    self.print_value(value.as_ref())?;
+                }
+                self.buf.push(']');
+            }
+            _ => panic!("wrong type"),
+        }
+        Ok(())
+    }
+
+    fn print_wrapper(&mut self, m: &Message) -> PrintResult<()> {
+        let field = m.descriptor().field_by_name("value");
+        match field.get_reflect(m) {
+            ReflectFieldRef::Optional(Some(value)) => self.print_value(value),
+            ReflectFieldRef::Optional(None) => self.print_default_value(field),
+            _ => panic!("wrong type"),
+        }
+    }
+
+    fn print_any(&mut self, m: &Message) -> PrintResult<()> {
+        let d = m.descriptor();
+        let type_url = d.field_by_name("type_url").get_str(m);
+        let value = d.field_by_name("value").get_bytes(m);
+        if type_url.is_empty() && value.is_empty() {
+            self.buf.push_str("{}");
+            return Ok(());
+        }
+
+        let content_type = match well_known::find_any_type(&self.options.any_types, type_url) {
+            Some(content_type) => content_type,
+            None => {
+                return Err(PrintError { message: format!("unknown type in Any: {}", type_url) });
+            }
+        };
+        let mut content = content_type.new_instance();
+        content.merge_from_bytes(value).map_err(|e| {
+            PrintError { message: format!("failed to decode Any content: {}", e) }
+        })?;
+
+        self.buf.push('{');
+        print_json_string("@type", &mut self.buf);
+        self.buf.push_str(": ");
+        print_json_string(type_url, &mut self.buf);
+        if well_known::is_special_well_known_type(content_type.full_name()) {
+            self.buf.push_str(", ");
+            print_json_string("value", &mut self.buf);
+            self.buf.push_str(": ");
+            self.print_message(&*content)?;
+        } else {
+            let mut first = false;
+            self.print_fields(&*content, &mut first)?;
+        }
+        self.buf.push('}');
+        Ok(())
+    }
+
+    pub fn print_message(&mut self, m: &Message) -> PrintResult<()> {
+        match m.descriptor().full_name() {
+            "google.protobuf.Any" => self.print_any(m),
+            "google.protobuf.Timestamp" => self.print_timestamp(m),
+            "google.protobuf.Duration" => self.print_duration(m),
+            "google.protobuf.FieldMask" => self.print_field_mask(m),
+            "google.protobuf.Struct" => self.print_struct(m),
+            "google.protobuf.Value" => self.print_struct_value(m),
+            "google.protobuf.ListValue" => self.print_list_value(m),
+            "google.protobuf.DoubleValue" |
+            "google.protobuf.FloatValue" |
+            "google.protobuf.Int64Value" |
+            "google.protobuf.UInt64Value" |
+            "google.protobuf.Int32Value" |
+            "google.protobuf.UInt32Value" |
+            "google.protobuf.BoolValue" |
+            "google.protobuf.StringValue" |
+            "google.protobuf.BytesValue" => self.print_wrapper(m),
+            _ => {
+                self.buf.push('{');
+                let mut first = true;
+                self.print_fields(m, &mut first)?;
+                self.buf.push('}');
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! JSON representation of well-known types.
+
+use reflect::MessageDescriptor;
+use well_known_types_util;
+
+/// Well-known types which have special JSON representation
+pub fn is_special_well_known_type(full_name: &str) -> bool {
+    match full_name {
+        "google.protobuf.Any" |
+        "google.protobuf.Timestamp" |
+        "google.protobuf.Duration" |
+        "google.protobuf.FieldMask" |
+        "google.protobuf.Struct" |
+        "google.protobuf.Value" |
+        "google.protobuf.ListValue" |
+        "google.protobuf.DoubleValue" |
+        "google.protobuf.FloatValue" |
+        "google.protobuf.Int64Value" |
+        "google.protobuf.UInt64Value" |
+        "google.protobuf.Int32Value" |
+        "google.protobuf.UInt32Value" |
+        "google.protobuf.BoolValue" |
+        "google.protobuf.StringValue" |
+        "google.protobuf.BytesValue" => true,
+        _ => false,
+    }
+}
+
+/// Find message type by `type_url` of `Any`
+/// in the types specified in options or in the global registry
+pub fn find_any_type(
+    any_types: &[&'static MessageDescriptor],
+    type_url: &str,
+) -> Option<&'static MessageDescriptor> {
+    let name = match well_known_types_util::type_url_full_name(type_url) {
+        Some(name) => name,
+        None => return None,
+    };
+    any_types
+        .iter()
+        .cloned()
+        .find(|d| d.full_name() == name)
+        .or_else(|| well_known_types_util::find_type_by_full_name(name))
+}
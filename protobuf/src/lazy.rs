@@ -1,6 +1,9 @@
 //! Lazily initialized data.
 //! Used in generated code.
 
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
 use std::mem;
 use std::sync;
 
@@ -36,7 +39,7 @@ impl<T> Lazy<T> {
 pub const ONCE_INIT: sync::Once = sync::ONCE_INIT;
 
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod test {
     use super::{Lazy, ONCE_INIT};
     use std::thread;
@@ -3,24 +3,41 @@
 // TODO: add docs
 //#![deny(missing_docs)]
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[macro_use]
+extern crate alloc;
+
 #[cfg(feature = "bytes")]
 extern crate bytes;
+#[cfg(feature = "serde_json")]
+extern crate serde_json;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde_derive")]
+#[macro_use]
+extern crate serde_derive;
 
 pub use unknown::UnknownValue;
 pub use unknown::UnknownValueRef;
 pub use unknown::UnknownValues;
 pub use unknown::UnknownValuesIter;
+pub use unknown::UnknownFields;
+pub use unknown::UnknownFieldsIter;
 pub use singular::SingularField;
 pub use singular::SingularPtrField;
-pub use core::Message;
-pub use core::ProtobufEnum;
-pub use core::parse_from_bytes;
-pub use core::parse_from_reader;
+pub use message::Message;
+pub use message::ProtobufEnum;
+pub use enum_or_unknown::ProtobufEnumOrUnknown;
+pub use message::parse_from_bytes;
+#[cfg(feature = "std")]
+pub use message::parse_from_reader;
 #[cfg(feature = "bytes")]
-pub use core::parse_from_carllerche_bytes;
-pub use core::parse_length_delimited_from;
-pub use core::parse_length_delimited_from_reader;
-pub use core::parse_length_delimited_from_bytes;
+pub use message::parse_from_carllerche_bytes;
+pub use message::parse_length_delimited_from;
+#[cfg(feature = "std")]
+pub use message::parse_length_delimited_from_reader;
+pub use message::parse_length_delimited_from_bytes;
 pub use stream::CodedInputStream;
 pub use stream::CodedOutputStream;
 pub use stream::wire_format;
@@ -35,18 +52,24 @@ pub mod descriptor;
 pub mod plugin;
 pub mod rustproto;
 
-mod core;
+mod message;
+mod enum_or_unknown;
 pub mod rt;
 pub mod lazy;
+#[cfg(feature = "std")]
 pub mod compiler_plugin;
 mod singular;
 pub mod reflect;
 pub mod text_format;
+pub mod json;
 pub mod stream;
 pub mod error;
 pub mod types;
 pub mod well_known_types;
+pub mod well_known_types_util;
 pub mod ext;
+pub mod raw;
+pub mod service;
 
 // used by test
 pub mod hex;
@@ -67,13 +90,23 @@ pub mod chars; // TODO: make private
 
 mod buf_read_iter;
 
+// `core` and `alloc` reexported as `std`, so `std::` paths work without `std` feature
+#[cfg(not(feature = "std"))]
+#[path = "std_facade.rs"]
+mod std;
+#[cfg(not(feature = "std"))]
+mod spin;
+
 
 // so `use protobuf::*` could work in mod descriptor and well_known_types
+#[allow(unused_imports)]
 mod protobuf {
     pub use descriptor;
+    #[cfg(feature = "std")]
     pub use descriptorx;
     pub use reflect;
-    pub use core::*;
+    pub use message::*;
+    pub use enum_or_unknown::ProtobufEnumOrUnknown;
     pub use error::*;
     pub use stream::*;
     pub use rt;
@@ -86,6 +119,8 @@ mod protobuf {
     pub use unknown::UnknownValueRef;
     pub use unknown::UnknownValues;
     pub use unknown::UnknownValuesIter;
+    pub use unknown::UnknownFields;
+    pub use unknown::UnknownFieldsIter;
     pub use singular::SingularField;
     pub use singular::SingularPtrField;
     pub use cached_size::CachedSize;
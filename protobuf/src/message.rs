@@ -1,7 +1,12 @@
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
 use std::any::Any;
 use std::any::TypeId;
 use std::fmt;
+#[cfg(feature = "std")]
 use std::io::Read;
+#[cfg(feature = "std")]
 use std::io::Write;
 
 #[cfg(feature = "bytes")]
@@ -17,6 +22,7 @@ use stream::CodedOutputStream;
 use stream::with_coded_output_stream_to_bytes;
 use error::ProtobufError;
 use error::ProtobufResult;
+use unknown::UnknownFields;
 
 
 /// Trait implemented for all generated structs for protobuf messages.
@@ -32,6 +38,24 @@ pub trait Message: fmt::Debug + Any + Send + Sync {
     fn read_from(_is: &mut CodedInputStream) -> ProtobufResult<Self>
         where Self : Sized;
 
+    /// Update this message with fields read from the stream until EOF or limit.
+    ///
+    /// Singular fields present in the stream replace current values
+    /// (message fields are merged recursively), repeated fields are appended.
+    /// Required fields are not checked.
+    fn merge_from(&mut self, is: &mut CodedInputStream) -> ProtobufResult<()>;
+
+    /// Update this message with fields read from bytes, see `merge_from`.
+    fn merge_from_bytes(&mut self, bytes: &[u8]) -> ProtobufResult<()> {
+        bytes.with_coded_input_stream(|is| self.merge_from(is))
+    }
+
+    /// Merge other message into this message.
+    ///
+    /// Result is the same as merging serialized `other` with `merge_from`.
+    fn merge(&mut self, other: &Self)
+        where Self : Sized;
+
     /// Write message to the stream.
     ///
     /// Sizes of this messages and nested messages must be cached
@@ -72,7 +96,7 @@ pub trait Message: fmt::Debug + Any + Send + Sync {
     /// Write the message to the vec, prepend the message with message length
     /// encoded as varint.
     fn write_length_delimited_to_vec(&self, vec: &mut Vec<u8>) -> ProtobufResult<()> {
-        let mut os = CodedOutputStream::new(vec);
+        let mut os = CodedOutputStream::vec(vec);
         self.write_length_delimited_to(&mut os)?;
         os.flush()?;
         Ok(())
@@ -90,6 +114,7 @@ pub trait Message: fmt::Debug + Any + Send + Sync {
     }
 
     /// Write the message to the writer.
+    #[cfg(feature = "std")]
     fn write_to_writer(&self, w: &mut Write) -> ProtobufResult<()> {
         w.with_coded_output_stream(|os| self.write_to(os))
     }
@@ -106,7 +131,7 @@ pub trait Message: fmt::Debug + Any + Send + Sync {
         let mut vec = Vec::new();
 
         {
-            let mut os = CodedOutputStream::new(&mut vec);
+            let mut os = CodedOutputStream::vec(&mut vec);
             self.write_to_with_cached_sizes(&mut os)?;
         }
 
@@ -115,6 +140,7 @@ pub trait Message: fmt::Debug + Any + Send + Sync {
 
     /// Write the message to the writer, prepend the message with message length
     /// encoded as varint.
+    #[cfg(feature = "std")]
     fn write_length_delimited_to_writer(&self, w: &mut Write) -> ProtobufResult<()> {
         w.with_coded_output_stream(|os| self.write_length_delimited_to(os))
     }
@@ -125,6 +151,12 @@ pub trait Message: fmt::Debug + Any + Send + Sync {
         with_coded_output_stream_to_bytes(|os| self.write_length_delimited_to(os))
     }
 
+    /// Fields of this message not known to the schema, including extensions.
+    fn get_unknown_fields(&self) -> &UnknownFields;
+
+    /// Mutable reference to unknown fields of this message.
+    fn mut_unknown_fields(&mut self) -> &mut UnknownFields;
+
     /// Get type id for downcasting.
     fn type_id(&self) -> TypeId {
         TypeId::of::<Self>()
@@ -168,6 +200,10 @@ pub fn message_down_cast<'a, M : Message + 'a>(m: &'a Message) -> &'a M {
     m.as_any().downcast_ref::<M>().unwrap()
 }
 
+pub fn message_down_cast_mut<'a, M : Message + 'a>(m: &'a mut Message) -> &'a mut M {
+    m.as_any_mut().downcast_mut::<M>().unwrap()
+}
+
 
 /// Trait implemented by all protobuf enum types.
 pub trait ProtobufEnum: Eq + Sized + Copy + 'static {
@@ -207,6 +243,7 @@ pub fn parse_from<M : Message>(is: &mut CodedInputStream) -> ProtobufResult<M> {
 
 /// Parse message from reader.
 /// Parse stops on EOF or when error encountered.
+#[cfg(feature = "std")]
 pub fn parse_from_reader<M : Message>(reader: &mut Read) -> ProtobufResult<M> {
     reader.with_coded_input_stream(|is| parse_from::<M>(is))
 }
@@ -236,6 +273,7 @@ pub fn parse_length_delimited_from<M : Message>(
 }
 
 /// Parse length-delimited message from `Read`.
+#[cfg(feature = "std")]
 pub fn parse_length_delimited_from_reader<M : Message>(
     r: &mut Read,
 ) -> ProtobufResult<M> {
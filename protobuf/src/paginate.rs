@@ -1,3 +1,6 @@
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
 pub trait PaginatableIterator: Sized {
     fn paginate(self, page: usize) -> Paginate<Self>;
 }
@@ -21,12 +21,30 @@
 use protobuf::Message as Message_imported_for_functions;
 use protobuf::ProtobufEnum as ProtobufEnum_imported_for_functions;
 
+/// An encoded CodeGeneratorRequest is written to the plugin's stdin.
 #[derive(PartialEq,Clone,Default)]
 pub struct CodeGeneratorRequest {
     // message fields
+    /// The .proto files that were explicitly listed on the command-line.  The
+    /// code generator should generate code only for these files.  Each file's
+    /// descriptor will be included in proto_file, below.
     pub file_to_generate: ::std::vec::Vec<::std::string::String>,
+    /// The generator parameter passed on the command-line.
     pub parameter: ::protobuf::SingularField<::std::string::String>,
+    /// FileDescriptorProtos for all files in files_to_generate and everything
+    /// they import.  The files will appear in topological order, so each file
+    /// appears before any file that imports it.
+    ///
+    /// protoc guarantees that all proto_files will be written after
+    /// the fields above, even though this is not technically guaranteed by the
+    /// protobuf wire format.  This theoretically could allow a plugin to stream
+    /// in the FileDescriptorProtos and handle them one by one rather than read
+    /// the entire set into memory at once.  However, as of this writing, this
+    /// is not similarly optimized on protoc's end -- it will store all fields in
+    /// memory at once before sending them to the plugin.
     pub proto_file: ::std::vec::Vec<::protobuf::descriptor::FileDescriptorProto>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl CodeGeneratorRequest {
@@ -141,37 +159,43 @@ impl ::protobuf::Message for CodeGeneratorRequest {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_file_to_generate: ::std::vec::Vec<::std::string::String> = ::std::vec::Vec::new();
-        let mut _field_parameter: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_proto_file: ::std::vec::Vec<::protobuf::descriptor::FileDescriptorProto> = ::std::vec::Vec::new();
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = CodeGeneratorRequest::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_repeated_string_into(wire_type, _is, &mut _field_file_to_generate)?;
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.file_to_generate)?;
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_parameter = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.parameter = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 15 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_proto_file)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.proto_file)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(CodeGeneratorRequest {
-            file_to_generate: _field_file_to_generate,
-            parameter: _field_parameter.unwrap_or_default(),
-            proto_file: _field_proto_file,
-        })
+    fn merge(&mut self, other: &Self) {
+        self.file_to_generate.extend(other.file_to_generate.iter().cloned());
+        if other.parameter.is_some() {
+            self.parameter = other.parameter.clone();
+        }
+        self.proto_file.extend(other.proto_file.iter().cloned());
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -188,6 +212,7 @@ impl ::protobuf::Message for CodeGeneratorRequest {
             let len = value.compute_size();
             _size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -203,16 +228,25 @@ impl ::protobuf::Message for CodeGeneratorRequest {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         };
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -269,11 +303,22 @@ impl ::protobuf::reflect::ProtobufValue for CodeGeneratorRequest {
     }
 }
 
+/// The plugin writes an encoded CodeGeneratorResponse to stdout.
 #[derive(PartialEq,Clone,Default)]
 pub struct CodeGeneratorResponse {
     // message fields
+    /// Error message.  If non-empty, code generation failed.  The plugin process
+    /// should exit with status code zero even if it reports an error in this way.
+    ///
+    /// This should be used to indicate errors in .proto files which prevent the
+    /// code generator from generating correct code.  Errors which indicate a
+    /// problem in protoc itself -- such as the input CodeGeneratorRequest being
+    /// unparseable -- should be reported by writing a message to stderr and
+    /// exiting with a non-zero status code.
     pub error: ::protobuf::SingularField<::std::string::String>,
     pub file: ::std::vec::Vec<CodeGeneratorResponse_File>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl CodeGeneratorResponse {
@@ -363,32 +408,39 @@ impl ::protobuf::Message for CodeGeneratorResponse {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_error: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_file: ::std::vec::Vec<CodeGeneratorResponse_File> = ::std::vec::Vec::new();
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = CodeGeneratorResponse::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_error = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.error = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 15 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_file)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.file)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(CodeGeneratorResponse {
-            error: _field_error.unwrap_or_default(),
-            file: _field_file,
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.error.is_some() {
+            self.error = other.error.clone();
+        }
+        self.file.extend(other.file.iter().cloned());
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -402,6 +454,7 @@ impl ::protobuf::Message for CodeGeneratorResponse {
             let len = value.compute_size();
             _size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -414,16 +467,25 @@ impl ::protobuf::Message for CodeGeneratorResponse {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         };
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -475,12 +537,64 @@ impl ::protobuf::reflect::ProtobufValue for CodeGeneratorResponse {
     }
 }
 
+/// Represents a single generated file.
 #[derive(PartialEq,Clone,Default)]
 pub struct CodeGeneratorResponse_File {
     // message fields
+    /// The file name, relative to the output directory.  The name must not
+    /// contain "." or ".." components and must be relative, not be absolute (so,
+    /// the file cannot lie outside the output directory).  "/" must be used as
+    /// the path separator, not "\\".
+    ///
+    /// If the name is omitted, the content will be appended to the previous
+    /// file.  This allows the generator to break large files into small chunks,
+    /// and allows the generated text to be streamed back to protoc so that large
+    /// files need not reside completely in memory at one time.  Note that as of
+    /// this writing protoc does not optimize for this -- it will read the entire
+    /// CodeGeneratorResponse before writing files to disk.
     pub name: ::protobuf::SingularField<::std::string::String>,
+    /// If non-empty, indicates that the named file should already exist, and the
+    /// content here is to be inserted into that file at a defined insertion
+    /// point.  This feature allows a code generator to extend the output
+    /// produced by another code generator.  The original generator may provide
+    /// insertion points by placing special annotations in the file that look
+    /// like:
+    ///   @@protoc_insertion_point(NAME)
+    /// The annotation can have arbitrary text before and after it on the line,
+    /// which allows it to be placed in a comment.  NAME should be replaced with
+    /// an identifier naming the point -- this is what other generators will use
+    /// as the insertion_point.  Code inserted at this point will be placed
+    /// immediately above the line containing the insertion point (thus multiple
+    /// insertions to the same point will come out in the order they were added).
+    /// The double-@ is intended to make it unlikely that the generated code
+    /// could contain things that look like insertion points by accident.
+    ///
+    /// For example, the C++ code generator places the following line in the
+    /// .pb.h files that it generates:
+    ///   // @@protoc_insertion_point(namespace_scope)
+    /// This line appears within the scope of the file's package namespace, but
+    /// outside of any particular class.  Another plugin can then specify the
+    /// insertion_point "namespace_scope" to generate additional classes or
+    /// other declarations that should be placed in this scope.
+    ///
+    /// Note that if the line containing the insertion point begins with
+    /// whitespace, the same whitespace will be added to every line of the
+    /// inserted text.  This is useful for languages like Python, where
+    /// indentation matters.  In these languages, the insertion point comment
+    /// should be indented the same amount as any inserted code will need to be
+    /// in order to work correctly in that context.
+    ///
+    /// The code generator that generates the initial file and the one which
+    /// inserts into it must both run as part of a single invocation of protoc.
+    /// Code generators are executed in the order in which they appear on the
+    /// command line.
+    ///
+    /// If |insertion_point| is present, |name| must also be present.
     pub insertion_point: ::protobuf::SingularField<::std::string::String>,
+    /// The file contents.
     pub content: ::protobuf::SingularField<::std::string::String>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl CodeGeneratorResponse_File {
@@ -612,43 +726,53 @@ impl ::protobuf::Message for CodeGeneratorResponse_File {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_name: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_insertion_point: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
-        let mut _field_content: ::std::option::Option<::protobuf::SingularField<::std::string::String>> = None;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = CodeGeneratorResponse_File::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_name = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.name = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_insertion_point = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.insertion_point = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 15 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_content = ::std::option::Option::Some(::protobuf::SingularField::some(_is.read_string()?));
+                    self.content = ::protobuf::SingularField::some(is.read_string()?);
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(CodeGeneratorResponse_File {
-            name: _field_name.unwrap_or_default(),
-            insertion_point: _field_insertion_point.unwrap_or_default(),
-            content: _field_content.unwrap_or_default(),
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.name.is_some() {
+            self.name = other.name.clone();
+        }
+        if other.insertion_point.is_some() {
+            self.insertion_point = other.insertion_point.clone();
+        }
+        if other.content.is_some() {
+            self.content = other.content.clone();
+        }
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -664,6 +788,7 @@ impl ::protobuf::Message for CodeGeneratorResponse_File {
         if let Some(ref v) = self.content.as_ref() {
             _size += ::protobuf::rt::string_size(15, &v);
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -677,16 +802,25 @@ impl ::protobuf::Message for CodeGeneratorResponse_File {
         if let Some(ref v) = self.content.as_ref() {
             _os.write_string(15, &v)?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -0,0 +1,369 @@
+//! Decoding of messages without schema, similar to `protoc --decode_raw`.
+//!
+//! Wire format does not tell field types, so values are kept as they are encoded:
+//! varints, fixed values and length-delimited bytes.
+//! Length-delimited value is decoded as a nested message if it is a valid message.
+//!
+//! ```
+//! let m = protobuf::raw::decode(&[0x08, 0x96, 0x01]).unwrap();
+//! assert_eq!("1: 150\n", protobuf::raw::print_to_string(&m));
+//! ```
+
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+use std::fmt;
+use std::fmt::Write;
+
+use error::ProtobufError;
+use error::ProtobufResult;
+use error::WireError;
+use hex::encode_hex;
+use stream::CodedInputStream;
+use stream::wire_format;
+use stream::wire_format::WireType;
+use text_format::quote_escape_bytes;
+use unknown::UnknownValue;
+
+// same as default recursion limit of `CodedInputStream`
+const RECURSION_LIMIT: u32 = 100;
+
+const HEX_DUMP_BYTES_PER_LINE: usize = 16;
+
+/// Message decoded without schema.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct RawMessage {
+    /// Fields in the order they are encoded.
+    pub fields: Vec<RawField>,
+}
+
+/// Field of a message decoded without schema.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RawField {
+    pub number: u32,
+    pub wire_type: WireType,
+    /// Offset of the field tag in the decoded bytes.
+    pub offset: u64,
+    /// Offset of the field value, after the length for length-delimited fields.
+    pub value_offset: u64,
+    /// Offset of the end of the field, including end group tag for groups.
+    pub end: u64,
+    pub value: RawValue,
+}
+
+/// Value of the field decoded without schema.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum RawValue {
+    /// Varint, fixed or length-delimited value.
+    Value(UnknownValue),
+    /// Length-delimited value which is a valid message.
+    Message(RawMessage),
+    /// Group.
+    Group(RawMessage),
+}
+
+/// Decode bytes as a message without schema.
+pub fn decode(bytes: &[u8]) -> ProtobufResult<RawMessage> {
+    let mut is = CodedInputStream::from_bytes(bytes);
+    let fields = read_fields(&mut is, 0, 0, None)?;
+    Ok(RawMessage { fields: fields })
+}
+
+// read fields until EOF or until end of given group
+fn read_fields(
+    is: &mut CodedInputStream,
+    base: u64,
+    depth: u32,
+    group: Option<u32>,
+) -> ProtobufResult<Vec<RawField>> {
+    let mut fields = Vec::new();
+    loop {
+        if is.eof()? {
+            return match group {
+                Some(..) => Err(ProtobufError::WireError(WireError::UnexpectedEof)),
+                None => Ok(fields),
+            };
+        }
+
+        let offset = base + is.pos();
+        let (number, wire_type) = is.read_tag_unpack()?;
+        let value = match wire_type {
+            wire_format::WireTypeEndGroup => {
+                if group == Some(number) {
+                    return Ok(fields);
+                }
+                return Err(ProtobufError::WireError(WireError::UnexpectedWireType(wire_type)));
+            }
+            wire_format::WireTypeStartGroup => {
+                if depth >= RECURSION_LIMIT {
+                    return Err(ProtobufError::WireError(WireError::OverRecursionLimit));
+                }
+                let value_offset = base + is.pos();
+                let group_fields = read_fields(is, base, depth + 1, Some(number))?;
+                fields.push(RawField {
+                    number: number,
+                    wire_type: wire_type,
+                    offset: offset,
+                    value_offset: value_offset,
+                    end: base + is.pos(),
+                    value: RawValue::Group(RawMessage { fields: group_fields }),
+                });
+                continue;
+            }
+            wire_type => is.read_unknown(wire_type)?,
+        };
+
+        let end = base + is.pos();
+        let (value_offset, value) = match value {
+            UnknownValue::LengthDelimited(bytes) => {
+                let value_offset = end - bytes.len() as u64;
+                match decode_nested(&bytes, value_offset, depth + 1) {
+                    Some(m) => (value_offset, RawValue::Message(m)),
+                    None => (value_offset, RawValue::Value(UnknownValue::LengthDelimited(bytes))),
+                }
+            }
+            value => (end - value_size(&value), RawValue::Value(value)),
+        };
+
+        fields.push(RawField {
+            number: number,
+            wire_type: wire_type,
+            offset: offset,
+            value_offset: value_offset,
+            end: end,
+            value: value,
+        });
+    }
+}
+
+// size of encoded non-length-delimited value
+fn value_size(value: &UnknownValue) -> u64 {
+    match *value {
+        UnknownValue::Fixed32(..) => 4,
+        UnknownValue::Fixed64(..) => 8,
+        UnknownValue::Varint(v) => ::rt::compute_raw_varint64_size(v) as u64,
+        UnknownValue::LengthDelimited(..) => unreachable!(),
+    }
+}
+
+// `None` if bytes are not a valid non-empty message
+fn decode_nested(bytes: &[u8], base: u64, depth: u32) -> Option<RawMessage> {
+    if bytes.is_empty() || depth >= RECURSION_LIMIT {
+        return None;
+    }
+    let mut is = CodedInputStream::from_bytes(bytes);
+    match read_fields(&mut is, base, depth, None) {
+        Ok(fields) => Some(RawMessage { fields: fields }),
+        Err(..) => None,
+    }
+}
+
+fn print_value(value: &UnknownValue, buf: &mut String) {
+    match *value {
+        UnknownValue::Varint(v) => write!(buf, "{}", v).unwrap(),
+        UnknownValue::Fixed32(v) => write!(buf, "0x{:08x}", v).unwrap(),
+        UnknownValue::Fixed64(v) => write!(buf, "0x{:016x}", v).unwrap(),
+        UnknownValue::LengthDelimited(ref bytes) => buf.push_str(&quote_escape_bytes(bytes)),
+    }
+}
+
+fn do_indent(buf: &mut String, indent: usize) {
+    for _ in 0..indent {
+        buf.push_str("  ");
+    }
+}
+
+fn print_to_internal(m: &RawMessage, buf: &mut String, offsets: bool, indent: usize) {
+    for field in &m.fields {
+        do_indent(buf, indent);
+        write!(buf, "{}", field.number).unwrap();
+        match field.value {
+            RawValue::Value(ref value) => {
+                buf.push_str(": ");
+                print_value(value, buf);
+            }
+            RawValue::Message(ref m) | RawValue::Group(ref m) => {
+                buf.push_str(" {");
+                if offsets {
+                    write!(buf, "  # offset {}", field.offset).unwrap();
+                }
+                buf.push('\n');
+                print_to_internal(m, buf, offsets, indent + 1);
+                do_indent(buf, indent);
+                buf.push('}');
+                if offsets {
+                    write!(buf, "  # end {}", field.end).unwrap();
+                }
+                buf.push('\n');
+                continue;
+            }
+        }
+        if offsets {
+            write!(buf, "  # offset {}", field.offset).unwrap();
+        }
+        buf.push('\n');
+    }
+}
+
+/// Print decoded message in text format, with field numbers as names.
+pub fn print_to_string(m: &RawMessage) -> String {
+    let mut r = String::new();
+    print_to_internal(m, &mut r, false, 0);
+    r
+}
+
+/// Print decoded message in text format,
+/// annotating each field with a comment containing its offset.
+pub fn print_to_string_with_offsets(m: &RawMessage) -> String {
+    let mut r = String::new();
+    print_to_internal(m, &mut r, true, 0);
+    r
+}
+
+impl fmt::Display for RawMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&print_to_string(self))
+    }
+}
+
+// write bytes of `start..end` range, annotating the first line with given text
+fn hex_dump_lines(bytes: &[u8], start: u64, end: u64, indent: usize, text: &str, buf: &mut String) {
+    let data = &bytes[start as usize..end as usize];
+    let mut first = true;
+    let mut offset = start;
+    for chunk in data.chunks(HEX_DUMP_BYTES_PER_LINE) {
+        write!(buf, "{:04x}  {:<47}", offset, encode_hex(chunk)).unwrap();
+        if first {
+            buf.push_str("  ");
+            do_indent(buf, indent);
+            buf.push_str(text);
+            first = false;
+        }
+        buf.push('\n');
+        offset += chunk.len() as u64;
+    }
+    if first {
+        // no bytes
+        write!(buf, "{:4}  {:47}  ", "", "").unwrap();
+        do_indent(buf, indent);
+        buf.push_str(text);
+        buf.push('\n');
+    }
+}
+
+fn hex_dump_internal(bytes: &[u8], m: &RawMessage, buf: &mut String, indent: usize) {
+    for field in &m.fields {
+        match field.value {
+            RawValue::Value(ref value) => {
+                let mut text = format!("{}: ", field.number);
+                print_value(value, &mut text);
+                hex_dump_lines(bytes, field.offset, field.end, indent, &text, buf);
+            }
+            RawValue::Message(ref m) => {
+                let text = format!("{} {{", field.number);
+                hex_dump_lines(bytes, field.offset, field.value_offset, indent, &text, buf);
+                hex_dump_internal(bytes, m, buf, indent + 1);
+                hex_dump_lines(bytes, field.end, field.end, indent, "}", buf);
+            }
+            RawValue::Group(ref m) => {
+                let text = format!("{} {{", field.number);
+                hex_dump_lines(bytes, field.offset, field.value_offset, indent, &text, buf);
+                hex_dump_internal(bytes, m, buf, indent + 1);
+                let end_tag_offset = m.fields.last().map_or(field.value_offset, |f| f.end);
+                hex_dump_lines(bytes, end_tag_offset, field.end, indent, "}", buf);
+            }
+        }
+    }
+}
+
+/// Decode bytes without schema and print them as a hex dump
+/// annotated with decoded fields.
+///
+/// Each line contains offset, encoded bytes and decoded field in text format.
+pub fn hex_dump(bytes: &[u8]) -> ProtobufResult<String> {
+    let m = decode(bytes)?;
+    let mut r = String::new();
+    hex_dump_internal(bytes, &m, &mut r, 0);
+    Ok(r)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use hex::decode_hex;
+    use stream::wire_format;
+    use unknown::UnknownValue;
+
+    #[test]
+    fn test_decode_scalars() {
+        let m = decode(&decode_hex("08 96 01 15 01 00 00 00 19 02 00 00 00 00 00 00 00")).unwrap();
+        assert_eq!(3, m.fields.len());
+        assert_eq!(1, m.fields[0].number);
+        assert_eq!(wire_format::WireTypeVarint, m.fields[0].wire_type);
+        assert_eq!(RawValue::Value(UnknownValue::Varint(150)), m.fields[0].value);
+        assert_eq!((0, 1, 3), (m.fields[0].offset, m.fields[0].value_offset, m.fields[0].end));
+        assert_eq!(RawValue::Value(UnknownValue::Fixed32(1)), m.fields[1].value);
+        assert_eq!((3, 4, 8), (m.fields[1].offset, m.fields[1].value_offset, m.fields[1].end));
+        assert_eq!(RawValue::Value(UnknownValue::Fixed64(2)), m.fields[2].value);
+        assert_eq!(
+            "1: 150\n2: 0x00000001\n3: 0x0000000000000002\n",
+            print_to_string(&m)
+        );
+    }
+
+    #[test]
+    fn test_decode_string() {
+        // "ab" is not a valid message
+        let m = decode(&decode_hex("12 02 61 62")).unwrap();
+        assert_eq!(
+            RawValue::Value(UnknownValue::LengthDelimited(b"ab".to_vec())),
+            m.fields[0].value
+        );
+        assert_eq!(2, m.fields[0].value_offset);
+        assert_eq!("2: \"ab\"\n", print_to_string(&m));
+    }
+
+    #[test]
+    fn test_decode_nested() {
+        let m = decode(&decode_hex("1a 03 08 96 01 08 01")).unwrap();
+        assert_eq!("3 {\n  1: 150\n}\n1: 1\n", print_to_string(&m));
+        match m.fields[0].value {
+            RawValue::Message(ref nested) => {
+                // offsets are relative to the decoded bytes
+                assert_eq!(2, nested.fields[0].offset);
+                assert_eq!(5, nested.fields[0].end);
+            }
+            ref v => panic!("expecting message: {:?}", v),
+        }
+        assert_eq!(
+            "3 {  # offset 0\n  1: 150  # offset 2\n}  # end 5\n1: 1  # offset 5\n",
+            print_to_string_with_offsets(&m)
+        );
+    }
+
+    #[test]
+    fn test_decode_group() {
+        let m = decode(&decode_hex("0b 10 01 0c 18 02")).unwrap();
+        assert_eq!("1 {\n  2: 1\n}\n3: 2\n", print_to_string(&m));
+        assert_eq!(4, m.fields[0].end);
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        assert!(decode(&decode_hex("08")).is_err());
+        assert!(decode(&decode_hex("0b 10 01")).is_err());
+        assert!(decode(&decode_hex("0c")).is_err());
+    }
+
+    #[test]
+    fn test_hex_dump() {
+        let dump = hex_dump(&decode_hex("1a 03 08 96 01 12 02 61 62")).unwrap();
+        let expected = "\
+0000  1a 03                                            3 {
+0002  08 96 01                                           1: 150
+                                                       }
+0005  12 02 61 62                                      2: \"ab\"
+";
+        assert_eq!(expected, dump);
+    }
+}
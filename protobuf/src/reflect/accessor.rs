@@ -1,12 +1,23 @@
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+#[cfg(feature = "std")]
 use std::hash::Hash;
+#[cfg(feature = "std")]
 use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fmt;
 
-use core::Message;
-use core::ProtobufEnum;
-use core::message_down_cast;
+use message::Message;
+use message::ProtobufEnum;
+use enum_or_unknown::ProtobufEnumOrUnknown;
+use message::message_down_cast;
+use message::message_down_cast_mut;
 use reflect::EnumValueDescriptor;
+use reflect::MessageDescriptor;
+use reflect::EnumDescriptor;
 use types::*;
+use well_known_types_util::WrapperMessage;
 
 use singular::SingularField;
 use singular::SingularPtrField;
@@ -18,11 +29,12 @@ use super::repeated::ReflectRepeatedMessage;
 use super::optional::ReflectOptional;
 use super::value::ProtobufValue;
 use super::value::ProtobufValueRef;
+use super::value::ProtobufValueBox;
 use super::ReflectFieldRef;
 
 
 /// this trait should not be used directly, use `FieldDescriptor` instead
-pub trait FieldAccessor {
+pub trait FieldAccessor : Send + Sync {
     fn name_generic(&self) -> &'static str;
     fn has_field_generic(&self, m: &Message) -> bool;
     fn len_field_generic(&self, m: &Message) -> usize;
@@ -39,47 +51,81 @@ pub trait FieldAccessor {
     fn get_f64_generic(&self, m: &Message) -> f64;
 
     fn get_reflect<'a>(&self, m: &'a Message) -> ReflectFieldRef<'a>;
+
+    fn set_singular_field_generic(&self, m: &mut Message, value: ProtobufValueBox);
+    fn clear_field_generic(&self, m: &mut Message);
+    fn mut_message_generic<'a>(&self, m: &'a mut Message) -> &'a mut Message;
+    fn push_repeated_generic(&self, m: &mut Message, value: ProtobufValueBox);
+    fn insert_map_generic(&self, m: &mut Message, key: ProtobufValueBox, value: ProtobufValueBox);
+
+    fn message_descriptor_generic(&self) -> Option<&'static MessageDescriptor>;
+    fn enum_descriptor_generic(&self) -> Option<&'static EnumDescriptor>;
 }
 
 
-trait GetSingularMessage<M> {
+trait GetSetSingularMessage<M> : Send + Sync {
     fn get_message<'a>(&self, m: &'a M) -> &'a Message;
+    fn mut_message<'a>(&self, m: &'a mut M) -> &'a mut Message;
+    fn set_message(&self, m: &mut M, value: Box<Message>);
 }
 
-struct GetSingularMessageImpl<M, N> {
+struct GetSetSingularMessageImpl<M, N> {
     get: for<'a> fn(&'a M) -> &'a N,
+    mut_: for<'a> fn(&'a mut M) -> &'a mut N,
+    set: fn(&mut M, N),
 }
 
-impl<M : Message, N : Message + 'static> GetSingularMessage<M> for GetSingularMessageImpl<M, N> {
+impl<M : Message, N : Message + 'static> GetSetSingularMessage<M>
+    for GetSetSingularMessageImpl<M, N> {
     fn get_message<'a>(&self, m: &'a M) -> &'a Message {
         (self.get)(m)
     }
+
+    fn mut_message<'a>(&self, m: &'a mut M) -> &'a mut Message {
+        (self.mut_)(m)
+    }
+
+    fn set_message(&self, m: &mut M, value: Box<Message>) {
+        match value.into_any().downcast::<N>() {
+            Ok(v) => (self.set)(m, *v),
+            Err(..) => panic!("wrong message type"),
+        }
+    }
 }
 
 
-trait GetSingularEnum<M> {
+trait GetSetSingularEnum<M> : Send + Sync {
     fn get_enum(&self, m: &M) -> &'static EnumValueDescriptor;
+    fn set_enum(&self, m: &mut M, value: &'static EnumValueDescriptor);
 }
 
-struct GetSingularEnumImpl<M, E> {
+struct GetSetSingularEnumImpl<M, E> {
     get: fn(&M) -> E,
+    set: fn(&mut M, E),
 }
 
-impl<M : Message, E : ProtobufEnum> GetSingularEnum<M> for GetSingularEnumImpl<M, E> {
+impl<M : Message, E : ProtobufEnum> GetSetSingularEnum<M> for GetSetSingularEnumImpl<M, E> {
     fn get_enum(&self, m: &M) -> &'static EnumValueDescriptor {
         (self.get)(m).descriptor()
     }
+
+    fn set_enum(&self, m: &mut M, value: &'static EnumValueDescriptor) {
+        match ProtobufValueBox::Enum(value).into_enum::<E>() {
+            Some(v) => (self.set)(m, v),
+            None => panic!("wrong type"),
+        }
+    }
 }
 
 
-trait GetRepeatedMessage<M> {
+trait GetRepeatedMessage<M> : Send + Sync {
     fn len_field(&self, m: &M) -> usize;
     fn get_message_item<'a>(&self, m: &'a M, index: usize) -> &'a Message;
     fn reflect_repeated_message<'a>(&self, m: &'a M) -> Box<ReflectRepeatedMessage<'a> + 'a>;
 }
 
 
-trait GetRepeatedEnum<M : Message + 'static> {
+trait GetRepeatedEnum<M : Message + 'static> : Send + Sync {
     fn len_field(&self, m: &M) -> usize;
     fn get_enum_item(&self, m: &M, index: usize) -> &'static EnumValueDescriptor;
     fn reflect_repeated_enum<'a>(&self, m: &'a M) -> Box<ReflectRepeatedEnum<'a> + 'a>;
@@ -87,19 +133,27 @@ trait GetRepeatedEnum<M : Message + 'static> {
 
 
 
-trait GetSetCopyFns<M> {
+trait GetSetCopyFns<M> : Send + Sync {
     fn get_field<'a>(&self, m: &'a M) -> ProtobufValueRef<'a>;
+    fn set_field(&self, m: &mut M, value: ProtobufValueBox);
 }
 
 struct GetSetCopyFnsImpl<M, V : ProtobufValue + Copy> {
     get: fn(&M) -> V,
-    _set: fn(&mut M, V),
+    set: fn(&mut M, V),
 }
 
 impl<M, V : ProtobufValue + Copy> GetSetCopyFns<M> for GetSetCopyFnsImpl<M, V> {
     fn get_field<'a>(&self, m: &'a M) -> ProtobufValueRef<'a> {
         (&(self.get)(m) as &ProtobufValue).as_ref_copy()
     }
+
+    fn set_field(&self, m: &mut M, value: ProtobufValueBox) {
+        match V::from_value_box(value) {
+            Some(v) => (self.set)(m, v),
+            None => panic!("wrong type"),
+        }
+    }
 }
 
 
@@ -107,8 +161,8 @@ enum SingularGetSet<M> {
     Copy(Box<GetSetCopyFns<M>>),
     String(for<'a> fn(&'a M) -> &'a str, fn(&mut M, String)),
     Bytes(for<'a> fn(&'a M) -> &'a [u8], fn(&mut M, Vec<u8>)),
-    Enum(Box<GetSingularEnum<M> + 'static>),
-    Message(Box<GetSingularMessage<M> + 'static>),
+    Enum(Box<GetSetSingularEnum<M> + 'static>),
+    Message(Box<GetSetSingularMessage<M> + 'static>),
 }
 
 impl<M : Message + 'static> SingularGetSet<M> {
@@ -121,9 +175,22 @@ impl<M : Message + 'static> SingularGetSet<M> {
             &SingularGetSet::Message(ref get) => ProtobufValueRef::Message(get.get_message(m)),
         }
     }
+
+    fn set_value(&self, m: &mut M, value: ProtobufValueBox) {
+        match (self, value) {
+            (&SingularGetSet::Copy(ref copy), value) => copy.set_field(m, value),
+            (&SingularGetSet::String(_, set), ProtobufValueBox::String(v)) => set(m, v),
+            (&SingularGetSet::Bytes(_, set), ProtobufValueBox::Bytes(v)) => set(m, v),
+            (&SingularGetSet::Enum(ref e), ProtobufValueBox::Enum(v)) => e.set_enum(m, v),
+            (&SingularGetSet::Message(ref a), ProtobufValueBox::Message(v)) => {
+                a.set_message(m, v)
+            }
+            _ => panic!("wrong type"),
+        }
+    }
 }
 
-trait FieldAccessor2<M, R : ?Sized>
+trait FieldAccessor2<M, R : ?Sized> : Send + Sync
 where
     M : Message + 'static,
 {
@@ -131,6 +198,15 @@ where
     fn mut_field<'a>(&self, &'a mut M) -> &'a mut R;
 }
 
+// protobuf 3 simple field is never unset, clear resets it to default value
+trait SimpleFieldAccessor2<M> : FieldAccessor2<M, ProtobufValue>
+where
+    M : Message + 'static,
+{
+    fn set_field(&self, m: &mut M, value: ProtobufValueBox);
+    fn clear_field(&self, m: &mut M);
+}
+
 struct MessageGetMut<M, L>
 where
     M : Message + 'static,
@@ -145,15 +221,18 @@ enum FieldAccessorFunctions<M> {
     SingularHasGetSet {
         has: fn(&M) -> bool,
         get_set: SingularGetSet<M>,
+        clear: fn(&mut M),
     },
     // protobuf 3 simple field
-    Simple(Box<FieldAccessor2<M, ProtobufValue>>),
+    Simple(Box<SimpleFieldAccessor2<M>>),
     // optional, required or message
     Optional(Box<FieldAccessor2<M, ReflectOptional>>),
     // repeated
     Repeated(Box<FieldAccessor2<M, ReflectRepeated>>),
     // protobuf 3 map
     Map(Box<FieldAccessor2<M, ReflectMap>>),
+    // wrapper well-known type stored as `Option` of the wrapped value
+    Wrapper(Box<WrapperFieldAccessor<M>>),
 }
 
 impl<M> fmt::Debug for FieldAccessorFunctions<M> {
@@ -169,6 +248,8 @@ impl<M> fmt::Debug for FieldAccessorFunctions<M> {
                 write!(f, "Repeated(..)"),
             &FieldAccessorFunctions::Map(..) =>
                 write!(f, "Map(..)"),
+            &FieldAccessorFunctions::Wrapper(..) =>
+                write!(f, "Wrapper(..)"),
         }
     }
 }
@@ -177,6 +258,9 @@ impl<M> fmt::Debug for FieldAccessorFunctions<M> {
 struct FieldAccessorImpl<M> {
     name: &'static str,
     fns: FieldAccessorFunctions<M>,
+    // element type descriptors (map value type for maps)
+    message_descriptor: fn() -> Option<&'static MessageDescriptor>,
+    enum_descriptor: fn() -> Option<&'static EnumDescriptor>,
 }
 
 impl<M : Message> FieldAccessorImpl<M> {
@@ -188,9 +272,11 @@ impl<M : Message> FieldAccessorImpl<M> {
             FieldAccessorFunctions::Optional(ref a) => {
                 a.get_field(m).to_option().map(|v| v.as_ref())
             }
+            FieldAccessorFunctions::Wrapper(..) => panic!("wrapper"),
             FieldAccessorFunctions::SingularHasGetSet {
                 ref has,
                 ref get_set,
+                ..
             } => {
                 if !has(m) {
                     None
@@ -216,6 +302,7 @@ impl<M : Message + 'static> FieldAccessor for FieldAccessorImpl<M> {
             FieldAccessorFunctions::Simple(ref a) => {
                 a.get_field(message_down_cast(m)).is_non_zero()
             }
+            FieldAccessorFunctions::Wrapper(ref a) => a.has_wrapper(message_down_cast(m)),
             _ => panic!(),
         }
     }
@@ -242,7 +329,11 @@ impl<M : Message + 'static> FieldAccessor for FieldAccessorImpl<M> {
             FieldAccessorFunctions::SingularHasGetSet {
                 get_set: SingularGetSet::Enum(ref get), ..
             } => get.get_enum(message_down_cast(m)),
-            _ => panic!(),
+            _ => match self.get_value_option(message_down_cast(m)) {
+                Some(ProtobufValueRef::Enum(v)) => v,
+                Some(ProtobufValueRef::I32(v)) => panic!("unknown enum value: {}", v),
+                _ => panic!(),
+            },
         }
     }
 
@@ -334,6 +425,9 @@ impl<M : Message + 'static> FieldAccessor for FieldAccessorImpl<M> {
                         .map(|v| v.as_ref()),
                 )
             }
+            FieldAccessorFunctions::Wrapper(ref a) => {
+                ReflectFieldRef::Wrapper(a.get_wrapper(message_down_cast(m)))
+            }
             FieldAccessorFunctions::Simple(ref accessor2) => {
                 ReflectFieldRef::Optional({
                     let v = accessor2.get_field(message_down_cast(m));
@@ -347,6 +441,7 @@ impl<M : Message + 'static> FieldAccessor for FieldAccessorImpl<M> {
             FieldAccessorFunctions::SingularHasGetSet {
                 ref has,
                 ref get_set,
+                ..
             } => {
                 ReflectFieldRef::Optional(if has(message_down_cast(m)) {
                     Some(get_set.get_ref(message_down_cast(m)))
@@ -356,22 +451,123 @@ impl<M : Message + 'static> FieldAccessor for FieldAccessorImpl<M> {
             }
         }
     }
+
+    fn set_singular_field_generic(&self, m: &mut Message, value: ProtobufValueBox) {
+        match self.fns {
+            FieldAccessorFunctions::SingularHasGetSet { ref get_set, .. } => {
+                get_set.set_value(message_down_cast_mut(m), value)
+            }
+            FieldAccessorFunctions::Simple(ref a) => a.set_field(message_down_cast_mut(m), value),
+            FieldAccessorFunctions::Optional(ref a) => {
+                a.mut_field(message_down_cast_mut(m)).set_value_box(value)
+            }
+            FieldAccessorFunctions::Wrapper(ref a) => match value {
+                ProtobufValueBox::Message(v) => a.set_wrapper(message_down_cast_mut(m), v),
+                _ => panic!("wrong type"),
+            },
+            FieldAccessorFunctions::Repeated(..) |
+            FieldAccessorFunctions::Map(..) => panic!("not a singular field"),
+        }
+    }
+
+    fn clear_field_generic(&self, m: &mut Message) {
+        match self.fns {
+            FieldAccessorFunctions::SingularHasGetSet { clear, .. } => {
+                clear(message_down_cast_mut(m))
+            }
+            FieldAccessorFunctions::Simple(ref a) => a.clear_field(message_down_cast_mut(m)),
+            FieldAccessorFunctions::Optional(ref a) => {
+                a.mut_field(message_down_cast_mut(m)).clear()
+            }
+            FieldAccessorFunctions::Repeated(ref a) => {
+                a.mut_field(message_down_cast_mut(m)).clear()
+            }
+            FieldAccessorFunctions::Map(ref a) => a.mut_field(message_down_cast_mut(m)).clear(),
+            FieldAccessorFunctions::Wrapper(ref a) => a.clear_wrapper(message_down_cast_mut(m)),
+        }
+    }
+
+    fn mut_message_generic<'a>(&self, m: &'a mut Message) -> &'a mut Message {
+        match self.fns {
+            FieldAccessorFunctions::SingularHasGetSet {
+                get_set: SingularGetSet::Message(ref a), ..
+            } => a.mut_message(message_down_cast_mut(m)),
+            FieldAccessorFunctions::Optional(ref a) => {
+                let descriptor = match (self.message_descriptor)() {
+                    Some(d) => d,
+                    None => panic!("not a message field"),
+                };
+                let field = a.mut_field(message_down_cast_mut(m));
+                if field.to_option().is_none() {
+                    field.set_value_box(ProtobufValueBox::Message(descriptor.new_instance()));
+                }
+                descriptor.downcast_mut(field.mut_option_any().unwrap())
+            }
+            FieldAccessorFunctions::Wrapper(..) => {
+                panic!("wrapper field stored as Option cannot be modified in place, \
+                        use modify_message")
+            }
+            ref fns => panic!("unknown accessor type: {:?}", fns),
+        }
+    }
+
+    fn push_repeated_generic(&self, m: &mut Message, value: ProtobufValueBox) {
+        match self.fns {
+            FieldAccessorFunctions::Repeated(ref a) => {
+                a.mut_field(message_down_cast_mut(m)).push(value)
+            }
+            _ => panic!("not repeated"),
+        }
+    }
+
+    fn insert_map_generic(&self, m: &mut Message, key: ProtobufValueBox, value: ProtobufValueBox) {
+        match self.fns {
+            FieldAccessorFunctions::Map(ref a) => {
+                a.mut_field(message_down_cast_mut(m)).insert(key, value)
+            }
+            _ => panic!("not a map"),
+        }
+    }
+
+    fn message_descriptor_generic(&self) -> Option<&'static MessageDescriptor> {
+        (self.message_descriptor)()
+    }
+
+    fn enum_descriptor_generic(&self) -> Option<&'static EnumDescriptor> {
+        (self.enum_descriptor)()
+    }
 }
 
 
 // singular
 
-fn set_panic<A, B>(_: &mut A, _: B) {
-    panic!()
+fn no_message_descriptor() -> Option<&'static MessageDescriptor> {
+    None
+}
+
+fn no_enum_descriptor() -> Option<&'static EnumDescriptor> {
+    None
+}
+
+fn message_descriptor_of<F : Message>() -> Option<&'static MessageDescriptor> {
+    Some(F::descriptor_static(None))
+}
+
+fn enum_descriptor_of<E : ProtobufEnum>() -> Option<&'static EnumDescriptor> {
+    Some(E::enum_descriptor_static(None))
 }
 
 // TODO: make_singular_xxx_accessor are used only for oneof fields
 // oneof codegen should be changed
 
+// `clear` is expected to do nothing if the field is not set
+
 pub fn make_singular_u32_accessor<M : Message + 'static>(
     name: &'static str,
     has: fn(&M) -> bool,
     get: fn(&M) -> u32,
+    set: fn(&mut M, u32),
+    clear: fn(&mut M),
 ) -> Box<FieldAccessor + 'static> {
     Box::new(FieldAccessorImpl {
         name: name,
@@ -379,9 +575,12 @@ pub fn make_singular_u32_accessor<M : Message + 'static>(
             has: has,
             get_set: SingularGetSet::Copy(Box::new(GetSetCopyFnsImpl {
                 get: get,
-                _set: set_panic,
+                set: set,
             })),
+            clear: clear,
         },
+        message_descriptor: no_message_descriptor,
+        enum_descriptor: no_enum_descriptor,
     })
 }
 
@@ -389,6 +588,8 @@ pub fn make_singular_i32_accessor<M : Message + 'static>(
     name: &'static str,
     has: fn(&M) -> bool,
     get: fn(&M) -> i32,
+    set: fn(&mut M, i32),
+    clear: fn(&mut M),
 ) -> Box<FieldAccessor + 'static> {
     Box::new(FieldAccessorImpl {
         name: name,
@@ -396,9 +597,12 @@ pub fn make_singular_i32_accessor<M : Message + 'static>(
             has: has,
             get_set: SingularGetSet::Copy(Box::new(GetSetCopyFnsImpl {
                 get: get,
-                _set: set_panic,
+                set: set,
             })),
+            clear: clear,
         },
+        message_descriptor: no_message_descriptor,
+        enum_descriptor: no_enum_descriptor,
     })
 }
 
@@ -406,6 +610,8 @@ pub fn make_singular_u64_accessor<M : Message + 'static>(
     name: &'static str,
     has: fn(&M) -> bool,
     get: fn(&M) -> u64,
+    set: fn(&mut M, u64),
+    clear: fn(&mut M),
 ) -> Box<FieldAccessor + 'static> {
     Box::new(FieldAccessorImpl {
         name: name,
@@ -413,9 +619,12 @@ pub fn make_singular_u64_accessor<M : Message + 'static>(
             has: has,
             get_set: SingularGetSet::Copy(Box::new(GetSetCopyFnsImpl {
                 get: get,
-                _set: set_panic,
+                set: set,
             })),
+            clear: clear,
         },
+        message_descriptor: no_message_descriptor,
+        enum_descriptor: no_enum_descriptor,
     })
 }
 
@@ -423,6 +632,8 @@ pub fn make_singular_i64_accessor<M : Message + 'static>(
     name: &'static str,
     has: fn(&M) -> bool,
     get: fn(&M) -> i64,
+    set: fn(&mut M, i64),
+    clear: fn(&mut M),
 ) -> Box<FieldAccessor + 'static> {
     Box::new(FieldAccessorImpl {
         name: name,
@@ -430,9 +641,12 @@ pub fn make_singular_i64_accessor<M : Message + 'static>(
             has: has,
             get_set: SingularGetSet::Copy(Box::new(GetSetCopyFnsImpl {
                 get: get,
-                _set: set_panic,
+                set: set,
             })),
+            clear: clear,
         },
+        message_descriptor: no_message_descriptor,
+        enum_descriptor: no_enum_descriptor,
     })
 }
 
@@ -440,6 +654,8 @@ pub fn make_singular_f32_accessor<M : Message + 'static>(
     name: &'static str,
     has: fn(&M) -> bool,
     get: fn(&M) -> f32,
+    set: fn(&mut M, f32),
+    clear: fn(&mut M),
 ) -> Box<FieldAccessor + 'static> {
     Box::new(FieldAccessorImpl {
         name: name,
@@ -447,9 +663,12 @@ pub fn make_singular_f32_accessor<M : Message + 'static>(
             has: has,
             get_set: SingularGetSet::Copy(Box::new(GetSetCopyFnsImpl {
                 get: get,
-                _set: set_panic,
+                set: set,
             })),
+            clear: clear,
         },
+        message_descriptor: no_message_descriptor,
+        enum_descriptor: no_enum_descriptor,
     })
 }
 
@@ -457,6 +676,8 @@ pub fn make_singular_f64_accessor<M : Message + 'static>(
     name: &'static str,
     has: fn(&M) -> bool,
     get: fn(&M) -> f64,
+    set: fn(&mut M, f64),
+    clear: fn(&mut M),
 ) -> Box<FieldAccessor + 'static> {
     Box::new(FieldAccessorImpl {
         name: name,
@@ -464,9 +685,12 @@ pub fn make_singular_f64_accessor<M : Message + 'static>(
             has: has,
             get_set: SingularGetSet::Copy(Box::new(GetSetCopyFnsImpl {
                 get: get,
-                _set: set_panic,
+                set: set,
             })),
+            clear: clear,
         },
+        message_descriptor: no_message_descriptor,
+        enum_descriptor: no_enum_descriptor,
     })
 }
 
@@ -474,6 +698,8 @@ pub fn make_singular_bool_accessor<M : Message + 'static>(
     name: &'static str,
     has: fn(&M) -> bool,
     get: fn(&M) -> bool,
+    set: fn(&mut M, bool),
+    clear: fn(&mut M),
 ) -> Box<FieldAccessor + 'static> {
     Box::new(FieldAccessorImpl {
         name: name,
@@ -481,9 +707,12 @@ pub fn make_singular_bool_accessor<M : Message + 'static>(
             has: has,
             get_set: SingularGetSet::Copy(Box::new(GetSetCopyFnsImpl {
                 get: get,
-                _set: set_panic,
+                set: set,
             })),
+            clear: clear,
         },
+        message_descriptor: no_message_descriptor,
+        enum_descriptor: no_enum_descriptor,
     })
 }
 
@@ -491,13 +720,46 @@ pub fn make_singular_enum_accessor<M : Message + 'static, E : ProtobufEnum + 'st
     name: &'static str,
     has: fn(&M) -> bool,
     get: fn(&M) -> E,
+    set: fn(&mut M, E),
+    clear: fn(&mut M),
 ) -> Box<FieldAccessor + 'static> {
     Box::new(FieldAccessorImpl {
         name: name,
         fns: FieldAccessorFunctions::SingularHasGetSet {
             has: has,
-            get_set: SingularGetSet::Enum(Box::new(GetSingularEnumImpl { get: get })),
+            get_set: SingularGetSet::Enum(Box::new(GetSetSingularEnumImpl {
+                get: get,
+                set: set,
+            })),
+            clear: clear,
         },
+        message_descriptor: no_message_descriptor,
+        enum_descriptor: enum_descriptor_of::<E>,
+    })
+}
+
+pub fn make_singular_enum_or_unknown_accessor<
+    M : Message + 'static,
+    E : ProtobufEnum + 'static,
+>(
+    name: &'static str,
+    has: fn(&M) -> bool,
+    get: fn(&M) -> ProtobufEnumOrUnknown<E>,
+    set: fn(&mut M, ProtobufEnumOrUnknown<E>),
+    clear: fn(&mut M),
+) -> Box<FieldAccessor + 'static> {
+    Box::new(FieldAccessorImpl {
+        name: name,
+        fns: FieldAccessorFunctions::SingularHasGetSet {
+            has: has,
+            get_set: SingularGetSet::Copy(Box::new(GetSetCopyFnsImpl {
+                get: get,
+                set: set,
+            })),
+            clear: clear,
+        },
+        message_descriptor: no_message_descriptor,
+        enum_descriptor: enum_descriptor_of::<E>,
     })
 }
 
@@ -505,13 +767,18 @@ pub fn make_singular_string_accessor<M : Message + 'static>(
     name: &'static str,
     has: fn(&M) -> bool,
     get: for<'a> fn(&'a M) -> &'a str,
+    set: fn(&mut M, String),
+    clear: fn(&mut M),
 ) -> Box<FieldAccessor + 'static> {
     Box::new(FieldAccessorImpl {
         name: name,
         fns: FieldAccessorFunctions::SingularHasGetSet {
             has: has,
-            get_set: SingularGetSet::String(get, set_panic),
+            get_set: SingularGetSet::String(get, set),
+            clear: clear,
         },
+        message_descriptor: no_message_descriptor,
+        enum_descriptor: no_enum_descriptor,
     })
 }
 
@@ -519,13 +786,18 @@ pub fn make_singular_bytes_accessor<M : Message + 'static>(
     name: &'static str,
     has: fn(&M) -> bool,
     get: for<'a> fn(&'a M) -> &'a [u8],
+    set: fn(&mut M, Vec<u8>),
+    clear: fn(&mut M),
 ) -> Box<FieldAccessor + 'static> {
     Box::new(FieldAccessorImpl {
         name: name,
         fns: FieldAccessorFunctions::SingularHasGetSet {
             has: has,
-            get_set: SingularGetSet::Bytes(get, set_panic),
+            get_set: SingularGetSet::Bytes(get, set),
+            clear: clear,
         },
+        message_descriptor: no_message_descriptor,
+        enum_descriptor: no_enum_descriptor,
     })
 }
 
@@ -533,13 +805,23 @@ pub fn make_singular_message_accessor<M : Message + 'static, F : Message + 'stat
     name: &'static str,
     has: fn(&M) -> bool,
     get: for<'a> fn(&'a M) -> &'a F,
+    set: fn(&mut M, F),
+    mut_: for<'a> fn(&'a mut M) -> &'a mut F,
+    clear: fn(&mut M),
 ) -> Box<FieldAccessor + 'static> {
     Box::new(FieldAccessorImpl {
         name: name,
         fns: FieldAccessorFunctions::SingularHasGetSet {
             has: has,
-            get_set: SingularGetSet::Message(Box::new(GetSingularMessageImpl { get: get })),
+            get_set: SingularGetSet::Message(Box::new(GetSetSingularMessageImpl {
+                get: get,
+                mut_: mut_,
+                set: set,
+            })),
+            clear: clear,
         },
+        message_descriptor: message_descriptor_of::<F>,
+        enum_descriptor: no_enum_descriptor,
     })
 }
 
@@ -577,6 +859,8 @@ where
             get_field: get_vec,
             mut_field: mut_vec,
         })),
+        message_descriptor: V::message_descriptor,
+        enum_descriptor: V::enum_descriptor,
     })
 }
 
@@ -599,6 +883,8 @@ where
                 mut_field: mut_vec,
             }),
         ),
+        message_descriptor: V::message_descriptor,
+        enum_descriptor: V::enum_descriptor,
     })
 }
 
@@ -631,6 +917,8 @@ where
             get_field: get_field,
             mut_field: mut_field,
         })),
+        message_descriptor: V::message_descriptor,
+        enum_descriptor: V::enum_descriptor,
     })
 }
 
@@ -667,6 +955,8 @@ where
                 mut_field: mut_field,
             }),
         ),
+        message_descriptor: V::message_descriptor,
+        enum_descriptor: V::enum_descriptor,
     })
 }
 
@@ -703,6 +993,8 @@ where
                 mut_field: mut_field,
             }),
         ),
+        message_descriptor: V::message_descriptor,
+        enum_descriptor: V::enum_descriptor,
     })
 }
 
@@ -720,6 +1012,23 @@ where
     }
 }
 
+impl<M, V> SimpleFieldAccessor2<M> for MessageGetMut<M, V>
+where
+    M : Message + 'static,
+    V : ProtobufValue + Clone + Default + 'static,
+{
+    fn set_field(&self, m: &mut M, value: ProtobufValueBox) {
+        match V::from_value_box(value) {
+            Some(v) => *(self.mut_field)(m) = v,
+            None => panic!("wrong type"),
+        }
+    }
+
+    fn clear_field(&self, m: &mut M) {
+        *(self.mut_field)(m) = V::default();
+    }
+}
+
 pub fn make_simple_field_accessor<M, V>(
     name: &'static str,
     get_field: for<'a> fn(&'a M) -> &'a V::Value,
@@ -728,6 +1037,7 @@ pub fn make_simple_field_accessor<M, V>(
 where
     M : Message + 'static,
     V : ProtobufType + 'static,
+    <V as ProtobufType>::Value : Default,
 {
     Box::new(FieldAccessorImpl {
         name: name,
@@ -735,10 +1045,77 @@ where
             get_field: get_field,
             mut_field: mut_field,
         })),
+        message_descriptor: V::message_descriptor,
+        enum_descriptor: V::enum_descriptor,
     })
 }
 
 
+// wrapper well-known type field stored as `Option` of the wrapped value,
+// wrapper message is constructed on access
+trait WrapperFieldAccessor<M> : Send + Sync {
+    fn has_wrapper(&self, m: &M) -> bool;
+    fn get_wrapper(&self, m: &M) -> Option<Box<Message>>;
+    fn set_wrapper(&self, m: &mut M, value: Box<Message>);
+    fn clear_wrapper(&self, m: &mut M);
+}
+
+struct WrapperGetMut<M, W : WrapperMessage> {
+    get_field: for<'a> fn(&'a M) -> &'a Option<<W::ValueType as ProtobufType>::Value>,
+    mut_field: for<'a> fn(&'a mut M) -> &'a mut Option<<W::ValueType as ProtobufType>::Value>,
+}
+
+impl<M, W> WrapperFieldAccessor<M> for WrapperGetMut<M, W>
+where
+    M : Message + 'static,
+    W : WrapperMessage,
+{
+    fn has_wrapper(&self, m: &M) -> bool {
+        (self.get_field)(m).is_some()
+    }
+
+    fn get_wrapper(&self, m: &M) -> Option<Box<Message>> {
+        (self.get_field)(m)
+            .as_ref()
+            .map(|v| Box::new(W::from_value(v.clone())) as Box<Message>)
+    }
+
+    fn set_wrapper(&self, m: &mut M, value: Box<Message>) {
+        match value.into_any().downcast::<W>() {
+            Ok(v) => *(self.mut_field)(m) = Some(v.into_value()),
+            Err(..) => panic!("wrong message type"),
+        }
+    }
+
+    fn clear_wrapper(&self, m: &mut M) {
+        *(self.mut_field)(m) = None;
+    }
+}
+
+pub fn make_option_wrapper_accessor<M, W>(
+    name: &'static str,
+    get_field: for<'a> fn(&'a M) -> &'a Option<<W::ValueType as ProtobufType>::Value>,
+    mut_field: for<'a> fn(&'a mut M)
+        -> &'a mut Option<<W::ValueType as ProtobufType>::Value>,
+) -> Box<FieldAccessor + 'static>
+where
+    M : Message + 'static,
+    W : WrapperMessage,
+{
+    Box::new(FieldAccessorImpl {
+        name: name,
+        fns: FieldAccessorFunctions::Wrapper(Box::new(WrapperGetMut::<M, W> {
+            get_field: get_field,
+            mut_field: mut_field,
+        })),
+        message_descriptor: message_descriptor_of::<W>,
+        enum_descriptor: no_enum_descriptor,
+    })
+}
+
+
+// `HashMap` is `BTreeMap` without `std`, so it is covered by the `BTreeMap` impl
+#[cfg(feature = "std")]
 impl<M, K, V> FieldAccessor2<M, ReflectMap> for MessageGetMut<M, HashMap<K, V>>
 where
     M : Message + 'static,
@@ -755,7 +1132,24 @@ where
     }
 }
 
+impl<M, K, V> FieldAccessor2<M, ReflectMap> for MessageGetMut<M, BTreeMap<K, V>>
+where
+    M : Message + 'static,
+    K : ProtobufValue + 'static,
+    V : ProtobufValue + 'static,
+    K : Ord,
+{
+    fn get_field<'a>(&self, m: &'a M) -> &'a ReflectMap {
+        (self.get_field)(m) as &ReflectMap
+    }
+
+    fn mut_field<'a>(&self, m: &'a mut M) -> &'a mut ReflectMap {
+        (self.mut_field)(m) as &mut ReflectMap
+    }
+}
+
 
+#[cfg(feature = "std")]
 pub fn make_map_accessor<M, K, V>(
     name: &'static str,
     get_field: for<'a> fn(&'a M)
@@ -777,5 +1171,38 @@ where
                 mut_field: mut_field,
             }),
         ),
+        message_descriptor: V::message_descriptor,
+        enum_descriptor: V::enum_descriptor,
     })
 }
+
+/// Accessor of `map` field stored in `BTreeMap`
+pub fn make_btree_map_accessor<M, K, V>(
+    name: &'static str,
+    get_field: for<'a> fn(&'a M)
+        -> &'a BTreeMap<K::Value, V::Value>,
+    mut_field: for<'a> fn(&'a mut M)
+        -> &'a mut BTreeMap<K::Value, V::Value>,
+) -> Box<FieldAccessor + 'static>
+where
+    M : Message + 'static,
+    K : ProtobufType + 'static,
+    V : ProtobufType + 'static,
+    <K as ProtobufType>::Value : Ord,
+{
+    Box::new(FieldAccessorImpl {
+        name: name,
+        fns: FieldAccessorFunctions::Map(
+            Box::new(MessageGetMut::<M, BTreeMap<K::Value, V::Value>> {
+                get_field: get_field,
+                mut_field: mut_field,
+            }),
+        ),
+        message_descriptor: V::message_descriptor,
+        enum_descriptor: V::enum_descriptor,
+    })
+}
+
+// `HashMap` is `BTreeMap` without `std`
+#[cfg(not(feature = "std"))]
+pub use self::make_btree_map_accessor as make_map_accessor;
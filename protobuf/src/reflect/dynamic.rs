@@ -0,0 +1,1471 @@
+//! Messages which types are loaded at runtime.
+//!
+//! `DescriptorPool` builds message descriptors from `FileDescriptorProto`s
+//! (for example, from `FileDescriptorSet` generated by `protoc --descriptor_set_out`),
+//! and `DynamicMessage` is a `Message` implementation backed by such descriptor.
+//! Dynamic messages can be serialized, parsed, accessed with reflection
+//! and printed in text format or JSON like generated messages.
+
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::collections::HashSet;
+#[cfg(feature = "std")]
+use std::error::Error;
+use std::fmt;
+use std::mem;
+use std::ptr;
+
+use message::Message;
+use message::message_down_cast;
+use message::message_down_cast_mut;
+use descriptor::DescriptorProto;
+use descriptor::EnumDescriptorProto;
+use descriptor::FieldDescriptorProto;
+use descriptor::FieldDescriptorProto_Label;
+use descriptor::FieldDescriptorProto_Type;
+use descriptor::FileDescriptorProto;
+use descriptor::FileDescriptorSet;
+use descriptorx::FileScope;
+use descriptorx::WithScope;
+use error::ProtobufError;
+use error::ProtobufResult;
+use error::WireError;
+use rt;
+use rt::ProtobufVarint;
+use rt::ProtobufVarintZigzag;
+use stream::CodedInputStream;
+use stream::CodedOutputStream;
+use stream::wire_format;
+use stream::with_coded_output_stream_to_bytes;
+use text_format;
+use unknown::UnknownFields;
+
+use super::accessor::FieldAccessor;
+use super::EnumDescriptor;
+use super::EnumValueDescriptor;
+use super::MessageDescriptor;
+use super::MessageFactory;
+use super::ProtobufValue;
+use super::ProtobufValueBox;
+use super::ProtobufValueRef;
+use super::ReflectFieldRef;
+
+
+/// Descriptor set cannot be loaded, e. g. field type is not found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescriptorPoolError {
+    pub message: String,
+}
+
+impl DescriptorPoolError {
+    fn new<S : Into<String>>(message: S) -> DescriptorPoolError {
+        DescriptorPoolError { message: message.into() }
+    }
+}
+
+impl fmt::Display for DescriptorPoolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for DescriptorPoolError {
+    fn description(&self) -> &str {
+        &self.message
+    }
+}
+
+pub type DescriptorPoolResult<T> = Result<T, DescriptorPoolError>;
+
+
+/// Message and enum types loaded from file descriptors.
+///
+/// Descriptors must be `'static` like descriptors of generated types,
+/// so pool is never deallocated: files, descriptors and the pool itself
+/// are leaked, and pool should be loaded once rather than per use.
+pub struct DescriptorPool {
+    messages: HashMap<String, &'static MessageDescriptor>,
+    enums: HashMap<String, &'static EnumDescriptor>,
+    // returned by reflection for unset message fields
+    default_instances: HashMap<String, DynamicMessage>,
+}
+
+impl DescriptorPool {
+    /// Load all messages and enums from given files.
+    ///
+    /// Files must be complete: all types referenced by fields
+    /// must be defined in these files.
+    pub fn from_files(
+        files: Vec<FileDescriptorProto>,
+    ) -> DescriptorPoolResult<&'static DescriptorPool> {
+        // validate before leaking anything
+        let types = DescriptorPool::message_types(&files)?;
+
+        let files: &'static [FileDescriptorProto] = Box::leak(files.into_boxed_slice());
+
+        // accessors and factories refer to the pool, so memory for it is allocated first,
+        // and the pool is written there once it is complete
+        let pool_ptr = Box::into_raw(Box::new(mem::MaybeUninit::<DescriptorPool>::uninit()))
+            as *mut DescriptorPool;
+        let pool = PoolRef(pool_ptr);
+
+        let mut enums = HashMap::new();
+        for (full_name, proto, open) in collect_enums(files) {
+            let descriptor: &'static EnumDescriptor =
+                Box::leak(Box::new(EnumDescriptor::new_with_proto(proto, open)));
+            enums.insert(full_name, descriptor);
+        }
+
+        let mut messages = HashMap::new();
+        let mut default_instances = HashMap::new();
+        for (full_name, proto, _) in collect_messages(files) {
+            let ty: &'static DynamicMessageType = match types.get(&full_name) {
+                Some(ty) => Box::leak(Box::new(ty.clone())),
+                // map entry
+                None => continue,
+            };
+            let fields = proto
+                .get_field()
+                .iter()
+                .enumerate()
+                .map(|(index, field)| {
+                    Box::new(DynamicFieldAccessor {
+                        name: field.get_name(),
+                        index: index,
+                        field: &ty.fields[index],
+                        pool: pool,
+                    }) as Box<FieldAccessor + 'static>
+                })
+                .collect();
+            let factory = DynamicMessageFactory {
+                full_name: full_name.clone(),
+                ty: ty,
+                pool: pool,
+            };
+            let descriptor: &'static MessageDescriptor = Box::leak(Box::new(
+                MessageDescriptor::new_with_factory(
+                    full_name.clone(),
+                    proto,
+                    Box::new(factory),
+                    fields,
+                ),
+            ));
+            let default_instance = DynamicMessage::empty(descriptor, ty, pool);
+            default_instances.insert(full_name.clone(), default_instance);
+            messages.insert(full_name, descriptor);
+        }
+
+        // nothing dereferenced the pool so far
+        unsafe {
+            ptr::write(pool_ptr, DescriptorPool {
+                messages: messages,
+                enums: enums,
+                default_instances: default_instances,
+            });
+        }
+
+        Ok(pool.get())
+    }
+
+    /// Load all messages and enums from given descriptor set.
+    pub fn from_file_descriptor_set(
+        file_descriptor_set: FileDescriptorSet,
+    ) -> DescriptorPoolResult<&'static DescriptorPool> {
+        DescriptorPool::from_files(file_descriptor_set.file)
+    }
+
+    /// Find message by fully-qualified name, e. g. `foo.bar.Baz`.
+    pub fn message_by_name(&self, full_name: &str) -> Option<&'static MessageDescriptor> {
+        self.messages.get(strip_dot(full_name)).map(|d| *d)
+    }
+
+    /// Find enum by fully-qualified name.
+    pub fn enum_by_name(&self, full_name: &str) -> Option<&'static EnumDescriptor> {
+        self.enums.get(strip_dot(full_name)).map(|d| *d)
+    }
+
+    fn message(&self, full_name: &str) -> &'static MessageDescriptor {
+        // type names are checked when pool is built
+        self.messages[full_name]
+    }
+
+    fn enumeration(&self, full_name: &str) -> &'static EnumDescriptor {
+        self.enums[full_name]
+    }
+
+    // compute field layout of all messages except map entries
+    fn message_types(
+        files: &[FileDescriptorProto],
+    ) -> DescriptorPoolResult<HashMap<String, DynamicMessageType>> {
+        let mut protos = HashMap::new();
+        let mut enum_names = HashSet::new();
+        let mut messages = Vec::new();
+        for (full_name, proto, proto3) in collect_messages(files) {
+            if protos.insert(full_name.clone(), proto).is_some() {
+                return Err(DescriptorPoolError::new(format!("duplicate type: {}", full_name)));
+            }
+            messages.push((full_name, proto, proto3));
+        }
+        for (full_name, ..) in collect_enums(files) {
+            if protos.contains_key(&full_name) || !enum_names.insert(full_name.clone()) {
+                return Err(DescriptorPoolError::new(format!("duplicate type: {}", full_name)));
+            }
+        }
+
+        let resolver = TypeResolver {
+            messages: &protos,
+            enums: &enum_names,
+        };
+
+        let mut r = HashMap::new();
+        for (full_name, proto, proto3) in messages {
+            if proto.get_options().get_map_entry() {
+                continue;
+            }
+            let ty = DynamicMessageType::new(&full_name, proto, proto3, &resolver)?;
+            r.insert(full_name, ty);
+        }
+        Ok(r)
+    }
+}
+
+fn strip_dot(name: &str) -> &str {
+    if name.starts_with(".") {
+        &name[1..]
+    } else {
+        name
+    }
+}
+
+fn full_name(file: &FileDescriptorProto, name_to_package: String) -> String {
+    if file.get_package().is_empty() {
+        name_to_package
+    } else {
+        format!("{}.{}", file.get_package(), name_to_package)
+    }
+}
+
+// all messages including nested, with full names and proto3 flag
+fn collect_messages<'a>(
+    files: &'a [FileDescriptorProto],
+) -> Vec<(String, &'a DescriptorProto, bool)> {
+    let mut r = Vec::new();
+    for file in files {
+        let proto3 = file.get_syntax() == "proto3";
+        for message in (FileScope { file_descriptor: file }).find_messages() {
+            r.push((full_name(file, message.name_to_package()), message.message, proto3));
+        }
+    }
+    r
+}
+
+// full name, enum, is open (proto3)
+fn collect_enums<'a>(
+    files: &'a [FileDescriptorProto],
+) -> Vec<(String, &'a EnumDescriptorProto, bool)> {
+    let mut r = Vec::new();
+    for file in files {
+        let open = file.get_syntax() == "proto3";
+        for en in (FileScope { file_descriptor: file }).find_enums() {
+            r.push((full_name(file, en.name_to_package()), en.en, open));
+        }
+    }
+    r
+}
+
+
+// Pointer to the pool, which is not dereferenced until the pool is written.
+// After that the pool is never modified or deallocated,
+// so the pointer can be shared between threads like `&'static DescriptorPool`.
+#[derive(Clone, Copy)]
+struct PoolRef(*const DescriptorPool);
+
+unsafe impl Sync for PoolRef {}
+unsafe impl Send for PoolRef {}
+
+impl PoolRef {
+    fn get(&self) -> &'static DescriptorPool {
+        unsafe { &*self.0 }
+    }
+}
+
+
+struct TypeResolver<'a, 'b : 'a> {
+    messages: &'a HashMap<String, &'b DescriptorProto>,
+    enums: &'a HashSet<String>,
+}
+
+impl<'a, 'b> TypeResolver<'a, 'b> {
+    fn elem_type(
+        &self,
+        message_name: &str,
+        field: &FieldDescriptorProto,
+    ) -> DescriptorPoolResult<ElemType> {
+        let type_name = match field.get_field_type() {
+            FieldDescriptorProto_Type::TYPE_MESSAGE |
+            FieldDescriptorProto_Type::TYPE_GROUP |
+            FieldDescriptorProto_Type::TYPE_ENUM => {
+                let type_name = field.get_type_name();
+                let found = type_name.starts_with(".") &&
+                    match field.get_field_type() {
+                        FieldDescriptorProto_Type::TYPE_ENUM => {
+                            self.enums.contains(&type_name[1..])
+                        }
+                        _ => self.messages.contains_key(&type_name[1..]),
+                    };
+                if !found {
+                    return Err(DescriptorPoolError::new(format!(
+                        "{}.{}: type not found: {}",
+                        message_name,
+                        field.get_name(),
+                        type_name
+                    )));
+                }
+                type_name[1..].to_owned()
+            }
+            _ => String::new(),
+        };
+        Ok(ElemType {
+            field_type: field.get_field_type(),
+            type_name: type_name,
+        })
+    }
+
+    fn field_kind(
+        &self,
+        message_name: &str,
+        field: &FieldDescriptorProto,
+    ) -> DescriptorPoolResult<FieldKind> {
+        let elem = self.elem_type(message_name, field)?;
+        if field.get_label() != FieldDescriptorProto_Label::LABEL_REPEATED {
+            return Ok(FieldKind::Singular(elem));
+        }
+
+        if elem.field_type == FieldDescriptorProto_Type::TYPE_MESSAGE {
+            let entry = self.messages[&elem.type_name];
+            if entry.get_options().get_map_entry() {
+                let entry_field = |number| {
+                    match entry.get_field().iter().find(|f| f.get_number() == number) {
+                        Some(f) => self.elem_type(&elem.type_name, f),
+                        None => Err(DescriptorPoolError::new(format!(
+                            "{}: map entry field {} not found",
+                            elem.type_name,
+                            number
+                        ))),
+                    }
+                };
+                return Ok(FieldKind::Map(entry_field(1)?, entry_field(2)?));
+            }
+        }
+
+        Ok(FieldKind::Repeated(elem))
+    }
+}
+
+
+/// Type of singular field value, repeated field element or map key or value
+#[derive(Clone)]
+struct ElemType {
+    field_type: FieldDescriptorProto_Type,
+    // full name without leading dot for message and enum types
+    type_name: String,
+}
+
+impl ElemType {
+    fn wire_type(&self) -> wire_format::WireType {
+        match self.field_type {
+            FieldDescriptorProto_Type::TYPE_DOUBLE |
+            FieldDescriptorProto_Type::TYPE_FIXED64 |
+            FieldDescriptorProto_Type::TYPE_SFIXED64 => wire_format::WireTypeFixed64,
+            FieldDescriptorProto_Type::TYPE_FLOAT |
+            FieldDescriptorProto_Type::TYPE_FIXED32 |
+            FieldDescriptorProto_Type::TYPE_SFIXED32 => wire_format::WireTypeFixed32,
+            FieldDescriptorProto_Type::TYPE_STRING |
+            FieldDescriptorProto_Type::TYPE_BYTES |
+            FieldDescriptorProto_Type::TYPE_MESSAGE => wire_format::WireTypeLengthDelimited,
+            FieldDescriptorProto_Type::TYPE_GROUP => wire_format::WireTypeStartGroup,
+            _ => wire_format::WireTypeVarint,
+        }
+    }
+
+    fn is_packable(&self) -> bool {
+        match self.wire_type() {
+            wire_format::WireTypeLengthDelimited | wire_format::WireTypeStartGroup => false,
+            _ => true,
+        }
+    }
+
+    fn message_descriptor(&self, pool: &DescriptorPool) -> Option<&'static MessageDescriptor> {
+        match self.field_type {
+            FieldDescriptorProto_Type::TYPE_MESSAGE | FieldDescriptorProto_Type::TYPE_GROUP => {
+                Some(pool.message(&self.type_name))
+            }
+            _ => None,
+        }
+    }
+
+    fn enum_descriptor(&self, pool: &DescriptorPool) -> Option<&'static EnumDescriptor> {
+        match self.field_type {
+            FieldDescriptorProto_Type::TYPE_ENUM => Some(pool.enumeration(&self.type_name)),
+            _ => None,
+        }
+    }
+
+    /// Proto2 enum, numbers unknown to the enum cannot be stored in the field
+    fn is_closed_enum(&self, pool: &DescriptorPool) -> bool {
+        match self.enum_descriptor(pool) {
+            Some(d) => !d.is_open(),
+            None => false,
+        }
+    }
+
+    fn enum_value(
+        &self,
+        pool: &DescriptorPool,
+        number: i32,
+    ) -> Option<&'static EnumValueDescriptor> {
+        self.enum_descriptor(pool)
+            .unwrap()
+            .values()
+            .iter()
+            .find(|v| v.value() == number)
+    }
+
+    /// Value returned by reflection when field is not set
+    fn default_value(&self, pool: &'static DescriptorPool) -> ProtobufValueRef<'static> {
+        match self.field_type {
+            FieldDescriptorProto_Type::TYPE_DOUBLE => ProtobufValueRef::F64(0.0),
+            FieldDescriptorProto_Type::TYPE_FLOAT => ProtobufValueRef::F32(0.0),
+            FieldDescriptorProto_Type::TYPE_INT64 |
+            FieldDescriptorProto_Type::TYPE_SFIXED64 |
+            FieldDescriptorProto_Type::TYPE_SINT64 => ProtobufValueRef::I64(0),
+            FieldDescriptorProto_Type::TYPE_UINT64 |
+            FieldDescriptorProto_Type::TYPE_FIXED64 => ProtobufValueRef::U64(0),
+            FieldDescriptorProto_Type::TYPE_INT32 |
+            FieldDescriptorProto_Type::TYPE_SFIXED32 |
+            FieldDescriptorProto_Type::TYPE_SINT32 => ProtobufValueRef::I32(0),
+            FieldDescriptorProto_Type::TYPE_UINT32 |
+            FieldDescriptorProto_Type::TYPE_FIXED32 => ProtobufValueRef::U32(0),
+            FieldDescriptorProto_Type::TYPE_BOOL => ProtobufValueRef::Bool(false),
+            FieldDescriptorProto_Type::TYPE_STRING => ProtobufValueRef::String(""),
+            FieldDescriptorProto_Type::TYPE_BYTES => ProtobufValueRef::Bytes(b""),
+            FieldDescriptorProto_Type::TYPE_ENUM => {
+                ProtobufValueRef::Enum(&self.enum_descriptor(pool).unwrap().values()[0])
+            }
+            FieldDescriptorProto_Type::TYPE_MESSAGE | FieldDescriptorProto_Type::TYPE_GROUP => {
+                ProtobufValueRef::Message(&pool.default_instances[&self.type_name])
+            }
+        }
+    }
+
+    /// Check that value has this type, panic otherwise.
+    ///
+    /// Enum values of generated enums are replaced with values of dynamic enum.
+    fn check_value(&self, pool: &DescriptorPool, value: ProtobufValueBox) -> ProtobufValueBox {
+        let ok = match (self.field_type, &value) {
+            (FieldDescriptorProto_Type::TYPE_DOUBLE, &ProtobufValueBox::F64(..)) |
+            (FieldDescriptorProto_Type::TYPE_FLOAT, &ProtobufValueBox::F32(..)) |
+            (FieldDescriptorProto_Type::TYPE_INT64, &ProtobufValueBox::I64(..)) |
+            (FieldDescriptorProto_Type::TYPE_SFIXED64, &ProtobufValueBox::I64(..)) |
+            (FieldDescriptorProto_Type::TYPE_SINT64, &ProtobufValueBox::I64(..)) |
+            (FieldDescriptorProto_Type::TYPE_UINT64, &ProtobufValueBox::U64(..)) |
+            (FieldDescriptorProto_Type::TYPE_FIXED64, &ProtobufValueBox::U64(..)) |
+            (FieldDescriptorProto_Type::TYPE_INT32, &ProtobufValueBox::I32(..)) |
+            (FieldDescriptorProto_Type::TYPE_SFIXED32, &ProtobufValueBox::I32(..)) |
+            (FieldDescriptorProto_Type::TYPE_SINT32, &ProtobufValueBox::I32(..)) |
+            (FieldDescriptorProto_Type::TYPE_UINT32, &ProtobufValueBox::U32(..)) |
+            (FieldDescriptorProto_Type::TYPE_FIXED32, &ProtobufValueBox::U32(..)) |
+            (FieldDescriptorProto_Type::TYPE_BOOL, &ProtobufValueBox::Bool(..)) |
+            (FieldDescriptorProto_Type::TYPE_STRING, &ProtobufValueBox::String(..)) |
+            (FieldDescriptorProto_Type::TYPE_BYTES, &ProtobufValueBox::Bytes(..)) => true,
+            (FieldDescriptorProto_Type::TYPE_ENUM, &ProtobufValueBox::Enum(v)) => {
+                match self.enum_value(pool, v.value()) {
+                    Some(v) => return ProtobufValueBox::Enum(v),
+                    None => panic!("unknown enum value: {}", v.value()),
+                }
+            }
+            (FieldDescriptorProto_Type::TYPE_ENUM, &ProtobufValueBox::I32(v)) => {
+                match self.enum_value(pool, v) {
+                    Some(v) => return ProtobufValueBox::Enum(v),
+                    None if self.enum_descriptor(pool).unwrap().is_open() => true,
+                    None => panic!("unknown enum value: {}", v),
+                }
+            }
+            (FieldDescriptorProto_Type::TYPE_MESSAGE, &ProtobufValueBox::Message(ref m)) |
+            (FieldDescriptorProto_Type::TYPE_GROUP, &ProtobufValueBox::Message(ref m)) => {
+                let expected = pool.message(&self.type_name);
+                m.descriptor() as *const MessageDescriptor == expected as *const MessageDescriptor
+            }
+            _ => false,
+        };
+        if !ok {
+            panic!("wrong type");
+        }
+        value
+    }
+
+    /// Read value without tag
+    ///
+    /// Enum numbers unknown to the enum are returned as `I32`.
+    /// Field number is needed to find the end of a group.
+    fn read(
+        &self,
+        pool: &'static DescriptorPool,
+        field_number: u32,
+        is: &mut CodedInputStream,
+    ) -> ProtobufResult<ProtobufValueBox> {
+        Ok(match self.field_type {
+            FieldDescriptorProto_Type::TYPE_DOUBLE => ProtobufValueBox::F64(is.read_double()?),
+            FieldDescriptorProto_Type::TYPE_FLOAT => ProtobufValueBox::F32(is.read_float()?),
+            FieldDescriptorProto_Type::TYPE_INT64 => ProtobufValueBox::I64(is.read_int64()?),
+            FieldDescriptorProto_Type::TYPE_UINT64 => ProtobufValueBox::U64(is.read_uint64()?),
+            FieldDescriptorProto_Type::TYPE_INT32 => ProtobufValueBox::I32(is.read_int32()?),
+            FieldDescriptorProto_Type::TYPE_FIXED64 => ProtobufValueBox::U64(is.read_fixed64()?),
+            FieldDescriptorProto_Type::TYPE_FIXED32 => ProtobufValueBox::U32(is.read_fixed32()?),
+            FieldDescriptorProto_Type::TYPE_BOOL => ProtobufValueBox::Bool(is.read_bool()?),
+            FieldDescriptorProto_Type::TYPE_STRING => ProtobufValueBox::String(is.read_string()?),
+            FieldDescriptorProto_Type::TYPE_BYTES => ProtobufValueBox::Bytes(is.read_bytes()?),
+            FieldDescriptorProto_Type::TYPE_UINT32 => ProtobufValueBox::U32(is.read_uint32()?),
+            FieldDescriptorProto_Type::TYPE_SFIXED32 => {
+                ProtobufValueBox::I32(is.read_sfixed32()?)
+            }
+            FieldDescriptorProto_Type::TYPE_SFIXED64 => {
+                ProtobufValueBox::I64(is.read_sfixed64()?)
+            }
+            FieldDescriptorProto_Type::TYPE_SINT32 => ProtobufValueBox::I32(is.read_sint32()?),
+            FieldDescriptorProto_Type::TYPE_SINT64 => ProtobufValueBox::I64(is.read_sint64()?),
+            FieldDescriptorProto_Type::TYPE_ENUM => {
+                let number = is.read_int32()?;
+                match self.enum_value(pool, number) {
+                    Some(v) => ProtobufValueBox::Enum(v),
+                    None => ProtobufValueBox::I32(number),
+                }
+            }
+            FieldDescriptorProto_Type::TYPE_MESSAGE => {
+                let mut m = DynamicMessage::new(pool.message(&self.type_name));
+                is.merge_message(&mut m)?;
+                ProtobufValueBox::Message(Box::new(m))
+            }
+            FieldDescriptorProto_Type::TYPE_GROUP => {
+                let mut m = DynamicMessage::new(pool.message(&self.type_name));
+                is.merge_group(&mut m, field_number)?;
+                ProtobufValueBox::Message(Box::new(m))
+            }
+        })
+    }
+
+    /// Size of value without tag, including length for length-delimited values
+    fn compute_size(&self, value: ProtobufValueRef) -> u32 {
+        match (self.field_type, value) {
+            (FieldDescriptorProto_Type::TYPE_DOUBLE, _) |
+            (FieldDescriptorProto_Type::TYPE_FIXED64, _) |
+            (FieldDescriptorProto_Type::TYPE_SFIXED64, _) => 8,
+            (FieldDescriptorProto_Type::TYPE_FLOAT, _) |
+            (FieldDescriptorProto_Type::TYPE_FIXED32, _) |
+            (FieldDescriptorProto_Type::TYPE_SFIXED32, _) => 4,
+            (FieldDescriptorProto_Type::TYPE_BOOL, _) => 1,
+            (FieldDescriptorProto_Type::TYPE_INT64, ProtobufValueRef::I64(v)) => v.len_varint(),
+            (FieldDescriptorProto_Type::TYPE_UINT64, ProtobufValueRef::U64(v)) => v.len_varint(),
+            (FieldDescriptorProto_Type::TYPE_INT32, ProtobufValueRef::I32(v)) => v.len_varint(),
+            (FieldDescriptorProto_Type::TYPE_UINT32, ProtobufValueRef::U32(v)) => v.len_varint(),
+            (FieldDescriptorProto_Type::TYPE_SINT32, ProtobufValueRef::I32(v)) => {
+                v.len_varint_zigzag()
+            }
+            (FieldDescriptorProto_Type::TYPE_SINT64, ProtobufValueRef::I64(v)) => {
+                v.len_varint_zigzag()
+            }
+            (FieldDescriptorProto_Type::TYPE_ENUM, ProtobufValueRef::Enum(v)) => {
+                v.value().len_varint()
+            }
+            (FieldDescriptorProto_Type::TYPE_ENUM, ProtobufValueRef::I32(v)) => v.len_varint(),
+            (FieldDescriptorProto_Type::TYPE_STRING, ProtobufValueRef::String(v)) => {
+                rt::compute_raw_varint32_size(v.len() as u32) + v.len() as u32
+            }
+            (FieldDescriptorProto_Type::TYPE_BYTES, ProtobufValueRef::Bytes(v)) => {
+                rt::compute_raw_varint32_size(v.len() as u32) + v.len() as u32
+            }
+            (FieldDescriptorProto_Type::TYPE_MESSAGE, ProtobufValueRef::Message(v)) => {
+                let len = v.compute_size();
+                rt::compute_raw_varint32_size(len) + len
+            }
+            // group tags are accounted by `DynamicField`
+            (FieldDescriptorProto_Type::TYPE_GROUP, ProtobufValueRef::Message(v)) => {
+                v.compute_size()
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Write value without tag
+    fn write(&self, value: ProtobufValueRef, os: &mut CodedOutputStream) -> ProtobufResult<()> {
+        match (self.field_type, value) {
+            (FieldDescriptorProto_Type::TYPE_DOUBLE, ProtobufValueRef::F64(v)) => {
+                os.write_double_no_tag(v)
+            }
+            (FieldDescriptorProto_Type::TYPE_FLOAT, ProtobufValueRef::F32(v)) => {
+                os.write_float_no_tag(v)
+            }
+            (FieldDescriptorProto_Type::TYPE_INT64, ProtobufValueRef::I64(v)) => {
+                os.write_int64_no_tag(v)
+            }
+            (FieldDescriptorProto_Type::TYPE_UINT64, ProtobufValueRef::U64(v)) => {
+                os.write_uint64_no_tag(v)
+            }
+            (FieldDescriptorProto_Type::TYPE_INT32, ProtobufValueRef::I32(v)) => {
+                os.write_int32_no_tag(v)
+            }
+            (FieldDescriptorProto_Type::TYPE_FIXED64, ProtobufValueRef::U64(v)) => {
+                os.write_fixed64_no_tag(v)
+            }
+            (FieldDescriptorProto_Type::TYPE_FIXED32, ProtobufValueRef::U32(v)) => {
+                os.write_fixed32_no_tag(v)
+            }
+            (FieldDescriptorProto_Type::TYPE_BOOL, ProtobufValueRef::Bool(v)) => {
+                os.write_bool_no_tag(v)
+            }
+            (FieldDescriptorProto_Type::TYPE_STRING, ProtobufValueRef::String(v)) => {
+                os.write_string_no_tag(v)
+            }
+            (FieldDescriptorProto_Type::TYPE_BYTES, ProtobufValueRef::Bytes(v)) => {
+                os.write_bytes_no_tag(v)
+            }
+            (FieldDescriptorProto_Type::TYPE_UINT32, ProtobufValueRef::U32(v)) => {
+                os.write_uint32_no_tag(v)
+            }
+            (FieldDescriptorProto_Type::TYPE_ENUM, ProtobufValueRef::Enum(v)) => {
+                os.write_enum_no_tag(v.value())
+            }
+            (FieldDescriptorProto_Type::TYPE_ENUM, ProtobufValueRef::I32(v)) => {
+                os.write_enum_no_tag(v)
+            }
+            (FieldDescriptorProto_Type::TYPE_SFIXED32, ProtobufValueRef::I32(v)) => {
+                os.write_sfixed32_no_tag(v)
+            }
+            (FieldDescriptorProto_Type::TYPE_SFIXED64, ProtobufValueRef::I64(v)) => {
+                os.write_sfixed64_no_tag(v)
+            }
+            (FieldDescriptorProto_Type::TYPE_SINT32, ProtobufValueRef::I32(v)) => {
+                os.write_sint32_no_tag(v)
+            }
+            (FieldDescriptorProto_Type::TYPE_SINT64, ProtobufValueRef::I64(v)) => {
+                os.write_sint64_no_tag(v)
+            }
+            (FieldDescriptorProto_Type::TYPE_MESSAGE, ProtobufValueRef::Message(v)) => {
+                os.write_raw_varint32(v.compute_size())?;
+                v.write_to_with_cached_sizes(os)
+            }
+            (FieldDescriptorProto_Type::TYPE_GROUP, ProtobufValueRef::Message(v)) => {
+                v.write_to_with_cached_sizes(os)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+
+#[derive(Clone)]
+enum FieldKind {
+    Singular(ElemType),
+    Repeated(ElemType),
+    Map(ElemType, ElemType),
+}
+
+#[derive(Clone)]
+struct DynamicField {
+    number: u32,
+    kind: FieldKind,
+    packed: bool,
+    required: bool,
+    // proto3 field without presence, not stored when value is zero
+    skip_zero: bool,
+    oneof_index: Option<i32>,
+}
+
+impl DynamicField {
+    fn new(
+        message_name: &str,
+        proto: &FieldDescriptorProto,
+        proto3: bool,
+        resolver: &TypeResolver,
+    ) -> DescriptorPoolResult<DynamicField> {
+        let kind = resolver.field_kind(message_name, proto)?;
+        let skip_zero = match kind {
+            FieldKind::Singular(ref elem) => {
+                proto3 && !proto.has_oneof_index() &&
+                    elem.field_type != FieldDescriptorProto_Type::TYPE_MESSAGE
+            }
+            _ => false,
+        };
+        Ok(DynamicField {
+            number: proto.get_number() as u32,
+            kind: kind,
+            packed: proto.get_options().get_packed(),
+            required: proto.get_label() == FieldDescriptorProto_Label::LABEL_REQUIRED,
+            skip_zero: skip_zero,
+            oneof_index: if proto.has_oneof_index() {
+                Some(proto.get_oneof_index())
+            } else {
+                None
+            },
+        })
+    }
+
+    fn empty_value(&self) -> DynamicFieldValue {
+        match self.kind {
+            FieldKind::Singular(..) => DynamicFieldValue::Singular(None),
+            FieldKind::Repeated(..) => DynamicFieldValue::Repeated(Vec::new()),
+            FieldKind::Map(..) => DynamicFieldValue::Map(HashMap::new()),
+        }
+    }
+
+    // element type, map value type for maps
+    fn value_type(&self) -> &ElemType {
+        match self.kind {
+            FieldKind::Singular(ref elem) |
+            FieldKind::Repeated(ref elem) |
+            FieldKind::Map(_, ref elem) => elem,
+        }
+    }
+
+    // size of tags of singular value or repeated element, two tags for groups
+    fn elem_tags_size(&self, elem: &ElemType) -> u32 {
+        let tag_size = rt::tag_size(self.number);
+        match elem.field_type {
+            FieldDescriptorProto_Type::TYPE_GROUP => tag_size * 2,
+            _ => tag_size,
+        }
+    }
+
+    fn compute_size(&self, value: &DynamicFieldValue) -> u32 {
+        let tag_size = rt::tag_size(self.number);
+        let mut size = 0;
+        match (&self.kind, value) {
+            (&FieldKind::Singular(ref elem), &DynamicFieldValue::Singular(Some(ref v))) => {
+                size += self.elem_tags_size(elem) + elem.compute_size(ProtobufValue::as_ref(v));
+            }
+            (&FieldKind::Repeated(ref elem), &DynamicFieldValue::Repeated(ref vs)) => {
+                if self.packed {
+                    if !vs.is_empty() {
+                        let data_size = self.packed_data_size(elem, vs);
+                        size += tag_size + rt::compute_raw_varint32_size(data_size) + data_size;
+                    }
+                } else {
+                    for v in vs {
+                        size += self.elem_tags_size(elem) +
+                            elem.compute_size(ProtobufValue::as_ref(v));
+                    }
+                }
+            }
+            (&FieldKind::Map(ref key_type, ref value_type), &DynamicFieldValue::Map(ref map)) => {
+                for (k, v) in map {
+                    let entry_size = 1 + key_type.compute_size(k.as_ref()) + 1 +
+                        value_type.compute_size(ProtobufValue::as_ref(v));
+                    size += tag_size + rt::compute_raw_varint32_size(entry_size) + entry_size;
+                }
+            }
+            _ => {}
+        }
+        size
+    }
+
+    fn packed_data_size(&self, elem: &ElemType, values: &[ProtobufValueBox]) -> u32 {
+        values
+            .iter()
+            .map(|v| elem.compute_size(ProtobufValue::as_ref(v)))
+            .sum()
+    }
+
+    // write singular value or repeated element with tag
+    fn write_elem(
+        &self,
+        elem: &ElemType,
+        value: &ProtobufValueBox,
+        os: &mut CodedOutputStream,
+    ) -> ProtobufResult<()> {
+        os.write_tag(self.number, elem.wire_type())?;
+        elem.write(ProtobufValue::as_ref(value), os)?;
+        if elem.field_type == FieldDescriptorProto_Type::TYPE_GROUP {
+            os.write_tag(self.number, wire_format::WireTypeEndGroup)?;
+        }
+        Ok(())
+    }
+
+    fn write(&self, value: &DynamicFieldValue, os: &mut CodedOutputStream) -> ProtobufResult<()> {
+        match (&self.kind, value) {
+            (&FieldKind::Singular(ref elem), &DynamicFieldValue::Singular(Some(ref v))) => {
+                self.write_elem(elem, v, os)?;
+            }
+            (&FieldKind::Repeated(ref elem), &DynamicFieldValue::Repeated(ref vs)) => {
+                if self.packed {
+                    if !vs.is_empty() {
+                        os.write_tag(self.number, wire_format::WireTypeLengthDelimited)?;
+                        os.write_raw_varint32(self.packed_data_size(elem, vs))?;
+                        for v in vs {
+                            elem.write(ProtobufValue::as_ref(v), os)?;
+                        }
+                    }
+                } else {
+                    for v in vs {
+                        self.write_elem(elem, v, os)?;
+                    }
+                }
+            }
+            (&FieldKind::Map(ref key_type, ref value_type), &DynamicFieldValue::Map(ref map)) => {
+                for (k, v) in map {
+                    let entry_size = 1 + key_type.compute_size(k.as_ref()) + 1 +
+                        value_type.compute_size(ProtobufValue::as_ref(v));
+                    os.write_tag(self.number, wire_format::WireTypeLengthDelimited)?;
+                    os.write_raw_varint32(entry_size)?;
+                    os.write_tag(1, key_type.wire_type())?;
+                    key_type.write(k.as_ref(), os)?;
+                    os.write_tag(2, value_type.wire_type())?;
+                    value_type.write(ProtobufValue::as_ref(v), os)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+
+/// Field layout of a message type
+#[derive(Clone)]
+struct DynamicMessageType {
+    fields: Vec<DynamicField>,
+    index_by_number: HashMap<u32, usize>,
+    // like in generated code, oneof fields are written after other fields
+    write_order: Vec<usize>,
+}
+
+impl DynamicMessageType {
+    fn new(
+        full_name: &str,
+        proto: &DescriptorProto,
+        proto3: bool,
+        resolver: &TypeResolver,
+    ) -> DescriptorPoolResult<DynamicMessageType> {
+        let mut fields = Vec::new();
+        let mut index_by_number = HashMap::new();
+        for (index, field) in proto.get_field().iter().enumerate() {
+            fields.push(DynamicField::new(full_name, field, proto3, resolver)?);
+            index_by_number.insert(field.get_number() as u32, index);
+        }
+
+        let mut write_order: Vec<usize> = (0..fields.len())
+            .filter(|&i| fields[i].oneof_index.is_none())
+            .collect();
+        for oneof_index in 0..proto.get_oneof_decl().len() as i32 {
+            write_order.extend(
+                (0..fields.len()).filter(|&i| fields[i].oneof_index == Some(oneof_index)),
+            );
+        }
+
+        Ok(DynamicMessageType {
+            fields: fields,
+            index_by_number: index_by_number,
+            write_order: write_order,
+        })
+    }
+}
+
+
+// Key of map field, float, bytes and message keys are not allowed
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum MapKey {
+    U32(u32),
+    U64(u64),
+    I32(i32),
+    I64(i64),
+    Bool(bool),
+    String(String),
+}
+
+impl ProtobufValue for MapKey {
+    fn as_ref(&self) -> ProtobufValueRef {
+        match *self {
+            MapKey::U32(v) => ProtobufValueRef::U32(v),
+            MapKey::U64(v) => ProtobufValueRef::U64(v),
+            MapKey::I32(v) => ProtobufValueRef::I32(v),
+            MapKey::I64(v) => ProtobufValueRef::I64(v),
+            MapKey::Bool(v) => ProtobufValueRef::Bool(v),
+            MapKey::String(ref v) => ProtobufValueRef::String(v),
+        }
+    }
+
+    fn from_value_box(value: ProtobufValueBox) -> Option<MapKey> {
+        match value {
+            ProtobufValueBox::U32(v) => Some(MapKey::U32(v)),
+            ProtobufValueBox::U64(v) => Some(MapKey::U64(v)),
+            ProtobufValueBox::I32(v) => Some(MapKey::I32(v)),
+            ProtobufValueBox::I64(v) => Some(MapKey::I64(v)),
+            ProtobufValueBox::Bool(v) => Some(MapKey::Bool(v)),
+            ProtobufValueBox::String(v) => Some(MapKey::String(v)),
+            _ => None,
+        }
+    }
+}
+
+
+enum DynamicFieldValue {
+    Singular(Option<ProtobufValueBox>),
+    Repeated(Vec<ProtobufValueBox>),
+    Map(HashMap<MapKey, ProtobufValueBox>),
+}
+
+
+/// Message of type loaded at runtime with `DescriptorPool`.
+///
+/// Message type is not known statically, so generic constructors are not supported:
+/// `Message::new` panics, and `Message::read_from` (thus `parse_from_bytes::<DynamicMessage>`
+/// and other generic parse functions) returns `ProtobufError::MessageTypeUnknown`.
+/// `DynamicMessage::new`, `DynamicMessage::read_from`, `DynamicMessage::parse_from_bytes`
+/// or `MessageDescriptor::new_instance` should be used instead.
+pub struct DynamicMessage {
+    descriptor: &'static MessageDescriptor,
+    ty: &'static DynamicMessageType,
+    pool: PoolRef,
+    fields: Vec<DynamicFieldValue>,
+    unknown_fields: UnknownFields,
+}
+
+impl DynamicMessage {
+    /// Create an empty message of given type.
+    ///
+    /// Panics if descriptor does not come from `DescriptorPool`.
+    pub fn new(descriptor: &'static MessageDescriptor) -> DynamicMessage {
+        match descriptor.new_instance().into_any().downcast::<DynamicMessage>() {
+            Ok(m) => *m,
+            Err(..) => panic!("not a dynamic message type: {}", descriptor.full_name()),
+        }
+    }
+
+    // does not dereference the pool, so it is used while the pool is built
+    fn empty(
+        descriptor: &'static MessageDescriptor,
+        ty: &'static DynamicMessageType,
+        pool: PoolRef,
+    ) -> DynamicMessage {
+        DynamicMessage {
+            descriptor: descriptor,
+            ty: ty,
+            pool: pool,
+            fields: ty.fields.iter().map(DynamicField::empty_value).collect(),
+            unknown_fields: UnknownFields::new(),
+        }
+    }
+
+    /// Parse message of given type from the stream.
+    pub fn read_from(
+        descriptor: &'static MessageDescriptor,
+        is: &mut CodedInputStream,
+    ) -> ProtobufResult<DynamicMessage> {
+        let mut m = DynamicMessage::new(descriptor);
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        Ok(m)
+    }
+
+    /// Parse message of given type from bytes.
+    pub fn parse_from_bytes(
+        descriptor: &'static MessageDescriptor,
+        bytes: &[u8],
+    ) -> ProtobufResult<DynamicMessage> {
+        let mut is = CodedInputStream::from_bytes(bytes);
+        let m = DynamicMessage::read_from(descriptor, &mut is)?;
+        is.check_eof()?;
+        Ok(m)
+    }
+
+    fn read_field(
+        &mut self,
+        index: usize,
+        wire_type: wire_format::WireType,
+        is: &mut CodedInputStream,
+    ) -> ProtobufResult<()> {
+        let pool = self.pool.get();
+        let ty = self.ty;
+        match ty.fields[index].kind {
+            FieldKind::Singular(ref elem) => {
+                if wire_type != elem.wire_type() {
+                    return Err(rt::unexpected_wire_type(wire_type));
+                }
+                if let DynamicFieldValue::Singular(Some(ProtobufValueBox::Message(ref mut m))) =
+                    self.fields[index]
+                {
+                    // message occurring multiple times is merged
+                    return match elem.field_type {
+                        FieldDescriptorProto_Type::TYPE_GROUP => {
+                            is.merge_group(&mut **m, ty.fields[index].number)
+                        }
+                        _ => is.merge_message(&mut **m),
+                    };
+                }
+                if let Some(value) = self.read_elem(index, elem, is)? {
+                    self.set_value(index, value);
+                }
+            }
+            FieldKind::Repeated(ref elem) => {
+                if wire_type == wire_format::WireTypeLengthDelimited && elem.is_packable() {
+                    let len = is.read_raw_varint64()?;
+                    let old_limit = is.push_limit(len)?;
+                    while !is.eof()? {
+                        if let Some(value) = self.read_elem(index, elem, is)? {
+                            self.push_value(index, value);
+                        }
+                    }
+                    is.pop_limit(old_limit);
+                } else if wire_type == elem.wire_type() {
+                    if let Some(value) = self.read_elem(index, elem, is)? {
+                        self.push_value(index, value);
+                    }
+                } else {
+                    return Err(rt::unexpected_wire_type(wire_type));
+                }
+            }
+            FieldKind::Map(ref key_type, ref value_type) => {
+                if wire_type != wire_format::WireTypeLengthDelimited {
+                    return Err(rt::unexpected_wire_type(wire_type));
+                }
+
+                let mut key = None;
+                let mut value = None;
+
+                let len = is.read_raw_varint64()?;
+                let old_limit = is.push_limit(len)?;
+                while !is.eof()? {
+                    let (field_number, wire_type) = is.read_tag_unpack()?;
+                    let elem = match field_number {
+                        1 => key_type,
+                        2 => value_type,
+                        _ => {
+                            is.skip_field(wire_type)?;
+                            continue;
+                        }
+                    };
+                    if wire_type != elem.wire_type() {
+                        return Err(rt::unexpected_wire_type(wire_type));
+                    }
+                    let v = Some(elem.read(pool, field_number, is)?);
+                    if field_number == 1 {
+                        key = v;
+                    } else {
+                        value = v;
+                    }
+                }
+                is.pop_limit(old_limit);
+
+                match (key, value) {
+                    // proto2: entry with number unknown to the enum is stored in unknown fields
+                    (Some(key), Some(ProtobufValueBox::I32(number)))
+                        if value_type.is_closed_enum(pool) =>
+                    {
+                        let entry = with_coded_output_stream_to_bytes(|os| {
+                            os.write_tag(1, key_type.wire_type())?;
+                            key_type.write(ProtobufValue::as_ref(&key), os)?;
+                            os.write_enum(2, number)
+                        })?;
+                        let field_number = self.ty.fields[index].number;
+                        self.unknown_fields.add_length_delimited(field_number, entry);
+                    }
+                    (Some(key), Some(value)) => match MapKey::from_value_box(key) {
+                        Some(key) => self.insert_value(index, key, value),
+                        None => return Err(ProtobufError::WireError(WireError::IncompleteMap)),
+                    },
+                    _ => return Err(ProtobufError::WireError(WireError::IncompleteMap)),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Read singular or repeated field value.
+    ///
+    /// Proto2 enums are closed: numbers unknown to the enum
+    /// are stored in unknown fields, and `None` is returned.
+    fn read_elem(
+        &mut self,
+        index: usize,
+        elem: &ElemType,
+        is: &mut CodedInputStream,
+    ) -> ProtobufResult<Option<ProtobufValueBox>> {
+        let pool = self.pool.get();
+        let field_number = self.ty.fields[index].number;
+        let value = elem.read(pool, field_number, is)?;
+        if let ProtobufValueBox::I32(number) = value {
+            if elem.is_closed_enum(pool) {
+                self.unknown_fields.add_varint(field_number, number as i64 as u64);
+                return Ok(None);
+            }
+        }
+        Ok(Some(value))
+    }
+
+    // value must be already checked
+    fn set_value(&mut self, index: usize, value: ProtobufValueBox) {
+        let field = &self.ty.fields[index];
+        if let Some(oneof_index) = field.oneof_index {
+            for (i, f) in self.ty.fields.iter().enumerate() {
+                if f.oneof_index == Some(oneof_index) {
+                    self.fields[i] = DynamicFieldValue::Singular(None);
+                }
+            }
+        }
+        self.fields[index] = DynamicFieldValue::Singular(
+            if field.skip_zero && !value.is_non_zero() {
+                None
+            } else {
+                Some(value)
+            },
+        );
+    }
+
+    fn push_value(&mut self, index: usize, value: ProtobufValueBox) {
+        match self.fields[index] {
+            DynamicFieldValue::Repeated(ref mut vs) => vs.push(value),
+            _ => panic!("not repeated"),
+        }
+    }
+
+    fn insert_value(&mut self, index: usize, key: MapKey, value: ProtobufValueBox) {
+        match self.fields[index] {
+            DynamicFieldValue::Map(ref mut map) => {
+                map.insert(key, value);
+            }
+            _ => panic!("not a map"),
+        }
+    }
+
+    // all singular values, repeated elements and map values
+    fn values<'a>(&'a self) -> Box<Iterator<Item = &'a ProtobufValueBox> + 'a> {
+        Box::new(self.fields.iter().flat_map(|field| -> Box<Iterator<Item = &'a ProtobufValueBox>> {
+            match *field {
+                DynamicFieldValue::Singular(ref v) => Box::new(v.iter()),
+                DynamicFieldValue::Repeated(ref vs) => Box::new(vs.iter()),
+                DynamicFieldValue::Map(ref map) => Box::new(map.values()),
+            }
+        }))
+    }
+}
+
+impl Message for DynamicMessage {
+    fn descriptor(&self) -> &'static MessageDescriptor {
+        self.descriptor
+    }
+
+    fn is_initialized(&self) -> bool {
+        for (field, value) in self.ty.fields.iter().zip(&self.fields) {
+            if let DynamicFieldValue::Singular(None) = *value {
+                if field.required {
+                    return false;
+                }
+            }
+        }
+        self.values().all(|v| match *v {
+            ProtobufValueBox::Message(ref m) => m.is_initialized(),
+            _ => true,
+        })
+    }
+
+    /// Always fails, `DynamicMessage::read_from` with descriptor should be used instead.
+    fn read_from(_is: &mut CodedInputStream) -> ProtobufResult<DynamicMessage> {
+        Err(ProtobufError::MessageTypeUnknown)
+    }
+
+    fn merge_from(&mut self, is: &mut CodedInputStream) -> ProtobufResult<()> {
+        while let Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
+            match self.ty.index_by_number.get(&field_number) {
+                Some(&index) => self.read_field(index, wire_type, is)?,
+                None => {
+                    let unknown_fields = &mut self.unknown_fields;
+                    rt::read_unknown_or_skip_group(field_number, wire_type, is, unknown_fields)?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn merge(&mut self, other: &DynamicMessage) {
+        assert!(
+            self.descriptor as *const MessageDescriptor ==
+                other.descriptor as *const MessageDescriptor,
+            "cannot merge {} into {}",
+            other.descriptor.full_name(),
+            self.descriptor.full_name()
+        );
+        // dynamic values cannot be cloned, so merge serialized message
+        let bytes = with_coded_output_stream_to_bytes(|os| other.write_to_with_cached_sizes(os))
+            .expect("write to vec");
+        self.merge_from_bytes(&bytes).expect("merge serialized message of the same type");
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut CodedOutputStream) -> ProtobufResult<()> {
+        for &index in &self.ty.write_order {
+            self.ty.fields[index].write(&self.fields[index], os)?;
+        }
+        os.write_unknown_fields(&self.unknown_fields)?;
+        Ok(())
+    }
+
+    fn compute_size(&self) -> u32 {
+        let fields_size: u32 = self.ty
+            .write_order
+            .iter()
+            .map(|&index| self.ty.fields[index].compute_size(&self.fields[index]))
+            .sum();
+        fields_size + rt::unknown_fields_size(&self.unknown_fields)
+    }
+
+    fn get_unknown_fields(&self) -> &UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &Any {
+        self as &Any
+    }
+
+    fn as_any_mut(&mut self) -> &mut Any {
+        self as &mut Any
+    }
+
+    fn into_any(self: Box<Self>) -> Box<Any> {
+        self
+    }
+
+    /// Always panics, `DynamicMessage::new` with descriptor should be used instead.
+    fn new() -> DynamicMessage {
+        panic!("message type is unknown, use `DynamicMessage::new` with descriptor");
+    }
+}
+
+impl fmt::Debug for DynamicMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        text_format::fmt(self, f)
+    }
+}
+
+
+struct DynamicMessageFactory {
+    full_name: String,
+    ty: &'static DynamicMessageType,
+    pool: PoolRef,
+}
+
+impl MessageFactory for DynamicMessageFactory {
+    fn new_instance(&self) -> Box<Message> {
+        let descriptor = self.pool.get().message(&self.full_name);
+        Box::new(DynamicMessage::empty(descriptor, self.ty, self.pool))
+    }
+
+    fn downcast_mut<'a>(&self, m: &'a mut Any) -> &'a mut Message {
+        m.downcast_mut::<DynamicMessage>().unwrap()
+    }
+}
+
+
+struct DynamicFieldAccessor {
+    name: &'static str,
+    index: usize,
+    field: &'static DynamicField,
+    pool: PoolRef,
+}
+
+impl DynamicFieldAccessor {
+    fn get_value<'a>(&self, m: &'a Message) -> &'a DynamicFieldValue {
+        let m: &DynamicMessage = message_down_cast(m);
+        &m.fields[self.index]
+    }
+
+    // value or default value of singular field
+    fn get_singular<'a>(&self, m: &'a Message) -> ProtobufValueRef<'a> {
+        match *self.get_value(m) {
+            DynamicFieldValue::Singular(Some(ref v)) => ProtobufValue::as_ref(v),
+            DynamicFieldValue::Singular(None) => {
+                self.field.value_type().default_value(self.pool.get())
+            }
+            _ => panic!("not a singular field"),
+        }
+    }
+}
+
+impl FieldAccessor for DynamicFieldAccessor {
+    fn name_generic(&self) -> &'static str {
+        self.name
+    }
+
+    fn has_field_generic(&self, m: &Message) -> bool {
+        match *self.get_value(m) {
+            DynamicFieldValue::Singular(ref v) => v.is_some(),
+            _ => panic!("not a singular field"),
+        }
+    }
+
+    fn len_field_generic(&self, m: &Message) -> usize {
+        match *self.get_value(m) {
+            DynamicFieldValue::Repeated(ref vs) => vs.len(),
+            DynamicFieldValue::Map(ref map) => map.len(),
+            _ => panic!("not repeated"),
+        }
+    }
+
+    fn get_message_generic<'a>(&self, m: &'a Message) -> &'a Message {
+        match self.get_singular(m) {
+            ProtobufValueRef::Message(v) => v,
+            _ => panic!("wrong type"),
+        }
+    }
+
+    fn get_enum_generic(&self, m: &Message) -> &'static EnumValueDescriptor {
+        match self.get_singular(m) {
+            ProtobufValueRef::Enum(v) => v,
+            _ => panic!("wrong type"),
+        }
+    }
+
+    fn get_str_generic<'a>(&self, m: &'a Message) -> &'a str {
+        match self.get_singular(m) {
+            ProtobufValueRef::String(v) => v,
+            _ => panic!("wrong type"),
+        }
+    }
+
+    fn get_bytes_generic<'a>(&self, m: &'a Message) -> &'a [u8] {
+        match self.get_singular(m) {
+            ProtobufValueRef::Bytes(v) => v,
+            _ => panic!("wrong type"),
+        }
+    }
+
+    fn get_u32_generic(&self, m: &Message) -> u32 {
+        match self.get_singular(m) {
+            ProtobufValueRef::U32(v) => v,
+            _ => panic!("wrong type"),
+        }
+    }
+
+    fn get_u64_generic(&self, m: &Message) -> u64 {
+        match self.get_singular(m) {
+            ProtobufValueRef::U64(v) => v,
+            _ => panic!("wrong type"),
+        }
+    }
+
+    fn get_i32_generic(&self, m: &Message) -> i32 {
+        match self.get_singular(m) {
+            ProtobufValueRef::I32(v) => v,
+            _ => panic!("wrong type"),
+        }
+    }
+
+    fn get_i64_generic(&self, m: &Message) -> i64 {
+        match self.get_singular(m) {
+            ProtobufValueRef::I64(v) => v,
+            _ => panic!("wrong type"),
+        }
+    }
+
+    fn get_bool_generic(&self, m: &Message) -> bool {
+        match self.get_singular(m) {
+            ProtobufValueRef::Bool(v) => v,
+            _ => panic!("wrong type"),
+        }
+    }
+
+    fn get_f32_generic(&self, m: &Message) -> f32 {
+        match self.get_singular(m) {
+            ProtobufValueRef::F32(v) => v,
+            _ => panic!("wrong type"),
+        }
+    }
+
+    fn get_f64_generic(&self, m: &Message) -> f64 {
+        match self.get_singular(m) {
+            ProtobufValueRef::F64(v) => v,
+            _ => panic!("wrong type"),
+        }
+    }
+
+    fn get_reflect<'a>(&self, m: &'a Message) -> ReflectFieldRef<'a> {
+        match *self.get_value(m) {
+            DynamicFieldValue::Singular(ref v) => {
+                ReflectFieldRef::Optional(v.as_ref().map(ProtobufValue::as_ref))
+            }
+            DynamicFieldValue::Repeated(ref vs) => ReflectFieldRef::Repeated(vs),
+            DynamicFieldValue::Map(ref map) => ReflectFieldRef::Map(map),
+        }
+    }
+
+    fn set_singular_field_generic(&self, m: &mut Message, value: ProtobufValueBox) {
+        match self.field.kind {
+            FieldKind::Singular(ref elem) => {
+                let value = elem.check_value(self.pool.get(), value);
+                message_down_cast_mut::<DynamicMessage>(m).set_value(self.index, value);
+            }
+            _ => panic!("not a singular field"),
+        }
+    }
+
+    fn clear_field_generic(&self, m: &mut Message) {
+        let m: &mut DynamicMessage = message_down_cast_mut(m);
+        m.fields[self.index] = self.field.empty_value();
+    }
+
+    fn mut_message_generic<'a>(&self, m: &'a mut Message) -> &'a mut Message {
+        let descriptor = match self.field.kind {
+            FieldKind::Singular(ref elem) => match elem.message_descriptor(self.pool.get()) {
+                Some(d) => d,
+                None => panic!("not a message field"),
+            },
+            _ => panic!("not a singular field"),
+        };
+        let m: &mut DynamicMessage = message_down_cast_mut(m);
+        if let DynamicFieldValue::Singular(None) = m.fields[self.index] {
+            let value = ProtobufValueBox::Message(Box::new(DynamicMessage::new(descriptor)));
+            m.set_value(self.index, value);
+        }
+        match m.fields[self.index] {
+            DynamicFieldValue::Singular(Some(ProtobufValueBox::Message(ref mut v))) => &mut **v,
+            _ => unreachable!(),
+        }
+    }
+
+    fn push_repeated_generic(&self, m: &mut Message, value: ProtobufValueBox) {
+        match self.field.kind {
+            FieldKind::Repeated(ref elem) => {
+                let value = elem.check_value(self.pool.get(), value);
+                message_down_cast_mut::<DynamicMessage>(m).push_value(self.index, value);
+            }
+            _ => panic!("not repeated"),
+        }
+    }
+
+    fn insert_map_generic(&self, m: &mut Message, key: ProtobufValueBox, value: ProtobufValueBox) {
+        match self.field.kind {
+            FieldKind::Map(ref key_type, ref value_type) => {
+                let key = match MapKey::from_value_box(key_type.check_value(self.pool.get(), key)) {
+                    Some(key) => key,
+                    None => panic!("wrong key type"),
+                };
+                let value = value_type.check_value(self.pool.get(), value);
+                message_down_cast_mut::<DynamicMessage>(m).insert_value(self.index, key, value);
+            }
+            _ => panic!("not a map"),
+        }
+    }
+
+    fn message_descriptor_generic(&self) -> Option<&'static MessageDescriptor> {
+        self.field.value_type().message_descriptor(self.pool.get())
+    }
+
+    fn enum_descriptor_generic(&self) -> Option<&'static EnumDescriptor> {
+        self.field.value_type().enum_descriptor(self.pool.get())
+    }
+}
@@ -0,0 +1,146 @@
+//! Message and field types used by parsers which emit serialized messages.
+
+use descriptor::DescriptorProto;
+use descriptor::FieldDescriptorProto;
+use descriptor::FieldDescriptorProto_Label;
+use descriptor::FieldDescriptorProto_Type;
+use error::ProtobufResult;
+use reflect::EnumDescriptor;
+use reflect::FieldDescriptor;
+use reflect::MessageDescriptor;
+use stream::wire_format;
+use stream::CodedOutputStream;
+
+
+/// Type of message being parsed
+#[derive(Clone, Copy)]
+pub enum MessageType {
+    Message(&'static MessageDescriptor),
+    /// Entry of the map field
+    MapEntry(&'static DescriptorProto, &'static FieldDescriptor),
+}
+
+/// Field being parsed
+pub struct FieldType {
+    pub proto: &'static FieldDescriptorProto,
+    pub message: Option<MessageType>,
+    pub enum_descriptor: Option<&'static EnumDescriptor>,
+}
+
+impl FieldType {
+    /// Write serialized value of message field, as length-delimited or as group
+    pub fn write_message_bytes(
+        &self,
+        bytes: &[u8],
+        os: &mut CodedOutputStream,
+    ) -> ProtobufResult<()> {
+        let number = self.proto.get_number() as u32;
+        match self.proto.get_field_type() {
+            FieldDescriptorProto_Type::TYPE_GROUP => {
+                os.write_tag(number, wire_format::WireTypeStartGroup)?;
+                os.write_raw_bytes(bytes)?;
+                os.write_tag(number, wire_format::WireTypeEndGroup)
+            }
+            _ => os.write_bytes(number, bytes),
+        }
+    }
+}
+
+/// Name of the field in text format: groups are named after the message type
+pub fn text_format_name(field: &FieldDescriptorProto) -> &str {
+    match field.get_field_type() {
+        FieldDescriptorProto_Type::TYPE_GROUP => {
+            let type_name = field.get_type_name();
+            match type_name.rfind('.') {
+                Some(pos) => &type_name[pos + 1..],
+                None => type_name,
+            }
+        }
+        _ => field.get_name(),
+    }
+}
+
+/// Find map entry message type if the field is a map field
+pub fn map_entry(
+    message: &'static MessageDescriptor,
+    field: &'static FieldDescriptor,
+) -> Option<&'static DescriptorProto> {
+    let proto = field.proto();
+    if proto.get_label() != FieldDescriptorProto_Label::LABEL_REPEATED ||
+        proto.get_field_type() != FieldDescriptorProto_Type::TYPE_MESSAGE
+    {
+        return None;
+    }
+
+    // map entry is always nested into message containing map field
+    let type_name = proto.get_type_name();
+    let name = match type_name.rfind('.') {
+        Some(pos) => &type_name[pos + 1..],
+        None => type_name,
+    };
+    message.proto().get_nested_type().iter().find(|nested| {
+        nested.get_name() == name && nested.get_options().get_map_entry()
+    })
+}
+
+impl MessageType {
+    pub fn name(&self) -> &str {
+        match *self {
+            MessageType::Message(d) => d.full_name(),
+            MessageType::MapEntry(entry, _) => entry.get_name(),
+        }
+    }
+
+    pub fn field_protos(&self) -> &'static [FieldDescriptorProto] {
+        match *self {
+            MessageType::Message(d) => d.proto().get_field(),
+            MessageType::MapEntry(entry, _) => entry.get_field(),
+        }
+    }
+
+    pub fn field_by_name(&self, name: &str) -> Option<FieldType> {
+        match *self {
+            MessageType::Message(d) => {
+                d.fields().iter().find(|f| f.name() == name).map(|f| {
+                    let proto = f.proto();
+                    let (message, enum_descriptor) = match proto.get_field_type() {
+                        FieldDescriptorProto_Type::TYPE_MESSAGE |
+                        FieldDescriptorProto_Type::TYPE_GROUP => {
+                            let message = match map_entry(d, f) {
+                                Some(entry) => MessageType::MapEntry(entry, f),
+                                None => MessageType::Message(f.message_descriptor()),
+                            };
+                            (Some(message), None)
+                        }
+                        FieldDescriptorProto_Type::TYPE_ENUM => (None, Some(f.enum_descriptor())),
+                        _ => (None, None),
+                    };
+                    FieldType {
+                        proto: proto,
+                        message: message,
+                        enum_descriptor: enum_descriptor,
+                    }
+                })
+            }
+            MessageType::MapEntry(entry, map_field) => {
+                entry.get_field().iter().find(|f| f.get_name() == name).map(|proto| {
+                    // map value type is taken from map field
+                    let (message, enum_descriptor) = match proto.get_field_type() {
+                        FieldDescriptorProto_Type::TYPE_MESSAGE => {
+                            (Some(MessageType::Message(map_field.message_descriptor())), None)
+                        }
+                        FieldDescriptorProto_Type::TYPE_ENUM => {
+                            (None, Some(map_field.enum_descriptor()))
+                        }
+                        _ => (None, None),
+                    };
+                    FieldType {
+                        proto: proto,
+                        message: message,
+                        enum_descriptor: enum_descriptor,
+                    }
+                })
+            }
+        }
+    }
+}
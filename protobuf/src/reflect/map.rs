@@ -1,26 +1,83 @@
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+#[cfg(feature = "std")]
 use std::hash::Hash;
+#[cfg(feature = "std")]
 use std::collections::HashMap;
-use std::collections::hash_map;
+use std::collections::BTreeMap;
 
 use super::value::ProtobufValue;
+use super::value::ProtobufValueBox;
 
 
-/// Implemented for `HashMap` with appropriate keys and values
+/// Implemented for `HashMap` and `BTreeMap` with appropriate keys and values
 pub trait ReflectMap: 'static {
     fn reflect_iter(&self) -> ReflectMapIter;
 
     fn len(&self) -> usize;
+
+    /// Insert an entry. Panics if key or value is of different type.
+    fn insert(&mut self, key: ProtobufValueBox, value: ProtobufValueBox);
+
+    fn clear(&mut self);
+}
+
+fn key_value_from_boxes<K : ProtobufValue, V : ProtobufValue>(
+    key: ProtobufValueBox,
+    value: ProtobufValueBox,
+) -> (K, V) {
+    let key = match K::from_value_box(key) {
+        Some(key) => key,
+        None => panic!("wrong key type"),
+    };
+    let value = match V::from_value_box(value) {
+        Some(value) => value,
+        None => panic!("wrong value type"),
+    };
+    (key, value)
 }
 
+// `HashMap` is `BTreeMap` without `std`, so it is covered by the `BTreeMap` impl
+#[cfg(feature = "std")]
 impl<K : ProtobufValue + Eq + Hash + 'static, V : ProtobufValue + 'static> ReflectMap
     for HashMap<K, V> {
     fn reflect_iter<'a>(&'a self) -> ReflectMapIter<'a> {
-        ReflectMapIter { imp: Box::new(ReflectMapIterImpl::<'a, K, V> { iter: self.iter() }) }
+        ReflectMapIter { imp: Box::new(ReflectMapIterImpl { iter: self.iter() }) }
     }
 
     fn len(&self) -> usize {
         HashMap::len(self)
     }
+
+    fn insert(&mut self, key: ProtobufValueBox, value: ProtobufValueBox) {
+        let (key, value) = key_value_from_boxes::<K, V>(key, value);
+        HashMap::insert(self, key, value);
+    }
+
+    fn clear(&mut self) {
+        HashMap::clear(self)
+    }
+}
+
+impl<K : ProtobufValue + Ord + 'static, V : ProtobufValue + 'static> ReflectMap
+    for BTreeMap<K, V> {
+    fn reflect_iter<'a>(&'a self) -> ReflectMapIter<'a> {
+        ReflectMapIter { imp: Box::new(ReflectMapIterImpl { iter: self.iter() }) }
+    }
+
+    fn len(&self) -> usize {
+        BTreeMap::len(self)
+    }
+
+    fn insert(&mut self, key: ProtobufValueBox, value: ProtobufValueBox) {
+        let (key, value) = key_value_from_boxes::<K, V>(key, value);
+        BTreeMap::insert(self, key, value);
+    }
+
+    fn clear(&mut self) {
+        BTreeMap::clear(self)
+    }
 }
 
 
@@ -28,15 +85,16 @@ trait ReflectMapIterTrait<'a> {
     fn next(&mut self) -> Option<(&'a ProtobufValue, &'a ProtobufValue)>;
 }
 
-struct ReflectMapIterImpl<'a, K : Eq + Hash + 'static, V : 'static> {
-    iter: hash_map::Iter<'a, K, V>,
+struct ReflectMapIterImpl<I> {
+    iter: I,
 }
 
 impl<
     'a,
-    K : ProtobufValue + Eq + Hash + 'static,
+    K : ProtobufValue + 'static,
     V : ProtobufValue + 'static,
-> ReflectMapIterTrait<'a> for ReflectMapIterImpl<'a, K, V> {
+    I : Iterator<Item = (&'a K, &'a V)>,
+> ReflectMapIterTrait<'a> for ReflectMapIterImpl<I> {
     fn next(&mut self) -> Option<(&'a ProtobufValue, &'a ProtobufValue)> {
         match self.iter.next() {
             Some((k, v)) => Some((k as &ProtobufValue, v as &ProtobufValue)),
@@ -1,11 +1,15 @@
 //! Reflection implementation for protobuf types.
 
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+use std::any::Any;
 use std::collections::HashMap;
 use std::default::Default;
 use std::marker;
 
-use core::Message;
-use core::ProtobufEnum;
+use message::Message;
+use message::ProtobufEnum;
 use descriptor::FileDescriptorProto;
 use descriptor::DescriptorProto;
 use descriptor::FieldDescriptorProto;
@@ -15,24 +19,29 @@ use descriptor::FieldDescriptorProto_Label;
 use descriptorx::find_enum_by_rust_name;
 use descriptorx::find_message_by_rust_name;
 use reflect::accessor::FieldAccessor;
+use strx;
 
 
 pub mod accessor;
+pub mod dynamic;
 mod map;
 mod repeated;
 mod value;
 mod optional;
+pub(crate) mod field_type;
 
 use self::repeated::ReflectRepeated;
 use self::map::ReflectMap;
 
 pub use self::value::ProtobufValue;
 pub use self::value::ProtobufValueRef;
+pub use self::value::ProtobufValueBox;
 
 
 pub struct FieldDescriptor {
     proto: &'static FieldDescriptorProto,
     accessor: Box<FieldAccessor + 'static>,
+    json_name: String,
 }
 
 impl FieldDescriptor {
@@ -41,9 +50,15 @@ impl FieldDescriptor {
         proto: &'static FieldDescriptorProto,
     ) -> FieldDescriptor {
         assert_eq!(proto.get_name(), a.name_generic());
+        let json_name = if proto.has_json_name() {
+            proto.get_json_name().to_owned()
+        } else {
+            strx::to_lower_camel_case(proto.get_name())
+        };
         FieldDescriptor {
             proto: proto,
             accessor: a,
+            json_name: json_name,
         }
     }
 
@@ -55,6 +70,11 @@ impl FieldDescriptor {
         self.proto.get_name()
     }
 
+    /// Field name in JSON, `lowerCamelCase` unless overridden with `json_name` option
+    pub fn json_name(&self) -> &str {
+        &self.json_name
+    }
+
     pub fn is_repeated(&self) -> bool {
         self.proto.get_label() == FieldDescriptorProto_Label::LABEL_REPEATED
     }
@@ -114,11 +134,93 @@ impl FieldDescriptor {
     pub fn get_reflect<'a>(&self, m: &'a Message) -> ReflectFieldRef<'a> {
         self.accessor.get_reflect(m)
     }
+
+    /// Set singular field value.
+    ///
+    /// Panics if field is repeated or value is of a different type.
+    pub fn set_singular_field(&self, m: &mut Message, value: ProtobufValueBox) {
+        self.accessor.set_singular_field_generic(m, value)
+    }
+
+    /// Unset singular field (set to default value for proto3 fields),
+    /// or remove all elements of repeated or map field.
+    pub fn clear_field(&self, m: &mut Message) {
+        self.accessor.clear_field_generic(m)
+    }
+
+    /// Get mutable reference to singular message field,
+    /// initializing it with empty message if it is not set.
+    ///
+    /// Panics for wrapper fields stored as `Option` of the wrapped value,
+    /// which have no message to reference, use `modify_message` for them.
+    pub fn mut_message<'a>(&self, m: &'a mut Message) -> &'a mut Message {
+        self.accessor.mut_message_generic(m)
+    }
+
+    /// Modify singular message field with given function,
+    /// initializing it with empty message if it is not set.
+    ///
+    /// Message is modified in place, except for wrapper fields stored as `Option`
+    /// of the wrapped value: for them a wrapper message is constructed,
+    /// modified and then stored back.
+    pub fn modify_message<F : FnOnce(&mut Message)>(&self, m: &mut Message, f: F) {
+        let wrapper = match self.get_reflect(m) {
+            ReflectFieldRef::Wrapper(w) => {
+                Some(w.unwrap_or_else(|| self.message_descriptor().new_instance()))
+            }
+            _ => None,
+        };
+        match wrapper {
+            Some(mut w) => {
+                f(&mut *w);
+                self.set_singular_field(m, ProtobufValueBox::Message(w));
+            }
+            None => f(self.mut_message(m)),
+        }
+    }
+
+    /// Append element to repeated field.
+    ///
+    /// Panics if field is not repeated or value is of a different type.
+    pub fn push_repeated(&self, m: &mut Message, value: ProtobufValueBox) {
+        self.accessor.push_repeated_generic(m, value)
+    }
+
+    /// Insert entry into map field, replacing previous value for the key.
+    ///
+    /// Panics if field is not a map or key or value is of a different type.
+    pub fn insert_map(&self, m: &mut Message, key: ProtobufValueBox, value: ProtobufValueBox) {
+        self.accessor.insert_map_generic(m, key, value)
+    }
+
+    /// Descriptor of the message type of this field
+    /// (of the value type for map fields).
+    ///
+    /// Panics if field is not a message field.
+    pub fn message_descriptor(&self) -> &'static MessageDescriptor {
+        match self.accessor.message_descriptor_generic() {
+            Some(d) => d,
+            None => panic!("not a message field: {}", self.name()),
+        }
+    }
+
+    /// Descriptor of the enum type of this field
+    /// (of the value type for map fields).
+    ///
+    /// Panics if field is not an enum field.
+    pub fn enum_descriptor(&self) -> &'static EnumDescriptor {
+        match self.accessor.enum_descriptor_generic() {
+            Some(d) => d,
+            None => panic!("not an enum field: {}", self.name()),
+        }
+    }
 }
 
 
-trait MessageFactory {
+trait MessageFactory : Send + Sync {
     fn new_instance(&self) -> Box<Message>;
+
+    fn downcast_mut<'a>(&self, m: &'a mut Any) -> &'a mut Message;
 }
 
 struct MessageFactoryTyped<M> {
@@ -140,6 +242,10 @@ impl<M : 'static + Message + Default> MessageFactory for MessageFactoryTyped<M>
         let m: M = Default::default();
         Box::new(m)
     }
+
+    fn downcast_mut<'a>(&self, m: &'a mut Any) -> &'a mut Message {
+        m.downcast_mut::<M>().unwrap()
+    }
 }
 
 pub struct MessageDescriptor {
@@ -164,28 +270,43 @@ impl MessageDescriptor {
     ) -> MessageDescriptor {
         let proto = find_message_by_rust_name(file, rust_name);
 
+        let mut full_name = file.get_package().to_string();
+        if full_name.len() > 0 {
+            full_name.push('.');
+        }
+        full_name.push_str(proto.message.get_name());
+
+        MessageDescriptor::new_with_factory(
+            full_name,
+            proto.message,
+            Box::new(MessageFactoryTyped::<M>::new()),
+            fields,
+        )
+    }
+
+    // shared by generated and dynamic messages
+    fn new_with_factory(
+        full_name: String,
+        proto: &'static DescriptorProto,
+        factory: Box<MessageFactory + 'static>,
+        fields: Vec<Box<FieldAccessor + 'static>>,
+    ) -> MessageDescriptor {
         let mut field_proto_by_name = HashMap::new();
-        for field_proto in proto.message.get_field() {
+        for field_proto in proto.get_field() {
             field_proto_by_name.insert(field_proto.get_name(), field_proto);
         }
 
         let mut index_by_name = HashMap::new();
         let mut index_by_number = HashMap::new();
-        for (i, f) in proto.message.get_field().iter().enumerate() {
+        for (i, f) in proto.get_field().iter().enumerate() {
             index_by_number.insert(f.get_number() as u32, i);
             index_by_name.insert(f.get_name().to_string(), i);
         }
 
-        let mut full_name = file.get_package().to_string();
-        if full_name.len() > 0 {
-            full_name.push('.');
-        }
-        full_name.push_str(proto.message.get_name());
-
         MessageDescriptor {
             full_name: full_name,
-            proto: proto.message,
-            factory: Box::new(MessageFactoryTyped::<M>::new()),
+            proto: proto,
+            factory: factory,
             fields: fields
                 .into_iter()
                 .map(|f| {
@@ -202,6 +323,15 @@ impl MessageDescriptor {
         self.factory.new_instance()
     }
 
+    /// View value of this message type as `Message`
+    pub(crate) fn downcast_mut<'a>(&self, m: &'a mut Any) -> &'a mut Message {
+        self.factory.downcast_mut(m)
+    }
+
+    pub fn proto(&self) -> &'static DescriptorProto {
+        self.proto
+    }
+
     pub fn name(&self) -> &'static str {
         self.proto.get_name()
     }
@@ -246,6 +376,7 @@ impl EnumValueDescriptor {
 pub struct EnumDescriptor {
     proto: &'static EnumDescriptorProto,
     values: Vec<EnumValueDescriptor>,
+    open: bool,
 
     index_by_name: HashMap<String, usize>,
     index_by_number: HashMap<i32, usize>,
@@ -261,26 +392,42 @@ impl EnumDescriptor {
     }
 
     pub fn new(rust_name: &'static str, file: &'static FileDescriptorProto) -> EnumDescriptor {
-        let proto = find_enum_by_rust_name(file, rust_name);
+        let open = file.get_syntax() == "proto3";
+        EnumDescriptor::new_with_proto(find_enum_by_rust_name(file, rust_name).en, open)
+    }
+
+    fn new_with_proto(proto: &'static EnumDescriptorProto, open: bool) -> EnumDescriptor {
         let mut index_by_name = HashMap::new();
         let mut index_by_number = HashMap::new();
-        for (i, v) in proto.en.get_value().iter().enumerate() {
+        for (i, v) in proto.get_value().iter().enumerate() {
             index_by_number.insert(v.get_number(), i);
             index_by_name.insert(v.get_name().to_string(), i);
         }
         EnumDescriptor {
-            proto: proto.en,
+            proto: proto,
             values: proto
-                .en
                 .get_value()
                 .iter()
                 .map(|v| EnumValueDescriptor { proto: v })
                 .collect(),
+            open: open,
             index_by_name: index_by_name,
             index_by_number: index_by_number,
         }
     }
 
+    /// Enum is defined in proto3 file.
+    ///
+    /// Fields of open enum type preserve numbers unknown to the enum,
+    /// while for proto2 (closed) enums such numbers are moved to unknown fields.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn values<'a>(&'a self) -> &'a [EnumValueDescriptor] {
+        &self.values
+    }
+
     pub fn value_by_name<'a>(&'a self, name: &str) -> &'a EnumValueDescriptor {
         // TODO: clone is weird
         let &index = self.index_by_name.get(&name.to_string()).unwrap();
@@ -291,6 +438,13 @@ impl EnumDescriptor {
         let &index = self.index_by_number.get(&number).unwrap();
         &self.values[index]
     }
+
+    /// Value descriptor is one of values of this enum, not just a value with the same number.
+    pub(crate) fn has_value(&self, value: &EnumValueDescriptor) -> bool {
+        self.values.iter().any(|v| {
+            v.proto as *const EnumValueDescriptorProto == value.proto as *const _
+        })
+    }
 }
 
 
@@ -298,4 +452,7 @@ pub enum ReflectFieldRef<'a> {
     Repeated(&'a ReflectRepeated),
     Map(&'a ReflectMap),
     Optional(Option<ProtobufValueRef<'a>>),
+    /// Wrapper well-known type field stored as `Option` of the wrapped value:
+    /// there is no message to reference, so wrapper message is constructed on access
+    Wrapper(Option<Box<Message>>),
 }
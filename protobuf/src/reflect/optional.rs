@@ -1,13 +1,26 @@
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+use std::any::Any;
 use std::mem;
 
 use super::value::ProtobufValue;
+use super::value::ProtobufValueBox;
 
 use singular::*;
 
 pub trait ReflectOptional: 'static {
     fn to_option(&self) -> Option<&ProtobufValue>;
 
+    /// Mutable reference to contained value, for downcasting to concrete type
+    fn mut_option_any(&mut self) -> Option<&mut Any>;
+
     fn set_value(&mut self, value: &ProtobufValue);
+
+    /// Replace contained value. Panics if value is of different type.
+    fn set_value_box(&mut self, value: ProtobufValueBox);
+
+    fn clear(&mut self);
 }
 
 impl<V : ProtobufValue + Clone + 'static> ReflectOptional for Option<V> {
@@ -15,12 +28,27 @@ impl<V : ProtobufValue + Clone + 'static> ReflectOptional for Option<V> {
         self.as_ref().map(|v| v as &ProtobufValue)
     }
 
+    fn mut_option_any(&mut self) -> Option<&mut Any> {
+        self.as_mut().map(|v| v as &mut Any)
+    }
+
     fn set_value(&mut self, value: &ProtobufValue) {
         match value.as_any().downcast_ref::<V>() {
             Some(v) => mem::replace(self, Some(v.clone())),
             None => panic!(),
         };
     }
+
+    fn set_value_box(&mut self, value: ProtobufValueBox) {
+        match V::from_value_box(value) {
+            Some(v) => *self = Some(v),
+            None => panic!("wrong type"),
+        }
+    }
+
+    fn clear(&mut self) {
+        *self = None;
+    }
 }
 
 impl<V : ProtobufValue + Clone + 'static> ReflectOptional for SingularField<V> {
@@ -28,12 +56,27 @@ impl<V : ProtobufValue + Clone + 'static> ReflectOptional for SingularField<V> {
         self.as_ref().map(|v| v as &ProtobufValue)
     }
 
+    fn mut_option_any(&mut self) -> Option<&mut Any> {
+        self.as_mut().map(|v| v as &mut Any)
+    }
+
     fn set_value(&mut self, value: &ProtobufValue) {
         match value.as_any().downcast_ref::<V>() {
             Some(v) => mem::replace(self, SingularField::some(v.clone())),
             None => panic!(),
         };
     }
+
+    fn set_value_box(&mut self, value: ProtobufValueBox) {
+        match V::from_value_box(value) {
+            Some(v) => *self = SingularField::some(v),
+            None => panic!("wrong type"),
+        }
+    }
+
+    fn clear(&mut self) {
+        *self = SingularField::none();
+    }
 }
 
 impl<V : ProtobufValue + Clone + 'static> ReflectOptional for SingularPtrField<V> {
@@ -41,10 +84,25 @@ impl<V : ProtobufValue + Clone + 'static> ReflectOptional for SingularPtrField<V
         self.as_ref().map(|v| v as &ProtobufValue)
     }
 
+    fn mut_option_any(&mut self) -> Option<&mut Any> {
+        self.as_mut().map(|v| v as &mut Any)
+    }
+
     fn set_value(&mut self, value: &ProtobufValue) {
         match value.as_any().downcast_ref::<V>() {
             Some(v) => mem::replace(self, SingularPtrField::some(v.clone())),
             None => panic!(),
         };
     }
+
+    fn set_value_box(&mut self, value: ProtobufValueBox) {
+        match V::from_value_box(value) {
+            Some(v) => *self = SingularPtrField::some(v),
+            None => panic!("wrong type"),
+        }
+    }
+
+    fn clear(&mut self) {
+        *self = SingularPtrField::none();
+    }
 }
@@ -1,12 +1,19 @@
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
 use std::slice;
 
 use super::value::ProtobufValue;
 use super::value::ProtobufValueRef;
+use super::value::ProtobufValueBox;
 
 pub trait ReflectRepeated: 'static {
     fn reflect_iter(&self) -> ReflectRepeatedIter;
     fn len(&self) -> usize;
     fn get(&self, index: usize) -> &ProtobufValue;
+    /// Append an element. Panics if value is of different type.
+    fn push(&mut self, value: ProtobufValueBox);
+    fn clear(&mut self);
 }
 
 impl<V : ProtobufValue + 'static> ReflectRepeated for Vec<V> {
@@ -23,6 +30,17 @@ impl<V : ProtobufValue + 'static> ReflectRepeated for Vec<V> {
     fn get(&self, index: usize) -> &ProtobufValue {
         &self[index]
     }
+
+    fn push(&mut self, value: ProtobufValueBox) {
+        match V::from_value_box(value) {
+            Some(v) => Vec::push(self, v),
+            None => panic!("wrong type"),
+        }
+    }
+
+    fn clear(&mut self) {
+        Vec::clear(self)
+    }
 }
 
 // useless
@@ -40,6 +58,14 @@ impl<V : ProtobufValue + 'static> ReflectRepeated for [V] {
     fn get(&self, index: usize) -> &ProtobufValue {
         &self[index]
     }
+
+    fn push(&mut self, _value: ProtobufValueBox) {
+        panic!("cannot push to slice")
+    }
+
+    fn clear(&mut self) {
+        panic!("cannot clear slice")
+    }
 }
 
 trait ReflectRepeatedIterTrait<'a> {
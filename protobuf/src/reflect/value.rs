@@ -5,7 +5,8 @@ use bytes::Bytes;
 #[cfg(feature = "bytes")]
 use chars::Chars;
 
-use core::*;
+use message::*;
+use enum_or_unknown::ProtobufEnumOrUnknown;
 use super::*;
 
 pub trait ProtobufValue: Any + 'static {
@@ -19,6 +20,16 @@ pub trait ProtobufValue: Any + 'static {
         self.as_ref().is_non_zero()
     }
 
+    /// Convert owned reflection value into a value of this type.
+    ///
+    /// Return `None` if value is of different type.
+    fn from_value_box(value: ProtobufValueBox) -> Option<Self>
+    where
+        Self : Sized,
+    {
+        value.downcast()
+    }
+
     fn as_ref_copy(&self) -> ProtobufValueRef<'static>
 //where Self : Copy // TODO
     {
@@ -103,6 +114,13 @@ impl ProtobufValue for Bytes {
     fn as_ref(&self) -> ProtobufValueRef {
         ProtobufValueRef::Bytes(&*self)
     }
+
+    fn from_value_box(value: ProtobufValueBox) -> Option<Bytes> {
+        match value {
+            ProtobufValueBox::Bytes(v) => Some(Bytes::from(v)),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(feature = "bytes")]
@@ -110,6 +128,33 @@ impl ProtobufValue for Chars {
     fn as_ref(&self) -> ProtobufValueRef {
         ProtobufValueRef::String(&*self)
     }
+
+    fn from_value_box(value: ProtobufValueBox) -> Option<Chars> {
+        match value {
+            ProtobufValueBox::String(v) => Some(Chars::from(v)),
+            _ => None,
+        }
+    }
+}
+
+// Numbers unknown to the enum are exposed as `I32`
+impl<E : ProtobufEnum> ProtobufValue for ProtobufEnumOrUnknown<E> {
+    fn as_ref(&self) -> ProtobufValueRef {
+        match self.enum_value() {
+            Ok(e) => ProtobufValueRef::Enum(e.descriptor()),
+            Err(v) => ProtobufValueRef::I32(v),
+        }
+    }
+
+    fn from_value_box(value: ProtobufValueBox) -> Option<ProtobufEnumOrUnknown<E>> {
+        match value {
+            ProtobufValueBox::Enum(v) if E::enum_descriptor_static(None).has_value(v) => {
+                Some(ProtobufEnumOrUnknown::from_i32(v.value()))
+            }
+            ProtobufValueBox::I32(v) => Some(ProtobufEnumOrUnknown::from_i32(v)),
+            _ => None,
+        }
+    }
 }
 
 // conflicting implementations, so generated code is used instead
@@ -118,6 +163,10 @@ impl<E : ProtobufEnum> ProtobufValue for E {
     fn as_ref(&self) -> ProtobufValueRef {
         ProtobufValueRef::Enum(self.descriptor())
     }
+
+    fn from_value_box(value: ProtobufValueBox) -> Option<E> {
+        value.into_enum()
+    }
 }
 
 impl<M : Message> ProtobufValue for M {
@@ -159,3 +208,74 @@ impl<'a> ProtobufValueRef<'a> {
         }
     }
 }
+
+
+/// Owned reflection value, used to modify messages through reflection
+pub enum ProtobufValueBox {
+    U32(u32),
+    U64(u64),
+    I32(i32),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+    Bool(bool),
+    String(String),
+    Bytes(Vec<u8>),
+    Enum(&'static EnumValueDescriptor),
+    Message(Box<Message>),
+}
+
+// used as element type of repeated and map fields of dynamic messages
+impl ProtobufValue for ProtobufValueBox {
+    fn as_ref(&self) -> ProtobufValueRef {
+        match *self {
+            ProtobufValueBox::U32(v) => ProtobufValueRef::U32(v),
+            ProtobufValueBox::U64(v) => ProtobufValueRef::U64(v),
+            ProtobufValueBox::I32(v) => ProtobufValueRef::I32(v),
+            ProtobufValueBox::I64(v) => ProtobufValueRef::I64(v),
+            ProtobufValueBox::F32(v) => ProtobufValueRef::F32(v),
+            ProtobufValueBox::F64(v) => ProtobufValueRef::F64(v),
+            ProtobufValueBox::Bool(v) => ProtobufValueRef::Bool(v),
+            ProtobufValueBox::String(ref v) => ProtobufValueRef::String(v),
+            ProtobufValueBox::Bytes(ref v) => ProtobufValueRef::Bytes(v),
+            ProtobufValueBox::Enum(v) => ProtobufValueRef::Enum(v),
+            ProtobufValueBox::Message(ref v) => ProtobufValueRef::Message(&**v),
+        }
+    }
+
+    fn from_value_box(value: ProtobufValueBox) -> Option<ProtobufValueBox> {
+        Some(value)
+    }
+}
+
+impl ProtobufValueBox {
+    /// Convert to value of given type, `None` if value has different type.
+    ///
+    /// Enum values cannot be converted with this function, use `into_enum` instead.
+    pub fn downcast<V : Any>(self) -> Option<V> {
+        let any: Box<Any> = match self {
+            ProtobufValueBox::U32(v) => Box::new(v),
+            ProtobufValueBox::U64(v) => Box::new(v),
+            ProtobufValueBox::I32(v) => Box::new(v),
+            ProtobufValueBox::I64(v) => Box::new(v),
+            ProtobufValueBox::F32(v) => Box::new(v),
+            ProtobufValueBox::F64(v) => Box::new(v),
+            ProtobufValueBox::Bool(v) => Box::new(v),
+            ProtobufValueBox::String(v) => Box::new(v),
+            ProtobufValueBox::Bytes(v) => Box::new(v),
+            ProtobufValueBox::Enum(..) => return None,
+            ProtobufValueBox::Message(v) => v.into_any(),
+        };
+        any.downcast::<V>().ok().map(|v| *v)
+    }
+
+    /// Convert to enum value, `None` if value is not a value of given enum type.
+    pub fn into_enum<E : ProtobufEnum>(self) -> Option<E> {
+        match self {
+            ProtobufValueBox::Enum(v) if E::enum_descriptor_static(None).has_value(v) => {
+                E::from_i32(v.value())
+            }
+            _ => None,
+        }
+    }
+}
@@ -1,29 +1,42 @@
 //! Functions used by generated protobuf code.
 //! Should not be used by programs written by hands.
 
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+#[cfg(feature = "std")]
 use std::hash::Hash;
+#[cfg(feature = "std")]
 use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 #[cfg(feature = "bytes")]
 use bytes::Bytes;
 #[cfg(feature = "bytes")]
 use chars::Chars;
 
-use core::*;
+use message::*;
+use enum_or_unknown::ProtobufEnumOrUnknown;
 use zigzag::*;
 use stream::wire_format;
 use stream::wire_format::WireType;
 use stream::wire_format::WireTypeFixed32;
 use stream::wire_format::WireTypeFixed64;
 use stream::wire_format::WireTypeLengthDelimited;
+use stream::wire_format::WireTypeStartGroup;
 use stream::wire_format::WireTypeVarint;
 use error::ProtobufError;
 use error::ProtobufResult;
 use error::WireError;
+use reflect::ProtobufValue;
 use singular::SingularField;
 use singular::SingularPtrField;
 use stream::CodedInputStream;
+use stream::CodedOutputStream;
 use types::*;
+use unknown::UnknownFields;
+use unknown::UnknownValueRef;
+use well_known_types_util::WrapperMessage;
 
 
 /// Given `u64` value compute varint encoded length.
@@ -146,6 +159,15 @@ pub fn vec_packed_enum_data_size<E : ProtobufEnum>(vec: &[E]) -> u32 {
         .fold(0, |a, i| a + i)
 }
 
+/// Size of serialized repeated packed enum field, excluding length and tag.
+pub fn vec_packed_enum_or_unknown_data_size<E : ProtobufEnum>(
+    vec: &[ProtobufEnumOrUnknown<E>],
+) -> u32 {
+    vec.iter()
+        .map(|e| compute_raw_varint32_size(e.value() as u32))
+        .fold(0, |a, i| a + i)
+}
+
 /// Size of serialized data with length prefix and tag
 pub fn vec_packed_varint_size<T : ProtobufVarint>(field_number: u32, vec: &[T]) -> u32 {
     if vec.is_empty() {
@@ -179,6 +201,19 @@ pub fn vec_packed_enum_size<E : ProtobufEnum>(field_number: u32, vec: &[E]) -> u
     }
 }
 
+/// Size of serialized data with length prefix and tag
+pub fn vec_packed_enum_or_unknown_size<E : ProtobufEnum>(
+    field_number: u32,
+    vec: &[ProtobufEnumOrUnknown<E>],
+) -> u32 {
+    if vec.is_empty() {
+        0
+    } else {
+        let data_size = vec_packed_enum_or_unknown_data_size(vec);
+        tag_size(field_number) + data_size.len_varint() + data_size
+    }
+}
+
 /// Compute tag size. Size of tag does not depend on wire type.
 pub fn tag_size(field_number: u32) -> u32 {
     wire_format::Tag::make(field_number, WireTypeFixed64)
@@ -219,6 +254,14 @@ pub fn enum_size<E : ProtobufEnum>(field_number: u32, value: E) -> u32 {
     tag_size(field_number) + enum_size_no_tag(value)
 }
 
+/// Size of encoded enum field value, known or not.
+pub fn enum_or_unknown_size<E : ProtobufEnum>(
+    field_number: u32,
+    value: ProtobufEnumOrUnknown<E>,
+) -> u32 {
+    tag_size(field_number) + value.value().len_varint()
+}
+
 fn bytes_size_no_tag(bytes: &[u8]) -> u32 {
     compute_raw_varint64_size(bytes.len() as u64) + bytes.len() as u32
 }
@@ -237,6 +280,26 @@ pub fn string_size(field_number: u32, s: &str) -> u32 {
     tag_size(field_number) + string_size_no_tag(s)
 }
 
+fn unknown_value_size_no_tag(value: UnknownValueRef) -> u32 {
+    match value {
+        UnknownValueRef::Fixed32(_) => 4,
+        UnknownValueRef::Fixed64(_) => 8,
+        UnknownValueRef::Varint(varint) => compute_raw_varint64_size(varint),
+        UnknownValueRef::LengthDelimited(bytes) => bytes_size_no_tag(bytes),
+    }
+}
+
+/// Size of encoded unknown fields.
+pub fn unknown_fields_size(unknown_fields: &UnknownFields) -> u32 {
+    let mut r = 0;
+    for (number, values) in unknown_fields {
+        for value in values {
+            r += tag_size(number) + unknown_value_size_no_tag(value);
+        }
+    }
+    r
+}
+
 /// Read repeated `int32` field into given vec.
 pub fn read_repeated_int32_into(
     wire_type: WireType,
@@ -461,6 +524,86 @@ pub fn read_repeated_enum_into<E : ProtobufEnum>(
     }
 }
 
+/// Read repeated `enum` field into given vec, preserving unknown values.
+pub fn read_repeated_enum_or_unknown_into<E : ProtobufEnum>(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut Vec<ProtobufEnumOrUnknown<E>>,
+) -> ProtobufResult<()> {
+    match wire_type {
+        WireTypeLengthDelimited => is.read_repeated_packed_enum_or_unknown_into(target),
+        WireTypeVarint => {
+            target.push(is.read_enum_or_unknown()?);
+            Ok(())
+        }
+        _ => Err(unexpected_wire_type(wire_type)),
+    }
+}
+
+/// Read proto2 `enum` field value.
+///
+/// Proto2 enums are closed: if the number is not known to `E`,
+/// it is stored in unknown fields and `None` is returned.
+pub fn read_proto2_enum_with_unknown_fields<E : ProtobufEnum>(
+    is: &mut CodedInputStream,
+    field_number: u32,
+    unknown_fields: &mut UnknownFields,
+) -> ProtobufResult<Option<E>> {
+    let i = is.read_int32()?;
+    match E::from_i32(i) {
+        Some(e) => Ok(Some(e)),
+        None => {
+            unknown_fields.add_varint(field_number, i as i64 as u64);
+            Ok(None)
+        }
+    }
+}
+
+fn read_repeated_packed_proto2_enum_with_unknown_fields_into<E : ProtobufEnum>(
+    is: &mut CodedInputStream,
+    target: &mut Vec<E>,
+    field_number: u32,
+    unknown_fields: &mut UnknownFields,
+) -> ProtobufResult<()> {
+    let len = is.read_raw_varint64()?;
+    let old_limit = is.push_limit(len)?;
+    while !is.eof()? {
+        if let Some(e) = read_proto2_enum_with_unknown_fields(is, field_number, unknown_fields)? {
+            target.push(e);
+        }
+    }
+    is.pop_limit(old_limit);
+    Ok(())
+}
+
+/// Read repeated proto2 `enum` field into given vec.
+///
+/// Numbers not known to `E` are stored in unknown fields.
+pub fn read_repeated_proto2_enum_with_unknown_fields_into<E : ProtobufEnum>(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut Vec<E>,
+    field_number: u32,
+    unknown_fields: &mut UnknownFields,
+) -> ProtobufResult<()> {
+    match wire_type {
+        WireTypeLengthDelimited => read_repeated_packed_proto2_enum_with_unknown_fields_into(
+            is,
+            target,
+            field_number,
+            unknown_fields,
+        ),
+        WireTypeVarint => {
+            let e = read_proto2_enum_with_unknown_fields(is, field_number, unknown_fields)?;
+            if let Some(e) = e {
+                target.push(e);
+            }
+            Ok(())
+        }
+        _ => Err(unexpected_wire_type(wire_type)),
+    }
+}
+
 /// Read repeated `string` field into given vec.
 pub fn read_repeated_string_into(
     wire_type: WireType,
@@ -675,6 +818,190 @@ pub fn read_singular_message_into<M : Message>(
     }
 }
 
+/// Read singular `message` field merging it into the current value.
+pub fn merge_singular_message_into<M : Message + Default>(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut SingularPtrField<M>,
+) -> ProtobufResult<()> {
+    match wire_type {
+        WireTypeLengthDelimited => {
+            if target.is_none() {
+                target.set_default();
+            }
+            is.merge_message(target.get_mut_ref())
+        }
+        _ => Err(unexpected_wire_type(wire_type)),
+    }
+}
+
+/// Read repeated `group` field.
+pub fn read_repeated_group_into<M : Message>(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut Vec<M>,
+    field_number: u32,
+) -> ProtobufResult<()> {
+    match wire_type {
+        WireTypeStartGroup => {
+            target.push(is.read_group(field_number)?);
+            Ok(())
+        }
+        _ => Err(unexpected_wire_type(wire_type)),
+    }
+}
+
+/// Read singular `group` field merging it into the current value.
+pub fn merge_singular_group_into<M : Message + Default>(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut SingularPtrField<M>,
+    field_number: u32,
+) -> ProtobufResult<()> {
+    match wire_type {
+        WireTypeStartGroup => {
+            if target.is_none() {
+                target.set_default();
+            }
+            is.merge_group(target.get_mut_ref(), field_number)
+        }
+        _ => Err(unexpected_wire_type(wire_type)),
+    }
+}
+
+/// Merge singular `message` field of other message into the field of this message.
+pub fn merge_singular_message<M : Message + Clone + Default>(
+    target: &mut SingularPtrField<M>,
+    other: &SingularPtrField<M>,
+) {
+    if let Some(other) = other.as_ref() {
+        if target.is_none() {
+            target.set_default();
+        }
+        target.get_mut_ref().merge(other);
+    }
+}
+
+/// Read singular `message` field stored as `Option<Box<M>>` merging it into the current value.
+pub fn merge_option_message_into<M : Message + Default>(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut Option<Box<M>>,
+) -> ProtobufResult<()> {
+    match wire_type {
+        WireTypeLengthDelimited => {
+            is.merge_message(&mut **target.get_or_insert_with(Default::default))
+        }
+        _ => Err(unexpected_wire_type(wire_type)),
+    }
+}
+
+/// Read singular `group` field stored as `Option<Box<M>>` merging it into the current value.
+pub fn merge_option_group_into<M : Message + Default>(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut Option<Box<M>>,
+    field_number: u32,
+) -> ProtobufResult<()> {
+    match wire_type {
+        WireTypeStartGroup => {
+            is.merge_group(&mut **target.get_or_insert_with(Default::default), field_number)
+        }
+        _ => Err(unexpected_wire_type(wire_type)),
+    }
+}
+
+/// Merge singular `message` field stored as `Option<Box<M>>`
+/// of other message into the field of this message.
+pub fn merge_option_message<M : Message + Clone + Default>(
+    target: &mut Option<Box<M>>,
+    other: &Option<Box<M>>,
+) {
+    if let Some(ref other) = *other {
+        target.get_or_insert_with(Default::default).merge(other);
+    }
+}
+
+/// Read wrapper well-known type field stored as `Option` of the wrapped value.
+pub fn read_wrapper<W : WrapperMessage>(
+    is: &mut CodedInputStream,
+) -> ProtobufResult<<W::ValueType as ProtobufType>::Value> {
+    Ok(is.read_message::<W>()?.into_value())
+}
+
+/// Read wrapper well-known type field merging it into the current value.
+pub fn merge_wrapper_into<W : WrapperMessage>(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut Option<<W::ValueType as ProtobufType>::Value>,
+) -> ProtobufResult<()> {
+    match wire_type {
+        WireTypeLengthDelimited => {
+            let mut wrapper = match target.take() {
+                Some(v) => W::from_value(v),
+                None => W::new(),
+            };
+            is.merge_message(&mut wrapper)?;
+            *target = Some(wrapper.into_value());
+            Ok(())
+        }
+        _ => Err(unexpected_wire_type(wire_type)),
+    }
+}
+
+/// Merge wrapper well-known type field of other message of the same type.
+pub fn merge_wrapper<W : WrapperMessage>(
+    target: &mut Option<<W::ValueType as ProtobufType>::Value>,
+    other: &Option<<W::ValueType as ProtobufType>::Value>,
+) {
+    if let Some(ref v) = *other {
+        // default `value` is not serialized, so merging it keeps current value
+        if target.is_none() || v.is_non_zero() {
+            *target = Some(v.clone());
+        }
+    }
+}
+
+// size of the wrapper message content
+fn wrapper_data_size<W : WrapperMessage>(value: &<W::ValueType as ProtobufType>::Value) -> u32 {
+    if value.is_non_zero() {
+        tag_size(1) + size_with_length_delimiter::<W::ValueType>(value) as u32
+    } else {
+        0
+    }
+}
+
+/// Size of wrapper well-known type field stored as `Option` of the wrapped value,
+/// including tag.
+pub fn wrapper_size<W : WrapperMessage>(
+    field_number: u32,
+    value: &Option<<W::ValueType as ProtobufType>::Value>,
+) -> u32 {
+    match *value {
+        Some(ref v) => {
+            let len = wrapper_data_size::<W>(v);
+            tag_size(field_number) + compute_raw_varint32_size(len) + len
+        }
+        None => 0,
+    }
+}
+
+/// Write wrapper well-known type field stored as `Option` of the wrapped value.
+pub fn write_wrapper<W : WrapperMessage>(
+    field_number: u32,
+    value: &Option<<W::ValueType as ProtobufType>::Value>,
+    os: &mut CodedOutputStream,
+) -> ProtobufResult<()> {
+    if let Some(ref v) = *value {
+        os.write_tag(field_number, WireTypeLengthDelimited)?;
+        os.write_raw_varint32(wrapper_data_size::<W>(v))?;
+        if v.is_non_zero() {
+            W::ValueType::write_with_cached_size(1, v, os)?;
+        }
+    }
+    Ok(())
+}
+
 fn skip_group(is: &mut CodedInputStream) -> ProtobufResult<()> {
     loop {
         let (_, wire_type) = is.read_tag_unpack()?;
@@ -701,6 +1028,24 @@ pub fn skip_unknown_or_group(
     }
 }
 
+/// Handle unknown field in generated code.
+/// Either store a value in unknown fields, or skip a group.
+pub fn read_unknown_or_skip_group(
+    field_number: u32,
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    unknown_fields: &mut UnknownFields,
+) -> ProtobufResult<()> {
+    match wire_type {
+        wire_format::WireTypeStartGroup => skip_group(is),
+        _ => {
+            let unknown = is.read_unknown(wire_type)?;
+            unknown_fields.add_value(field_number, unknown);
+            Ok(())
+        }
+    }
+}
+
 
 /// Create an error for unexpected wire type.
 ///
@@ -711,15 +1056,16 @@ pub fn unexpected_wire_type(wire_type: WireType) -> ProtobufError {
 }
 
 
-/// Compute serialized size of `map` field and cache nested field sizes.
-pub fn compute_map_size<K, V>(field_number: u32, map: &HashMap<K::Value, V::Value>) -> u32
+fn compute_map_entries_size<'a, K, V, I>(field_number: u32, entries: I) -> u32
 where
     K : ProtobufType,
     V : ProtobufType,
-    K::Value : Eq + Hash,
+    K::Value : 'a,
+    V::Value : 'a,
+    I : Iterator<Item = (&'a K::Value, &'a V::Value)>,
 {
     let mut sum = 0;
-    for (k, v) in map {
+    for (k, v) in entries {
         let key_tag_size = 1;
         let value_tag_size = 1;
 
@@ -732,17 +1078,40 @@ where
     sum
 }
 
+/// Compute serialized size of `map` field and cache nested field sizes.
+#[cfg(feature = "std")]
+pub fn compute_map_size<K, V>(field_number: u32, map: &HashMap<K::Value, V::Value>) -> u32
+where
+    K : ProtobufType,
+    V : ProtobufType,
+    K::Value : Eq + Hash,
+{
+    compute_map_entries_size::<K, V, _>(field_number, map.iter())
+}
 
-/// Read `map` field.
-pub fn read_map_into<K, V>(
+/// Compute serialized size of `map` field stored in `BTreeMap` and cache nested field sizes.
+pub fn compute_btree_map_size<K, V>(field_number: u32, map: &BTreeMap<K::Value, V::Value>) -> u32
+where
+    K : ProtobufType,
+    V : ProtobufType,
+    K::Value : Ord,
+{
+    compute_map_entries_size::<K, V, _>(field_number, map.iter())
+}
+
+// `HashMap` is `BTreeMap` without `std`
+#[cfg(not(feature = "std"))]
+pub use self::compute_btree_map_size as compute_map_size;
+
+
+/// Read map entry
+fn read_map_entry<K, V>(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut HashMap<K::Value, V::Value>,
-) -> ProtobufResult<()>
+) -> ProtobufResult<(K::Value, V::Value)>
 where
     K : ProtobufType,
     V : ProtobufType,
-    K::Value : Eq + Hash,
 {
     if wire_type != WireType::WireTypeLengthDelimited {
         return Err(unexpected_wire_type(wire_type));
@@ -774,15 +1143,48 @@ where
     is.pop_limit(old_limit);
 
     match (key, value) {
-        (None, _) | (_, None) => return Err(ProtobufError::WireError(WireError::IncompleteMap)),
-        (Some(key), Some(value)) => {
-            target.insert(key, value);
-        }
+        (Some(key), Some(value)) => Ok((key, value)),
+        (None, _) | (_, None) => Err(ProtobufError::WireError(WireError::IncompleteMap)),
     }
+}
 
+/// Read `map` field.
+#[cfg(feature = "std")]
+pub fn read_map_into<K, V>(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut HashMap<K::Value, V::Value>,
+) -> ProtobufResult<()>
+where
+    K : ProtobufType,
+    V : ProtobufType,
+    K::Value : Eq + Hash,
+{
+    let (key, value) = read_map_entry::<K, V>(wire_type, is)?;
+    target.insert(key, value);
+    Ok(())
+}
+
+/// Read `map` field stored in `BTreeMap`.
+pub fn read_btree_map_into<K, V>(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut BTreeMap<K::Value, V::Value>,
+) -> ProtobufResult<()>
+where
+    K : ProtobufType,
+    V : ProtobufType,
+    K::Value : Ord,
+{
+    let (key, value) = read_map_entry::<K, V>(wire_type, is)?;
+    target.insert(key, value);
     Ok(())
 }
 
+// `HashMap` is `BTreeMap` without `std`
+#[cfg(not(feature = "std"))]
+pub use self::read_btree_map_into as read_map_into;
+
 pub fn size_with_length_delimiter<T: ProtobufType>(value: &T::Value) -> usize {
     let size = T::compute_size(value) as usize;
 
@@ -1,3 +1,6 @@
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
 static RUST_KEYWORDS: &'static [&'static str] = &[
     "as",
     "break",
@@ -28,13 +28,55 @@ pub mod exts {
 
     pub const carllerche_bytes_for_string_all: ::protobuf::ext::ExtFieldOptional<::protobuf::descriptor::FileOptions, ::protobuf::types::ProtobufTypeBool> = ::protobuf::ext::ExtFieldOptional { field_number: 17012, phantom: ::std::marker::PhantomData };
 
+    pub const wrappers_as_option_all: ::protobuf::ext::ExtFieldOptional<::protobuf::descriptor::FileOptions, ::protobuf::types::ProtobufTypeBool> = ::protobuf::ext::ExtFieldOptional { field_number: 17013, phantom: ::std::marker::PhantomData };
+
+    pub const serde_derive_all: ::protobuf::ext::ExtFieldOptional<::protobuf::descriptor::FileOptions, ::protobuf::types::ProtobufTypeBool> = ::protobuf::ext::ExtFieldOptional { field_number: 17014, phantom: ::std::marker::PhantomData };
+
+    pub const with_methods_all: ::protobuf::ext::ExtFieldOptional<::protobuf::descriptor::FileOptions, ::protobuf::types::ProtobufTypeBool> = ::protobuf::ext::ExtFieldOptional { field_number: 17015, phantom: ::std::marker::PhantomData };
+
+    pub const idiomatic_names_all: ::protobuf::ext::ExtFieldOptional<::protobuf::descriptor::FileOptions, ::protobuf::types::ProtobufTypeBool> = ::protobuf::ext::ExtFieldOptional { field_number: 17016, phantom: ::std::marker::PhantomData };
+
+    pub const singular_field_option_all: ::protobuf::ext::ExtFieldOptional<::protobuf::descriptor::FileOptions, ::protobuf::types::ProtobufTypeBool> = ::protobuf::ext::ExtFieldOptional { field_number: 17017, phantom: ::std::marker::PhantomData };
+
+    pub const package_modules_all: ::protobuf::ext::ExtFieldOptional<::protobuf::descriptor::FileOptions, ::protobuf::types::ProtobufTypeBool> = ::protobuf::ext::ExtFieldOptional { field_number: 17018, phantom: ::std::marker::PhantomData };
+
+    pub const message_derive_all: ::protobuf::ext::ExtFieldRepeated<::protobuf::descriptor::FileOptions, ::protobuf::types::ProtobufTypeString> = ::protobuf::ext::ExtFieldRepeated { field_number: 17019, phantom: ::std::marker::PhantomData };
+
+    pub const message_attr_all: ::protobuf::ext::ExtFieldRepeated<::protobuf::descriptor::FileOptions, ::protobuf::types::ProtobufTypeString> = ::protobuf::ext::ExtFieldRepeated { field_number: 17020, phantom: ::std::marker::PhantomData };
+
+    pub const enum_derive_all: ::protobuf::ext::ExtFieldRepeated<::protobuf::descriptor::FileOptions, ::protobuf::types::ProtobufTypeString> = ::protobuf::ext::ExtFieldRepeated { field_number: 17021, phantom: ::std::marker::PhantomData };
+
+    pub const enum_attr_all: ::protobuf::ext::ExtFieldRepeated<::protobuf::descriptor::FileOptions, ::protobuf::types::ProtobufTypeString> = ::protobuf::ext::ExtFieldRepeated { field_number: 17022, phantom: ::std::marker::PhantomData };
+
+    pub const no_std_all: ::protobuf::ext::ExtFieldOptional<::protobuf::descriptor::FileOptions, ::protobuf::types::ProtobufTypeBool> = ::protobuf::ext::ExtFieldOptional { field_number: 17023, phantom: ::std::marker::PhantomData };
+
     pub const carllerche_bytes_for_bytes: ::protobuf::ext::ExtFieldOptional<::protobuf::descriptor::MessageOptions, ::protobuf::types::ProtobufTypeBool> = ::protobuf::ext::ExtFieldOptional { field_number: 17011, phantom: ::std::marker::PhantomData };
 
     pub const carllerche_bytes_for_string: ::protobuf::ext::ExtFieldOptional<::protobuf::descriptor::MessageOptions, ::protobuf::types::ProtobufTypeBool> = ::protobuf::ext::ExtFieldOptional { field_number: 17012, phantom: ::std::marker::PhantomData };
 
+    pub const wrappers_as_option: ::protobuf::ext::ExtFieldOptional<::protobuf::descriptor::MessageOptions, ::protobuf::types::ProtobufTypeBool> = ::protobuf::ext::ExtFieldOptional { field_number: 17013, phantom: ::std::marker::PhantomData };
+
+    pub const serde_derive: ::protobuf::ext::ExtFieldOptional<::protobuf::descriptor::MessageOptions, ::protobuf::types::ProtobufTypeBool> = ::protobuf::ext::ExtFieldOptional { field_number: 17014, phantom: ::std::marker::PhantomData };
+
+    pub const singular_field_option: ::protobuf::ext::ExtFieldOptional<::protobuf::descriptor::MessageOptions, ::protobuf::types::ProtobufTypeBool> = ::protobuf::ext::ExtFieldOptional { field_number: 17017, phantom: ::std::marker::PhantomData };
+
+    pub const message_derive: ::protobuf::ext::ExtFieldRepeated<::protobuf::descriptor::MessageOptions, ::protobuf::types::ProtobufTypeString> = ::protobuf::ext::ExtFieldRepeated { field_number: 17019, phantom: ::std::marker::PhantomData };
+
+    pub const message_attr: ::protobuf::ext::ExtFieldRepeated<::protobuf::descriptor::MessageOptions, ::protobuf::types::ProtobufTypeString> = ::protobuf::ext::ExtFieldRepeated { field_number: 17020, phantom: ::std::marker::PhantomData };
+
+    pub const enum_derive: ::protobuf::ext::ExtFieldRepeated<::protobuf::descriptor::EnumOptions, ::protobuf::types::ProtobufTypeString> = ::protobuf::ext::ExtFieldRepeated { field_number: 17021, phantom: ::std::marker::PhantomData };
+
+    pub const enum_attr: ::protobuf::ext::ExtFieldRepeated<::protobuf::descriptor::EnumOptions, ::protobuf::types::ProtobufTypeString> = ::protobuf::ext::ExtFieldRepeated { field_number: 17022, phantom: ::std::marker::PhantomData };
+
     pub const carllerche_bytes_for_bytes_field: ::protobuf::ext::ExtFieldOptional<::protobuf::descriptor::FieldOptions, ::protobuf::types::ProtobufTypeBool> = ::protobuf::ext::ExtFieldOptional { field_number: 17011, phantom: ::std::marker::PhantomData };
 
     pub const carllerche_bytes_for_string_field: ::protobuf::ext::ExtFieldOptional<::protobuf::descriptor::FieldOptions, ::protobuf::types::ProtobufTypeBool> = ::protobuf::ext::ExtFieldOptional { field_number: 17012, phantom: ::std::marker::PhantomData };
+
+    pub const wrappers_as_option_field: ::protobuf::ext::ExtFieldOptional<::protobuf::descriptor::FieldOptions, ::protobuf::types::ProtobufTypeBool> = ::protobuf::ext::ExtFieldOptional { field_number: 17013, phantom: ::std::marker::PhantomData };
+
+    pub const singular_field_option_field: ::protobuf::ext::ExtFieldOptional<::protobuf::descriptor::FieldOptions, ::protobuf::types::ProtobufTypeBool> = ::protobuf::ext::ExtFieldOptional { field_number: 17017, phantom: ::std::marker::PhantomData };
+
+    pub const field_attr: ::protobuf::ext::ExtFieldRepeated<::protobuf::descriptor::FieldOptions, ::protobuf::types::ProtobufTypeString> = ::protobuf::ext::ExtFieldRepeated { field_number: 17020, phantom: ::std::marker::PhantomData };
 }
 
 static file_descriptor_proto_data: &'static [u8] = b"\
@@ -42,44 +84,176 @@ static file_descriptor_proto_data: &'static [u8] = b"\
     roto:b\n\x1ecarllerche_bytes_for_bytes_all\x18\xf3\x84\x01\x20\x01(\x08\
     \x12\x1c.google.protobuf.FileOptionsR\x1acarllercheBytesForBytesAll:d\n\
     \x1fcarllerche_bytes_for_string_all\x18\xf4\x84\x01\x20\x01(\x08\x12\x1c\
-    .google.protobuf.FileOptionsR\x1bcarllercheBytesForStringAll:^\n\x1acarl\
-    lerche_bytes_for_bytes\x18\xf3\x84\x01\x20\x01(\x08\x12\x1f.google.proto\
-    buf.MessageOptionsR\x17carllercheBytesForBytes:`\n\x1bcarllerche_bytes_f\
-    or_string\x18\xf4\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOp\
-    tionsR\x18carllercheBytesForString:g\n\x20carllerche_bytes_for_bytes_fie\
-    ld\x18\xf3\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\
-    \x1ccarllercheBytesForBytesField:i\n!carllerche_bytes_for_string_field\
+    .google.protobuf.FileOptionsR\x1bcarllercheBytesForStringAll:S\n\x16wrap\
+    pers_as_option_all\x18\xf5\x84\x01\x20\x01(\x08\x12\x1c.google.protobuf.\
+    FileOptionsR\x13wrappersAsOptionAll:H\n\x10serde_derive_all\x18\xf6\x84\
+    \x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x0eserdeDeriveAll\
+    :H\n\x10with_methods_all\x18\xf7\x84\x01\x20\x01(\x08\x12\x1c.google.pro\
+    tobuf.FileOptionsR\x0ewithMethodsAll:N\n\x13idiomatic_names_all\x18\xf8\
+    \x84\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x11idiomaticN\
+    amesAll:Y\n\x19singular_field_option_all\x18\xf9\x84\x01\x20\x01(\x08\
+    \x12\x1c.google.protobuf.FileOptionsR\x16singularFieldOptionAll:N\n\x13p\
+    ackage_modules_all\x18\xfa\x84\x01\x20\x01(\x08\x12\x1c.google.protobuf.\
+    FileOptionsR\x11packageModulesAll:L\n\x12message_derive_all\x18\xfb\x84\
+    \x01\x20\x03(\t\x12\x1c.google.protobuf.FileOptionsR\x10messageDeriveAll\
+    :H\n\x10message_attr_all\x18\xfc\x84\x01\x20\x03(\t\x12\x1c.google.proto\
+    buf.FileOptionsR\x0emessageAttrAll:F\n\x0fenum_derive_all\x18\xfd\x84\
+    \x01\x20\x03(\t\x12\x1c.google.protobuf.FileOptionsR\renumDeriveAll:B\n\
+    \renum_attr_all\x18\xfe\x84\x01\x20\x03(\t\x12\x1c.google.protobuf.FileO\
+    ptionsR\x0benumAttrAll:<\n\nno_std_all\x18\xff\x84\x01\x20\x01(\x08\x12\
+    \x1c.google.protobuf.FileOptionsR\x08noStdAll:^\n\x1acarllerche_bytes_fo\
+    r_bytes\x18\xf3\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOpti\
+    onsR\x17carllercheBytesForBytes:`\n\x1bcarllerche_bytes_for_string\x18\
+    \xf4\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\x18car\
+    llercheBytesForString:O\n\x12wrappers_as_option\x18\xf5\x84\x01\x20\x01(\
+    \x08\x12\x1f.google.protobuf.MessageOptionsR\x10wrappersAsOption:D\n\x0c\
+    serde_derive\x18\xf6\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.Messag\
+    eOptionsR\x0bserdeDerive:U\n\x15singular_field_option\x18\xf9\x84\x01\
+    \x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\x13singularFieldOp\
+    tion:H\n\x0emessage_derive\x18\xfb\x84\x01\x20\x03(\t\x12\x1f.google.pro\
+    tobuf.MessageOptionsR\rmessageDerive:D\n\x0cmessage_attr\x18\xfc\x84\x01\
+    \x20\x03(\t\x12\x1f.google.protobuf.MessageOptionsR\x0bmessageAttr:?\n\
+    \x0benum_derive\x18\xfd\x84\x01\x20\x03(\t\x12\x1c.google.protobuf.EnumO\
+    ptionsR\nenumDerive:;\n\tenum_attr\x18\xfe\x84\x01\x20\x03(\t\x12\x1c.go\
+    ogle.protobuf.EnumOptionsR\x08enumAttr:g\n\x20carllerche_bytes_for_bytes\
+    _field\x18\xf3\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptions\
+    R\x1ccarllercheBytesForBytesField:i\n!carllerche_bytes_for_string_field\
     \x18\xf4\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x1dc\
-    arllercheBytesForStringFieldJ\xcb\x06\n\x06\x12\x04\0\0\x1c\x01\n\x08\n\
-    \x01\x0c\x12\x03\0\0\x12\n\t\n\x02\x03\0\x12\x03\x02\x07)\nh\n\x01\x02\
-    \x12\x03\x07\x08\x112^\x20see\x20https://github.com/gogo/protobuf/blob/m\
-    aster/gogoproto/gogo.proto\n\x20for\x20the\x20original\x20idea\n\n\t\n\
-    \x01\x07\x12\x04\t\0\x0e\x01\n2\n\x02\x07\0\x12\x03\x0b\x049\x1a'\x20Use\
-    \x20`bytes::Bytes`\x20for\x20`bytes`\x20fields\n\n\n\n\x03\x07\0\x02\x12\
-    \x03\t\x07\"\n\n\n\x03\x07\0\x04\x12\x03\x0b\x04\x0c\n\n\n\x03\x07\0\x05\
-    \x12\x03\x0b\r\x11\n\n\n\x03\x07\0\x01\x12\x03\x0b\x120\n\n\n\x03\x07\0\
-    \x03\x12\x03\x0b38\n3\n\x02\x07\x01\x12\x03\r\x04:\x1a(\x20Use\x20`bytes\
-    ::Bytes`\x20for\x20`string`\x20fields\n\n\n\n\x03\x07\x01\x02\x12\x03\t\
-    \x07\"\n\n\n\x03\x07\x01\x04\x12\x03\r\x04\x0c\n\n\n\x03\x07\x01\x05\x12\
-    \x03\r\r\x11\n\n\n\x03\x07\x01\x01\x12\x03\r\x121\n\n\n\x03\x07\x01\x03\
-    \x12\x03\r49\n\t\n\x01\x07\x12\x04\x10\0\x15\x01\n2\n\x02\x07\x02\x12\
-    \x03\x12\x045\x1a'\x20Use\x20`bytes::Bytes`\x20for\x20`bytes`\x20fields\
-    \n\n\n\n\x03\x07\x02\x02\x12\x03\x10\x07%\n\n\n\x03\x07\x02\x04\x12\x03\
-    \x12\x04\x0c\n\n\n\x03\x07\x02\x05\x12\x03\x12\r\x11\n\n\n\x03\x07\x02\
-    \x01\x12\x03\x12\x12,\n\n\n\x03\x07\x02\x03\x12\x03\x12/4\n3\n\x02\x07\
-    \x03\x12\x03\x14\x046\x1a(\x20Use\x20`bytes::Bytes`\x20for\x20`string`\
-    \x20fields\n\n\n\n\x03\x07\x03\x02\x12\x03\x10\x07%\n\n\n\x03\x07\x03\
-    \x04\x12\x03\x14\x04\x0c\n\n\n\x03\x07\x03\x05\x12\x03\x14\r\x11\n\n\n\
-    \x03\x07\x03\x01\x12\x03\x14\x12-\n\n\n\x03\x07\x03\x03\x12\x03\x1405\n\
-    \t\n\x01\x07\x12\x04\x17\0\x1c\x01\n2\n\x02\x07\x04\x12\x03\x19\x04;\x1a\
-    '\x20Use\x20`bytes::Bytes`\x20for\x20`bytes`\x20fields\n\n\n\n\x03\x07\
-    \x04\x02\x12\x03\x17\x07#\n\n\n\x03\x07\x04\x04\x12\x03\x19\x04\x0c\n\n\
-    \n\x03\x07\x04\x05\x12\x03\x19\r\x11\n\n\n\x03\x07\x04\x01\x12\x03\x19\
-    \x122\n\n\n\x03\x07\x04\x03\x12\x03\x195:\n3\n\x02\x07\x05\x12\x03\x1b\
-    \x04<\x1a(\x20Use\x20`bytes::Bytes`\x20for\x20`string`\x20fields\n\n\n\n\
-    \x03\x07\x05\x02\x12\x03\x17\x07#\n\n\n\x03\x07\x05\x04\x12\x03\x1b\x04\
-    \x0c\n\n\n\x03\x07\x05\x05\x12\x03\x1b\r\x11\n\n\n\x03\x07\x05\x01\x12\
-    \x03\x1b\x123\n\n\n\x03\x07\x05\x03\x12\x03\x1b6;\
+    arllercheBytesForStringField:X\n\x18wrappers_as_option_field\x18\xf5\x84\
+    \x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x15wrappersAsOpt\
+    ionField:^\n\x1bsingular_field_option_field\x18\xf9\x84\x01\x20\x01(\x08\
+    \x12\x1d.google.protobuf.FieldOptionsR\x18singularFieldOptionField:>\n\n\
+    field_attr\x18\xfc\x84\x01\x20\x03(\t\x12\x1d.google.protobuf.FieldOptio\
+    nsR\tfieldAttrJ\xb2\x20\n\x06\x12\x04\0\0I\x01\n\x08\n\x01\x0c\x12\x03\0\
+    \0\x12\n\t\n\x02\x03\0\x12\x03\x02\0*\nh\n\x01\x02\x12\x03\x07\0\x122^\
+    \x20see\x20https://github.com/gogo/protobuf/blob/master/gogoproto/gogo.p\
+    roto\n\x20for\x20the\x20original\x20idea\n\n\t\n\x01\x07\x12\x04\t\0$\
+    \x01\n2\n\x02\x07\0\x12\x03\x0b\x049\x1a'\x20Use\x20`bytes::Bytes`\x20fo\
+    r\x20`bytes`\x20fields\n\n\n\n\x03\x07\0\x02\x12\x03\t\x07\"\n\n\n\x03\
+    \x07\0\x04\x12\x03\x0b\x04\x0c\n\n\n\x03\x07\0\x05\x12\x03\x0b\r\x11\n\n\
+    \n\x03\x07\0\x01\x12\x03\x0b\x120\n\n\n\x03\x07\0\x03\x12\x03\x0b38\n3\n\
+    \x02\x07\x01\x12\x03\r\x04:\x1a(\x20Use\x20`bytes::Bytes`\x20for\x20`str\
+    ing`\x20fields\n\n\n\n\x03\x07\x01\x02\x12\x03\t\x07\"\n\n\n\x03\x07\x01\
+    \x04\x12\x03\r\x04\x0c\n\n\n\x03\x07\x01\x05\x12\x03\r\r\x11\n\n\n\x03\
+    \x07\x01\x01\x12\x03\r\x121\n\n\n\x03\x07\x01\x03\x12\x03\r49\nr\n\x02\
+    \x07\x02\x12\x03\x0f\x041\x1ag\x20Use\x20`Option<i32>`,\x20`Option<Strin\
+    g>`\x20etc\x20for\x20fields\x20of\x20wrapper\x20types\x20like\x20`google\
+    .protobuf.Int32Value`\n\n\n\n\x03\x07\x02\x02\x12\x03\t\x07\"\n\n\n\x03\
+    \x07\x02\x04\x12\x03\x0f\x04\x0c\n\n\n\x03\x07\x02\x05\x12\x03\x0f\r\x11\
+    \n\n\n\x03\x07\x02\x01\x12\x03\x0f\x12(\n\n\n\x03\x07\x02\x03\x12\x03\
+    \x0f+0\na\n\x02\x07\x03\x12\x03\x11\x04+\x1aV\x20Use\x20`serde_derive`\
+    \x20to\x20implement\x20`Serialize`\x20and\x20`Deserialize`\x20for\x20mes\
+    sages\x20and\x20enums\n\n\n\n\x03\x07\x03\x02\x12\x03\t\x07\"\n\n\n\x03\
+    \x07\x03\x04\x12\x03\x11\x04\x0c\n\n\n\x03\x07\x03\x05\x12\x03\x11\r\x11\
+    \n\n\n\x03\x07\x03\x01\x12\x03\x11\x12\"\n\n\n\x03\x07\x03\x03\x12\x03\
+    \x11%*\nC\n\x02\x07\x04\x12\x03\x13\x04+\x1a8\x20Generate\x20chainable\
+    \x20`with_xxx(self,\x20v)\x20->\x20Self`\x20setters\n\n\n\n\x03\x07\x04\
+    \x02\x12\x03\t\x07\"\n\n\n\x03\x07\x04\x04\x12\x03\x13\x04\x0c\n\n\n\x03\
+    \x07\x04\x05\x12\x03\x13\r\x11\n\n\n\x03\x07\x04\x01\x12\x03\x13\x12\"\n\
+    \n\n\x03\x07\x04\x03\x12\x03\x13%*\nb\n\x02\x07\x05\x12\x03\x15\x04.\x1a\
+    W\x20Generate\x20nested\x20types\x20in\x20modules\x20and\x20CamelCase\
+    \x20enum\x20variants\x20without\x20enum\x20name\x20prefix\n\n\n\n\x03\
+    \x07\x05\x02\x12\x03\t\x07\"\n\n\n\x03\x07\x05\x04\x12\x03\x15\x04\x0c\n\
+    \n\n\x03\x07\x05\x05\x12\x03\x15\r\x11\n\n\n\x03\x07\x05\x01\x12\x03\x15\
+    \x12%\n\n\n\x03\x07\x05\x03\x12\x03\x15(-\n}\n\x02\x07\x06\x12\x03\x17\
+    \x044\x1ar\x20Use\x20`Option<T>`\x20and\x20`Option<Box<T>>`\x20instead\
+    \x20of\x20`SingularField<T>`\x20and\x20`SingularPtrField<T>`\x20for\x20s\
+    ingular\x20fields\n\n\n\n\x03\x07\x06\x02\x12\x03\t\x07\"\n\n\n\x03\x07\
+    \x06\x04\x12\x03\x17\x04\x0c\n\n\n\x03\x07\x06\x05\x12\x03\x17\r\x11\n\n\
+    \n\x03\x07\x06\x01\x12\x03\x17\x12+\n\n\n\x03\x07\x06\x03\x12\x03\x17.3\
+    \ni\n\x02\x07\x07\x12\x03\x19\x04.\x1a^\x20Generate\x20file\x20in\x20mod\
+    ule\x20tree\x20following\x20`package`\x20declaration\x20and\x20`mod.rs`\
+    \x20files\x20for\x20the\x20tree\n\n\n\n\x03\x07\x07\x02\x12\x03\t\x07\"\
+    \n\n\n\x03\x07\x07\x04\x12\x03\x19\x04\x0c\n\n\n\x03\x07\x07\x05\x12\x03\
+    \x19\r\x11\n\n\n\x03\x07\x07\x01\x12\x03\x19\x12%\n\n\n\x03\x07\x07\x03\
+    \x12\x03\x19(-\nA\n\x02\x07\x08\x12\x03\x1b\x04/\x1a6\x20Extra\x20derive\
+    s\x20for\x20all\x20messages,\x20e.\x20g.\x20`Eq`\x20or\x20`Hash`\n\n\n\n\
+    \x03\x07\x08\x02\x12\x03\t\x07\"\n\n\n\x03\x07\x08\x04\x12\x03\x1b\x04\
+    \x0c\n\n\n\x03\x07\x08\x05\x12\x03\x1b\r\x13\n\n\n\x03\x07\x08\x01\x12\
+    \x03\x1b\x14&\n\n\n\x03\x07\x08\x03\x12\x03\x1b).\nT\n\x02\x07\t\x12\x03\
+    \x1d\x04-\x1aI\x20Extra\x20attributes\x20for\x20all\x20messages\x20witho\
+    ut\x20`#[]`,\x20e.\x20g.\x20`non_exhaustive`\n\n\n\n\x03\x07\t\x02\x12\
+    \x03\t\x07\"\n\n\n\x03\x07\t\x04\x12\x03\x1d\x04\x0c\n\n\n\x03\x07\t\x05\
+    \x12\x03\x1d\r\x13\n\n\n\x03\x07\t\x01\x12\x03\x1d\x14$\n\n\n\x03\x07\t\
+    \x03\x12\x03\x1d',\n<\n\x02\x07\n\x12\x03\x1f\x04,\x1a1\x20Extra\x20deri\
+    ves\x20for\x20all\x20enums,\x20e.\x20g.\x20`PartialOrd`\n\n\n\n\x03\x07\
+    \n\x02\x12\x03\t\x07\"\n\n\n\x03\x07\n\x04\x12\x03\x1f\x04\x0c\n\n\n\x03\
+    \x07\n\x05\x12\x03\x1f\r\x13\n\n\n\x03\x07\n\x01\x12\x03\x1f\x14#\n\n\n\
+    \x03\x07\n\x03\x12\x03\x1f&+\nT\n\x02\x07\x0b\x12\x03!\x04*\x1aI\x20Extr\
+    a\x20attributes\x20for\x20all\x20enums\x20without\x20`#[]`,\x20e.\x20g.\
+    \x20`allow(deprecated)`\n\n\n\n\x03\x07\x0b\x02\x12\x03\t\x07\"\n\n\n\
+    \x03\x07\x0b\x04\x12\x03!\x04\x0c\n\n\n\x03\x07\x0b\x05\x12\x03!\r\x13\n\
+    \n\n\x03\x07\x0b\x01\x12\x03!\x14!\n\n\n\x03\x07\x0b\x03\x12\x03!$)\nt\n\
+    \x02\x07\x0c\x12\x03#\x04%\x1ai\x20Generate\x20code\x20for\x20`no_std`\
+    \x20crates:\x20`core`\x20and\x20`alloc`\x20paths\x20instead\x20of\x20`st\
+    d`,\x20`BTreeMap`\x20for\x20map\x20fields\n\n\n\n\x03\x07\x0c\x02\x12\
+    \x03\t\x07\"\n\n\n\x03\x07\x0c\x04\x12\x03#\x04\x0c\n\n\n\x03\x07\x0c\
+    \x05\x12\x03#\r\x11\n\n\n\x03\x07\x0c\x01\x12\x03#\x12\x1c\n\n\n\x03\x07\
+    \x0c\x03\x12\x03#\x1f$\n\t\n\x01\x07\x12\x04&\05\x01\n2\n\x02\x07\r\x12\
+    \x03(\x045\x1a'\x20Use\x20`bytes::Bytes`\x20for\x20`bytes`\x20fields\n\n\
+    \n\n\x03\x07\r\x02\x12\x03&\x07%\n\n\n\x03\x07\r\x04\x12\x03(\x04\x0c\n\
+    \n\n\x03\x07\r\x05\x12\x03(\r\x11\n\n\n\x03\x07\r\x01\x12\x03(\x12,\n\n\
+    \n\x03\x07\r\x03\x12\x03(/4\n3\n\x02\x07\x0e\x12\x03*\x046\x1a(\x20Use\
+    \x20`bytes::Bytes`\x20for\x20`string`\x20fields\n\n\n\n\x03\x07\x0e\x02\
+    \x12\x03&\x07%\n\n\n\x03\x07\x0e\x04\x12\x03*\x04\x0c\n\n\n\x03\x07\x0e\
+    \x05\x12\x03*\r\x11\n\n\n\x03\x07\x0e\x01\x12\x03*\x12-\n\n\n\x03\x07\
+    \x0e\x03\x12\x03*05\nr\n\x02\x07\x0f\x12\x03,\x04-\x1ag\x20Use\x20`Optio\
+    n<i32>`,\x20`Option<String>`\x20etc\x20for\x20fields\x20of\x20wrapper\
+    \x20types\x20like\x20`google.protobuf.Int32Value`\n\n\n\n\x03\x07\x0f\
+    \x02\x12\x03&\x07%\n\n\n\x03\x07\x0f\x04\x12\x03,\x04\x0c\n\n\n\x03\x07\
+    \x0f\x05\x12\x03,\r\x11\n\n\n\x03\x07\x0f\x01\x12\x03,\x12$\n\n\n\x03\
+    \x07\x0f\x03\x12\x03,',\ng\n\x02\x07\x10\x12\x03.\x04'\x1a\\\x20Use\x20`\
+    serde_derive`\x20to\x20implement\x20`Serialize`\x20and\x20`Deserialize`\
+    \x20for\x20message\x20and\x20nested\x20types\n\n\n\n\x03\x07\x10\x02\x12\
+    \x03&\x07%\n\n\n\x03\x07\x10\x04\x12\x03.\x04\x0c\n\n\n\x03\x07\x10\x05\
+    \x12\x03.\r\x11\n\n\n\x03\x07\x10\x01\x12\x03.\x12\x1e\n\n\n\x03\x07\x10\
+    \x03\x12\x03.!&\n}\n\x02\x07\x11\x12\x030\x040\x1ar\x20Use\x20`Option<T>\
+    `\x20and\x20`Option<Box<T>>`\x20instead\x20of\x20`SingularField<T>`\x20a\
+    nd\x20`SingularPtrField<T>`\x20for\x20singular\x20fields\n\n\n\n\x03\x07\
+    \x11\x02\x12\x03&\x07%\n\n\n\x03\x07\x11\x04\x12\x030\x04\x0c\n\n\n\x03\
+    \x07\x11\x05\x12\x030\r\x11\n\n\n\x03\x07\x11\x01\x12\x030\x12'\n\n\n\
+    \x03\x07\x11\x03\x12\x030*/\nT\n\x02\x07\x12\x12\x032\x04+\x1aI\x20Extra\
+    \x20derives\x20for\x20the\x20message\x20and\x20its\x20oneof\x20enums,\
+    \x20e.\x20g.\x20`Eq`\x20or\x20`Hash`\n\n\n\n\x03\x07\x12\x02\x12\x03&\
+    \x07%\n\n\n\x03\x07\x12\x04\x12\x032\x04\x0c\n\n\n\x03\x07\x12\x05\x12\
+    \x032\r\x13\n\n\n\x03\x07\x12\x01\x12\x032\x14\"\n\n\n\x03\x07\x12\x03\
+    \x12\x032%*\nS\n\x02\x07\x13\x12\x034\x04)\x1aH\x20Extra\x20attributes\
+    \x20for\x20the\x20message\x20without\x20`#[]`,\x20e.\x20g.\x20`non_exhau\
+    stive`\n\n\n\n\x03\x07\x13\x02\x12\x03&\x07%\n\n\n\x03\x07\x13\x04\x12\
+    \x034\x04\x0c\n\n\n\x03\x07\x13\x05\x12\x034\r\x13\n\n\n\x03\x07\x13\x01\
+    \x12\x034\x14\x20\n\n\n\x03\x07\x13\x03\x12\x034#(\n\t\n\x01\x07\x12\x04\
+    7\0<\x01\n;\n\x02\x07\x14\x12\x039\x04(\x1a0\x20Extra\x20derives\x20for\
+    \x20the\x20enum,\x20e.\x20g.\x20`PartialOrd`\n\n\n\n\x03\x07\x14\x02\x12\
+    \x037\x07\"\n\n\n\x03\x07\x14\x04\x12\x039\x04\x0c\n\n\n\x03\x07\x14\x05\
+    \x12\x039\r\x13\n\n\n\x03\x07\x14\x01\x12\x039\x14\x1f\n\n\n\x03\x07\x14\
+    \x03\x12\x039\"'\nS\n\x02\x07\x15\x12\x03;\x04&\x1aH\x20Extra\x20attribu\
+    tes\x20for\x20the\x20enum\x20without\x20`#[]`,\x20e.\x20g.\x20`allow(dep\
+    recated)`\n\n\n\n\x03\x07\x15\x02\x12\x037\x07\"\n\n\n\x03\x07\x15\x04\
+    \x12\x03;\x04\x0c\n\n\n\x03\x07\x15\x05\x12\x03;\r\x13\n\n\n\x03\x07\x15\
+    \x01\x12\x03;\x14\x1d\n\n\n\x03\x07\x15\x03\x12\x03;\x20%\n\t\n\x01\x07\
+    \x12\x04>\0I\x01\n2\n\x02\x07\x16\x12\x03@\x04;\x1a'\x20Use\x20`bytes::B\
+    ytes`\x20for\x20`bytes`\x20fields\n\n\n\n\x03\x07\x16\x02\x12\x03>\x07#\
+    \n\n\n\x03\x07\x16\x04\x12\x03@\x04\x0c\n\n\n\x03\x07\x16\x05\x12\x03@\r\
+    \x11\n\n\n\x03\x07\x16\x01\x12\x03@\x122\n\n\n\x03\x07\x16\x03\x12\x03@5\
+    :\n3\n\x02\x07\x17\x12\x03B\x04<\x1a(\x20Use\x20`bytes::Bytes`\x20for\
+    \x20`string`\x20fields\n\n\n\n\x03\x07\x17\x02\x12\x03>\x07#\n\n\n\x03\
+    \x07\x17\x04\x12\x03B\x04\x0c\n\n\n\x03\x07\x17\x05\x12\x03B\r\x11\n\n\n\
+    \x03\x07\x17\x01\x12\x03B\x123\n\n\n\x03\x07\x17\x03\x12\x03B6;\nr\n\x02\
+    \x07\x18\x12\x03D\x043\x1ag\x20Use\x20`Option<i32>`,\x20`Option<String>`\
+    \x20etc\x20for\x20fields\x20of\x20wrapper\x20types\x20like\x20`google.pr\
+    otobuf.Int32Value`\n\n\n\n\x03\x07\x18\x02\x12\x03>\x07#\n\n\n\x03\x07\
+    \x18\x04\x12\x03D\x04\x0c\n\n\n\x03\x07\x18\x05\x12\x03D\r\x11\n\n\n\x03\
+    \x07\x18\x01\x12\x03D\x12*\n\n\n\x03\x07\x18\x03\x12\x03D-2\n}\n\x02\x07\
+    \x19\x12\x03F\x046\x1ar\x20Use\x20`Option<T>`\x20and\x20`Option<Box<T>>`\
+    \x20instead\x20of\x20`SingularField<T>`\x20and\x20`SingularPtrField<T>`\
+    \x20for\x20singular\x20fields\n\n\n\n\x03\x07\x19\x02\x12\x03>\x07#\n\n\
+    \n\x03\x07\x19\x04\x12\x03F\x04\x0c\n\n\n\x03\x07\x19\x05\x12\x03F\r\x11\
+    \n\n\n\x03\x07\x19\x01\x12\x03F\x12-\n\n\n\x03\x07\x19\x03\x12\x03F05\nU\
+    \n\x02\x07\x1a\x12\x03H\x04'\x1aJ\x20Extra\x20attributes\x20for\x20the\
+    \x20struct\x20field\x20without\x20`#[]`,\x20e.\x20g.\x20`serde(skip)`\n\
+    \n\n\n\x03\x07\x1a\x02\x12\x03>\x07#\n\n\n\x03\x07\x1a\x04\x12\x03H\x04\
+    \x0c\n\n\n\x03\x07\x1a\x05\x12\x03H\r\x13\n\n\n\x03\x07\x1a\x01\x12\x03H\
+    \x14\x1e\n\n\n\x03\x07\x1a\x03\x12\x03H!&\
 ";
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
@@ -0,0 +1,356 @@
+//! Runtime support for services generated from `service` definitions.
+//!
+//! Generated code does not depend on any RPC implementation:
+//! clients send serialized messages through a [`Channel`](trait.Channel.html),
+//! and server side is exposed as a [`ServiceHandler`](trait.ServiceHandler.html)
+//! which receives serialized messages. Transport needs to implement
+//! `Channel` and to dispatch incoming calls to `ServiceHandler`.
+
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+#[cfg(feature = "std")]
+use std::error::Error;
+use std::fmt;
+use std::iter;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use message::Message;
+use message::parse_from_bytes;
+use error::ProtobufError;
+
+/// Kind of service method, as declared in `.proto` file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MethodType {
+    /// Single request, single response
+    Unary,
+    /// Stream of requests, single response
+    ClientStreaming,
+    /// Single request, stream of responses
+    ServerStreaming,
+    /// Stream of requests, stream of responses
+    BidiStreaming,
+}
+
+impl MethodType {
+    pub fn new(client_streaming: bool, server_streaming: bool) -> MethodType {
+        match (client_streaming, server_streaming) {
+            (false, false) => MethodType::Unary,
+            (true, false) => MethodType::ClientStreaming,
+            (false, true) => MethodType::ServerStreaming,
+            (true, true) => MethodType::BidiStreaming,
+        }
+    }
+
+    pub fn is_client_streaming(&self) -> bool {
+        match *self {
+            MethodType::ClientStreaming | MethodType::BidiStreaming => true,
+            MethodType::Unary | MethodType::ServerStreaming => false,
+        }
+    }
+
+    pub fn is_server_streaming(&self) -> bool {
+        match *self {
+            MethodType::ServerStreaming | MethodType::BidiStreaming => true,
+            MethodType::Unary | MethodType::ClientStreaming => false,
+        }
+    }
+}
+
+/// Service method, generated as part of `ServiceDescriptor`.
+#[derive(Debug)]
+pub struct MethodDescriptor {
+    /// Method name as declared in `.proto` file, e. g. `SayHello`
+    pub name: &'static str,
+    /// Path identifying the method in a transport: `/package.Service/Method`
+    pub full_path: &'static str,
+    pub method_type: MethodType,
+    /// Fully-qualified name of request message
+    pub input_type: &'static str,
+    /// Fully-qualified name of response message
+    pub output_type: &'static str,
+}
+
+/// Service description, generated as a static for each service.
+#[derive(Debug)]
+pub struct ServiceDescriptor {
+    /// Fully-qualified service name, e. g. `helloworld.Greeter`
+    pub full_name: &'static str,
+    pub methods: &'static [MethodDescriptor],
+}
+
+impl ServiceDescriptor {
+    /// Find method by name as declared in `.proto` file
+    pub fn method_by_name(&self, name: &str) -> Option<&'static MethodDescriptor> {
+        self.methods.iter().find(|m| m.name == name)
+    }
+
+    /// Find method by `/package.Service/Method` path
+    pub fn method_by_path(&self, full_path: &str) -> Option<&'static MethodDescriptor> {
+        self.methods.iter().find(|m| m.full_path == full_path)
+    }
+}
+
+/// Error returned by service implementation or by transport.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceError {
+    pub message: String,
+}
+
+impl ServiceError {
+    pub fn new<S : Into<String>>(message: S) -> ServiceError {
+        ServiceError { message: message.into() }
+    }
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ServiceError {
+    fn description(&self) -> &str {
+        &self.message
+    }
+}
+
+impl From<ProtobufError> for ServiceError {
+    fn from(err: ProtobufError) -> ServiceError {
+        ServiceError::new(format!("protobuf error: {}", err))
+    }
+}
+
+pub type ServiceResult<T> = Result<T, ServiceError>;
+
+/// Stream of requests or responses of a streaming method.
+pub type Streaming<T> = Box<Iterator<Item = ServiceResult<T>>>;
+
+/// Transport used by generated clients.
+///
+/// Requests and responses are passed serialized. Unary requests
+/// are passed as a stream of one message, and unary responses must be
+/// returned as a stream of one message.
+pub trait Channel {
+    fn call(
+        &self,
+        method: &'static MethodDescriptor,
+        requests: Streaming<Vec<u8>>,
+    ) -> ServiceResult<Streaming<Vec<u8>>>;
+}
+
+impl<'a, C : Channel + ?Sized> Channel for &'a C {
+    fn call(
+        &self,
+        method: &'static MethodDescriptor,
+        requests: Streaming<Vec<u8>>,
+    ) -> ServiceResult<Streaming<Vec<u8>>> {
+        (**self).call(method, requests)
+    }
+}
+
+impl<C : Channel + ?Sized> Channel for Box<C> {
+    fn call(
+        &self,
+        method: &'static MethodDescriptor,
+        requests: Streaming<Vec<u8>>,
+    ) -> ServiceResult<Streaming<Vec<u8>>> {
+        (**self).call(method, requests)
+    }
+}
+
+impl<C : Channel + ?Sized> Channel for Rc<C> {
+    fn call(
+        &self,
+        method: &'static MethodDescriptor,
+        requests: Streaming<Vec<u8>>,
+    ) -> ServiceResult<Streaming<Vec<u8>>> {
+        (**self).call(method, requests)
+    }
+}
+
+impl<C : Channel + ?Sized> Channel for Arc<C> {
+    fn call(
+        &self,
+        method: &'static MethodDescriptor,
+        requests: Streaming<Vec<u8>>,
+    ) -> ServiceResult<Streaming<Vec<u8>>> {
+        (**self).call(method, requests)
+    }
+}
+
+/// Server side of a service, implemented by generated `XxxServer` types.
+///
+/// Message encoding is the same as in `Channel`.
+pub trait ServiceHandler {
+    fn descriptor(&self) -> &'static ServiceDescriptor;
+
+    fn call(
+        &self,
+        method: &'static MethodDescriptor,
+        requests: Streaming<Vec<u8>>,
+    ) -> ServiceResult<Streaming<Vec<u8>>>;
+}
+
+/// Channel which invokes service handler directly, useful for testing.
+///
+/// Messages are still serialized and parsed, like with a real transport.
+pub struct InProcessChannel<H : ServiceHandler> {
+    handler: H,
+}
+
+impl<H : ServiceHandler> InProcessChannel<H> {
+    pub fn new(handler: H) -> InProcessChannel<H> {
+        InProcessChannel { handler: handler }
+    }
+
+    pub fn handler(&self) -> &H {
+        &self.handler
+    }
+}
+
+impl<H : ServiceHandler> Channel for InProcessChannel<H> {
+    fn call(
+        &self,
+        method: &'static MethodDescriptor,
+        requests: Streaming<Vec<u8>>,
+    ) -> ServiceResult<Streaming<Vec<u8>>> {
+        match self.handler.descriptor().method_by_path(method.full_path) {
+            Some(method) => self.handler.call(method, requests),
+            None => Err(unknown_method(method.full_path)),
+        }
+    }
+}
+
+// Functions below are used by generated code
+
+#[doc(hidden)]
+pub fn unknown_method(full_path: &str) -> ServiceError {
+    ServiceError::new(format!("unknown method: {}", full_path))
+}
+
+fn once<T : 'static>(value: T) -> Streaming<T> {
+    Box::new(iter::once(Ok(value)))
+}
+
+fn encode_stream<M : Message>(messages: Streaming<M>) -> Streaming<Vec<u8>> {
+    Box::new(messages.map(|m| m.and_then(|m| Ok(m.write_to_bytes()?))))
+}
+
+fn decode_stream<M : Message>(messages: Streaming<Vec<u8>>) -> Streaming<M> {
+    Box::new(messages.map(|m| m.and_then(|m| Ok(parse_from_bytes(&m)?))))
+}
+
+/// Take the only message of the stream of unary request or response.
+fn single<M : Message>(mut messages: Streaming<Vec<u8>>) -> ServiceResult<M> {
+    let first = match messages.next() {
+        Some(m) => m?,
+        None => return Err(ServiceError::new("expecting one message, got none")),
+    };
+    if messages.next().is_some() {
+        return Err(ServiceError::new("expecting one message, got more"));
+    }
+    Ok(parse_from_bytes(&first)?)
+}
+
+#[doc(hidden)]
+pub fn call_unary<C : Channel + ?Sized, Req : Message, Resp : Message>(
+    channel: &C,
+    method: &'static MethodDescriptor,
+    request: &Req,
+) -> ServiceResult<Resp> {
+    let responses = channel.call(method, once(request.write_to_bytes()?))?;
+    single(responses)
+}
+
+#[doc(hidden)]
+pub fn call_client_streaming<C, Req, Resp, I>(
+    channel: &C,
+    method: &'static MethodDescriptor,
+    requests: I,
+) -> ServiceResult<Resp>
+where
+    C : Channel + ?Sized,
+    Req : Message,
+    Resp : Message,
+    I : IntoIterator<Item = Req>,
+    I::IntoIter : 'static,
+{
+    let requests = encode_stream(Box::new(requests.into_iter().map(Ok)));
+    single(channel.call(method, requests)?)
+}
+
+#[doc(hidden)]
+pub fn call_server_streaming<C : Channel + ?Sized, Req : Message, Resp : Message>(
+    channel: &C,
+    method: &'static MethodDescriptor,
+    request: &Req,
+) -> ServiceResult<Streaming<Resp>> {
+    let responses = channel.call(method, once(request.write_to_bytes()?))?;
+    Ok(decode_stream(responses))
+}
+
+#[doc(hidden)]
+pub fn call_bidi_streaming<C, Req, Resp, I>(
+    channel: &C,
+    method: &'static MethodDescriptor,
+    requests: I,
+) -> ServiceResult<Streaming<Resp>>
+where
+    C : Channel + ?Sized,
+    Req : Message,
+    Resp : Message,
+    I : IntoIterator<Item = Req>,
+    I::IntoIter : 'static,
+{
+    let requests = encode_stream(Box::new(requests.into_iter().map(Ok)));
+    Ok(decode_stream(channel.call(method, requests)?))
+}
+
+#[doc(hidden)]
+pub fn handle_unary<Req, Resp, F>(requests: Streaming<Vec<u8>>, f: F)
+    -> ServiceResult<Streaming<Vec<u8>>>
+where
+    Req : Message,
+    Resp : Message,
+    F : FnOnce(Req) -> ServiceResult<Resp>,
+{
+    let response = f(single(requests)?)?;
+    Ok(once(response.write_to_bytes()?))
+}
+
+#[doc(hidden)]
+pub fn handle_client_streaming<Req, Resp, F>(requests: Streaming<Vec<u8>>, f: F)
+    -> ServiceResult<Streaming<Vec<u8>>>
+where
+    Req : Message,
+    Resp : Message,
+    F : FnOnce(Streaming<Req>) -> ServiceResult<Resp>,
+{
+    let response = f(decode_stream(requests))?;
+    Ok(once(response.write_to_bytes()?))
+}
+
+#[doc(hidden)]
+pub fn handle_server_streaming<Req, Resp, F>(requests: Streaming<Vec<u8>>, f: F)
+    -> ServiceResult<Streaming<Vec<u8>>>
+where
+    Req : Message,
+    Resp : Message,
+    F : FnOnce(Req) -> ServiceResult<Streaming<Resp>>,
+{
+    Ok(encode_stream(f(single(requests)?)?))
+}
+
+#[doc(hidden)]
+pub fn handle_bidi_streaming<Req, Resp, F>(requests: Streaming<Vec<u8>>, f: F)
+    -> ServiceResult<Streaming<Vec<u8>>>
+where
+    Req : Message,
+    Resp : Message,
+    F : FnOnce(Streaming<Req>) -> ServiceResult<Streaming<Resp>>,
+{
+    Ok(encode_stream(f(decode_stream(requests))?))
+}
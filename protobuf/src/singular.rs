@@ -1,9 +1,15 @@
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
 use std::hash::Hash;
 use std::hash::Hasher;
 use std::option;
 use std::default::Default;
 use std::fmt;
 
+#[cfg(feature = "with-serde")]
+use serde;
+
 pub struct SingularField<T>(Option<T>);
 
 /// Like `Option<Box<T>>`, but keeps the actual element on `clear`.
@@ -374,3 +380,33 @@ impl<'a, T> IntoIterator for &'a SingularPtrField<T> {
         self.iter()
     }
 }
+
+/// Serialized as `Option<T>`.
+#[cfg(feature = "with-serde")]
+impl<T : serde::Serialize> serde::Serialize for SingularField<T> {
+    fn serialize<S : serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&self.as_ref(), serializer)
+    }
+}
+
+#[cfg(feature = "with-serde")]
+impl<'de, T : serde::Deserialize<'de>> serde::Deserialize<'de> for SingularField<T> {
+    fn deserialize<D : serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde::Deserialize::deserialize(deserializer).map(SingularField::from_option)
+    }
+}
+
+/// Serialized as `Option<T>`.
+#[cfg(feature = "with-serde")]
+impl<T : serde::Serialize> serde::Serialize for SingularPtrField<T> {
+    fn serialize<S : serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&self.as_ref(), serializer)
+    }
+}
+
+#[cfg(feature = "with-serde")]
+impl<'de, T : serde::Deserialize<'de>> serde::Deserialize<'de> for SingularPtrField<T> {
+    fn deserialize<D : serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde::Deserialize::deserialize(deserializer).map(SingularPtrField::from_option)
+    }
+}
@@ -0,0 +1,101 @@
+//! Spin lock based replacements of `std::sync::Once` and `std::sync::RwLock`
+//! used when `std` feature is disabled.
+//!
+//! Only the parts of API used by the crate are implemented.
+//! Locks are held for very short time (lazy initialization of descriptors
+//! and access to the `Any` registry), so spinning is acceptable.
+
+use std::cell::UnsafeCell;
+use std::ops::Deref;
+use std::ops::DerefMut;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+const INCOMPLETE: usize = 0;
+const RUNNING: usize = 1;
+const COMPLETE: usize = 2;
+
+/// Same as `std::sync::Once`, except that panic in initialization
+/// makes other threads spin forever instead of poisoning.
+pub struct Once {
+    state: AtomicUsize,
+}
+
+pub const ONCE_INIT: Once = Once { state: AtomicUsize::new(INCOMPLETE) };
+
+impl Once {
+    pub fn call_once<F : FnOnce()>(&self, f: F) {
+        loop {
+            match self.state.compare_exchange(INCOMPLETE, RUNNING, Ordering::Acquire, Ordering::Acquire) {
+                Ok(_) => {
+                    f();
+                    self.state.store(COMPLETE, Ordering::Release);
+                    return;
+                }
+                Err(COMPLETE) => return,
+                Err(_) => ::std::hint::spin_loop(),
+            }
+        }
+    }
+}
+
+/// Same as `std::sync::RwLock`, but readers are not concurrent.
+pub struct RwLock<T> {
+    locked: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T : Send> Send for RwLock<T> {}
+unsafe impl<T : Send + Sync> Sync for RwLock<T> {}
+
+/// Locks cannot be poisoned, but result is returned for compatibility with `std`.
+pub type LockResult<G> = Result<G, ()>;
+
+impl<T> RwLock<T> {
+    pub fn new(value: T) -> RwLock<T> {
+        RwLock {
+            locked: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    fn lock(&self) -> RwLockGuard<T> {
+        while self.locked.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).is_err() {
+            ::std::hint::spin_loop();
+        }
+        RwLockGuard { lock: self }
+    }
+
+    pub fn read(&self) -> LockResult<RwLockGuard<T>> {
+        Ok(self.lock())
+    }
+
+    pub fn write(&self) -> LockResult<RwLockGuard<T>> {
+        Ok(self.lock())
+    }
+}
+
+pub struct RwLockGuard<'a, T : 'a> {
+    lock: &'a RwLock<T>,
+}
+
+impl<'a, T> Deref for RwLockGuard<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<'a, T> DerefMut for RwLockGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.lock.value.get() }
+    }
+}
+
+impl<'a, T> Drop for RwLockGuard<'a, T> {
+    fn drop(&mut self) {
+        self.lock.locked.store(false, Ordering::Release);
+    }
+}
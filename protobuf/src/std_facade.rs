@@ -0,0 +1,74 @@
+//! `std` of the crate when `std` feature is disabled.
+//!
+//! Modules of `core` and `alloc` are reexported under their names in `std`,
+//! so code of the crate and generated code in it use `std::` paths in both modes.
+//!
+//! Hash maps are not available in `alloc`, so `HashMap` is an alias to `BTreeMap`
+//! (keys of protobuf maps are integers, bools and strings, which are all `Ord`).
+//! `Once` and `RwLock` are implemented with spin locks in `spin` module.
+
+// not every module is used in every configuration
+#![allow(unused_imports)]
+
+pub use core::any;
+pub use core::cell;
+pub use core::char;
+pub use core::cmp;
+pub use core::convert;
+pub use core::default;
+pub use core::f32;
+pub use core::f64;
+pub use core::hash;
+pub use core::hint;
+pub use core::i32;
+pub use core::i64;
+pub use core::iter;
+pub use core::marker;
+pub use core::mem;
+pub use core::ops;
+pub use core::option;
+pub use core::ptr;
+pub use core::result;
+pub use core::u32;
+pub use core::u64;
+
+pub use alloc::boxed;
+pub use alloc::fmt;
+pub use alloc::rc;
+pub use alloc::slice;
+pub use alloc::str;
+pub use alloc::string;
+pub use alloc::vec;
+
+pub mod collections {
+    pub use alloc::collections::*;
+    pub use alloc::collections::BTreeMap as HashMap;
+    pub use alloc::collections::BTreeSet as HashSet;
+
+    pub mod hash_map {
+        pub use alloc::collections::btree_map::*;
+    }
+}
+
+pub mod sync {
+    pub use alloc::sync::*;
+    pub use core::sync::*;
+    pub use spin::Once;
+    pub use spin::ONCE_INIT;
+    pub use spin::RwLock;
+}
+
+pub mod time {
+    pub use core::time::Duration;
+}
+
+pub mod prelude {
+    pub mod v1 {
+        pub use core::prelude::v1::*;
+        pub use alloc::borrow::ToOwned;
+        pub use alloc::boxed::Box;
+        pub use alloc::string::String;
+        pub use alloc::string::ToString;
+        pub use alloc::vec::Vec;
+    }
+}
@@ -1,7 +1,16 @@
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+use std::collections::BTreeMap;
+#[cfg(feature = "std")]
 use std::collections::HashMap;
+#[cfg(feature = "std")]
 use std::hash::Hash;
+#[cfg(feature = "std")]
 use std::io::Write;
+#[cfg(feature = "std")]
 use std::io::{BufRead, Read};
+#[cfg(feature = "std")]
 use std::io;
 use std::mem;
 use std::slice;
@@ -13,10 +22,12 @@ use chars::Chars;
 
 use varint;
 use cached_size::SizeCache;
-use core::Message;
-use core::ProtobufEnum;
+use message::Message;
+use message::ProtobufEnum;
+use enum_or_unknown::ProtobufEnumOrUnknown;
 use unknown::UnknownValue;
 use unknown::UnknownValueRef;
+use unknown::UnknownFields;
 use zigzag::decode_zig_zag_32;
 use zigzag::decode_zig_zag_64;
 use zigzag::encode_zig_zag_32;
@@ -124,13 +135,17 @@ pub struct CodedInputStream<'a> {
     source: BufReadIter<'a>,
     recursion_level: u32,
     recursion_limit: u32,
+    /// Field number of the group being read, if any
+    group: Option<u32>,
 }
 
 impl<'a> CodedInputStream<'a> {
+    #[cfg(feature = "std")]
     pub fn new(read: &'a mut Read) -> CodedInputStream<'a> {
         CodedInputStream::from_buf_read_iter(BufReadIter::from_read(read))
     }
 
+    #[cfg(feature = "std")]
     pub fn from_buffered_reader(buf_read: &'a mut BufRead) -> CodedInputStream<'a> {
         CodedInputStream::from_buf_read_iter(BufReadIter::from_buf_read(buf_read))
     }
@@ -149,6 +164,7 @@ impl<'a> CodedInputStream<'a> {
             source: source,
             recursion_level: 0,
             recursion_limit: DEFAULT_RECURSION_LIMIT,
+            group: None,
         }
     }
 
@@ -340,6 +356,29 @@ impl<'a> CodedInputStream<'a> {
         self.read_tag().map(|t| t.unpack())
     }
 
+    /// Read tag of the next field of the message being read.
+    ///
+    /// Return `None` at the end of the message: at EOF (or limit) for
+    /// length-delimited messages, or after the end group tag for groups.
+    pub fn read_field_tag_unpack(
+        &mut self,
+    ) -> ProtobufResult<Option<(u32, wire_format::WireType)>> {
+        if self.eof()? {
+            return match self.group {
+                Some(_) => Err(ProtobufError::WireError(WireError::UnexpectedEof)),
+                None => Ok(None),
+            };
+        }
+        let (field_number, wire_type) = self.read_tag_unpack()?;
+        if wire_type == wire_format::WireTypeEndGroup {
+            return match self.group {
+                Some(group) if group == field_number => Ok(None),
+                _ => Err(ProtobufError::WireError(WireError::UnexpectedWireType(wire_type))),
+            };
+        }
+        Ok(Some((field_number, wire_type)))
+    }
+
     pub fn read_double(&mut self) -> ProtobufResult<f64> {
         let bits = self.read_raw_little_endian64()?;
         unsafe { Ok(mem::transmute::<u64, f64>(bits)) }
@@ -402,6 +441,13 @@ impl<'a> CodedInputStream<'a> {
         }
     }
 
+    /// Read `enum` field value, preserving numbers unknown to `E`.
+    pub fn read_enum_or_unknown<E : ProtobufEnum>(
+        &mut self,
+    ) -> ProtobufResult<ProtobufEnumOrUnknown<E>> {
+        Ok(ProtobufEnumOrUnknown::from_i32(self.read_int32()?))
+    }
+
     pub fn read_repeated_packed_double_into(
         &mut self,
         target: &mut Vec<f64>,
@@ -594,6 +640,19 @@ impl<'a> CodedInputStream<'a> {
         Ok(())
     }
 
+    pub fn read_repeated_packed_enum_or_unknown_into<E : ProtobufEnum>(
+        &mut self,
+        target: &mut Vec<ProtobufEnumOrUnknown<E>>,
+    ) -> ProtobufResult<()> {
+        let len = self.read_raw_varint64()?;
+        let old_limit = self.push_limit(len)?;
+        while !self.eof()? {
+            target.push(self.read_enum_or_unknown()?);
+        }
+        self.pop_limit(old_limit);
+        Ok(())
+    }
+
     pub fn read_unknown(
         &mut self,
         wire_type: wire_format::WireType,
@@ -690,21 +749,63 @@ impl<'a> CodedInputStream<'a> {
         Ok(())
     }
 
+    /// Read length-delimited message and merge it into given message.
+    pub fn merge_message<M : Message + ?Sized>(&mut self, message: &mut M) -> ProtobufResult<()> {
+        let len = self.read_raw_varint64()?;
+        let old_limit = self.push_limit(len)?;
+        let old_group = self.group.take();
+        self.incr_recursion()?;
+        let result = message.merge_from(self);
+        self.decr_recursion();
+        self.group = old_group;
+        self.pop_limit(old_limit);
+        result
+    }
+
     pub fn read_message<M : Message>(&mut self) -> ProtobufResult<M> {
         let len = self.read_raw_varint64()?;
         let old_limit = self.push_limit(len)?;
+        let old_group = self.group.take();
         let result = M::read_from(self);
+        self.group = old_group;
         self.pop_limit(old_limit);
         result
     }
+
+    /// Read group content (start group tag must be already read)
+    /// and merge it into given message.
+    pub fn merge_group<M : Message + ?Sized>(
+        &mut self,
+        message: &mut M,
+        field_number: u32,
+    ) -> ProtobufResult<()> {
+        let old_group = mem::replace(&mut self.group, Some(field_number));
+        self.incr_recursion()?;
+        let result = message.merge_from(self);
+        self.decr_recursion();
+        self.group = old_group;
+        result
+    }
+
+    /// Read group content, start group tag must be already read.
+    pub fn read_group<M : Message>(&mut self, field_number: u32) -> ProtobufResult<M> {
+        let old_group = mem::replace(&mut self.group, Some(field_number));
+        self.incr_recursion()?;
+        let result = M::read_from(self);
+        self.decr_recursion();
+        self.group = old_group;
+        result
+    }
 }
 
+#[cfg(feature = "std")]
 impl<'a> Read for CodedInputStream<'a> {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         self.source.read(buf).map_err(Into::into)
     }
 }
 
+#[cfg(feature = "std")]
 impl<'a> BufRead for CodedInputStream<'a> {
     fn fill_buf(&mut self) -> io::Result<&[u8]> {
         self.source.fill_buf().map_err(Into::into)
@@ -721,6 +822,7 @@ pub trait WithCodedOutputStream {
         F : FnOnce(&mut CodedOutputStream) -> ProtobufResult<T>;
 }
 
+#[cfg(feature = "std")]
 impl<'a> WithCodedOutputStream for &'a mut (Write + 'a) {
     fn with_coded_output_stream<T, F>(self, cb: F) -> ProtobufResult<T>
     where
@@ -738,7 +840,7 @@ impl<'a> WithCodedOutputStream for &'a mut Vec<u8> {
     where
         F : FnOnce(&mut CodedOutputStream) -> ProtobufResult<T>,
     {
-        let mut os = CodedOutputStream::new(self);
+        let mut os = CodedOutputStream::vec(self);
         let r = cb(&mut os)?;
         os.flush()?;
         Ok(r)
@@ -760,6 +862,7 @@ pub trait WithCodedInputStream {
         F : FnOnce(&mut CodedInputStream) -> ProtobufResult<T>;
 }
 
+#[cfg(feature = "std")]
 impl<'a> WithCodedInputStream for &'a mut (Read + 'a) {
     fn with_coded_input_stream<T, F>(self, cb: F) -> ProtobufResult<T>
     where
@@ -772,6 +875,7 @@ impl<'a> WithCodedInputStream for &'a mut (Read + 'a) {
     }
 }
 
+#[cfg(feature = "std")]
 impl<'a> WithCodedInputStream for &'a mut (BufRead + 'a) {
     fn with_coded_input_stream<T, F>(self, cb: F) -> ProtobufResult<T>
     where
@@ -810,16 +914,32 @@ impl<'a> WithCodedInputStream for &'a Bytes {
 }
 
 
+/// Output target of `CodedOutputStream`
+enum OutputTarget<'a> {
+    #[cfg(feature = "std")]
+    Write(&'a mut Write),
+    Vec(&'a mut Vec<u8>),
+}
+
 pub struct CodedOutputStream<'a> {
-    writer: &'a mut Write,
+    target: OutputTarget<'a>,
     // size cache
     pub sizes: SizeCache,
 }
 
 impl<'a> CodedOutputStream<'a> {
+    #[cfg(feature = "std")]
     pub fn new(writer: &'a mut Write) -> CodedOutputStream<'a> {
         CodedOutputStream {
-            writer: writer,
+            target: OutputTarget::Write(writer),
+            sizes: SizeCache::new(),
+        }
+    }
+
+    /// Output stream which appends to the vec, available without `std` feature
+    pub fn vec(vec: &'a mut Vec<u8>) -> CodedOutputStream<'a> {
+        CodedOutputStream {
+            target: OutputTarget::Vec(vec),
             sizes: SizeCache::new(),
         }
     }
@@ -829,12 +949,27 @@ impl<'a> CodedOutputStream<'a> {
     }
 
     pub fn write_raw_byte(&mut self, byte: u8) -> ProtobufResult<()> {
-        self.writer.write(&[byte])?;
-        Ok(())
+        self.write_raw_bytes(&[byte])
     }
 
     pub fn write_raw_bytes(&mut self, bytes: &[u8]) -> ProtobufResult<()> {
-        self.writer.write(bytes)?;
+        match self.target {
+            #[cfg(feature = "std")]
+            OutputTarget::Write(ref mut writer) => {
+                writer.write(bytes)?;
+            }
+            OutputTarget::Vec(ref mut vec) => vec.extend_from_slice(bytes),
+        }
+        Ok(())
+    }
+
+    /// Flush the underlying writer
+    pub fn flush(&mut self) -> ProtobufResult<()> {
+        match self.target {
+            #[cfg(feature = "std")]
+            OutputTarget::Write(ref mut writer) => writer.flush()?,
+            OutputTarget::Vec(_) => {}
+        }
         Ok(())
     }
 
@@ -955,6 +1090,29 @@ impl<'a> CodedOutputStream<'a> {
         }
     }
 
+    pub fn write_unknown(
+        &mut self,
+        field_number: u32,
+        value: UnknownValueRef,
+    ) -> ProtobufResult<()> {
+        self.write_tag(field_number, value.wire_type())?;
+        self.write_unknown_no_tag(value)?;
+        Ok(())
+    }
+
+    /// Write unknown fields ordered by field number,
+    /// so the output does not depend on map iteration order.
+    pub fn write_unknown_fields(&mut self, fields: &UnknownFields) -> ProtobufResult<()> {
+        let mut fields: Vec<_> = fields.iter().collect();
+        fields.sort_by_key(|&(number, _)| number);
+        for (number, values) in fields {
+            for value in values {
+                self.write_unknown(number, value)?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn write_uint64(&mut self, field_number: u32, value: u64) -> ProtobufResult<()> {
         self.write_tag(field_number, wire_format::WireTypeVarint)?;
         self.write_uint64_no_tag(value)?;
@@ -1066,7 +1224,16 @@ impl<'a> CodedOutputStream<'a> {
         Ok(())
     }
 
+    /// Write group, message sizes must be already known.
+    pub fn write_group<M : Message>(&mut self, field_number: u32, msg: &M) -> ProtobufResult<()> {
+        self.write_tag(field_number, wire_format::WireTypeStartGroup)?;
+        msg.write_to_with_cached_sizes(self)?;
+        self.write_tag(field_number, wire_format::WireTypeEndGroup)?;
+        Ok(())
+    }
+
     /// Write map, message sizes must be already known.
+    #[cfg(feature = "std")]
     pub fn write_map<K, V>(
         &mut self,
         field_number: u32,
@@ -1077,7 +1244,49 @@ impl<'a> CodedOutputStream<'a> {
         V : ProtobufType,
         K::Value : Eq + Hash,
     {
-        for (k, v) in map {
+        self.write_map_entries::<K, V, _>(field_number, map.iter())
+    }
+
+    /// Write map, message sizes must be already known.
+    ///
+    /// `HashMap` is `BTreeMap` without `std`.
+    #[cfg(not(feature = "std"))]
+    pub fn write_map<K, V>(
+        &mut self,
+        field_number: u32,
+        map: &BTreeMap<K::Value, V::Value>,
+    ) -> ProtobufResult<()>
+    where
+        K : ProtobufType,
+        V : ProtobufType,
+        K::Value : Ord,
+    {
+        self.write_btree_map::<K, V>(field_number, map)
+    }
+
+    /// Write map stored in `BTreeMap`, message sizes must be already known.
+    pub fn write_btree_map<K, V>(
+        &mut self,
+        field_number: u32,
+        map: &BTreeMap<K::Value, V::Value>,
+    ) -> ProtobufResult<()>
+    where
+        K : ProtobufType,
+        V : ProtobufType,
+        K::Value : Ord,
+    {
+        self.write_map_entries::<K, V, _>(field_number, map.iter())
+    }
+
+    fn write_map_entries<'b, K, V, I>(&mut self, field_number: u32, entries: I) -> ProtobufResult<()>
+    where
+        K : ProtobufType,
+        V : ProtobufType,
+        K::Value : 'b,
+        V::Value : 'b,
+        I : Iterator<Item = (&'b K::Value, &'b V::Value)>,
+    {
+        for (k, v) in entries {
 
             let key_tag_size = 1;
             let value_tag_size = 1;
@@ -1106,6 +1315,7 @@ impl<'a> CodedOutputStream<'a> {
     }
 }
 
+#[cfg(feature = "std")]
 impl<'a> Write for CodedOutputStream<'a> {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         self.write_raw_bytes(buf)?;
@@ -1113,12 +1323,12 @@ impl<'a> Write for CodedOutputStream<'a> {
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        self.writer.flush()
+        CodedOutputStream::flush(self).map_err(Into::into)
     }
 }
 
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod test {
 
     use std::io;
@@ -1,3 +1,6 @@
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
 pub fn remove_to<'s>(s: &'s str, c: char) -> &'s str {
     match s.rfind(c) {
         Some(pos) => &s[(pos + 1)..],
@@ -13,11 +16,44 @@ pub fn remove_suffix<'s>(s: &'s str, suffix: &str) -> Option<&'s str> {
     }
 }
 
+/// Convert `foo_bar` to `fooBar` the way `protoc` computes `json_name`
+pub fn to_lower_camel_case(s: &str) -> String {
+    let mut r = String::with_capacity(s.len());
+    let mut capitalize_next = false;
+    for c in s.chars() {
+        if c == '_' {
+            capitalize_next = true;
+        } else if capitalize_next {
+            r.extend(c.to_uppercase());
+            capitalize_next = false;
+        } else {
+            r.push(c);
+        }
+    }
+    r
+}
+
+/// Convert `fooBar` to `foo_bar`, inverse of `to_lower_camel_case`
+pub fn to_snake_case(s: &str) -> String {
+    let mut r = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_ascii_uppercase() {
+            r.push('_');
+            r.push(c.to_ascii_lowercase());
+        } else {
+            r.push(c);
+        }
+    }
+    r
+}
+
 #[cfg(test)]
 mod test {
 
     use super::remove_to;
     use super::remove_suffix;
+    use super::to_lower_camel_case;
+    use super::to_snake_case;
 
     #[test]
     fn test_remove_to() {
@@ -32,4 +68,17 @@ mod test {
         assert_eq!(None, remove_suffix("aaa", "bbb"));
     }
 
+    #[test]
+    fn test_to_lower_camel_case() {
+        assert_eq!("fooBar", to_lower_camel_case("foo_bar"));
+        assert_eq!("fooBar1Baz", to_lower_camel_case("foo_bar_1_baz"));
+        assert_eq!("foo", to_lower_camel_case("foo"));
+    }
+
+    #[test]
+    fn test_to_snake_case() {
+        assert_eq!("foo_bar", to_snake_case("fooBar"));
+        assert_eq!("foo", to_snake_case("foo"));
+    }
+
 }
@@ -0,0 +1,320 @@
+//! Tokenizer for protobuf text format.
+
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+use std::char;
+
+use super::Loc;
+use super::ParseError;
+use super::ParseResult;
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    /// Identifier, e. g. field name, enum value name or `inf`
+    Ident(String),
+    /// Single punctuation character: `{`, `:`, `-` etc
+    Symbol(char),
+    /// Integer literal without sign
+    IntLit(u64),
+    /// Float literal without sign
+    FloatLit(f64),
+    /// Quoted string literal, unescaped
+    StrLit(Vec<u8>),
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenWithLoc {
+    pub token: Token,
+    pub loc: Loc,
+}
+
+pub struct Lexer<'a> {
+    input: &'a str,
+    pos: usize,
+    loc: Loc,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a str) -> Lexer<'a> {
+        Lexer {
+            input: input,
+            pos: 0,
+            loc: Loc { line: 1, col: 1 },
+        }
+    }
+
+    /// Location of the next character
+    pub fn loc(&self) -> Loc {
+        self.loc
+    }
+
+    fn rem(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn peek_char(&self) -> Option<char> {
+        self.rem().chars().next()
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.peek_char()?;
+        self.pos += c.len_utf8();
+        if c == '\n' {
+            self.loc.line += 1;
+            self.loc.col = 1;
+        } else {
+            self.loc.col += 1;
+        }
+        Some(c)
+    }
+
+    fn next_char_if<P : Fn(char) -> bool>(&mut self, p: P) -> Option<char> {
+        match self.peek_char() {
+            Some(c) if p(c) => self.next_char(),
+            _ => None,
+        }
+    }
+
+    fn error<T>(&self, loc: Loc, message: &str) -> ParseResult<T> {
+        Err(ParseError::new(loc, message))
+    }
+
+    fn skip_ws_and_comments(&mut self) {
+        loop {
+            match self.peek_char() {
+                Some(c) if c.is_whitespace() => {
+                    self.next_char();
+                }
+                Some('#') => {
+                    while let Some(c) = self.next_char() {
+                        if c == '\n' {
+                            break;
+                        }
+                    }
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn next_ident(&mut self) -> String {
+        let start = self.pos;
+        while let Some(_) = self.next_char_if(|c| c.is_ascii_alphanumeric() || c == '_') {}
+        self.input[start..self.pos].to_owned()
+    }
+
+    fn next_number(&mut self, loc: Loc) -> ParseResult<Token> {
+        let start = self.pos;
+        let mut prev = '\0';
+        while let Some(c) = self.next_char_if(move |c| {
+            c.is_ascii_alphanumeric() || c == '.' || ((c == '-' || c == '+') &&
+                (prev == 'e' || prev == 'E'))
+        })
+        {
+            prev = c;
+        }
+        let text = &self.input[start..self.pos];
+
+        if text.starts_with("0x") || text.starts_with("0X") {
+            return match u64::from_str_radix(&text[2..], 16) {
+                Ok(v) => Ok(Token::IntLit(v)),
+                Err(_) => self.error(loc, &format!("invalid hex literal: {}", text)),
+            };
+        }
+
+        let is_float = text.contains('.') || text.ends_with('f') || text.ends_with('F') ||
+            text.contains('e') || text.contains('E');
+        if is_float {
+            let without_suffix = if text.ends_with('f') || text.ends_with('F') {
+                &text[..text.len() - 1]
+            } else {
+                text
+            };
+            return match without_suffix.parse::<f64>() {
+                Ok(v) => Ok(Token::FloatLit(v)),
+                Err(_) => self.error(loc, &format!("invalid float literal: {}", text)),
+            };
+        }
+
+        let r = if text.len() > 1 && text.starts_with('0') {
+            u64::from_str_radix(&text[1..], 8)
+        } else {
+            text.parse::<u64>()
+        };
+        match r {
+            Ok(v) => Ok(Token::IntLit(v)),
+            Err(_) => self.error(loc, &format!("invalid integer literal: {}", text)),
+        }
+    }
+
+    fn next_hex_digits(&mut self, max: usize) -> u32 {
+        let mut r = 0;
+        for _ in 0..max {
+            match self.next_char_if(|c| c.is_digit(16)) {
+                Some(c) => r = r * 16 + c.to_digit(16).unwrap(),
+                None => break,
+            }
+        }
+        r
+    }
+
+    fn next_escape(&mut self, loc: Loc, r: &mut Vec<u8>) -> ParseResult<()> {
+        let c = match self.next_char() {
+            Some(c) => c,
+            None => return self.error(loc, "unterminated string literal"),
+        };
+        let b = match c {
+            'a' => b'\x07',
+            'b' => b'\x08',
+            'f' => b'\x0c',
+            'n' => b'\n',
+            'r' => b'\r',
+            't' => b'\t',
+            'v' => b'\x0b',
+            '\\' => b'\\',
+            '\'' => b'\'',
+            '"' => b'"',
+            '?' => b'?',
+            c if c.is_digit(8) => {
+                let mut v = c.to_digit(8).unwrap();
+                for _ in 0..2 {
+                    match self.next_char_if(|c| c.is_digit(8)) {
+                        Some(c) => v = v * 8 + c.to_digit(8).unwrap(),
+                        None => break,
+                    }
+                }
+                if v > 0xff {
+                    return self.error(loc, "octal escape out of range");
+                }
+                v as u8
+            }
+            'x' | 'X' => {
+                if self.peek_char().map_or(true, |c| !c.is_digit(16)) {
+                    return self.error(loc, "expecting hex digits after \\x");
+                }
+                self.next_hex_digits(2) as u8
+            }
+            'u' | 'U' => {
+                let len = if c == 'u' { 4 } else { 8 };
+                let code = self.next_hex_digits(len);
+                match char::from_u32(code) {
+                    Some(c) => {
+                        let mut buf = [0; 4];
+                        r.extend(c.encode_utf8(&mut buf).as_bytes());
+                        return Ok(());
+                    }
+                    None => return self.error(loc, "invalid unicode escape"),
+                }
+            }
+            _ => return self.error(loc, &format!("unknown escape: \\{}", c)),
+        };
+        r.push(b);
+        Ok(())
+    }
+
+    fn next_str_lit(&mut self, loc: Loc, quote: char) -> ParseResult<Token> {
+        let mut r = Vec::new();
+        loop {
+            match self.next_char() {
+                None | Some('\n') => return self.error(loc, "unterminated string literal"),
+                Some(c) if c == quote => return Ok(Token::StrLit(r)),
+                Some('\\') => self.next_escape(loc, &mut r)?,
+                Some(c) => {
+                    let mut buf = [0; 4];
+                    r.extend(c.encode_utf8(&mut buf).as_bytes());
+                }
+            }
+        }
+    }
+
+    /// Next token or `None` on EOF
+    pub fn next_token(&mut self) -> ParseResult<Option<TokenWithLoc>> {
+        self.skip_ws_and_comments();
+
+        let loc = self.loc;
+        let c = match self.peek_char() {
+            Some(c) => c,
+            None => return Ok(None),
+        };
+
+        let token = if c.is_ascii_alphabetic() || c == '_' {
+            Token::Ident(self.next_ident())
+        } else if c.is_ascii_digit() ||
+            (c == '.' && self.rem()[1..].starts_with(|c: char| c.is_ascii_digit()))
+        {
+            self.next_number(loc)?
+        } else if c == '"' || c == '\'' {
+            self.next_char();
+            self.next_str_lit(loc, c)?
+        } else {
+            self.next_char();
+            Token::Symbol(c)
+        };
+
+        Ok(Some(TokenWithLoc {
+            token: token,
+            loc: loc,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tokens(input: &str) -> Vec<Token> {
+        let mut lexer = Lexer::new(input);
+        let mut r = Vec::new();
+        while let Some(t) = lexer.next_token().expect("next_token") {
+            r.push(t.token);
+        }
+        r
+    }
+
+    #[test]
+    fn test_tokens() {
+        assert_eq!(
+            vec![
+                Token::Ident("a".to_owned()),
+                Token::Symbol(':'),
+                Token::IntLit(10),
+                Token::Ident("b_c".to_owned()),
+                Token::Symbol('{'),
+                Token::Symbol('-'),
+                Token::FloatLit(1.5),
+                Token::Symbol('}'),
+            ],
+            tokens("a: 10 # comment\n b_c { -1.5 }")
+        );
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(vec![Token::IntLit(31)], tokens("0x1f"));
+        assert_eq!(vec![Token::IntLit(8)], tokens("010"));
+        assert_eq!(vec![Token::IntLit(0)], tokens("0"));
+        assert_eq!(vec![Token::FloatLit(0.5)], tokens(".5"));
+        assert_eq!(vec![Token::FloatLit(2.0)], tokens("2f"));
+        assert_eq!(vec![Token::FloatLit(1e-3)], tokens("1e-3"));
+        assert!(Lexer::new("12abc").next_token().is_err());
+    }
+
+    #[test]
+    fn test_str_lit() {
+        assert_eq!(vec![Token::StrLit(b"a\"b\n".to_vec())], tokens(r#""a\"b\n""#));
+        assert_eq!(vec![Token::StrLit(b"'".to_vec())], tokens(r#"'\''"#));
+        assert_eq!(vec![Token::StrLit(b"\x01\xff".to_vec())], tokens(r#""\001\xff""#));
+        assert_eq!(vec![Token::StrLit("ю".as_bytes().to_vec())], tokens(r#""ю""#));
+        assert_eq!(vec![Token::StrLit("ю".as_bytes().to_vec())], tokens(r#""\u044e""#));
+        assert!(Lexer::new("\"abc").next_token().is_err());
+    }
+
+    #[test]
+    fn test_loc() {
+        let mut lexer = Lexer::new("a\n  b");
+        assert_eq!(Loc { line: 1, col: 1 }, lexer.next_token().unwrap().unwrap().loc);
+        assert_eq!(Loc { line: 2, col: 3 }, lexer.next_token().unwrap().unwrap().loc);
+    }
+}
@@ -1,10 +1,33 @@
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
 use std;
+#[cfg(feature = "std")]
+use std::error::Error;
 use std::fmt;
 use std::fmt::Write;
-use core::Message;
+use message::Message;
+use message::parse_from_bytes;
+use reflect::field_type;
 use reflect::ReflectFieldRef;
 use reflect::ProtobufValueRef;
+use well_known_types;
+use well_known_types_util;
+
+mod lexer;
+mod parse;
 
+use self::parse::Parser;
+
+/// Text format printer options
+#[derive(Default, Clone)]
+pub struct PrintOptions {
+    /// Print `google.protobuf.Timestamp` as RFC 3339 string and `google.protobuf.Duration`
+    /// as seconds with `s` suffix, e. g. `"1972-01-01T10:00:20.021Z"` and `"1.500s"`,
+    /// instead of messages with `seconds` and `nanos` fields.
+    /// Parser accepts both forms.
+    pub human_readable_time: bool,
+}
 
 fn quote_bytes_to(bytes: &[u8], buf: &mut String) {
     for &c in bytes {
@@ -124,6 +147,7 @@ fn print_end_field(buf: &mut String, pretty: bool) {
 
 fn print_field(
     buf: &mut String,
+    options: &PrintOptions,
     pretty: bool,
     indent: usize,
     first: &mut bool,
@@ -134,13 +158,21 @@ fn print_field(
 
     match value {
         ProtobufValueRef::Message(m) => {
-            buf.push_str(" {");
-            if pretty {
-                buf.push_str("\n");
+            match format_time(m, options) {
+                Some(s) => {
+                    buf.push_str(": ");
+                    print_str_to(&s, buf);
+                }
+                None => {
+                    buf.push_str(" {");
+                    if pretty {
+                        buf.push_str("\n");
+                    }
+                    print_to_internal(m, buf, options, pretty, indent + 1);
+                    do_indent(buf, pretty, indent);
+                    buf.push_str("}");
+                }
             }
-            print_to_internal(m, buf, pretty, indent + 1);
-            do_indent(buf, pretty, indent);
-            buf.push_str("}");
         }
         ProtobufValueRef::Enum(e) => {
             buf.push_str(": ");
@@ -180,10 +212,56 @@ fn print_field(
     print_end_field(buf, pretty);
 }
 
-fn print_to_internal(m: &Message, buf: &mut String, pretty: bool, indent: usize) {
+/// `Timestamp` or `Duration` as string if `human_readable_time` option is set,
+/// `None` for other messages and for values out of range, which are printed as messages
+fn format_time(m: &Message, options: &PrintOptions) -> Option<String> {
+    if !options.human_readable_time {
+        return None;
+    }
+    let d = m.descriptor();
+    let format = match d.full_name() {
+        "google.protobuf.Timestamp" => well_known_types::format_timestamp,
+        "google.protobuf.Duration" => well_known_types::format_duration,
+        _ => return None,
+    };
+    let seconds = d.field_by_name("seconds").get_i64(m);
+    let nanos = d.field_by_name("nanos").get_i32(m);
+    format(seconds, nanos).ok()
+}
+
+/// Type URL and content of `Any` if content type is registered
+/// (see `well_known_types_util::register_type`) and content is valid
+fn expand_any(m: &Message) -> Option<(&str, Box<Message>)> {
+    let d = m.descriptor();
+    if d.full_name() != "google.protobuf.Any" {
+        return None;
+    }
+    let type_url = d.field_by_name("type_url").get_str(m);
+    let content_type = well_known_types_util::find_type_by_url(type_url)?;
+    let mut content = content_type.new_instance();
+    content.merge_from_bytes(d.field_by_name("value").get_bytes(m)).ok()?;
+    Some((type_url, content))
+}
+
+fn print_to_internal(
+    m: &Message,
+    buf: &mut String,
+    options: &PrintOptions,
+    pretty: bool,
+    indent: usize,
+) {
     let d = m.descriptor();
     let mut first = true;
+
+    if let Some((type_url, content)) = expand_any(m) {
+        let name = format!("[{}]", type_url);
+        let value = ProtobufValueRef::Message(&*content);
+        print_field(buf, options, pretty, indent, &mut first, &name, value);
+        return;
+    }
+
     for f in d.fields() {
+        let name = field_type::text_format_name(f.proto());
         match f.get_reflect(m) {
             ReflectFieldRef::Map(map) => {
                 for (k, v) in map {
@@ -195,9 +273,18 @@ fn print_to_internal(m: &Message, buf: &mut String, pretty: bool, indent: usize)
 
                     let mut entry_first = true;
 
-                    print_field(buf, pretty, indent + 1, &mut entry_first, "key", k.as_ref());
                     print_field(
                         buf,
+                        options,
+                        pretty,
+                        indent + 1,
+                        &mut entry_first,
+                        "key",
+                        k.as_ref(),
+                    );
+                    print_field(
+                        buf,
+                        options,
                         pretty,
                         indent + 1,
                         &mut entry_first,
@@ -212,12 +299,18 @@ fn print_to_internal(m: &Message, buf: &mut String, pretty: bool, indent: usize)
             ReflectFieldRef::Repeated(repeated) => {
                 // TODO: do not print zeros for v3
                 for v in repeated {
-                    print_field(buf, pretty, indent, &mut first, f.name(), v.as_ref());
+                    print_field(buf, options, pretty, indent, &mut first, name, v.as_ref());
                 }
             }
             ReflectFieldRef::Optional(optional) => {
                 if let Some(v) = optional {
-                    print_field(buf, pretty, indent, &mut first, f.name(), v);
+                    print_field(buf, options, pretty, indent, &mut first, name, v);
+                }
+            }
+            ReflectFieldRef::Wrapper(wrapper) => {
+                if let Some(w) = wrapper {
+                    let v = ProtobufValueRef::Message(&*w);
+                    print_field(buf, options, pretty, indent, &mut first, name, v);
                 }
             }
         }
@@ -227,26 +320,118 @@ fn print_to_internal(m: &Message, buf: &mut String, pretty: bool, indent: usize)
 }
 
 pub fn print_to(m: &Message, buf: &mut String) {
-    print_to_internal(m, buf, false, 0)
+    print_to_internal(m, buf, &PrintOptions::default(), false, 0)
 }
 
-fn print_to_string_internal(m: &Message, pretty: bool) -> String {
+fn print_to_string_internal(m: &Message, options: &PrintOptions, pretty: bool) -> String {
     let mut r = String::new();
-    print_to_internal(m, &mut r, pretty, 0);
+    print_to_internal(m, &mut r, options, pretty, 0);
     r.to_string()
 }
 
 pub fn print_to_string(m: &Message) -> String {
-    print_to_string_internal(m, false)
+    print_to_string_internal(m, &PrintOptions::default(), false)
+}
+
+/// Print message to string with specified options
+pub fn print_to_string_with_options(m: &Message, options: &PrintOptions) -> String {
+    print_to_string_internal(m, options, false)
 }
 
 pub fn fmt(m: &Message, f: &mut fmt::Formatter) -> fmt::Result {
     let pretty = f.alternate();
-    f.write_str(&print_to_string_internal(m, pretty))
+    f.write_str(&print_to_string_internal(m, &PrintOptions::default(), pretty))
+}
+
+/// Location in text format input, line and column are 1-based
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Loc {
+    pub line: u32,
+    pub col: u32,
+}
+
+impl fmt::Display for Loc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.col)
+    }
+}
+
+/// Text format parse error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub loc: Loc,
+    pub message: String,
+}
+
+impl ParseError {
+    fn new<S : Into<String>>(loc: Loc, message: S) -> ParseError {
+        ParseError {
+            loc: loc,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.loc, self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ParseError {
+    fn description(&self) -> &str {
+        &self.message
+    }
+}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// Parse message from text format.
+///
+/// Non-repeated fields must be specified at most once,
+/// and all required fields must be specified.
+pub fn parse_from_str<M : Message>(text: &str) -> ParseResult<M> {
+    let mut parser = Parser::new(text, false);
+    let bytes = parser.parse_message(M::descriptor_static(None))?;
+    match parse_from_bytes(&bytes) {
+        Ok(m) => Ok(m),
+        Err(e) => Err(ParseError::new(parser.loc()?, e.to_string())),
+    }
+}
+
+/// Parse message from text format into given empty message,
+/// e. g. into `DynamicMessage` which type is not known statically.
+///
+/// Same checks as in `parse_from_str` are done.
+pub fn parse_from_str_into(m: &mut Message, text: &str) -> ParseResult<()> {
+    let d = m.descriptor();
+    let mut parser = Parser::new(text, false);
+    let bytes = parser.parse_message(d)?;
+    match m.merge_from_bytes(&bytes).and_then(|()| m.check_initialized()) {
+        Ok(()) => Ok(()),
+        Err(e) => Err(ParseError::new(parser.loc()?, e.to_string())),
+    }
+}
+
+/// Parse text format and merge it into given message.
+///
+/// Fields specified in the text replace (or, for repeated fields, are appended to)
+/// fields of the message, see `Message::merge_from`.
+pub fn merge_from_str(m: &mut Message, text: &str) -> ParseResult<()> {
+    let d = m.descriptor();
+    let mut parser = Parser::new(text, true);
+    let bytes = parser.parse_message(d)?;
+    match m.merge_from_bytes(&bytes) {
+        Ok(()) => Ok(()),
+        Err(e) => Err(ParseError::new(parser.loc()?, e.to_string())),
+    }
 }
 
 #[cfg(test)]
 mod test {
+    #[cfg(not(feature = "std"))]
+    use std::prelude::v1::*;
 
     fn escape(data: &[u8]) -> String {
         let mut s = String::with_capacity(data.len() * 4);
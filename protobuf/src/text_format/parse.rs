@@ -0,0 +1,554 @@
+//! Text format parser.
+//!
+//! Parser is driven by message descriptors and emits serialized message,
+//! which is then read with `Message::read_from`.
+
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+use std::collections::HashSet;
+use std::f64;
+use std::i32;
+use std::i64;
+use std::u32;
+
+use descriptor::FieldDescriptorProto_Label;
+use descriptor::FieldDescriptorProto_Type;
+use reflect::EnumDescriptor;
+use reflect::MessageDescriptor;
+use reflect::field_type;
+use reflect::field_type::FieldType;
+use reflect::field_type::MessageType;
+use stream::CodedOutputStream;
+use well_known_types;
+use well_known_types_util;
+
+use super::lexer::Lexer;
+use super::lexer::Token;
+use super::lexer::TokenWithLoc;
+use super::Loc;
+use super::ParseError;
+use super::ParseResult;
+
+
+pub struct Parser<'a> {
+    lexer: Lexer<'a>,
+    peeked: Option<TokenWithLoc>,
+    /// Allow non-repeated fields to be specified multiple times, and
+    /// do not check required fields (because they may be already set)
+    merge: bool,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(input: &'a str, merge: bool) -> Parser<'a> {
+        Parser {
+            lexer: Lexer::new(input),
+            peeked: None,
+            merge: merge,
+        }
+    }
+
+    fn peek(&mut self) -> ParseResult<Option<&Token>> {
+        if self.peeked.is_none() {
+            self.peeked = self.lexer.next_token()?;
+        }
+        Ok(self.peeked.as_ref().map(|t| &t.token))
+    }
+
+    fn next(&mut self) -> ParseResult<Option<Token>> {
+        match self.peeked.take() {
+            Some(t) => Ok(Some(t.token)),
+            None => Ok(self.lexer.next_token()?.map(|t| t.token)),
+        }
+    }
+
+    /// Location of the next token
+    pub fn loc(&mut self) -> ParseResult<Loc> {
+        self.peek()?;
+        Ok(match self.peeked {
+            Some(ref t) => t.loc,
+            None => self.lexer.loc(),
+        })
+    }
+
+    fn error<T, S : Into<String>>(&mut self, message: S) -> ParseResult<T> {
+        let loc = self.loc()?;
+        Err(ParseError::new(loc, message))
+    }
+
+    fn next_symbol_if_eq(&mut self, symbol: char) -> ParseResult<bool> {
+        if self.peek()? == Some(&Token::Symbol(symbol)) {
+            self.next()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn expect_symbol(&mut self, symbol: char) -> ParseResult<()> {
+        if !self.next_symbol_if_eq(symbol)? {
+            return self.error(format!("expecting '{}'", symbol));
+        }
+        Ok(())
+    }
+
+    fn read_u64(&mut self) -> ParseResult<u64> {
+        let v = match self.peek()? {
+            Some(&Token::IntLit(v)) => v,
+            _ => return self.error("expecting integer"),
+        };
+        self.next()?;
+        Ok(v)
+    }
+
+    fn read_i64(&mut self) -> ParseResult<i64> {
+        let neg = self.next_symbol_if_eq('-')?;
+        let loc = self.loc()?;
+        let v = self.read_u64()?;
+        if neg {
+            if v > i64::MAX as u64 + 1 {
+                return Err(ParseError::new(loc, "integer is out of range"));
+            }
+            Ok((v as i64).wrapping_neg())
+        } else {
+            if v > i64::MAX as u64 {
+                return Err(ParseError::new(loc, "integer is out of range"));
+            }
+            Ok(v as i64)
+        }
+    }
+
+    fn read_i32(&mut self) -> ParseResult<i32> {
+        let loc = self.loc()?;
+        let v = self.read_i64()?;
+        if v < i32::MIN as i64 || v > i32::MAX as i64 {
+            return Err(ParseError::new(loc, "integer is out of range"));
+        }
+        Ok(v as i32)
+    }
+
+    fn read_u32(&mut self) -> ParseResult<u32> {
+        let loc = self.loc()?;
+        let v = self.read_u64()?;
+        if v > u32::MAX as u64 {
+            return Err(ParseError::new(loc, "integer is out of range"));
+        }
+        Ok(v as u32)
+    }
+
+    fn read_f64(&mut self) -> ParseResult<f64> {
+        let neg = self.next_symbol_if_eq('-')?;
+        let v = match self.peek()? {
+            Some(&Token::FloatLit(v)) => Some(v),
+            Some(&Token::IntLit(v)) => Some(v as f64),
+            Some(&Token::Ident(ref ident)) => {
+                match &ident.to_lowercase()[..] {
+                    "inf" | "infinity" => Some(f64::INFINITY),
+                    "nan" => Some(f64::NAN),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        match v {
+            Some(v) => {
+                self.next()?;
+                Ok(if neg { -v } else { v })
+            }
+            None => self.error("expecting float"),
+        }
+    }
+
+    fn read_bool(&mut self) -> ParseResult<bool> {
+        let v = match self.peek()? {
+            Some(&Token::Ident(ref ident)) => {
+                match &ident[..] {
+                    "true" | "True" | "t" => Some(true),
+                    "false" | "False" | "f" => Some(false),
+                    _ => None,
+                }
+            }
+            Some(&Token::IntLit(1)) => Some(true),
+            Some(&Token::IntLit(0)) => Some(false),
+            _ => None,
+        };
+        match v {
+            Some(v) => {
+                self.next()?;
+                Ok(v)
+            }
+            None => self.error("expecting bool"),
+        }
+    }
+
+    fn read_bytes(&mut self) -> ParseResult<Vec<u8>> {
+        let mut r = Vec::new();
+        match self.peek()? {
+            Some(&Token::StrLit(..)) => {}
+            _ => return self.error("expecting string"),
+        }
+        // adjacent string literals are concatenated
+        while let Some(&Token::StrLit(..)) = self.peek()? {
+            match self.next()? {
+                Some(Token::StrLit(bytes)) => r.extend(bytes),
+                _ => unreachable!(),
+            }
+        }
+        Ok(r)
+    }
+
+    fn read_string(&mut self) -> ParseResult<String> {
+        let loc = self.loc()?;
+        match String::from_utf8(self.read_bytes()?) {
+            Ok(s) => Ok(s),
+            Err(_) => Err(ParseError::new(loc, "string is not valid UTF-8")),
+        }
+    }
+
+    fn read_enum(&mut self, d: &EnumDescriptor) -> ParseResult<i32> {
+        let loc = self.loc()?;
+        let name = match self.peek()? {
+            Some(&Token::Ident(ref name)) => Some(name.clone()),
+            _ => None,
+        };
+        match name {
+            Some(name) => {
+                self.next()?;
+                match d.values().iter().find(|v| v.name() == name) {
+                    Some(v) => Ok(v.value()),
+                    None => Err(ParseError::new(
+                        loc,
+                        format!("unknown value {} of enum {}", name, d.name()),
+                    )),
+                }
+            }
+            None => {
+                let number = self.read_i32()?;
+                if !d.is_open() && !d.values().iter().any(|v| v.value() == number) {
+                    return Err(ParseError::new(
+                        loc,
+                        format!("unknown value {} of enum {}", number, d.name()),
+                    ));
+                }
+                Ok(number)
+            }
+        }
+    }
+
+    fn merge_scalar_value(
+        &mut self,
+        field: &FieldType,
+        os: &mut CodedOutputStream,
+    ) -> ParseResult<()> {
+        let number = field.proto.get_number() as u32;
+        let r = match field.proto.get_field_type() {
+            FieldDescriptorProto_Type::TYPE_DOUBLE => os.write_double(number, self.read_f64()?),
+            FieldDescriptorProto_Type::TYPE_FLOAT => {
+                os.write_float(number, self.read_f64()? as f32)
+            }
+            FieldDescriptorProto_Type::TYPE_INT64 => os.write_int64(number, self.read_i64()?),
+            FieldDescriptorProto_Type::TYPE_UINT64 => os.write_uint64(number, self.read_u64()?),
+            FieldDescriptorProto_Type::TYPE_INT32 => os.write_int32(number, self.read_i32()?),
+            FieldDescriptorProto_Type::TYPE_FIXED64 => {
+                os.write_fixed64(number, self.read_u64()?)
+            }
+            FieldDescriptorProto_Type::TYPE_FIXED32 => {
+                os.write_fixed32(number, self.read_u32()?)
+            }
+            FieldDescriptorProto_Type::TYPE_BOOL => os.write_bool(number, self.read_bool()?),
+            FieldDescriptorProto_Type::TYPE_STRING => {
+                let s = self.read_string()?;
+                os.write_string(number, &s)
+            }
+            FieldDescriptorProto_Type::TYPE_BYTES => {
+                let b = self.read_bytes()?;
+                os.write_bytes(number, &b)
+            }
+            FieldDescriptorProto_Type::TYPE_UINT32 => os.write_uint32(number, self.read_u32()?),
+            FieldDescriptorProto_Type::TYPE_ENUM => {
+                let d = field.enum_descriptor.expect("enum descriptor");
+                os.write_enum(number, self.read_enum(d)?)
+            }
+            FieldDescriptorProto_Type::TYPE_SFIXED32 => {
+                os.write_sfixed32(number, self.read_i32()?)
+            }
+            FieldDescriptorProto_Type::TYPE_SFIXED64 => {
+                os.write_sfixed64(number, self.read_i64()?)
+            }
+            FieldDescriptorProto_Type::TYPE_SINT32 => os.write_sint32(number, self.read_i32()?),
+            FieldDescriptorProto_Type::TYPE_SINT64 => os.write_sint64(number, self.read_i64()?),
+            FieldDescriptorProto_Type::TYPE_MESSAGE |
+            FieldDescriptorProto_Type::TYPE_GROUP => unreachable!(),
+        };
+        r.expect("write to vec");
+        Ok(())
+    }
+
+    /// `Timestamp` or `Duration` specified as string,
+    /// like printed with `PrintOptions::human_readable_time`
+    fn merge_time_value(
+        &mut self,
+        field: &FieldType,
+        message: MessageType,
+        os: &mut CodedOutputStream,
+    ) -> ParseResult<()> {
+        let loc = self.loc()?;
+        let s = self.read_string()?;
+        let parsed = match message.name() {
+            "google.protobuf.Timestamp" => well_known_types::parse_timestamp(&s),
+            "google.protobuf.Duration" => well_known_types::parse_duration(&s),
+            _ => return Err(ParseError::new(loc, "expecting '{' or '<'")),
+        };
+        let (seconds, nanos) = match parsed {
+            Ok(r) => r,
+            Err(()) => {
+                return Err(ParseError::new(
+                    loc,
+                    format!("incorrect {}: {:?}", message.name(), s),
+                ));
+            }
+        };
+        let mut bytes = Vec::new();
+        {
+            let mut nested = CodedOutputStream::vec(&mut bytes);
+            if seconds != 0 {
+                nested.write_int64(1, seconds).expect("write to vec");
+            }
+            if nanos != 0 {
+                nested.write_int32(2, nanos).expect("write to vec");
+            }
+        }
+        field.write_message_bytes(&bytes, os).expect("write to vec");
+        Ok(())
+    }
+
+    fn merge_message_value(
+        &mut self,
+        field: &FieldType,
+        message: MessageType,
+        os: &mut CodedOutputStream,
+    ) -> ParseResult<()> {
+        if let Some(&Token::StrLit(..)) = self.peek()? {
+            return self.merge_time_value(field, message, os);
+        }
+        let bytes = self.read_message_bytes(message)?;
+        field.write_message_bytes(&bytes, os).expect("write to vec");
+        Ok(())
+    }
+
+    /// Parse message in braces or angle brackets, return serialized message
+    fn read_message_bytes(&mut self, message: MessageType) -> ParseResult<Vec<u8>> {
+        let end = if self.next_symbol_if_eq('{')? {
+            '}'
+        } else if self.next_symbol_if_eq('<')? {
+            '>'
+        } else {
+            return self.error("expecting '{' or '<'");
+        };
+        self.merge_fields(message, Some(end))
+    }
+
+    /// Type URL in square brackets after `[`, e. g. `type.googleapis.com/x.Y]`
+    fn read_type_url(&mut self) -> ParseResult<String> {
+        let mut r = String::new();
+        loop {
+            match self.next()? {
+                Some(Token::Ident(ident)) => r.push_str(&ident),
+                Some(Token::IntLit(n)) => r.push_str(&n.to_string()),
+                Some(Token::Symbol(']')) if !r.is_empty() => return Ok(r),
+                Some(Token::Symbol(c)) if c == '.' || c == '/' || c == '-' => r.push(c),
+                _ => return self.error("expecting type URL"),
+            }
+        }
+    }
+
+    /// Content of `Any` in expanded form, `[type.googleapis.com/x.Y] { ... }`,
+    /// `[` is already consumed
+    fn merge_any_content(
+        &mut self,
+        loc: Loc,
+        os: &mut CodedOutputStream,
+        seen_fields: &mut HashSet<i32>,
+    ) -> ParseResult<()> {
+        let type_url = self.read_type_url()?;
+        let content_type = match well_known_types_util::find_type_by_url(&type_url) {
+            Some(d) => d,
+            None => {
+                return Err(ParseError::new(loc, format!("unknown type in Any: {}", type_url)));
+            }
+        };
+
+        // type_url and value fields
+        let first = seen_fields.insert(1);
+        seen_fields.insert(2);
+        if !first && !self.merge {
+            return Err(ParseError::new(loc, "Any content is specified multiple times"));
+        }
+
+        // colon is optional before message value
+        self.next_symbol_if_eq(':')?;
+        let bytes = self.read_message_bytes(MessageType::Message(content_type))?;
+        os.write_string(1, &type_url).expect("write to vec");
+        os.write_bytes(2, &bytes).expect("write to vec");
+
+        // optional field separator
+        if !self.next_symbol_if_eq(';')? {
+            self.next_symbol_if_eq(',')?;
+        }
+
+        Ok(())
+    }
+
+    fn merge_field(
+        &mut self,
+        message: MessageType,
+        os: &mut CodedOutputStream,
+        seen_fields: &mut HashSet<i32>,
+        seen_oneofs: &mut HashSet<i32>,
+    ) -> ParseResult<()> {
+        let loc = self.loc()?;
+        let name = match self.next()? {
+            Some(Token::Ident(name)) => name,
+            Some(Token::Symbol('[')) if message.name() == "google.protobuf.Any" => {
+                return self.merge_any_content(loc, os, seen_fields);
+            }
+            Some(Token::Symbol('[')) => {
+                return Err(ParseError::new(loc, "extensions are not supported"));
+            }
+            _ => return Err(ParseError::new(loc, "expecting field name")),
+        };
+
+        // group is named after its type, but field name is accepted too
+        let field = message.field_protos().iter().find(|f| {
+            f.get_name() == name || field_type::text_format_name(f) == name
+        });
+        let field = match field.and_then(|f| message.field_by_name(f.get_name())) {
+            Some(field) => field,
+            None => {
+                return Err(ParseError::new(
+                    loc,
+                    format!("message {} has no field {}", message.name(), name),
+                ));
+            }
+        };
+
+        let repeated = field.proto.get_label() == FieldDescriptorProto_Label::LABEL_REPEATED;
+
+        let first = seen_fields.insert(field.proto.get_number());
+        if !repeated && !self.merge {
+            if !first {
+                return Err(ParseError::new(
+                    loc,
+                    format!("non-repeated field {} is specified multiple times", name),
+                ));
+            }
+            if field.proto.has_oneof_index() &&
+                !seen_oneofs.insert(field.proto.get_oneof_index())
+            {
+                return Err(ParseError::new(
+                    loc,
+                    format!("field {} is a member of oneof which is already set", name),
+                ));
+            }
+        }
+
+        match field.message {
+            Some(nested) => {
+                // colon is optional before message value
+                self.next_symbol_if_eq(':')?;
+                if repeated && self.next_symbol_if_eq('[')? {
+                    if !self.next_symbol_if_eq(']')? {
+                        loop {
+                            self.merge_message_value(&field, nested, os)?;
+                            if self.next_symbol_if_eq(']')? {
+                                break;
+                            }
+                            self.expect_symbol(',')?;
+                        }
+                    }
+                } else {
+                    self.merge_message_value(&field, nested, os)?;
+                }
+            }
+            None => {
+                self.expect_symbol(':')?;
+                if repeated && self.next_symbol_if_eq('[')? {
+                    if !self.next_symbol_if_eq(']')? {
+                        loop {
+                            self.merge_scalar_value(&field, os)?;
+                            if self.next_symbol_if_eq(']')? {
+                                break;
+                            }
+                            self.expect_symbol(',')?;
+                        }
+                    }
+                } else {
+                    self.merge_scalar_value(&field, os)?;
+                }
+            }
+        }
+
+        // optional field separator
+        if !self.next_symbol_if_eq(';')? {
+            self.next_symbol_if_eq(',')?;
+        }
+
+        Ok(())
+    }
+
+    /// Parse fields until `end` symbol (or until EOF if `end` is `None`)
+    fn merge_fields(&mut self, message: MessageType, end: Option<char>) -> ParseResult<Vec<u8>> {
+        let mut r = Vec::new();
+        let mut seen_fields = HashSet::new();
+        let mut seen_oneofs = HashSet::new();
+        let end_loc;
+        {
+            let mut os = CodedOutputStream::vec(&mut r);
+            loop {
+                let loc = self.loc()?;
+                match end {
+                    Some(end) => {
+                        if self.next_symbol_if_eq(end)? {
+                            end_loc = loc;
+                            break;
+                        }
+                        if self.peek()?.is_none() {
+                            return self.error(format!("expecting '{}'", end));
+                        }
+                    }
+                    None => {
+                        if self.peek()?.is_none() {
+                            end_loc = loc;
+                            break;
+                        }
+                    }
+                }
+                self.merge_field(message, &mut os, &mut seen_fields, &mut seen_oneofs)?;
+            }
+        }
+
+        if !self.merge {
+            for field in message.field_protos() {
+                if field.get_label() == FieldDescriptorProto_Label::LABEL_REQUIRED &&
+                    !seen_fields.contains(&field.get_number())
+                {
+                    return Err(ParseError::new(
+                        end_loc,
+                        format!(
+                            "message {} is missing required field {}",
+                            message.name(),
+                            field.get_name()
+                        ),
+                    ));
+                }
+            }
+        }
+
+        Ok(r)
+    }
+
+    /// Parse whole input as message of given type, return serialized message
+    pub fn parse_message(&mut self, d: &'static MessageDescriptor) -> ParseResult<Vec<u8>> {
+        self.merge_fields(MessageType::Message(d), None)
+    }
+}
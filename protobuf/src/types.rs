@@ -1,3 +1,6 @@
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
 use std::marker;
 
 #[cfg(feature = "bytes")]
@@ -7,13 +10,20 @@ use chars::Chars;
 
 use stream::CodedInputStream;
 use stream::CodedOutputStream;
+use stream::with_coded_output_stream_to_bytes;
+use error::ProtobufError;
 use error::ProtobufResult;
-use core::ProtobufEnum;
-use core::Message;
+use error::WireError;
+use message::ProtobufEnum;
+use enum_or_unknown::ProtobufEnumOrUnknown;
+use message::Message;
 use wire_format::WireType;
 use rt;
 use reflect::ProtobufValue;
+use reflect::MessageDescriptor;
+use reflect::EnumDescriptor;
 use unknown::UnknownValues;
+use unknown::UnknownValueRef;
 
 pub trait ProtobufType {
     type Value: ProtobufValue + Clone + 'static;
@@ -24,8 +34,25 @@ pub trait ProtobufType {
 
     fn compute_size(value: &Self::Value) -> u32;
 
-    fn get_from_unknown(_unknown_values: &UnknownValues) -> Option<Self::Value> {
-        unimplemented!()
+    /// Decode the field value stored in unknown fields.
+    ///
+    /// If the field occurs multiple times, the last value is returned
+    /// (message values are merged instead).
+    fn get_from_unknown(unknown_values: &UnknownValues) -> Option<Self::Value> {
+        Self::get_repeated_from_unknown(unknown_values).pop()
+    }
+
+    /// Decode all field values stored in unknown fields, packed or not.
+    ///
+    /// Values which cannot be decoded as this type are skipped.
+    fn get_repeated_from_unknown(unknown_values: &UnknownValues) -> Vec<Self::Value> {
+        let mut r = Vec::new();
+        for value in unknown_values {
+            if let Ok(values) = read_unknown_value::<Self>(value) {
+                r.extend(values);
+            }
+        }
+        r
     }
 
     fn write_with_cached_size(
@@ -33,6 +60,37 @@ pub trait ProtobufType {
         value: &Self::Value,
         os: &mut CodedOutputStream,
     ) -> ProtobufResult<()>;
+
+    /// Descriptor of the message type, `None` if this is not a message type
+    fn message_descriptor() -> Option<&'static MessageDescriptor> {
+        None
+    }
+
+    /// Descriptor of the enum type, `None` if this is not an enum type
+    fn enum_descriptor() -> Option<&'static EnumDescriptor> {
+        None
+    }
+}
+
+fn read_unknown_value<T : ProtobufType + ?Sized>(
+    value: UnknownValueRef,
+) -> ProtobufResult<Vec<T::Value>> {
+    let mut r = Vec::new();
+    if value.wire_type() == T::wire_type() {
+        let bytes = with_coded_output_stream_to_bytes(|os| os.write_unknown_no_tag(value))?;
+        let mut is = CodedInputStream::from_bytes(&bytes);
+        r.push(T::read(&mut is)?);
+        is.check_eof()?;
+    } else if let UnknownValueRef::LengthDelimited(bytes) = value {
+        // packed repeated field
+        let mut is = CodedInputStream::from_bytes(bytes);
+        while !is.eof()? {
+            r.push(T::read(&mut is)?);
+        }
+    } else {
+        return Err(ProtobufError::WireError(WireError::UnexpectedWireType(value.wire_type())));
+    }
+    Ok(r)
 }
 
 pub struct ProtobufTypeFloat;
@@ -58,6 +116,7 @@ pub struct ProtobufTypeCarllercheBytes;
 pub struct ProtobufTypeCarllercheChars;
 
 pub struct ProtobufTypeEnum<E : ProtobufEnum>(marker::PhantomData<E>);
+pub struct ProtobufTypeEnumOrUnknown<E : ProtobufEnum>(marker::PhantomData<E>);
 pub struct ProtobufTypeMessage<M : Message>(marker::PhantomData<M>);
 
 impl ProtobufType for ProtobufTypeFloat {
@@ -359,10 +418,6 @@ impl ProtobufType for ProtobufTypeBool {
         is.read_bool()
     }
 
-    fn get_from_unknown(unknown: &UnknownValues) -> Option<bool> {
-        unknown.varint.iter().rev().next().map(|&v| v != 0)
-    }
-
     fn compute_size(_value: &bool) -> u32 {
         1
     }
@@ -496,6 +551,38 @@ impl<E : ProtobufEnum + ProtobufValue> ProtobufType for ProtobufTypeEnum<E> {
     ) -> ProtobufResult<()> {
         os.write_enum_obj(field_number, *value)
     }
+
+    fn enum_descriptor() -> Option<&'static EnumDescriptor> {
+        Some(E::enum_descriptor_static(None))
+    }
+}
+
+impl<E : ProtobufEnum> ProtobufType for ProtobufTypeEnumOrUnknown<E> {
+    type Value = ProtobufEnumOrUnknown<E>;
+
+    fn wire_type() -> WireType {
+        WireType::WireTypeVarint
+    }
+
+    fn read(is: &mut CodedInputStream) -> ProtobufResult<ProtobufEnumOrUnknown<E>> {
+        is.read_enum_or_unknown()
+    }
+
+    fn compute_size(value: &ProtobufEnumOrUnknown<E>) -> u32 {
+        rt::compute_raw_varint32_size(value.value() as u32) // TODO: wrap
+    }
+
+    fn write_with_cached_size(
+        field_number: u32,
+        value: &ProtobufEnumOrUnknown<E>,
+        os: &mut CodedOutputStream,
+    ) -> ProtobufResult<()> {
+        os.write_enum(field_number, value.value())
+    }
+
+    fn enum_descriptor() -> Option<&'static EnumDescriptor> {
+        Some(E::enum_descriptor_static(None))
+    }
 }
 
 impl<M : Message + Clone + ProtobufValue> ProtobufType for ProtobufTypeMessage<M> {
@@ -513,6 +600,17 @@ impl<M : Message + Clone + ProtobufValue> ProtobufType for ProtobufTypeMessage<M
         value.compute_size()
     }
 
+    /// Like protobuf parser does with a message field occurring multiple times,
+    /// merge all the occurrences.
+    fn get_from_unknown(unknown_values: &UnknownValues) -> Option<M> {
+        let mut values = Self::get_repeated_from_unknown(unknown_values).into_iter();
+        let mut r = values.next()?;
+        for value in values {
+            r.merge(&value);
+        }
+        Some(r)
+    }
+
     fn write_with_cached_size(
         field_number: u32,
         value: &Self::Value,
@@ -520,4 +618,8 @@ impl<M : Message + Clone + ProtobufValue> ProtobufType for ProtobufTypeMessage<M
     ) -> ProtobufResult<()> {
         os.write_message(field_number, value)
     }
+
+    fn message_descriptor() -> Option<&'static MessageDescriptor> {
+        Some(M::descriptor_static(None))
+    }
 }
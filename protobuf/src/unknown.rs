@@ -1,7 +1,14 @@
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+use std::collections::HashMap;
+use std::collections::hash_map;
+use std::hash::Hash;
+use std::hash::Hasher;
 use std::slice;
 use stream::wire_format;
 
-#[derive(Debug)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub enum UnknownValue {
     Fixed32(u32),
     Fixed64(u64),
@@ -42,7 +49,8 @@ impl<'o> UnknownValueRef<'o> {
     }
 }
 
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug, Default, Hash)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct UnknownValues {
     pub fixed32: Vec<u32>,
     pub fixed64: Vec<u64>,
@@ -111,3 +119,146 @@ impl<'o> Iterator for UnknownValuesIter<'o> {
         None
     }
 }
+
+/// Fields of a message which were not recognized by its schema,
+/// for example extension fields or fields added in a newer version of the schema.
+///
+/// Unknown fields are preserved on parsing and written back on serialization.
+/// Unknown groups are skipped.
+#[derive(Clone, Eq, Debug, Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
+pub struct UnknownFields {
+    /// `None` if no unknown fields were seen, to keep empty messages small.
+    pub fields: Option<Box<HashMap<u32, UnknownValues>>>,
+}
+
+impl UnknownFields {
+    pub fn new() -> UnknownFields {
+        Default::default()
+    }
+
+    fn init_map(&mut self) -> &mut HashMap<u32, UnknownValues> {
+        if self.fields.is_none() {
+            self.fields = Some(Default::default());
+        }
+        self.fields.as_mut().unwrap()
+    }
+
+    fn find_field<'a>(&'a mut self, number: u32) -> &'a mut UnknownValues {
+        self.init_map().entry(number).or_insert_with(Default::default)
+    }
+
+    pub fn add_fixed32(&mut self, number: u32, fixed32: u32) {
+        self.find_field(number).fixed32.push(fixed32);
+    }
+
+    pub fn add_fixed64(&mut self, number: u32, fixed64: u64) {
+        self.find_field(number).fixed64.push(fixed64);
+    }
+
+    pub fn add_varint(&mut self, number: u32, varint: u64) {
+        self.find_field(number).varint.push(varint);
+    }
+
+    pub fn add_length_delimited(&mut self, number: u32, length_delimited: Vec<u8>) {
+        self.find_field(number).length_delimited.push(length_delimited);
+    }
+
+    pub fn add_value(&mut self, number: u32, value: UnknownValue) {
+        self.find_field(number).add_value(value);
+    }
+
+    /// Values of the field with given number, `None` if the field is absent.
+    pub fn get(&self, number: u32) -> Option<&UnknownValues> {
+        match self.fields {
+            Some(ref map) => map.get(&number),
+            None => None,
+        }
+    }
+
+    /// Remove all values of the field with given number.
+    pub fn remove(&mut self, number: u32) {
+        if let Some(ref mut map) = self.fields {
+            map.remove(&number);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self.fields {
+            Some(ref map) => map.is_empty(),
+            None => true,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        if let Some(ref mut map) = self.fields {
+            map.clear();
+        }
+    }
+
+    /// Append all the values of other unknown fields.
+    pub fn merge(&mut self, other: &UnknownFields) {
+        for (number, values) in other {
+            let field = self.find_field(number);
+            field.fixed32.extend(&values.fixed32);
+            field.fixed64.extend(&values.fixed64);
+            field.varint.extend(&values.varint);
+            field.length_delimited.extend(values.length_delimited.iter().cloned());
+        }
+    }
+
+    pub fn iter<'s>(&'s self) -> UnknownFieldsIter<'s> {
+        UnknownFieldsIter {
+            entries: self.fields.as_ref().map(|m| m.iter()),
+        }
+    }
+}
+
+/// Empty map is equal to no map, so unknown fields are equal
+/// to new ones after all the fields are removed
+impl PartialEq for UnknownFields {
+    fn eq(&self, other: &UnknownFields) -> bool {
+        match (&self.fields, &other.fields) {
+            (&Some(ref a), &Some(ref b)) => a == b,
+            _ => self.is_empty() && other.is_empty(),
+        }
+    }
+}
+
+/// Hash does not depend on order of fields in the map,
+/// so messages can derive `Hash`
+impl Hash for UnknownFields {
+    fn hash<H : Hasher>(&self, state: &mut H) {
+        if let Some(ref map) = self.fields {
+            if !map.is_empty() {
+                let mut fields: Vec<_> = map.iter().collect();
+                fields.sort_by_key(|&(number, _)| *number);
+                fields.hash(state);
+            }
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a UnknownFields {
+    type Item = (u32, &'a UnknownValues);
+    type IntoIter = UnknownFieldsIter<'a>;
+
+    fn into_iter(self) -> UnknownFieldsIter<'a> {
+        self.iter()
+    }
+}
+
+pub struct UnknownFieldsIter<'s> {
+    entries: Option<hash_map::Iter<'s, u32, UnknownValues>>,
+}
+
+impl<'s> Iterator for UnknownFieldsIter<'s> {
+    type Item = (u32, &'s UnknownValues);
+
+    fn next(&mut self) -> Option<(u32, &'s UnknownValues)> {
+        match self.entries {
+            Some(ref mut entries) => entries.next().map(|(&number, values)| (number, values)),
+            None => None,
+        }
+    }
+}
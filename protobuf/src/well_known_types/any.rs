@@ -21,11 +21,115 @@
 use protobuf::Message as Message_imported_for_functions;
 use protobuf::ProtobufEnum as ProtobufEnum_imported_for_functions;
 
+/// `Any` contains an arbitrary serialized protocol buffer message along with a
+/// URL that describes the type of the serialized message.
+///
+/// Protobuf library provides support to pack/unpack Any values in the form
+/// of utility functions or additional generated methods of the Any type.
+///
+/// Example 1: Pack and unpack a message in C++.
+///
+/// ```text
+/// Foo foo = ...;
+/// Any any;
+/// any.PackFrom(foo);
+/// ...
+/// if (any.UnpackTo(&foo)) {
+///   ...
+/// }
+/// ```
+///
+/// Example 2: Pack and unpack a message in Java.
+///
+/// ```text
+/// Foo foo = ...;
+/// Any any = Any.pack(foo);
+/// ...
+/// if (any.is(Foo.class)) {
+///   foo = any.unpack(Foo.class);
+/// }
+/// ```
+///
+///  Example 3: Pack and unpack a message in Python.
+///
+/// ```text
+/// foo = Foo(...)
+/// any = Any()
+/// any.Pack(foo)
+/// ...
+/// if any.Is(Foo.DESCRIPTOR):
+///   any.Unpack(foo)
+///   ...
+/// ```
+///
+/// The pack methods provided by protobuf library will by default use
+/// 'type.googleapis.com/full.type.name' as the type URL and the unpack
+/// methods only use the fully qualified type name after the last '/'
+/// in the type URL, for example "foo.bar.com/x/y.z" will yield type
+/// name "y.z".
+///
+///
+/// JSON
+/// ====
+/// The JSON representation of an `Any` value uses the regular
+/// representation of the deserialized, embedded message, with an
+/// additional field `@type` which contains the type URL. Example:
+///
+/// ```text
+/// package google.profile;
+/// message Person {
+///   string first_name = 1;
+///   string last_name = 2;
+/// }
+///
+/// {
+///   "@type": "type.googleapis.com/google.profile.Person",
+///   "firstName": <string>,
+///   "lastName": <string>
+/// }
+/// ```
+///
+/// If the embedded message type is well-known and has a custom JSON
+/// representation, that representation will be embedded adding a field
+/// `value` which holds the custom JSON in addition to the `@type`
+/// field. Example (for message \[google.protobuf.Duration\]\[\]):
+///
+/// ```text
+/// {
+///   "@type": "type.googleapis.com/google.protobuf.Duration",
+///   "value": "1.212s"
+/// }
+/// ```
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct Any {
     // message fields
+    /// A URL/resource name whose content describes the type of the
+    /// serialized protocol buffer message.
+    ///
+    /// For URLs which use the scheme `http`, `https`, or no scheme, the
+    /// following restrictions and interpretations apply:
+    ///
+    /// * If no scheme is provided, `https` is assumed.
+    /// * The last segment of the URL's path must represent the fully
+    ///   qualified name of the type (as in `path/google.protobuf.Duration`).
+    ///   The name should be in a canonical form (e.g., leading "." is
+    ///   not accepted).
+    /// * An HTTP GET on the URL must yield a \[google.protobuf.Type\]\[\]
+    ///   value in binary format, or produce an error.
+    /// * Applications are allowed to cache lookup results based on the
+    ///   URL, or have them precompiled into a binary to avoid any
+    ///   lookup. Therefore, binary compatibility needs to be preserved
+    ///   on changes to types. (Use versioned type names to manage
+    ///   breaking changes.)
+    ///
+    /// Schemes other than `http`, `https` (or the empty scheme) might be
+    /// used with implementation specific semantics.
     pub type_url: ::std::string::String,
+    /// Must be a valid serialized protocol buffer of the above specified type.
     pub value: ::std::vec::Vec<u8>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl Any {
@@ -101,35 +205,44 @@ impl ::protobuf::Message for Any {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_type_url: ::std::option::Option<::std::string::String> = None;
-        let mut _field_value: ::std::option::Option<::std::vec::Vec<u8>> = None;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = Any::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_type_url = ::std::option::Option::Some(_is.read_string()?);
+                    self.type_url = is.read_string()?;
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_value = ::std::option::Option::Some(_is.read_bytes()?);
+                    self.value = is.read_bytes()?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(Any {
-            type_url: _field_type_url.unwrap_or_default(),
-            value: _field_value.unwrap_or_default(),
-        })
+    fn merge(&mut self, other: &Self) {
+        if !other.type_url.is_empty() {
+            self.type_url = other.type_url.clone();
+        }
+        if !other.value.is_empty() {
+            self.value = other.value.clone();
+        }
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -142,6 +255,7 @@ impl ::protobuf::Message for Any {
         if !self.value.is_empty() {
             _size += ::protobuf::rt::bytes_size(2, &self.value);
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -152,16 +266,25 @@ impl ::protobuf::Message for Any {
         if !self.value.is_empty() {
             _os.write_bytes(2, &self.value)?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -21,16 +21,48 @@
 use protobuf::Message as Message_imported_for_functions;
 use protobuf::ProtobufEnum as ProtobufEnum_imported_for_functions;
 
+/// Api is a light-weight descriptor for a protocol buffer service.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct Api {
     // message fields
+    /// The fully qualified name of this api, including package name
+    /// followed by the api's simple name.
     pub name: ::std::string::String,
+    /// The methods of this api, in unspecified order.
     pub methods: ::std::vec::Vec<Method>,
+    /// Any metadata attached to the API.
     pub options: ::std::vec::Vec<::protobuf::well_known_types::Option>,
+    /// A version string for this api. If specified, must have the form
+    /// `major-version.minor-version`, as in `1.10`. If the minor version
+    /// is omitted, it defaults to zero. If the entire version field is
+    /// empty, the major version is derived from the package name, as
+    /// outlined below. If the field is not empty, the version in the
+    /// package name will be verified to be consistent with what is
+    /// provided here.
+    ///
+    /// The versioning schema uses \[semantic
+    /// versioning\](http://semver.org) where the major version number
+    /// indicates a breaking change and the minor version an additive,
+    /// non-breaking change. Both version numbers are signals to users
+    /// what to expect from different versions, and should be carefully
+    /// chosen based on the product plan.
+    ///
+    /// The major version is also reflected in the package name of the
+    /// API, which must end in `v<major-version>`, as in
+    /// `google.feature.v1`. For major versions 0 and 1, the suffix can
+    /// be omitted. Zero major versions must only be used for
+    /// experimental, none-GA apis.
     pub version: ::std::string::String,
+    /// Source context for the protocol buffer service represented by this
+    /// message.
     pub source_context: ::protobuf::SingularPtrField<::protobuf::well_known_types::SourceContext>,
+    /// Included APIs. See \[Mixin\]\[\].
     pub mixins: ::std::vec::Vec<Mixin>,
-    pub syntax: ::protobuf::well_known_types::Syntax,
+    /// The source syntax of the service.
+    pub syntax: ::protobuf::ProtobufEnumOrUnknown<::protobuf::well_known_types::Syntax>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl Api {
@@ -212,19 +244,19 @@ impl Api {
 
 
     // Param is passed by value, moved
-    pub fn set_syntax(&mut self, v: ::protobuf::well_known_types::Syntax) {
+    pub fn set_syntax(&mut self, v: ::protobuf::ProtobufEnumOrUnknown<::protobuf::well_known_types::Syntax>) {
         self.syntax = v;
     }
 
-    pub fn get_syntax(&self) -> ::protobuf::well_known_types::Syntax {
+    pub fn get_syntax(&self) -> ::protobuf::ProtobufEnumOrUnknown<::protobuf::well_known_types::Syntax> {
         self.syntax
     }
 
-    fn get_syntax_for_reflect(&self) -> &::protobuf::well_known_types::Syntax {
+    fn get_syntax_for_reflect(&self) -> &::protobuf::ProtobufEnumOrUnknown<::protobuf::well_known_types::Syntax> {
         &self.syntax
     }
 
-    fn mut_syntax_for_reflect(&mut self) -> &mut ::protobuf::well_known_types::Syntax {
+    fn mut_syntax_for_reflect(&mut self) -> &mut ::protobuf::ProtobufEnumOrUnknown<::protobuf::well_known_types::Syntax> {
         &mut self.syntax
     }
 }
@@ -254,66 +286,69 @@ impl ::protobuf::Message for Api {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_name: ::std::option::Option<::std::string::String> = None;
-        let mut _field_methods: ::std::vec::Vec<Method> = ::std::vec::Vec::new();
-        let mut _field_options: ::std::vec::Vec<::protobuf::well_known_types::Option> = ::std::vec::Vec::new();
-        let mut _field_version: ::std::option::Option<::std::string::String> = None;
-        let mut _field_source_context: ::std::option::Option<::protobuf::SingularPtrField<::protobuf::well_known_types::SourceContext>> = None;
-        let mut _field_mixins: ::std::vec::Vec<Mixin> = ::std::vec::Vec::new();
-        let mut _field_syntax: ::std::option::Option<::protobuf::well_known_types::Syntax> = None;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = Api::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_name = ::std::option::Option::Some(_is.read_string()?);
+                    self.name = is.read_string()?;
                 },
                 2 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_methods)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.methods)?;
                 },
                 3 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_options)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.options)?;
                 },
                 4 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_version = ::std::option::Option::Some(_is.read_string()?);
+                    self.version = is.read_string()?;
                 },
                 5 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    _field_source_context = ::std::option::Option::Some(::protobuf::SingularPtrField::some(_is.read_message()?));
+                    ::protobuf::rt::merge_singular_message_into(wire_type, is, &mut self.source_context)?;
                 },
                 6 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_mixins)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.mixins)?;
                 },
                 7 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_syntax = ::std::option::Option::Some(_is.read_enum()?);
+                    self.syntax = is.read_enum_or_unknown()?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(Api {
-            name: _field_name.unwrap_or_default(),
-            methods: _field_methods,
-            options: _field_options,
-            version: _field_version.unwrap_or_default(),
-            source_context: _field_source_context.unwrap_or_default(),
-            mixins: _field_mixins,
-            syntax: _field_syntax.unwrap_or_default(),
-        })
+    fn merge(&mut self, other: &Self) {
+        if !other.name.is_empty() {
+            self.name = other.name.clone();
+        }
+        self.methods.extend(other.methods.iter().cloned());
+        self.options.extend(other.options.iter().cloned());
+        if !other.version.is_empty() {
+            self.version = other.version.clone();
+        }
+        ::protobuf::rt::merge_singular_message(&mut self.source_context, &other.source_context);
+        self.mixins.extend(other.mixins.iter().cloned());
+        if other.syntax != ::protobuf::ProtobufEnumOrUnknown::new(::protobuf::well_known_types::Syntax::SYNTAX_PROTO2) {
+            self.syntax = other.syntax.clone();
+        }
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -342,9 +377,10 @@ impl ::protobuf::Message for Api {
             let len = value.compute_size();
             _size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
-        if self.syntax != ::protobuf::well_known_types::Syntax::SYNTAX_PROTO2 {
-            _size += ::protobuf::rt::enum_size(7, self.syntax);
+        if self.syntax != ::protobuf::ProtobufEnumOrUnknown::new(::protobuf::well_known_types::Syntax::SYNTAX_PROTO2) {
+            _size += ::protobuf::rt::enum_or_unknown_size(7, self.syntax);
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -375,19 +411,28 @@ impl ::protobuf::Message for Api {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         };
-        if self.syntax != ::protobuf::well_known_types::Syntax::SYNTAX_PROTO2 {
+        if self.syntax != ::protobuf::ProtobufEnumOrUnknown::new(::protobuf::well_known_types::Syntax::SYNTAX_PROTO2) {
             _os.write_enum(7, self.syntax.value())?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -437,7 +482,7 @@ impl ::protobuf::Message for Api {
                     Api::get_mixins_for_reflect,
                     Api::mut_mixins_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<::protobuf::well_known_types::Syntax>>(
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnumOrUnknown<::protobuf::well_known_types::Syntax>>(
                     "syntax",
                     Api::get_syntax_for_reflect,
                     Api::mut_syntax_for_reflect,
@@ -464,16 +509,27 @@ impl ::protobuf::reflect::ProtobufValue for Api {
     }
 }
 
+/// Method represents a method of an api.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct Method {
     // message fields
+    /// The simple name of this method.
     pub name: ::std::string::String,
+    /// A URL of the input message type.
     pub request_type_url: ::std::string::String,
+    /// If true, the request is streamed.
     pub request_streaming: bool,
+    /// The URL of the output message type.
     pub response_type_url: ::std::string::String,
+    /// If true, the response is streamed.
     pub response_streaming: bool,
+    /// Any metadata attached to the method.
     pub options: ::std::vec::Vec<::protobuf::well_known_types::Option>,
-    pub syntax: ::protobuf::well_known_types::Syntax,
+    /// The source syntax of this method.
+    pub syntax: ::protobuf::ProtobufEnumOrUnknown<::protobuf::well_known_types::Syntax>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl Method {
@@ -638,19 +694,19 @@ impl Method {
 
 
     // Param is passed by value, moved
-    pub fn set_syntax(&mut self, v: ::protobuf::well_known_types::Syntax) {
+    pub fn set_syntax(&mut self, v: ::protobuf::ProtobufEnumOrUnknown<::protobuf::well_known_types::Syntax>) {
         self.syntax = v;
     }
 
-    pub fn get_syntax(&self) -> ::protobuf::well_known_types::Syntax {
+    pub fn get_syntax(&self) -> ::protobuf::ProtobufEnumOrUnknown<::protobuf::well_known_types::Syntax> {
         self.syntax
     }
 
-    fn get_syntax_for_reflect(&self) -> &::protobuf::well_known_types::Syntax {
+    fn get_syntax_for_reflect(&self) -> &::protobuf::ProtobufEnumOrUnknown<::protobuf::well_known_types::Syntax> {
         &self.syntax
     }
 
-    fn mut_syntax_for_reflect(&mut self) -> &mut ::protobuf::well_known_types::Syntax {
+    fn mut_syntax_for_reflect(&mut self) -> &mut ::protobuf::ProtobufEnumOrUnknown<::protobuf::well_known_types::Syntax> {
         &mut self.syntax
     }
 }
@@ -665,72 +721,84 @@ impl ::protobuf::Message for Method {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_name: ::std::option::Option<::std::string::String> = None;
-        let mut _field_request_type_url: ::std::option::Option<::std::string::String> = None;
-        let mut _field_request_streaming: ::std::option::Option<bool> = None;
-        let mut _field_response_type_url: ::std::option::Option<::std::string::String> = None;
-        let mut _field_response_streaming: ::std::option::Option<bool> = None;
-        let mut _field_options: ::std::vec::Vec<::protobuf::well_known_types::Option> = ::std::vec::Vec::new();
-        let mut _field_syntax: ::std::option::Option<::protobuf::well_known_types::Syntax> = None;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = Method::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_name = ::std::option::Option::Some(_is.read_string()?);
+                    self.name = is.read_string()?;
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_request_type_url = ::std::option::Option::Some(_is.read_string()?);
+                    self.request_type_url = is.read_string()?;
                 },
                 3 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_request_streaming = ::std::option::Option::Some(_is.read_bool()?);
+                    self.request_streaming = is.read_bool()?;
                 },
                 4 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_response_type_url = ::std::option::Option::Some(_is.read_string()?);
+                    self.response_type_url = is.read_string()?;
                 },
                 5 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_response_streaming = ::std::option::Option::Some(_is.read_bool()?);
+                    self.response_streaming = is.read_bool()?;
                 },
                 6 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_options)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.options)?;
                 },
                 7 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_syntax = ::std::option::Option::Some(_is.read_enum()?);
+                    self.syntax = is.read_enum_or_unknown()?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(Method {
-            name: _field_name.unwrap_or_default(),
-            request_type_url: _field_request_type_url.unwrap_or_default(),
-            request_streaming: _field_request_streaming.unwrap_or_default(),
-            response_type_url: _field_response_type_url.unwrap_or_default(),
-            response_streaming: _field_response_streaming.unwrap_or_default(),
-            options: _field_options,
-            syntax: _field_syntax.unwrap_or_default(),
-        })
+    fn merge(&mut self, other: &Self) {
+        if !other.name.is_empty() {
+            self.name = other.name.clone();
+        }
+        if !other.request_type_url.is_empty() {
+            self.request_type_url = other.request_type_url.clone();
+        }
+        if other.request_streaming != false {
+            self.request_streaming = other.request_streaming.clone();
+        }
+        if !other.response_type_url.is_empty() {
+            self.response_type_url = other.response_type_url.clone();
+        }
+        if other.response_streaming != false {
+            self.response_streaming = other.response_streaming.clone();
+        }
+        self.options.extend(other.options.iter().cloned());
+        if other.syntax != ::protobuf::ProtobufEnumOrUnknown::new(::protobuf::well_known_types::Syntax::SYNTAX_PROTO2) {
+            self.syntax = other.syntax.clone();
+        }
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -756,9 +824,10 @@ impl ::protobuf::Message for Method {
             let len = value.compute_size();
             _size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
-        if self.syntax != ::protobuf::well_known_types::Syntax::SYNTAX_PROTO2 {
-            _size += ::protobuf::rt::enum_size(7, self.syntax);
+        if self.syntax != ::protobuf::ProtobufEnumOrUnknown::new(::protobuf::well_known_types::Syntax::SYNTAX_PROTO2) {
+            _size += ::protobuf::rt::enum_or_unknown_size(7, self.syntax);
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -783,19 +852,28 @@ impl ::protobuf::Message for Method {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         };
-        if self.syntax != ::protobuf::well_known_types::Syntax::SYNTAX_PROTO2 {
+        if self.syntax != ::protobuf::ProtobufEnumOrUnknown::new(::protobuf::well_known_types::Syntax::SYNTAX_PROTO2) {
             _os.write_enum(7, self.syntax.value())?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -845,7 +923,7 @@ impl ::protobuf::Message for Method {
                     Method::get_options_for_reflect,
                     Method::mut_options_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<::protobuf::well_known_types::Syntax>>(
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnumOrUnknown<::protobuf::well_known_types::Syntax>>(
                     "syntax",
                     Method::get_syntax_for_reflect,
                     Method::mut_syntax_for_reflect,
@@ -872,11 +950,104 @@ impl ::protobuf::reflect::ProtobufValue for Method {
     }
 }
 
+/// Declares an API to be included in this API. The including API must
+/// redeclare all the methods from the included API, but documentation
+/// and options are inherited as follows:
+///
+/// - If after comment and whitespace stripping, the documentation
+///   string of the redeclared method is empty, it will be inherited
+///   from the original method.
+///
+/// - Each annotation belonging to the service config (http,
+///   visibility) which is not set in the redeclared method will be
+///   inherited.
+///
+/// - If an http annotation is inherited, the path pattern will be
+///   modified as follows. Any version prefix will be replaced by the
+///   version of the including API plus the \[root\]\[\] path if specified.
+///
+/// Example of a simple mixin:
+///
+/// ```text
+/// package google.acl.v1;
+/// service AccessControl {
+///   // Get the underlying ACL object.
+///   rpc GetAcl(GetAclRequest) returns (Acl) {
+///     option (google.api.http).get = "/v1/{resource=**}:getAcl";
+///   }
+/// }
+///
+/// package google.storage.v2;
+/// service Storage {
+///   rpc GetAcl(GetAclRequest) returns (Acl);
+///
+///   // Get a data record.
+///   rpc GetData(GetDataRequest) returns (Data) {
+///     option (google.api.http).get = "/v2/{resource=**}";
+///   }
+/// }
+/// ```
+///
+/// Example of a mixin configuration:
+///
+/// ```text
+/// apis:
+/// - name: google.storage.v2.Storage
+///   mixins:
+///   - name: google.acl.v1.AccessControl
+/// ```
+///
+/// The mixin construct implies that all methods in `AccessControl` are
+/// also declared with same name and request/response types in
+/// `Storage`. A documentation generator or annotation processor will
+/// see the effective `Storage.GetAcl` method after inherting
+/// documentation and annotations as follows:
+///
+/// ```text
+/// service Storage {
+///   // Get the underlying ACL object.
+///   rpc GetAcl(GetAclRequest) returns (Acl) {
+///     option (google.api.http).get = "/v2/{resource=**}:getAcl";
+///   }
+///   ...
+/// }
+/// ```
+///
+/// Note how the version in the path pattern changed from `v1` to `v2`.
+///
+/// If the `root` field in the mixin is specified, it should be a
+/// relative path under which inherited HTTP paths are placed. Example:
+///
+/// ```text
+/// apis:
+/// - name: google.storage.v2.Storage
+///   mixins:
+///   - name: google.acl.v1.AccessControl
+///     root: acls
+/// ```
+///
+/// This implies the following inherited HTTP annotation:
+///
+/// ```text
+/// service Storage {
+///   // Get the underlying ACL object.
+///   rpc GetAcl(GetAclRequest) returns (Acl) {
+///     option (google.api.http).get = "/v2/acls/{resource=**}:getAcl";
+///   }
+///   ...
+/// }
+/// ```
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct Mixin {
     // message fields
+    /// The fully qualified name of the API which is included.
     pub name: ::std::string::String,
+    /// If non-empty specifies a path under which inherited HTTP paths
+    /// are rooted.
     pub root: ::std::string::String,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl Mixin {
@@ -952,35 +1123,44 @@ impl ::protobuf::Message for Mixin {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_name: ::std::option::Option<::std::string::String> = None;
-        let mut _field_root: ::std::option::Option<::std::string::String> = None;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = Mixin::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_name = ::std::option::Option::Some(_is.read_string()?);
+                    self.name = is.read_string()?;
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_root = ::std::option::Option::Some(_is.read_string()?);
+                    self.root = is.read_string()?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(Mixin {
-            name: _field_name.unwrap_or_default(),
-            root: _field_root.unwrap_or_default(),
-        })
+    fn merge(&mut self, other: &Self) {
+        if !other.name.is_empty() {
+            self.name = other.name.clone();
+        }
+        if !other.root.is_empty() {
+            self.root = other.root.clone();
+        }
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -993,6 +1173,7 @@ impl ::protobuf::Message for Mixin {
         if !self.root.is_empty() {
             _size += ::protobuf::rt::string_size(2, &self.root);
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -1003,16 +1184,25 @@ impl ::protobuf::Message for Mixin {
         if !self.root.is_empty() {
             _os.write_string(2, &self.root)?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
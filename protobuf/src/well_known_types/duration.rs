@@ -21,11 +21,74 @@
 use protobuf::Message as Message_imported_for_functions;
 use protobuf::ProtobufEnum as ProtobufEnum_imported_for_functions;
 
+/// A Duration represents a signed, fixed-length span of time represented
+/// as a count of seconds and fractions of seconds at nanosecond
+/// resolution. It is independent of any calendar and concepts like "day"
+/// or "month". It is related to Timestamp in that the difference between
+/// two Timestamp values is a Duration and it can be added or subtracted
+/// from a Timestamp. Range is approximately +-10,000 years.
+///
+/// Example 1: Compute Duration from two Timestamps in pseudo code.
+///
+/// ```text
+/// Timestamp start = ...;
+/// Timestamp end = ...;
+/// Duration duration = ...;
+///
+/// duration.seconds = end.seconds - start.seconds;
+/// duration.nanos = end.nanos - start.nanos;
+///
+/// if (duration.seconds < 0 && duration.nanos > 0) {
+///   duration.seconds += 1;
+///   duration.nanos -= 1000000000;
+/// } else if (durations.seconds > 0 && duration.nanos < 0) {
+///   duration.seconds -= 1;
+///   duration.nanos += 1000000000;
+/// }
+/// ```
+///
+/// Example 2: Compute Timestamp from Timestamp + Duration in pseudo code.
+///
+/// ```text
+/// Timestamp start = ...;
+/// Duration duration = ...;
+/// Timestamp end = ...;
+///
+/// end.seconds = start.seconds + duration.seconds;
+/// end.nanos = start.nanos + duration.nanos;
+///
+/// if (end.nanos < 0) {
+///   end.seconds -= 1;
+///   end.nanos += 1000000000;
+/// } else if (end.nanos >= 1000000000) {
+///   end.seconds += 1;
+///   end.nanos -= 1000000000;
+/// }
+/// ```
+///
+/// Example 3: Compute Duration from datetime.timedelta in Python.
+///
+/// ```text
+/// td = datetime.timedelta(days=3, minutes=10)
+/// duration = Duration()
+/// duration.FromTimedelta(td)
+/// ```
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct Duration {
     // message fields
+    /// Signed seconds of the span of time. Must be from -315,576,000,000
+    /// to +315,576,000,000 inclusive.
     pub seconds: i64,
+    /// Signed fractions of a second at nanosecond resolution of the span
+    /// of time. Durations less than one second are represented with a 0
+    /// `seconds` field and a positive or negative `nanos` field. For durations
+    /// of one second or more, a non-zero value for the `nanos` field must be
+    /// of the same sign as the `seconds` field. Must be from -999,999,999
+    /// to +999,999,999 inclusive.
     pub nanos: i32,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl Duration {
@@ -89,35 +152,44 @@ impl ::protobuf::Message for Duration {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_seconds: ::std::option::Option<i64> = None;
-        let mut _field_nanos: ::std::option::Option<i32> = None;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = Duration::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_seconds = ::std::option::Option::Some(_is.read_int64()?);
+                    self.seconds = is.read_int64()?;
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_nanos = ::std::option::Option::Some(_is.read_int32()?);
+                    self.nanos = is.read_int32()?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(Duration {
-            seconds: _field_seconds.unwrap_or_default(),
-            nanos: _field_nanos.unwrap_or_default(),
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.seconds != 0 {
+            self.seconds = other.seconds.clone();
+        }
+        if other.nanos != 0 {
+            self.nanos = other.nanos.clone();
+        }
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -130,6 +202,7 @@ impl ::protobuf::Message for Duration {
         if self.nanos != 0 {
             _size += ::protobuf::rt::value_size(2, self.nanos, ::protobuf::wire_format::WireTypeVarint);
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -140,16 +213,25 @@ impl ::protobuf::Message for Duration {
         if self.nanos != 0 {
             _os.write_int32(2, self.nanos)?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
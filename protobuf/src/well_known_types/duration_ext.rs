@@ -0,0 +1,238 @@
+//! Written by hand: conversions of generated `Duration`.
+
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+use std::convert::TryFrom;
+use std::fmt::Write;
+use std::time::Duration as StdDuration;
+
+use super::Duration;
+
+use super::time_ext::DURATION_SECONDS_MAX;
+use super::time_ext::NANOS_PER_SECOND;
+use super::time_ext::parse_nanos;
+use super::time_ext::write_nanos;
+use super::time_ext::TimeError;
+use super::time_ext::TimeResult;
+
+/// Carry nanos into seconds so that nanos are less than a second
+/// and have the same sign as seconds
+fn normalize(seconds: i64, nanos: i32) -> Option<(i64, i32)> {
+    let seconds = seconds.checked_add((nanos / NANOS_PER_SECOND) as i64)?;
+    let nanos = nanos % NANOS_PER_SECOND;
+    Some(if seconds > 0 && nanos < 0 {
+        (seconds - 1, nanos + NANOS_PER_SECOND)
+    } else if seconds < 0 && nanos > 0 {
+        (seconds + 1, nanos - NANOS_PER_SECOND)
+    } else {
+        (seconds, nanos)
+    })
+}
+
+/// Format duration as seconds with `s` suffix, e. g. `-1.500s`
+pub(crate) fn format_duration(seconds: i64, nanos: i32) -> Result<String, String> {
+    if seconds < -DURATION_SECONDS_MAX || seconds > DURATION_SECONDS_MAX {
+        return Err(format!("duration seconds out of range: {}", seconds));
+    }
+    if nanos <= -1000000000 || nanos >= 1000000000 || (seconds > 0 && nanos < 0) ||
+        (seconds < 0 && nanos > 0)
+    {
+        return Err(format!("invalid duration nanos: {}", nanos));
+    }
+
+    let mut r = String::new();
+    if seconds < 0 || nanos < 0 {
+        r.push('-');
+    }
+    write!(r, "{}", seconds.abs()).unwrap();
+    write_nanos(nanos.abs() as u32, &mut r);
+    r.push('s');
+    Ok(r)
+}
+
+/// Parse duration string, returns seconds and nanos
+pub(crate) fn parse_duration(s: &str) -> Result<(i64, i32), ()> {
+    let s = match s.len() {
+        0 => return Err(()),
+        len if s.ends_with('s') => &s[..len - 1],
+        _ => return Err(()),
+    };
+
+    let (negative, s) = if s.starts_with('-') {
+        (true, &s[1..])
+    } else {
+        (false, s)
+    };
+
+    let (seconds, nanos) = match s.find('.') {
+        Some(pos) => (&s[..pos], parse_nanos(&s[pos + 1..])?),
+        None => (s, 0),
+    };
+
+    if seconds.is_empty() || !seconds.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(());
+    }
+    let seconds: i64 = seconds.parse().map_err(|_| ())?;
+    if seconds > DURATION_SECONDS_MAX {
+        return Err(());
+    }
+
+    if negative {
+        Ok((-seconds, -(nanos as i32)))
+    } else {
+        Ok((seconds, nanos as i32))
+    }
+}
+
+impl Duration {
+    /// Construct duration from seconds and nanos, which may have different signs,
+    /// and nanos may exceed a second.
+    ///
+    /// Returns error if the duration is outside of the range allowed by the spec,
+    /// which is approximately +-10000 years.
+    pub fn from_seconds_nanos(seconds: i64, nanos: i32) -> TimeResult<Duration> {
+        let (seconds, nanos) = match normalize(seconds, nanos) {
+            Some((seconds, nanos)) if seconds >= -DURATION_SECONDS_MAX &&
+                seconds <= DURATION_SECONDS_MAX => (seconds, nanos),
+            _ => {
+                return Err(TimeError::new(
+                    format!("duration out of range: {}s {}ns", seconds, nanos),
+                ));
+            }
+        };
+        let mut r = Duration::new();
+        r.seconds = seconds;
+        r.nanos = nanos;
+        Ok(r)
+    }
+
+    /// Duration is within the range allowed by the spec and nanos are normalized.
+    pub fn is_valid(&self) -> bool {
+        self.seconds >= -DURATION_SECONDS_MAX && self.seconds <= DURATION_SECONDS_MAX &&
+            self.nanos > -NANOS_PER_SECOND && self.nanos < NANOS_PER_SECOND &&
+            !(self.seconds > 0 && self.nanos < 0) && !(self.seconds < 0 && self.nanos > 0)
+    }
+
+    /// Duration is less than zero.
+    pub fn is_negative(&self) -> bool {
+        self.seconds < 0 || (self.seconds == 0 && self.nanos < 0)
+    }
+
+    /// Format duration as seconds with `s` suffix, e. g. `1.500s` or `-0.000001s`.
+    ///
+    /// Fractional part has 0, 3, 6 or 9 digits, whichever is enough to represent nanos.
+    /// Returns error if duration is out of range.
+    pub fn to_seconds_string(&self) -> TimeResult<String> {
+        let d = Duration::from_seconds_nanos(self.seconds, self.nanos)?;
+        format_duration(d.seconds, d.nanos).map_err(TimeError::new)
+    }
+
+    /// Parse duration formatted as seconds with `s` suffix, e. g. `1.5s` or `-3s`.
+    ///
+    /// Fractional part, if present, must have from 1 to 9 digits.
+    pub fn parse_seconds_string(s: &str) -> TimeResult<Duration> {
+        match parse_duration(s) {
+            Ok((seconds, nanos)) => Duration::from_seconds_nanos(seconds, nanos),
+            Err(()) => Err(TimeError::new(format!("incorrect duration: {:?}", s))),
+        }
+    }
+}
+
+impl TryFrom<StdDuration> for Duration {
+    type Error = TimeError;
+
+    /// Fails if duration is longer than allowed by the spec.
+    fn try_from(duration: StdDuration) -> TimeResult<Duration> {
+        if duration.as_secs() > DURATION_SECONDS_MAX as u64 {
+            return Err(TimeError::new(
+                format!("duration out of range: {}s", duration.as_secs()),
+            ));
+        }
+        Duration::from_seconds_nanos(duration.as_secs() as i64, duration.subsec_nanos() as i32)
+    }
+}
+
+impl TryFrom<Duration> for StdDuration {
+    type Error = TimeError;
+
+    /// Fails if duration is negative or out of range.
+    fn try_from(duration: Duration) -> TimeResult<StdDuration> {
+        let d = Duration::from_seconds_nanos(duration.seconds, duration.nanos)?;
+        if d.is_negative() {
+            return Err(TimeError::new(
+                format!("negative duration: {}s {}ns", d.seconds, d.nanos),
+            ));
+        }
+        Ok(StdDuration::new(d.seconds as u64, d.nanos as u32))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn duration(seconds: i64, nanos: i32) -> Duration {
+        let mut d = Duration::new();
+        d.seconds = seconds;
+        d.nanos = nanos;
+        d
+    }
+
+    #[test]
+    fn std_duration() {
+        assert_eq!(Ok(duration(3, 5)), Duration::try_from(StdDuration::new(3, 5)));
+        assert_eq!(Ok(StdDuration::new(3, 5)), StdDuration::try_from(duration(3, 5)));
+        // not normalized
+        assert_eq!(Ok(StdDuration::new(2, 999999995)), StdDuration::try_from(duration(3, -5)));
+        assert!(StdDuration::try_from(duration(-3, -5)).is_err());
+        assert!(StdDuration::try_from(duration(0, -5)).is_err());
+        assert!(Duration::try_from(StdDuration::from_secs(u64::max_value())).is_err());
+    }
+
+    #[test]
+    fn normalization() {
+        assert_eq!(Ok(duration(-2, -500000000)), Duration::from_seconds_nanos(-3, 500000000));
+        assert_eq!(Ok(duration(1, 500000000)), Duration::from_seconds_nanos(0, 1500000000));
+        assert_eq!(Ok(duration(0, -1)), Duration::from_seconds_nanos(0, -1));
+        assert!(duration(0, -1).is_valid());
+        assert!(duration(0, -1).is_negative());
+        assert!(!duration(1, -1).is_valid());
+        assert!(!duration(DURATION_SECONDS_MAX + 1, 0).is_valid());
+        assert!(Duration::from_seconds_nanos(DURATION_SECONDS_MAX, NANOS_PER_SECOND).is_err());
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(Ok("0s".to_owned()), format_duration(0, 0));
+        assert_eq!(Ok("1.500s".to_owned()), format_duration(1, 500000000));
+        assert_eq!(Ok("-1.000001s".to_owned()), format_duration(-1, -1000));
+        assert_eq!(Ok("-0.000000001s".to_owned()), format_duration(0, -1));
+        assert!(format_duration(1, -1).is_err());
+        assert!(format_duration(DURATION_SECONDS_MAX + 1, 0).is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(Ok((0, 0)), parse_duration("0s"));
+        assert_eq!(Ok((1, 500000000)), parse_duration("1.5s"));
+        assert_eq!(Ok((-1, -1000)), parse_duration("-1.000001s"));
+        assert_eq!(Ok((0, -1)), parse_duration("-0.000000001s"));
+        assert!(parse_duration("1").is_err());
+        assert!(parse_duration("s").is_err());
+        assert!(parse_duration("1.s").is_err());
+        assert!(parse_duration("+1s").is_err());
+        assert!(parse_duration("315576000001s").is_err());
+    }
+
+    #[test]
+    fn seconds_string() {
+        assert_eq!(Ok("1.500s".to_owned()), duration(1, 500000000).to_seconds_string());
+        // not normalized
+        assert_eq!(Ok("-0.999999999s".to_owned()), duration(-1, 1).to_seconds_string());
+        assert!(duration(DURATION_SECONDS_MAX + 1, 0).to_seconds_string().is_err());
+
+        assert_eq!(Ok(duration(-1, -500000000)), Duration::parse_seconds_string("-1.5s"));
+        assert!(Duration::parse_seconds_string("1.5").is_err());
+    }
+}
@@ -3,21 +3,27 @@
 mod any;
 mod api;
 mod duration;
+mod duration_ext;
 mod empty;
 mod field_mask;
 mod source_context;
 mod struct_pb;
+mod time_ext;
 mod timestamp;
+mod timestamp_ext;
 mod type_pb;
 mod wrappers;
 
 pub use self::any::*;
 pub use self::api::*;
 pub use self::duration::*;
+pub use self::duration_ext::*;
 pub use self::empty::*;
 pub use self::field_mask::*;
 pub use self::source_context::*;
 pub use self::struct_pb::*;
+pub use self::time_ext::*;
 pub use self::timestamp::*;
+pub use self::timestamp_ext::*;
 pub use self::type_pb::*;
 pub use self::wrappers::*;
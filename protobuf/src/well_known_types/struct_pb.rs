@@ -21,10 +21,22 @@
 use protobuf::Message as Message_imported_for_functions;
 use protobuf::ProtobufEnum as ProtobufEnum_imported_for_functions;
 
+/// `Struct` represents a structured data value, consisting of fields
+/// which map to dynamically typed values. In some languages, `Struct`
+/// might be supported by a native representation. For example, in
+/// scripting languages like JS a struct is represented as an
+/// object. The details of that representation are described together
+/// with the proto support for the language.
+///
+/// The JSON representation for `Struct` is JSON object.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct Struct {
     // message fields
+    /// Unordered map of dynamically typed values.
     pub fields: ::std::collections::HashMap<::std::string::String, Value>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl Struct {
@@ -73,24 +85,30 @@ impl ::protobuf::Message for Struct {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_fields: ::std::collections::HashMap<::std::string::String, Value> = ::std::collections::HashMap::new();
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = Struct::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<Value>>(wire_type, _is, &mut _field_fields)?;
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<Value>>(wire_type, is, &mut self.fields)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(Struct {
-            fields: _field_fields,
-        })
+    fn merge(&mut self, other: &Self) {
+        self.fields.extend(other.fields.iter().map(|(k, v)| (k.clone(), v.clone())));
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -98,21 +116,31 @@ impl ::protobuf::Message for Struct {
     fn compute_size(&self) -> u32 {
         let mut _size = 0;
         _size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<Value>>(1, &self.fields);
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
     fn write_to_with_cached_sizes(&self, _os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
         _os.write_map::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<Value>>(1, &self.fields)?;
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -159,19 +187,37 @@ impl ::protobuf::reflect::ProtobufValue for Struct {
     }
 }
 
+/// `Value` represents a dynamically typed value which can be either
+/// null, a number, a string, a boolean, a recursive struct value, or a
+/// list of values. A producer of value is expected to set one of that
+/// variants, absence of any variant indicates an error.
+///
+/// The JSON representation for `Value` is JSON value.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct Value {
     // message oneof groups
+    /// The kind of value.
     pub kind: ::std::option::Option<Value_oneof_kind>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
+/// The kind of value.
 #[derive(Clone,PartialEq)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub enum Value_oneof_kind {
-    null_value(NullValue),
+    /// Represents a null value.
+    null_value(::protobuf::ProtobufEnumOrUnknown<NullValue>),
+    /// Represents a double value.
     number_value(f64),
+    /// Represents a string value.
     string_value(::std::string::String),
+    /// Represents a boolean value.
     bool_value(bool),
+    /// Represents a structured value.
     struct_value(Struct),
+    /// Represents a repeated `Value`.
     list_value(ListValue),
 }
 
@@ -201,14 +247,20 @@ impl Value {
     }
 
     // Param is passed by value, moved
-    pub fn set_null_value(&mut self, v: NullValue) {
+    pub fn set_null_value(&mut self, v: ::protobuf::ProtobufEnumOrUnknown<NullValue>) {
         self.kind = ::std::option::Option::Some(Value_oneof_kind::null_value(v))
     }
 
-    pub fn get_null_value(&self) -> NullValue {
+    pub fn get_null_value(&self) -> ::protobuf::ProtobufEnumOrUnknown<NullValue> {
         match self.kind {
             ::std::option::Option::Some(Value_oneof_kind::null_value(v)) => v,
-            _ => NullValue::NULL_VALUE,
+            _ => ::protobuf::ProtobufEnumOrUnknown::new(NullValue::NULL_VALUE),
+        }
+    }
+
+    pub fn clear_null_value(&mut self) {
+        if self.has_null_value() {
+            self.kind = ::std::option::Option::None;
         }
     }
 
@@ -234,6 +286,12 @@ impl Value {
         }
     }
 
+    pub fn clear_number_value(&mut self) {
+        if self.has_number_value() {
+            self.kind = ::std::option::Option::None;
+        }
+    }
+
     // string string_value = 3;
 
 
@@ -268,6 +326,12 @@ impl Value {
         }
     }
 
+    pub fn clear_string_value(&mut self) {
+        if self.has_string_value() {
+            self.kind = ::std::option::Option::None;
+        }
+    }
+
     // bool bool_value = 4;
 
 
@@ -290,6 +354,12 @@ impl Value {
         }
     }
 
+    pub fn clear_bool_value(&mut self) {
+        if self.has_bool_value() {
+            self.kind = ::std::option::Option::None;
+        }
+    }
+
     // .google.protobuf.Struct struct_value = 5;
 
 
@@ -324,6 +394,12 @@ impl Value {
         }
     }
 
+    pub fn clear_struct_value(&mut self) {
+        if self.has_struct_value() {
+            self.kind = ::std::option::Option::None;
+        }
+    }
+
     // .google.protobuf.ListValue list_value = 6;
 
 
@@ -357,6 +433,12 @@ impl Value {
             _ => ListValue::default_instance(),
         }
     }
+
+    pub fn clear_list_value(&mut self) {
+        if self.has_list_value() {
+            self.kind = ::std::option::Option::None;
+        }
+    }
 }
 
 impl ::protobuf::Message for Value {
@@ -374,39 +456,75 @@ impl ::protobuf::Message for Value {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_kind: ::std::option::Option<::std::option::Option<Value_oneof_kind>> = None;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = Value::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
-                    _field_kind = ::std::option::Option::Some(::std::option::Option::Some(Value_oneof_kind::null_value(_is.read_enum()?)));
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.kind = ::std::option::Option::Some(Value_oneof_kind::null_value(is.read_enum_or_unknown()?));
                 },
                 2 => {
-                    _field_kind = ::std::option::Option::Some(::std::option::Option::Some(Value_oneof_kind::number_value(_is.read_double()?)));
+                    if wire_type != ::protobuf::wire_format::WireTypeFixed64 {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.kind = ::std::option::Option::Some(Value_oneof_kind::number_value(is.read_double()?));
                 },
                 3 => {
-                    _field_kind = ::std::option::Option::Some(::std::option::Option::Some(Value_oneof_kind::string_value(_is.read_string()?)));
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.kind = ::std::option::Option::Some(Value_oneof_kind::string_value(is.read_string()?));
                 },
                 4 => {
-                    _field_kind = ::std::option::Option::Some(::std::option::Option::Some(Value_oneof_kind::bool_value(_is.read_bool()?)));
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.kind = ::std::option::Option::Some(Value_oneof_kind::bool_value(is.read_bool()?));
                 },
                 5 => {
-                    _field_kind = ::std::option::Option::Some(::std::option::Option::Some(Value_oneof_kind::struct_value(_is.read_message()?)));
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    match self.kind {
+                        ::std::option::Option::Some(Value_oneof_kind::struct_value(ref mut v)) => is.merge_message(v)?,
+                        _ => self.kind = ::std::option::Option::Some(Value_oneof_kind::struct_value(is.read_message()?)),
+                    };
                 },
                 6 => {
-                    _field_kind = ::std::option::Option::Some(::std::option::Option::Some(Value_oneof_kind::list_value(_is.read_message()?)));
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    match self.kind {
+                        ::std::option::Option::Some(Value_oneof_kind::list_value(ref mut v)) => is.merge_message(v)?,
+                        _ => self.kind = ::std::option::Option::Some(Value_oneof_kind::list_value(is.read_message()?)),
+                    };
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(Value {
-            kind: _field_kind.ok_or_else(|| ::protobuf::ProtobufError::message_not_initialized("missing required field: Value::kind"))?,
-        })
+    fn merge(&mut self, other: &Self) {
+        if let ::std::option::Option::Some(ref v) = other.kind {
+            match (&mut self.kind, v) {
+                (&mut ::std::option::Option::Some(Value_oneof_kind::struct_value(ref mut m)), &Value_oneof_kind::struct_value(ref v)) => m.merge(v),
+                (&mut ::std::option::Option::Some(Value_oneof_kind::list_value(ref mut m)), &Value_oneof_kind::list_value(ref v)) => m.merge(v),
+                (o, v) => *o = ::std::option::Option::Some(v.clone()),
+            };
+        }
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -416,7 +534,7 @@ impl ::protobuf::Message for Value {
         if let ::std::option::Option::Some(ref v) = self.kind {
             match v {
                 &Value_oneof_kind::null_value(v) => {
-                    _size += ::protobuf::rt::enum_size(1, v);
+                    _size += ::protobuf::rt::enum_or_unknown_size(1, v);
                 },
                 &Value_oneof_kind::number_value(v) => {
                     _size += 9;
@@ -437,6 +555,7 @@ impl ::protobuf::Message for Value {
                 },
             };
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -467,16 +586,25 @@ impl ::protobuf::Message for Value {
                 },
             };
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -496,35 +624,49 @@ impl ::protobuf::Message for Value {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_singular_enum_accessor::<_, NullValue>(
+                fields.push(::protobuf::reflect::accessor::make_singular_enum_or_unknown_accessor::<_, NullValue>(
                     "null_value",
                     Value::has_null_value,
                     Value::get_null_value,
+                    Value::set_null_value,
+                    Value::clear_null_value,
                 ));
                 fields.push(::protobuf::reflect::accessor::make_singular_f64_accessor::<_>(
                     "number_value",
                     Value::has_number_value,
                     Value::get_number_value,
+                    Value::set_number_value,
+                    Value::clear_number_value,
                 ));
                 fields.push(::protobuf::reflect::accessor::make_singular_string_accessor::<_>(
                     "string_value",
                     Value::has_string_value,
                     Value::get_string_value,
+                    Value::set_string_value,
+                    Value::clear_string_value,
                 ));
                 fields.push(::protobuf::reflect::accessor::make_singular_bool_accessor::<_>(
                     "bool_value",
                     Value::has_bool_value,
                     Value::get_bool_value,
+                    Value::set_bool_value,
+                    Value::clear_bool_value,
                 ));
                 fields.push(::protobuf::reflect::accessor::make_singular_message_accessor::<_, Struct>(
                     "struct_value",
                     Value::has_struct_value,
                     Value::get_struct_value,
+                    Value::set_struct_value,
+                    Value::mut_struct_value,
+                    Value::clear_struct_value,
                 ));
                 fields.push(::protobuf::reflect::accessor::make_singular_message_accessor::<_, ListValue>(
                     "list_value",
                     Value::has_list_value,
                     Value::get_list_value,
+                    Value::set_list_value,
+                    Value::mut_list_value,
+                    Value::clear_list_value,
                 ));
                 ::protobuf::reflect::MessageDescriptor::new::<Value>(
                     "Value",
@@ -548,10 +690,17 @@ impl ::protobuf::reflect::ProtobufValue for Value {
     }
 }
 
+/// `ListValue` is a wrapper around a repeated field of values.
+///
+/// The JSON representation for `ListValue` is JSON array.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct ListValue {
     // message fields
+    /// Repeated field of dynamically typed values.
     pub values: ::std::vec::Vec<Value>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl ListValue {
@@ -605,24 +754,30 @@ impl ::protobuf::Message for ListValue {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_values: ::std::vec::Vec<Value> = ::std::vec::Vec::new();
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = ListValue::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_values)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.values)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(ListValue {
-            values: _field_values,
-        })
+    fn merge(&mut self, other: &Self) {
+        self.values.extend(other.values.iter().cloned());
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -633,6 +788,7 @@ impl ::protobuf::Message for ListValue {
             let len = value.compute_size();
             _size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -642,16 +798,25 @@ impl ::protobuf::Message for ListValue {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         };
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -698,8 +863,14 @@ impl ::protobuf::reflect::ProtobufValue for ListValue {
     }
 }
 
+/// `NullValue` is a singleton enumeration to represent the null value for the
+/// `Value` type union.
+///
+///  The JSON representation for `NullValue` is JSON `null`.
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub enum NullValue {
+    /// Null value.
     NULL_VALUE = 0,
 }
 
@@ -748,6 +919,10 @@ impl ::protobuf::reflect::ProtobufValue for NullValue {
     fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
         ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
     }
+
+    fn from_value_box(value: ::protobuf::reflect::ProtobufValueBox) -> ::std::option::Option<Self> {
+        value.into_enum()
+    }
 }
 
 static file_descriptor_proto_data: &'static [u8] = b"\
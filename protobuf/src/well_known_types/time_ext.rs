@@ -0,0 +1,70 @@
+//! Written by hand: definitions shared by `timestamp_ext` and `duration_ext`.
+
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+#[cfg(feature = "std")]
+use std::error::Error;
+use std::fmt;
+use std::fmt::Write;
+
+/// `0001-01-01T00:00:00Z`
+pub(crate) const TIMESTAMP_SECONDS_MIN: i64 = -62135596800;
+/// `9999-12-31T23:59:59Z`
+pub(crate) const TIMESTAMP_SECONDS_MAX: i64 = 253402300799;
+/// Approximately 10000 years
+pub(crate) const DURATION_SECONDS_MAX: i64 = 315576000000;
+
+pub(crate) const NANOS_PER_SECOND: i32 = 1000000000;
+
+/// Append `.fff`, `.ffffff` or `.fffffffff` if `nanos` is not zero
+pub(crate) fn write_nanos(nanos: u32, buf: &mut String) {
+    if nanos == 0 {
+    } else if nanos % 1000000 == 0 {
+        write!(buf, ".{:03}", nanos / 1000000).unwrap();
+    } else if nanos % 1000 == 0 {
+        write!(buf, ".{:06}", nanos / 1000).unwrap();
+    } else {
+        write!(buf, ".{:09}", nanos).unwrap();
+    }
+}
+
+/// Parse `fffffffff` part after decimal point into nanoseconds
+pub(crate) fn parse_nanos(fraction: &str) -> Result<u32, ()> {
+    if fraction.is_empty() || fraction.len() > 9 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(());
+    }
+    let mut nanos: u32 = fraction.parse().map_err(|_| ())?;
+    for _ in fraction.len()..9 {
+        nanos *= 10;
+    }
+    Ok(nanos)
+}
+
+/// `Timestamp` or `Duration` is outside of the range allowed by the spec,
+/// or it cannot be represented by the target type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeError {
+    pub message: String,
+}
+
+impl TimeError {
+    pub(crate) fn new<S : Into<String>>(message: S) -> TimeError {
+        TimeError { message: message.into() }
+    }
+}
+
+impl fmt::Display for TimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for TimeError {
+    fn description(&self) -> &str {
+        &self.message
+    }
+}
+
+pub type TimeResult<T> = Result<T, TimeError>;
@@ -0,0 +1,398 @@
+//! Written by hand: conversions of generated `Timestamp`.
+
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+#[cfg(feature = "std")]
+use std::convert::TryFrom;
+use std::fmt::Write;
+#[cfg(feature = "std")]
+use std::time::Duration as StdDuration;
+#[cfg(feature = "std")]
+use std::time::SystemTime;
+#[cfg(feature = "std")]
+use std::time::UNIX_EPOCH;
+
+use super::Duration;
+use super::Timestamp;
+
+use super::time_ext::NANOS_PER_SECOND;
+use super::time_ext::parse_nanos;
+use super::time_ext::write_nanos;
+use super::time_ext::TIMESTAMP_SECONDS_MAX;
+use super::time_ext::TIMESTAMP_SECONDS_MIN;
+use super::time_ext::TimeError;
+use super::time_ext::TimeResult;
+
+/// Carry nanos into seconds so that nanos are in `0..1000000000`
+fn normalize(seconds: i64, nanos: i32) -> Option<(i64, i32)> {
+    let seconds = seconds.checked_add(nanos.div_euclid(NANOS_PER_SECOND) as i64)?;
+    Some((seconds, nanos.rem_euclid(NANOS_PER_SECOND)))
+}
+
+const SECONDS_PER_DAY: i64 = 86400;
+
+/// Number of days since 1970-01-01 in proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Inverse of `days_from_civil`
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Format timestamp as RFC 3339 string, e. g. `1972-01-01T10:00:20.021Z`
+pub(crate) fn format_timestamp(seconds: i64, nanos: i32) -> Result<String, String> {
+    if seconds < TIMESTAMP_SECONDS_MIN || seconds > TIMESTAMP_SECONDS_MAX {
+        return Err(format!("timestamp seconds out of range: {}", seconds));
+    }
+    if nanos < 0 || nanos > 999999999 {
+        return Err(format!("timestamp nanos out of range: {}", nanos));
+    }
+
+    let days = seconds.div_euclid(SECONDS_PER_DAY);
+    let seconds_of_day = seconds.rem_euclid(SECONDS_PER_DAY);
+    let (year, month, day) = civil_from_days(days);
+
+    let mut r = String::new();
+    write!(
+        r,
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day / 60 % 60,
+        seconds_of_day % 60
+    ).unwrap();
+    write_nanos(nanos as u32, &mut r);
+    r.push('Z');
+    Ok(r)
+}
+
+/// Parse RFC 3339 timestamp, offset is allowed, returns seconds and nanos
+pub(crate) fn parse_timestamp(s: &str) -> Result<(i64, i32), ()> {
+    fn number(s: &str, start: usize, len: usize) -> Result<u32, ()> {
+        let digits = s.get(start..start + len).ok_or(())?;
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(());
+        }
+        digits.parse().map_err(|_| ())
+    }
+
+    fn expect(s: &str, pos: usize, chars: &[u8]) -> Result<(), ()> {
+        match s.as_bytes().get(pos) {
+            Some(c) if chars.contains(c) => Ok(()),
+            _ => Err(()),
+        }
+    }
+
+    let year = number(s, 0, 4)?;
+    expect(s, 4, b"-")?;
+    let month = number(s, 5, 2)?;
+    expect(s, 7, b"-")?;
+    let day = number(s, 8, 2)?;
+    expect(s, 10, b"Tt")?;
+    let hour = number(s, 11, 2)?;
+    expect(s, 13, b":")?;
+    let minute = number(s, 14, 2)?;
+    expect(s, 16, b":")?;
+    let second = number(s, 17, 2)?;
+
+    if month < 1 || month > 12 || day < 1 || day > 31 || hour > 23 || minute > 59 ||
+        second > 59
+    {
+        return Err(());
+    }
+
+    let mut rem = &s[19..];
+
+    let mut nanos = 0;
+    if rem.starts_with('.') {
+        let len = rem[1..].find(|c: char| !c.is_ascii_digit()).unwrap_or(rem.len() - 1);
+        nanos = parse_nanos(&rem[1..1 + len])?;
+        rem = &rem[1 + len..];
+    }
+
+    let offset_seconds = if rem == "Z" || rem == "z" {
+        0
+    } else {
+        expect(rem, 0, b"+-")?;
+        let offset_hour = number(rem, 1, 2)?;
+        expect(rem, 3, b":")?;
+        let offset_minute = number(rem, 4, 2)?;
+        if rem.len() != 6 || offset_hour > 23 || offset_minute > 59 {
+            return Err(());
+        }
+        let offset = (offset_hour * 3600 + offset_minute * 60) as i64;
+        if rem.starts_with('-') { -offset } else { offset }
+    };
+
+    let days = days_from_civil(year as i64, month, day);
+    if civil_from_days(days) != (year as i64, month, day) {
+        // e. g. February 30
+        return Err(());
+    }
+
+    let seconds = days * SECONDS_PER_DAY + (hour * 3600 + minute * 60 + second) as i64 -
+        offset_seconds;
+    if seconds < TIMESTAMP_SECONDS_MIN || seconds > TIMESTAMP_SECONDS_MAX {
+        return Err(());
+    }
+    Ok((seconds, nanos as i32))
+}
+
+impl Timestamp {
+    /// Current time.
+    ///
+    /// Panics if the system clock is outside of the range allowed by the spec,
+    /// `Timestamp::try_from(SystemTime::now())` can be used to handle this as an error.
+    #[cfg(feature = "std")]
+    pub fn now() -> Timestamp {
+        Timestamp::try_from(SystemTime::now()).expect("system time out of range")
+    }
+
+    /// Construct timestamp from seconds and nanos since Unix epoch,
+    /// nanos may be negative or exceed a second.
+    ///
+    /// Returns error if the timestamp is outside of the range allowed by the spec,
+    /// `0001-01-01T00:00:00Z` to `9999-12-31T23:59:59.999999999Z`.
+    pub fn from_seconds_nanos(seconds: i64, nanos: i32) -> TimeResult<Timestamp> {
+        let (seconds, nanos) = match normalize(seconds, nanos) {
+            Some((seconds, nanos)) if seconds >= TIMESTAMP_SECONDS_MIN &&
+                seconds <= TIMESTAMP_SECONDS_MAX => (seconds, nanos),
+            _ => {
+                return Err(TimeError::new(
+                    format!("timestamp out of range: {}s {}ns", seconds, nanos),
+                ));
+            }
+        };
+        let mut r = Timestamp::new();
+        r.seconds = seconds;
+        r.nanos = nanos;
+        Ok(r)
+    }
+
+    /// Timestamp is within the range allowed by the spec and nanos are normalized.
+    pub fn is_valid(&self) -> bool {
+        self.seconds >= TIMESTAMP_SECONDS_MIN && self.seconds <= TIMESTAMP_SECONDS_MAX &&
+            self.nanos >= 0 && self.nanos < NANOS_PER_SECOND
+    }
+
+    /// Add duration, `None` if the result is out of range.
+    pub fn checked_add(&self, duration: &Duration) -> Option<Timestamp> {
+        let seconds = self.seconds.checked_add(duration.seconds)?;
+        let nanos = self.nanos.checked_add(duration.nanos)?;
+        Timestamp::from_seconds_nanos(seconds, nanos).ok()
+    }
+
+    /// Subtract duration, `None` if the result is out of range.
+    pub fn checked_sub(&self, duration: &Duration) -> Option<Timestamp> {
+        let seconds = self.seconds.checked_sub(duration.seconds)?;
+        let nanos = self.nanos.checked_sub(duration.nanos)?;
+        Timestamp::from_seconds_nanos(seconds, nanos).ok()
+    }
+
+    /// Format timestamp as RFC 3339 string in UTC, e. g. `1972-01-01T10:00:20.021Z`.
+    ///
+    /// Fractional part has 0, 3, 6 or 9 digits, whichever is enough to represent nanos.
+    /// Returns error if timestamp is out of range.
+    pub fn to_rfc3339(&self) -> TimeResult<String> {
+        let t = Timestamp::from_seconds_nanos(self.seconds, self.nanos)?;
+        format_timestamp(t.seconds, t.nanos).map_err(TimeError::new)
+    }
+
+    /// Parse RFC 3339 timestamp, e. g. `1972-01-01T10:00:20.021Z`
+    /// or `1972-01-01T12:00:20.021+02:00`.
+    ///
+    /// Fractional part, if present, must have from 1 to 9 digits.
+    pub fn parse_rfc3339(s: &str) -> TimeResult<Timestamp> {
+        match parse_timestamp(s) {
+            Ok((seconds, nanos)) => Timestamp::from_seconds_nanos(seconds, nanos),
+            Err(()) => Err(TimeError::new(format!("incorrect RFC 3339 timestamp: {:?}", s))),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<SystemTime> for Timestamp {
+    type Error = TimeError;
+
+    /// Fails if time is outside of the range allowed by the spec.
+    fn try_from(time: SystemTime) -> TimeResult<Timestamp> {
+        let (negative, d) = match time.duration_since(UNIX_EPOCH) {
+            Ok(d) => (false, d),
+            Err(e) => (true, e.duration()),
+        };
+        let seconds = i64::try_from(d.as_secs()).map_err(|_| {
+            TimeError::new(format!("system time out of range: {:?}", time))
+        })?;
+        let nanos = d.subsec_nanos() as i32;
+        if negative {
+            Timestamp::from_seconds_nanos(-seconds, -nanos)
+        } else {
+            Timestamp::from_seconds_nanos(seconds, nanos)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<Timestamp> for SystemTime {
+    type Error = TimeError;
+
+    /// Fails if timestamp is out of range (of the spec or of `SystemTime`).
+    fn try_from(timestamp: Timestamp) -> TimeResult<SystemTime> {
+        let t = Timestamp::from_seconds_nanos(timestamp.seconds, timestamp.nanos)?;
+        let r = if t.seconds >= 0 {
+            UNIX_EPOCH.checked_add(StdDuration::new(t.seconds as u64, t.nanos as u32))
+        } else {
+            // nanos are added to negative seconds
+            UNIX_EPOCH
+                .checked_sub(StdDuration::from_secs(t.seconds.wrapping_neg() as u64))
+                .and_then(|r| r.checked_add(StdDuration::from_nanos(t.nanos as u64)))
+        };
+        r.ok_or_else(|| {
+            TimeError::new(format!("timestamp cannot be represented as SystemTime: {}s", t.seconds))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn timestamp(seconds: i64, nanos: i32) -> Timestamp {
+        let mut t = Timestamp::new();
+        t.seconds = seconds;
+        t.nanos = nanos;
+        t
+    }
+
+    fn duration(seconds: i64, nanos: i32) -> Duration {
+        let mut d = Duration::new();
+        d.seconds = seconds;
+        d.nanos = nanos;
+        d
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn system_time() {
+        let t = UNIX_EPOCH + StdDuration::new(10, 20);
+        assert_eq!(Ok(timestamp(10, 20)), Timestamp::try_from(t));
+        assert_eq!(Ok(t), SystemTime::try_from(timestamp(10, 20)));
+
+        let t = UNIX_EPOCH - StdDuration::new(10, 20);
+        assert_eq!(Ok(timestamp(-11, 999999980)), Timestamp::try_from(t));
+        assert_eq!(Ok(t), SystemTime::try_from(timestamp(-11, 999999980)));
+        // not normalized
+        assert_eq!(Ok(t), SystemTime::try_from(timestamp(-10, -20)));
+
+        assert!(SystemTime::try_from(timestamp(TIMESTAMP_SECONDS_MAX + 1, 0)).is_err());
+
+        let t = UNIX_EPOCH + StdDuration::from_secs(TIMESTAMP_SECONDS_MAX as u64 + 1);
+        assert!(Timestamp::try_from(t).is_err());
+        let t = UNIX_EPOCH - StdDuration::new(-TIMESTAMP_SECONDS_MIN as u64, 1);
+        assert!(Timestamp::try_from(t).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn now() {
+        assert!(Timestamp::now().is_valid());
+    }
+
+    #[test]
+    fn range() {
+        assert!(timestamp(TIMESTAMP_SECONDS_MIN, 0).is_valid());
+        assert!(timestamp(TIMESTAMP_SECONDS_MAX, 999999999).is_valid());
+        assert!(!timestamp(TIMESTAMP_SECONDS_MIN - 1, 999999999).is_valid());
+        assert!(!timestamp(0, -1).is_valid());
+        assert!(Timestamp::from_seconds_nanos(TIMESTAMP_SECONDS_MAX, 1000000000).is_err());
+        assert_eq!(Ok(timestamp(-1, 999999999)), Timestamp::from_seconds_nanos(0, -1));
+    }
+
+    #[test]
+    fn add_sub() {
+        let t = timestamp(10, 900000000);
+        assert_eq!(Some(timestamp(11, 100000000)), t.checked_add(&duration(0, 200000000)));
+        assert_eq!(Some(timestamp(9, 800000000)), t.checked_add(&duration(-1, -100000000)));
+        assert_eq!(Some(timestamp(-1, 950000000)), t.checked_sub(&duration(10, 950000000)));
+        assert_eq!(None, t.checked_add(&duration(TIMESTAMP_SECONDS_MAX, 0)));
+        assert_eq!(None, t.checked_sub(&duration(i64::max_value(), 0)));
+    }
+
+    #[test]
+    fn test_civil() {
+        assert_eq!(0, days_from_civil(1970, 1, 1));
+        assert_eq!((1970, 1, 1), civil_from_days(0));
+        assert_eq!(-719162, days_from_civil(1, 1, 1));
+        assert_eq!((2000, 2, 29), civil_from_days(days_from_civil(2000, 2, 29)));
+        assert_eq!((9999, 12, 31), civil_from_days(days_from_civil(9999, 12, 31)));
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(Ok("1970-01-01T00:00:00Z".to_owned()), format_timestamp(0, 0));
+        assert_eq!(
+            Ok("1972-01-01T10:00:20.021Z".to_owned()),
+            format_timestamp(63108020, 21000000)
+        );
+        assert_eq!(Ok("1969-12-31T23:59:59.000001Z".to_owned()), format_timestamp(-1, 1000));
+        assert_eq!(Ok("0001-01-01T00:00:00Z".to_owned()), format_timestamp(TIMESTAMP_SECONDS_MIN, 0));
+        assert_eq!(
+            Ok("9999-12-31T23:59:59.999999999Z".to_owned()),
+            format_timestamp(TIMESTAMP_SECONDS_MAX, 999999999)
+        );
+        assert!(format_timestamp(TIMESTAMP_SECONDS_MAX + 1, 0).is_err());
+        assert!(format_timestamp(0, -1).is_err());
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(Ok((0, 0)), parse_timestamp("1970-01-01T00:00:00Z"));
+        assert_eq!(Ok((63108020, 21000000)), parse_timestamp("1972-01-01T10:00:20.021Z"));
+        assert_eq!(Ok((63108020, 1)), parse_timestamp("1972-01-01T10:00:20.000000001Z"));
+        assert_eq!(Ok((63108020, 0)), parse_timestamp("1972-01-01T12:30:20+02:30"));
+        assert_eq!(Ok((63108020, 0)), parse_timestamp("1972-01-01T09:00:20-01:00"));
+        assert_eq!(Ok((-1, 0)), parse_timestamp("1969-12-31t23:59:59z"));
+        assert!(parse_timestamp("1972-02-30T00:00:00Z").is_err());
+        assert!(parse_timestamp("1972-01-01T00:00:00").is_err());
+        assert!(parse_timestamp("1972-01-01 00:00:00Z").is_err());
+        assert!(parse_timestamp("1972-01-01T00:00:00.Z").is_err());
+        assert!(parse_timestamp("1972-01-01T00:00:00.0000000001Z").is_err());
+        assert!(parse_timestamp("0000-12-31T23:59:59Z").is_err());
+    }
+
+    #[test]
+    fn rfc3339() {
+        let t = timestamp(63108020, 21000000);
+        assert_eq!(Ok("1972-01-01T10:00:20.021Z".to_owned()), t.to_rfc3339());
+        // not normalized
+        let t = timestamp(0, -1000000);
+        assert_eq!(Ok("1969-12-31T23:59:59.999Z".to_owned()), t.to_rfc3339());
+        assert!(timestamp(TIMESTAMP_SECONDS_MAX + 1, 0).to_rfc3339().is_err());
+
+        assert_eq!(
+            Ok(timestamp(63108020, 21000000)),
+            Timestamp::parse_rfc3339("1972-01-01T12:00:20.021+02:00")
+        );
+        assert!(Timestamp::parse_rfc3339("1972-01-01T10:00:20.021").is_err());
+    }
+}
@@ -21,15 +21,25 @@
 use protobuf::Message as Message_imported_for_functions;
 use protobuf::ProtobufEnum as ProtobufEnum_imported_for_functions;
 
+/// A protocol buffer message type.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct Type {
     // message fields
+    /// The fully qualified message name.
     pub name: ::std::string::String,
+    /// The list of fields.
     pub fields: ::std::vec::Vec<Field>,
+    /// The list of types appearing in `oneof` definitions in this type.
     pub oneofs: ::std::vec::Vec<::std::string::String>,
+    /// The protocol buffer options.
     pub options: ::std::vec::Vec<Option>,
+    /// The source context.
     pub source_context: ::protobuf::SingularPtrField<::protobuf::well_known_types::SourceContext>,
-    pub syntax: Syntax,
+    /// The source syntax.
+    pub syntax: ::protobuf::ProtobufEnumOrUnknown<Syntax>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl Type {
@@ -185,19 +195,19 @@ impl Type {
 
 
     // Param is passed by value, moved
-    pub fn set_syntax(&mut self, v: Syntax) {
+    pub fn set_syntax(&mut self, v: ::protobuf::ProtobufEnumOrUnknown<Syntax>) {
         self.syntax = v;
     }
 
-    pub fn get_syntax(&self) -> Syntax {
+    pub fn get_syntax(&self) -> ::protobuf::ProtobufEnumOrUnknown<Syntax> {
         self.syntax
     }
 
-    fn get_syntax_for_reflect(&self) -> &Syntax {
+    fn get_syntax_for_reflect(&self) -> &::protobuf::ProtobufEnumOrUnknown<Syntax> {
         &self.syntax
     }
 
-    fn mut_syntax_for_reflect(&mut self) -> &mut Syntax {
+    fn mut_syntax_for_reflect(&mut self) -> &mut ::protobuf::ProtobufEnumOrUnknown<Syntax> {
         &mut self.syntax
     }
 }
@@ -222,58 +232,60 @@ impl ::protobuf::Message for Type {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_name: ::std::option::Option<::std::string::String> = None;
-        let mut _field_fields: ::std::vec::Vec<Field> = ::std::vec::Vec::new();
-        let mut _field_oneofs: ::std::vec::Vec<::std::string::String> = ::std::vec::Vec::new();
-        let mut _field_options: ::std::vec::Vec<Option> = ::std::vec::Vec::new();
-        let mut _field_source_context: ::std::option::Option<::protobuf::SingularPtrField<::protobuf::well_known_types::SourceContext>> = None;
-        let mut _field_syntax: ::std::option::Option<Syntax> = None;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = Type::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_name = ::std::option::Option::Some(_is.read_string()?);
+                    self.name = is.read_string()?;
                 },
                 2 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_fields)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.fields)?;
                 },
                 3 => {
-                    ::protobuf::rt::read_repeated_string_into(wire_type, _is, &mut _field_oneofs)?;
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.oneofs)?;
                 },
                 4 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_options)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.options)?;
                 },
                 5 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    _field_source_context = ::std::option::Option::Some(::protobuf::SingularPtrField::some(_is.read_message()?));
+                    ::protobuf::rt::merge_singular_message_into(wire_type, is, &mut self.source_context)?;
                 },
                 6 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_syntax = ::std::option::Option::Some(_is.read_enum()?);
+                    self.syntax = is.read_enum_or_unknown()?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(Type {
-            name: _field_name.unwrap_or_default(),
-            fields: _field_fields,
-            oneofs: _field_oneofs,
-            options: _field_options,
-            source_context: _field_source_context.unwrap_or_default(),
-            syntax: _field_syntax.unwrap_or_default(),
-        })
+    fn merge(&mut self, other: &Self) {
+        if !other.name.is_empty() {
+            self.name = other.name.clone();
+        }
+        self.fields.extend(other.fields.iter().cloned());
+        self.oneofs.extend(other.oneofs.iter().cloned());
+        self.options.extend(other.options.iter().cloned());
+        ::protobuf::rt::merge_singular_message(&mut self.source_context, &other.source_context);
+        if other.syntax != ::protobuf::ProtobufEnumOrUnknown::new(Syntax::SYNTAX_PROTO2) {
+            self.syntax = other.syntax.clone();
+        }
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -298,9 +310,10 @@ impl ::protobuf::Message for Type {
             let len = v.compute_size();
             _size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         }
-        if self.syntax != Syntax::SYNTAX_PROTO2 {
-            _size += ::protobuf::rt::enum_size(6, self.syntax);
+        if self.syntax != ::protobuf::ProtobufEnumOrUnknown::new(Syntax::SYNTAX_PROTO2) {
+            _size += ::protobuf::rt::enum_or_unknown_size(6, self.syntax);
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -326,19 +339,28 @@ impl ::protobuf::Message for Type {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         }
-        if self.syntax != Syntax::SYNTAX_PROTO2 {
+        if self.syntax != ::protobuf::ProtobufEnumOrUnknown::new(Syntax::SYNTAX_PROTO2) {
             _os.write_enum(6, self.syntax.value())?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -383,7 +405,7 @@ impl ::protobuf::Message for Type {
                     Type::get_source_context_for_reflect,
                     Type::mut_source_context_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<Syntax>>(
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnumOrUnknown<Syntax>>(
                     "syntax",
                     Type::get_syntax_for_reflect,
                     Type::mut_syntax_for_reflect,
@@ -410,19 +432,35 @@ impl ::protobuf::reflect::ProtobufValue for Type {
     }
 }
 
+/// A single field of a message type.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct Field {
     // message fields
-    pub kind: Field_Kind,
-    pub cardinality: Field_Cardinality,
+    /// The field type.
+    pub kind: ::protobuf::ProtobufEnumOrUnknown<Field_Kind>,
+    /// The field cardinality.
+    pub cardinality: ::protobuf::ProtobufEnumOrUnknown<Field_Cardinality>,
+    /// The field number.
     pub number: i32,
+    /// The field name.
     pub name: ::std::string::String,
+    /// The field type URL, without the scheme, for message or enumeration
+    /// types. Example: `"type.googleapis.com/google.protobuf.Timestamp"`.
     pub type_url: ::std::string::String,
+    /// The index of the field type in `Type.oneofs`, for message or enumeration
+    /// types. The first type has index 1; zero means the type is not in the list.
     pub oneof_index: i32,
+    /// Whether to use alternative packed wire representation.
     pub packed: bool,
+    /// The protocol buffer options.
     pub options: ::std::vec::Vec<Option>,
+    /// The field JSON name.
     pub json_name: ::std::string::String,
+    /// The string value of the default value of this field. Proto2 syntax only.
     pub default_value: ::std::string::String,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl Field {
@@ -444,19 +482,19 @@ impl Field {
 
 
     // Param is passed by value, moved
-    pub fn set_kind(&mut self, v: Field_Kind) {
+    pub fn set_kind(&mut self, v: ::protobuf::ProtobufEnumOrUnknown<Field_Kind>) {
         self.kind = v;
     }
 
-    pub fn get_kind(&self) -> Field_Kind {
+    pub fn get_kind(&self) -> ::protobuf::ProtobufEnumOrUnknown<Field_Kind> {
         self.kind
     }
 
-    fn get_kind_for_reflect(&self) -> &Field_Kind {
+    fn get_kind_for_reflect(&self) -> &::protobuf::ProtobufEnumOrUnknown<Field_Kind> {
         &self.kind
     }
 
-    fn mut_kind_for_reflect(&mut self) -> &mut Field_Kind {
+    fn mut_kind_for_reflect(&mut self) -> &mut ::protobuf::ProtobufEnumOrUnknown<Field_Kind> {
         &mut self.kind
     }
 
@@ -464,19 +502,19 @@ impl Field {
 
 
     // Param is passed by value, moved
-    pub fn set_cardinality(&mut self, v: Field_Cardinality) {
+    pub fn set_cardinality(&mut self, v: ::protobuf::ProtobufEnumOrUnknown<Field_Cardinality>) {
         self.cardinality = v;
     }
 
-    pub fn get_cardinality(&self) -> Field_Cardinality {
+    pub fn get_cardinality(&self) -> ::protobuf::ProtobufEnumOrUnknown<Field_Cardinality> {
         self.cardinality
     }
 
-    fn get_cardinality_for_reflect(&self) -> &Field_Cardinality {
+    fn get_cardinality_for_reflect(&self) -> &::protobuf::ProtobufEnumOrUnknown<Field_Cardinality> {
         &self.cardinality
     }
 
-    fn mut_cardinality_for_reflect(&mut self) -> &mut Field_Cardinality {
+    fn mut_cardinality_for_reflect(&mut self) -> &mut ::protobuf::ProtobufEnumOrUnknown<Field_Cardinality> {
         &mut self.cardinality
     }
 
@@ -680,107 +718,122 @@ impl ::protobuf::Message for Field {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_kind: ::std::option::Option<Field_Kind> = None;
-        let mut _field_cardinality: ::std::option::Option<Field_Cardinality> = None;
-        let mut _field_number: ::std::option::Option<i32> = None;
-        let mut _field_name: ::std::option::Option<::std::string::String> = None;
-        let mut _field_type_url: ::std::option::Option<::std::string::String> = None;
-        let mut _field_oneof_index: ::std::option::Option<i32> = None;
-        let mut _field_packed: ::std::option::Option<bool> = None;
-        let mut _field_options: ::std::vec::Vec<Option> = ::std::vec::Vec::new();
-        let mut _field_json_name: ::std::option::Option<::std::string::String> = None;
-        let mut _field_default_value: ::std::option::Option<::std::string::String> = None;
-
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = Field::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_kind = ::std::option::Option::Some(_is.read_enum()?);
+                    self.kind = is.read_enum_or_unknown()?;
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_cardinality = ::std::option::Option::Some(_is.read_enum()?);
+                    self.cardinality = is.read_enum_or_unknown()?;
                 },
                 3 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_number = ::std::option::Option::Some(_is.read_int32()?);
+                    self.number = is.read_int32()?;
                 },
                 4 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_name = ::std::option::Option::Some(_is.read_string()?);
+                    self.name = is.read_string()?;
                 },
                 6 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_type_url = ::std::option::Option::Some(_is.read_string()?);
+                    self.type_url = is.read_string()?;
                 },
                 7 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_oneof_index = ::std::option::Option::Some(_is.read_int32()?);
+                    self.oneof_index = is.read_int32()?;
                 },
                 8 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_packed = ::std::option::Option::Some(_is.read_bool()?);
+                    self.packed = is.read_bool()?;
                 },
                 9 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_options)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.options)?;
                 },
                 10 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_json_name = ::std::option::Option::Some(_is.read_string()?);
+                    self.json_name = is.read_string()?;
                 },
                 11 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_default_value = ::std::option::Option::Some(_is.read_string()?);
+                    self.default_value = is.read_string()?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(Field {
-            kind: _field_kind.unwrap_or_default(),
-            cardinality: _field_cardinality.unwrap_or_default(),
-            number: _field_number.unwrap_or_default(),
-            name: _field_name.unwrap_or_default(),
-            type_url: _field_type_url.unwrap_or_default(),
-            oneof_index: _field_oneof_index.unwrap_or_default(),
-            packed: _field_packed.unwrap_or_default(),
-            options: _field_options,
-            json_name: _field_json_name.unwrap_or_default(),
-            default_value: _field_default_value.unwrap_or_default(),
-        })
+    fn merge(&mut self, other: &Self) {
+        if other.kind != ::protobuf::ProtobufEnumOrUnknown::new(Field_Kind::TYPE_UNKNOWN) {
+            self.kind = other.kind.clone();
+        }
+        if other.cardinality != ::protobuf::ProtobufEnumOrUnknown::new(Field_Cardinality::CARDINALITY_UNKNOWN) {
+            self.cardinality = other.cardinality.clone();
+        }
+        if other.number != 0 {
+            self.number = other.number.clone();
+        }
+        if !other.name.is_empty() {
+            self.name = other.name.clone();
+        }
+        if !other.type_url.is_empty() {
+            self.type_url = other.type_url.clone();
+        }
+        if other.oneof_index != 0 {
+            self.oneof_index = other.oneof_index.clone();
+        }
+        if other.packed != false {
+            self.packed = other.packed.clone();
+        }
+        self.options.extend(other.options.iter().cloned());
+        if !other.json_name.is_empty() {
+            self.json_name = other.json_name.clone();
+        }
+        if !other.default_value.is_empty() {
+            self.default_value = other.default_value.clone();
+        }
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut _size = 0;
-        if self.kind != Field_Kind::TYPE_UNKNOWN {
-            _size += ::protobuf::rt::enum_size(1, self.kind);
+        if self.kind != ::protobuf::ProtobufEnumOrUnknown::new(Field_Kind::TYPE_UNKNOWN) {
+            _size += ::protobuf::rt::enum_or_unknown_size(1, self.kind);
         }
-        if self.cardinality != Field_Cardinality::CARDINALITY_UNKNOWN {
-            _size += ::protobuf::rt::enum_size(2, self.cardinality);
+        if self.cardinality != ::protobuf::ProtobufEnumOrUnknown::new(Field_Cardinality::CARDINALITY_UNKNOWN) {
+            _size += ::protobuf::rt::enum_or_unknown_size(2, self.cardinality);
         }
         if self.number != 0 {
             _size += ::protobuf::rt::value_size(3, self.number, ::protobuf::wire_format::WireTypeVarint);
@@ -807,14 +860,15 @@ impl ::protobuf::Message for Field {
         if !self.default_value.is_empty() {
             _size += ::protobuf::rt::string_size(11, &self.default_value);
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
     fn write_to_with_cached_sizes(&self, _os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
-        if self.kind != Field_Kind::TYPE_UNKNOWN {
+        if self.kind != ::protobuf::ProtobufEnumOrUnknown::new(Field_Kind::TYPE_UNKNOWN) {
             _os.write_enum(1, self.kind.value())?;
         }
-        if self.cardinality != Field_Cardinality::CARDINALITY_UNKNOWN {
+        if self.cardinality != ::protobuf::ProtobufEnumOrUnknown::new(Field_Cardinality::CARDINALITY_UNKNOWN) {
             _os.write_enum(2, self.cardinality.value())?;
         }
         if self.number != 0 {
@@ -843,16 +897,25 @@ impl ::protobuf::Message for Field {
         if !self.default_value.is_empty() {
             _os.write_string(11, &self.default_value)?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -872,12 +935,12 @@ impl ::protobuf::Message for Field {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<Field_Kind>>(
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnumOrUnknown<Field_Kind>>(
                     "kind",
                     Field::get_kind_for_reflect,
                     Field::mut_kind_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<Field_Cardinality>>(
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnumOrUnknown<Field_Cardinality>>(
                     "cardinality",
                     Field::get_cardinality_for_reflect,
                     Field::mut_cardinality_for_reflect,
@@ -944,26 +1007,47 @@ impl ::protobuf::reflect::ProtobufValue for Field {
     }
 }
 
+/// Basic field types.
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub enum Field_Kind {
+    /// Field type unknown.
     TYPE_UNKNOWN = 0,
+    /// Field type double.
     TYPE_DOUBLE = 1,
+    /// Field type float.
     TYPE_FLOAT = 2,
+    /// Field type int64.
     TYPE_INT64 = 3,
+    /// Field type uint64.
     TYPE_UINT64 = 4,
+    /// Field type int32.
     TYPE_INT32 = 5,
+    /// Field type fixed64.
     TYPE_FIXED64 = 6,
+    /// Field type fixed32.
     TYPE_FIXED32 = 7,
+    /// Field type bool.
     TYPE_BOOL = 8,
+    /// Field type string.
     TYPE_STRING = 9,
+    /// Field type group. Proto2 syntax only, and deprecated.
     TYPE_GROUP = 10,
+    /// Field type message.
     TYPE_MESSAGE = 11,
+    /// Field type bytes.
     TYPE_BYTES = 12,
+    /// Field type uint32.
     TYPE_UINT32 = 13,
+    /// Field type enum.
     TYPE_ENUM = 14,
+    /// Field type sfixed32.
     TYPE_SFIXED32 = 15,
+    /// Field type sfixed64.
     TYPE_SFIXED64 = 16,
+    /// Field type sint32.
     TYPE_SINT32 = 17,
+    /// Field type sint64.
     TYPE_SINT64 = 18,
 }
 
@@ -1048,13 +1132,23 @@ impl ::protobuf::reflect::ProtobufValue for Field_Kind {
     fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
         ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
     }
+
+    fn from_value_box(value: ::protobuf::reflect::ProtobufValueBox) -> ::std::option::Option<Self> {
+        value.into_enum()
+    }
 }
 
+/// Whether a field is optional, required, or repeated.
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub enum Field_Cardinality {
+    /// For fields with unknown cardinality.
     CARDINALITY_UNKNOWN = 0,
+    /// For optional fields.
     CARDINALITY_OPTIONAL = 1,
+    /// For required fields. Proto2 syntax only.
     CARDINALITY_REQUIRED = 2,
+    /// For repeated fields.
     CARDINALITY_REPEATED = 3,
 }
 
@@ -1109,16 +1203,29 @@ impl ::protobuf::reflect::ProtobufValue for Field_Cardinality {
     fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
         ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
     }
+
+    fn from_value_box(value: ::protobuf::reflect::ProtobufValueBox) -> ::std::option::Option<Self> {
+        value.into_enum()
+    }
 }
 
+/// Enum type definition.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct Enum {
     // message fields
+    /// Enum type name.
     pub name: ::std::string::String,
+    /// Enum value definitions.
     pub enumvalue: ::std::vec::Vec<EnumValue>,
+    /// Protocol buffer options.
     pub options: ::std::vec::Vec<Option>,
+    /// The source context.
     pub source_context: ::protobuf::SingularPtrField<::protobuf::well_known_types::SourceContext>,
-    pub syntax: Syntax,
+    /// The source syntax.
+    pub syntax: ::protobuf::ProtobufEnumOrUnknown<Syntax>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl Enum {
@@ -1249,19 +1356,19 @@ impl Enum {
 
 
     // Param is passed by value, moved
-    pub fn set_syntax(&mut self, v: Syntax) {
+    pub fn set_syntax(&mut self, v: ::protobuf::ProtobufEnumOrUnknown<Syntax>) {
         self.syntax = v;
     }
 
-    pub fn get_syntax(&self) -> Syntax {
+    pub fn get_syntax(&self) -> ::protobuf::ProtobufEnumOrUnknown<Syntax> {
         self.syntax
     }
 
-    fn get_syntax_for_reflect(&self) -> &Syntax {
+    fn get_syntax_for_reflect(&self) -> &::protobuf::ProtobufEnumOrUnknown<Syntax> {
         &self.syntax
     }
 
-    fn mut_syntax_for_reflect(&mut self) -> &mut Syntax {
+    fn mut_syntax_for_reflect(&mut self) -> &mut ::protobuf::ProtobufEnumOrUnknown<Syntax> {
         &mut self.syntax
     }
 }
@@ -1286,53 +1393,56 @@ impl ::protobuf::Message for Enum {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_name: ::std::option::Option<::std::string::String> = None;
-        let mut _field_enumvalue: ::std::vec::Vec<EnumValue> = ::std::vec::Vec::new();
-        let mut _field_options: ::std::vec::Vec<Option> = ::std::vec::Vec::new();
-        let mut _field_source_context: ::std::option::Option<::protobuf::SingularPtrField<::protobuf::well_known_types::SourceContext>> = None;
-        let mut _field_syntax: ::std::option::Option<Syntax> = None;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = Enum::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_name = ::std::option::Option::Some(_is.read_string()?);
+                    self.name = is.read_string()?;
                 },
                 2 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_enumvalue)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.enumvalue)?;
                 },
                 3 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_options)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.options)?;
                 },
                 4 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    _field_source_context = ::std::option::Option::Some(::protobuf::SingularPtrField::some(_is.read_message()?));
+                    ::protobuf::rt::merge_singular_message_into(wire_type, is, &mut self.source_context)?;
                 },
                 5 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_syntax = ::std::option::Option::Some(_is.read_enum()?);
+                    self.syntax = is.read_enum_or_unknown()?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(Enum {
-            name: _field_name.unwrap_or_default(),
-            enumvalue: _field_enumvalue,
-            options: _field_options,
-            source_context: _field_source_context.unwrap_or_default(),
-            syntax: _field_syntax.unwrap_or_default(),
-        })
+    fn merge(&mut self, other: &Self) {
+        if !other.name.is_empty() {
+            self.name = other.name.clone();
+        }
+        self.enumvalue.extend(other.enumvalue.iter().cloned());
+        self.options.extend(other.options.iter().cloned());
+        ::protobuf::rt::merge_singular_message(&mut self.source_context, &other.source_context);
+        if other.syntax != ::protobuf::ProtobufEnumOrUnknown::new(Syntax::SYNTAX_PROTO2) {
+            self.syntax = other.syntax.clone();
+        }
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -1354,9 +1464,10 @@ impl ::protobuf::Message for Enum {
             let len = v.compute_size();
             _size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         }
-        if self.syntax != Syntax::SYNTAX_PROTO2 {
-            _size += ::protobuf::rt::enum_size(5, self.syntax);
+        if self.syntax != ::protobuf::ProtobufEnumOrUnknown::new(Syntax::SYNTAX_PROTO2) {
+            _size += ::protobuf::rt::enum_or_unknown_size(5, self.syntax);
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -1379,19 +1490,28 @@ impl ::protobuf::Message for Enum {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         }
-        if self.syntax != Syntax::SYNTAX_PROTO2 {
+        if self.syntax != ::protobuf::ProtobufEnumOrUnknown::new(Syntax::SYNTAX_PROTO2) {
             _os.write_enum(5, self.syntax.value())?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -1431,7 +1551,7 @@ impl ::protobuf::Message for Enum {
                     Enum::get_source_context_for_reflect,
                     Enum::mut_source_context_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<Syntax>>(
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnumOrUnknown<Syntax>>(
                     "syntax",
                     Enum::get_syntax_for_reflect,
                     Enum::mut_syntax_for_reflect,
@@ -1458,12 +1578,19 @@ impl ::protobuf::reflect::ProtobufValue for Enum {
     }
 }
 
+/// Enum value definition.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct EnumValue {
     // message fields
+    /// Enum value name.
     pub name: ::std::string::String,
+    /// Enum value number.
     pub number: i32,
+    /// Protocol buffer options.
     pub options: ::std::vec::Vec<Option>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl EnumValue {
@@ -1563,40 +1690,48 @@ impl ::protobuf::Message for EnumValue {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_name: ::std::option::Option<::std::string::String> = None;
-        let mut _field_number: ::std::option::Option<i32> = None;
-        let mut _field_options: ::std::vec::Vec<Option> = ::std::vec::Vec::new();
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = EnumValue::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_name = ::std::option::Option::Some(_is.read_string()?);
+                    self.name = is.read_string()?;
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_number = ::std::option::Option::Some(_is.read_int32()?);
+                    self.number = is.read_int32()?;
                 },
                 3 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, _is, &mut _field_options)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.options)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(EnumValue {
-            name: _field_name.unwrap_or_default(),
-            number: _field_number.unwrap_or_default(),
-            options: _field_options,
-        })
+    fn merge(&mut self, other: &Self) {
+        if !other.name.is_empty() {
+            self.name = other.name.clone();
+        }
+        if other.number != 0 {
+            self.number = other.number.clone();
+        }
+        self.options.extend(other.options.iter().cloned());
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -1613,6 +1748,7 @@ impl ::protobuf::Message for EnumValue {
             let len = value.compute_size();
             _size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -1628,16 +1764,25 @@ impl ::protobuf::Message for EnumValue {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         };
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -1694,11 +1839,18 @@ impl ::protobuf::reflect::ProtobufValue for EnumValue {
     }
 }
 
+/// A protocol buffer option, which can be attached to a message, field,
+/// enumeration, etc.
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub struct Option {
     // message fields
+    /// The option's name. For example, `"java_package"`.
     pub name: ::std::string::String,
+    /// The option's value. For example, `"com.google.protobuf"`.
     pub value: ::protobuf::SingularPtrField<::protobuf::well_known_types::Any>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
 }
 
 impl Option {
@@ -1786,35 +1938,39 @@ impl ::protobuf::Message for Option {
         true
     }
 
-    fn read_from(_is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
-        let mut _field_name: ::std::option::Option<::std::string::String> = None;
-        let mut _field_value: ::std::option::Option<::protobuf::SingularPtrField<::protobuf::well_known_types::Any>> = None;
+    fn read_from(is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<Self> where Self : Sized {
+        let mut m = Option::new();
+        m.merge_from(is)?;
+        m.check_initialized()?;
+        ::std::result::Result::Ok(m)
+    }
 
-        while !_is.eof()? {
-            let (field_number, wire_type) = _is.read_tag_unpack()?;
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while let ::std::option::Option::Some((field_number, wire_type)) = is.read_field_tag_unpack()? {
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    _field_name = ::std::option::Option::Some(_is.read_string()?);
+                    self.name = is.read_string()?;
                 },
                 2 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    _field_value = ::std::option::Option::Some(::protobuf::SingularPtrField::some(_is.read_message()?));
+                    ::protobuf::rt::merge_singular_message_into(wire_type, is, &mut self.value)?;
                 },
                 _ => {
-                    ::protobuf::rt::skip_unknown_or_group(field_number, wire_type, _is)?;
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, &mut self.unknown_fields)?;
                 },
             };
         }
+        ::std::result::Result::Ok(())
+    }
 
-        ::std::result::Result::Ok(Option {
-            name: _field_name.unwrap_or_default(),
-            value: _field_value.unwrap_or_default(),
-        })
+    fn merge(&mut self, other: &Self) {
+        if !other.name.is_empty() {
+            self.name = other.name.clone();
+        }
+        ::protobuf::rt::merge_singular_message(&mut self.value, &other.value);
+        self.unknown_fields.merge(&other.unknown_fields);
     }
 
     // Compute sizes of nested messages
@@ -1828,6 +1984,7 @@ impl ::protobuf::Message for Option {
             let len = v.compute_size();
             _size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         }
+        _size += ::protobuf::rt::unknown_fields_size(&self.unknown_fields);
         _size
     }
 
@@ -1840,16 +1997,25 @@ impl ::protobuf::Message for Option {
             _os.write_raw_varint32(v.compute_size())?;
             v.write_to_with_cached_sizes(_os)?;
         }
+        _os.write_unknown_fields(&self.unknown_fields)?;
         ::std::result::Result::Ok(())
     }
 
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
     fn as_any_mut(&mut self) -> &mut ::std::any::Any {
         self as &mut ::std::any::Any
     }
-    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
         self
     }
 
@@ -1901,9 +2067,13 @@ impl ::protobuf::reflect::ProtobufValue for Option {
     }
 }
 
+/// The syntax in which a protocol buffer element is defined.
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 pub enum Syntax {
+    /// Syntax `proto2`.
     SYNTAX_PROTO2 = 0,
+    /// Syntax `proto3`.
     SYNTAX_PROTO3 = 1,
 }
 
@@ -1954,6 +2124,10 @@ impl ::protobuf::reflect::ProtobufValue for Syntax {
     fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
         ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
     }
+
+    fn from_value_box(value: ::protobuf::reflect::ProtobufValueBox) -> ::std::option::Option<Self> {
+        value.into_enum()
+    }
 }
 
 static file_descriptor_proto_data: &'static [u8] = b"\
@@ -0,0 +1,158 @@
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use message::Message;
+use message::parse_from_bytes;
+use error::ProtobufResult;
+use lazy::Lazy;
+use lazy::ONCE_INIT;
+use reflect::MessageDescriptor;
+use well_known_types;
+use well_known_types::Any;
+
+/// Type URL prefix used by `Any::pack`
+pub const TYPE_URL_PREFIX: &'static str = "type.googleapis.com";
+
+/// Message full name from `Any` type URL, which is the part after the last `/`
+pub fn type_url_full_name(type_url: &str) -> Option<&str> {
+    match type_url.rfind('/') {
+        Some(pos) => Some(&type_url[pos + 1..]),
+        None => None,
+    }
+}
+
+impl Any {
+    /// Serialize message into `Any` with type URL `type.googleapis.com/<full name>`.
+    pub fn pack<M : Message>(message: &M) -> ProtobufResult<Any> {
+        Any::pack_dyn(message)
+    }
+
+    /// Same as `pack`, but for messages of type not known at compile time.
+    pub fn pack_dyn(message: &Message) -> ProtobufResult<Any> {
+        let mut r = Any::new();
+        r.type_url = format!("{}/{}", TYPE_URL_PREFIX, message.descriptor().full_name());
+        r.value = message.write_to_bytes()?;
+        Ok(r)
+    }
+
+    /// Type URL refers to message type `M`.
+    pub fn is<M : Message>(&self) -> bool {
+        type_url_full_name(&self.type_url) == Some(MessageDescriptor::for_type::<M>().full_name())
+    }
+
+    /// Parse content if it is of type `M`, `None` if `Any` contains another type.
+    pub fn unpack<M : Message>(&self) -> ProtobufResult<Option<M>> {
+        if !self.is::<M>() {
+            return Ok(None);
+        }
+        parse_from_bytes(&self.value).map(Some)
+    }
+
+    /// Parse content of the type found in the global registry,
+    /// `None` if type is not registered (see `register_type`).
+    pub fn unpack_dyn(&self) -> ProtobufResult<Option<Box<Message>>> {
+        let d = match find_type_by_url(&self.type_url) {
+            Some(d) => d,
+            None => return Ok(None),
+        };
+        let mut r = d.new_instance();
+        r.merge_from_bytes(&self.value)?;
+        r.check_initialized()?;
+        Ok(Some(r))
+    }
+}
+
+type Registry = RwLock<HashMap<String, &'static MessageDescriptor>>;
+
+fn registry() -> &'static Registry {
+    static mut REGISTRY: Lazy<Registry> = Lazy {
+        lock: ONCE_INIT,
+        ptr: 0 as *const Registry,
+    };
+    unsafe {
+        REGISTRY.get(|| {
+            let well_known = [
+                MessageDescriptor::for_type::<well_known_types::Any>(),
+                MessageDescriptor::for_type::<well_known_types::Duration>(),
+                MessageDescriptor::for_type::<well_known_types::Empty>(),
+                MessageDescriptor::for_type::<well_known_types::FieldMask>(),
+                MessageDescriptor::for_type::<well_known_types::Struct>(),
+                MessageDescriptor::for_type::<well_known_types::Value>(),
+                MessageDescriptor::for_type::<well_known_types::ListValue>(),
+                MessageDescriptor::for_type::<well_known_types::Timestamp>(),
+                MessageDescriptor::for_type::<well_known_types::DoubleValue>(),
+                MessageDescriptor::for_type::<well_known_types::FloatValue>(),
+                MessageDescriptor::for_type::<well_known_types::Int64Value>(),
+                MessageDescriptor::for_type::<well_known_types::UInt64Value>(),
+                MessageDescriptor::for_type::<well_known_types::Int32Value>(),
+                MessageDescriptor::for_type::<well_known_types::UInt32Value>(),
+                MessageDescriptor::for_type::<well_known_types::BoolValue>(),
+                MessageDescriptor::for_type::<well_known_types::StringValue>(),
+                MessageDescriptor::for_type::<well_known_types::BytesValue>(),
+            ];
+            let types = well_known.iter().map(|d| (d.full_name().to_owned(), *d)).collect();
+            RwLock::new(types)
+        })
+    }
+}
+
+/// Register message type in the global registry,
+/// so `Any` containing it could be unpacked with `Any::unpack_dyn`,
+/// and printed in expanded form by text format and JSON printers.
+///
+/// Well-known types are registered by default.
+pub fn register_type(d: &'static MessageDescriptor) {
+    let mut types = registry().write().unwrap();
+    types.insert(d.full_name().to_owned(), d);
+}
+
+/// Register message type `M` in the global registry.
+pub fn register_message<M : Message>() {
+    register_type(MessageDescriptor::for_type::<M>())
+}
+
+/// Find registered message type by full name, e. g. `google.protobuf.Duration`.
+pub fn find_type_by_full_name(full_name: &str) -> Option<&'static MessageDescriptor> {
+    registry().read().unwrap().get(full_name).cloned()
+}
+
+/// Find registered message type by `Any` type URL,
+/// e. g. `type.googleapis.com/google.protobuf.Duration`.
+pub fn find_type_by_url(type_url: &str) -> Option<&'static MessageDescriptor> {
+    type_url_full_name(type_url).and_then(find_type_by_full_name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use well_known_types::Duration;
+    use well_known_types::Timestamp;
+
+    #[test]
+    fn pack_unpack() {
+        let mut d = Duration::new();
+        d.seconds = 10;
+        let any = Any::pack(&d).unwrap();
+        assert_eq!("type.googleapis.com/google.protobuf.Duration", any.type_url);
+        assert!(any.is::<Duration>());
+        assert!(!any.is::<Timestamp>());
+        assert_eq!(Some(d.clone()), any.unpack::<Duration>().unwrap());
+        assert_eq!(None, any.unpack::<Timestamp>().unwrap());
+
+        let content = any.unpack_dyn().unwrap().unwrap();
+        assert_eq!("google.protobuf.Duration", content.descriptor().full_name());
+        assert_eq!(Some(&d), content.as_any().downcast_ref::<Duration>());
+    }
+
+    #[test]
+    fn type_url() {
+        assert_eq!(Some("x.Y"), type_url_full_name("example.com/a/x.Y"));
+        assert_eq!(None, type_url_full_name("x.Y"));
+        assert!(find_type_by_url("type.googleapis.com/google.protobuf.Timestamp").is_some());
+        assert!(find_type_by_url("google.protobuf.Timestamp").is_none());
+        assert!(find_type_by_url("type.googleapis.com/x.Unknown").is_none());
+    }
+}
@@ -0,0 +1,316 @@
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+use std::collections::BTreeMap;
+
+use message::Message;
+use descriptor::FieldDescriptorProto_Type;
+use reflect::FieldDescriptor;
+use reflect::MessageDescriptor;
+use reflect::ProtobufValueBox;
+use reflect::ProtobufValueRef;
+use reflect::ReflectFieldRef;
+use stream::CodedOutputStream;
+use well_known_types::FieldMask;
+
+use super::FieldMaskError;
+use super::FieldMaskResult;
+
+/// Field is a non-repeated message field, so mask path may continue into it
+fn is_singular_message(field: &FieldDescriptor) -> bool {
+    match field.proto().get_field_type() {
+        FieldDescriptorProto_Type::TYPE_MESSAGE |
+        FieldDescriptorProto_Type::TYPE_GROUP => !field.is_repeated(),
+        _ => false,
+    }
+}
+
+/// Path `p` is equal to `prefix` or is a subpath of `prefix`
+fn is_prefix(prefix: &str, p: &str) -> bool {
+    p == prefix || (p.starts_with(prefix) && p.as_bytes()[prefix.len()] == b'.')
+}
+
+impl FieldMask {
+    /// Construct field mask from paths like `a.b`.
+    pub fn from_paths<I, S>(paths: I) -> FieldMask
+    where
+        I : IntoIterator<Item = S>,
+        S : Into<String>,
+    {
+        let mut r = FieldMask::new();
+        r.paths = paths.into_iter().map(Into::into).collect();
+        r
+    }
+
+    /// Check that each path refers to a field of given message type:
+    /// path components are field names, and all components except the last one
+    /// are non-repeated message fields.
+    pub fn validate(&self, d: &MessageDescriptor) -> FieldMaskResult<()> {
+        for path in &self.paths {
+            let mut message = d;
+            let mut components = path.split('.').peekable();
+            while let Some(name) = components.next() {
+                let field = match message.fields().iter().find(|f| f.name() == name) {
+                    Some(field) => field,
+                    None => {
+                        return Err(FieldMaskError::new(format!(
+                            "incorrect path {:?}: message {} has no field {:?}",
+                            path,
+                            message.full_name(),
+                            name
+                        )));
+                    }
+                };
+                if components.peek().is_some() {
+                    if !is_singular_message(field) {
+                        return Err(FieldMaskError::new(format!(
+                            "incorrect path {:?}: field {} is not a singular message field",
+                            path,
+                            name
+                        )));
+                    }
+                    message = field.message_descriptor();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Canonical form: paths are sorted, duplicates and paths covered by other paths
+    /// (e. g. `a.b` when `a` is present) are removed.
+    pub fn normalize(&self) -> FieldMask {
+        let mut paths: Vec<&str> = self.paths.iter().map(|p| &p[..]).collect();
+        paths.sort();
+        let mut r = FieldMask::new();
+        for p in paths {
+            if !r.paths.iter().any(|prefix| is_prefix(prefix, p)) {
+                r.paths.push(p.to_owned());
+            }
+        }
+        r
+    }
+
+    /// Mask which covers all fields covered by either mask, normalized.
+    pub fn union(&self, other: &FieldMask) -> FieldMask {
+        let paths = self.paths.iter().chain(&other.paths).cloned();
+        FieldMask::from_paths(paths).normalize()
+    }
+
+    /// Mask which covers fields covered by both masks, normalized.
+    pub fn intersection(&self, other: &FieldMask) -> FieldMask {
+        let a = self.normalize();
+        let b = other.normalize();
+        let mut paths = Vec::new();
+        for p in &a.paths {
+            for q in &b.paths {
+                if is_prefix(p, q) {
+                    paths.push(q.clone());
+                } else if is_prefix(q, p) {
+                    paths.push(p.clone());
+                }
+            }
+        }
+        FieldMask::from_paths(paths).normalize()
+    }
+}
+
+/// Field mask as a tree of field names, leaf means the whole field
+#[derive(Default)]
+struct FieldMaskTree {
+    children: BTreeMap<String, FieldMaskTree>,
+}
+
+impl FieldMaskTree {
+    fn new(mask: &FieldMask) -> FieldMaskTree {
+        let mut r = FieldMaskTree::default();
+        for path in &mask.normalize().paths {
+            let mut node = &mut r;
+            for name in path.split('.') {
+                node = node.children.entry(name.to_owned()).or_insert_with(Default::default);
+            }
+        }
+        r
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+/// Clear all fields of the message except fields covered by the mask.
+///
+/// Paths which do not refer to fields of the message are ignored.
+pub fn trim(m: &mut Message, mask: &FieldMask) {
+    trim_tree(m, &FieldMaskTree::new(mask))
+}
+
+fn trim_tree(m: &mut Message, tree: &FieldMaskTree) {
+    let d = m.descriptor();
+    for field in d.fields() {
+        match tree.children.get(field.name()) {
+            None => field.clear_field(m),
+            Some(subtree) => {
+                if !subtree.is_leaf() && is_singular_message(field) && field.has_field(m) {
+                    field.modify_message(m, |m| trim_tree(m, subtree));
+                }
+            }
+        }
+    }
+}
+
+/// How `merge_with_mask` handles fields covered by the mask
+#[derive(Default, Clone)]
+pub struct MergeOptions {
+    /// Replace message fields in destination instead of merging source into them,
+    /// clear destination field if it is not set in source
+    pub replace_message_fields: bool,
+    /// Replace repeated and map fields instead of appending source elements
+    pub replace_repeated_fields: bool,
+}
+
+/// Copy fields covered by the mask from `src` to `dst`, which must be of the same type.
+///
+/// Singular non-message fields are always replaced: if field is not set in source,
+/// it is cleared in destination. Handling of message and repeated fields
+/// is controlled by options. Paths which do not refer to fields of the message are ignored.
+pub fn merge_with_mask(
+    src: &Message,
+    dst: &mut Message,
+    mask: &FieldMask,
+    options: &MergeOptions,
+) {
+    assert_eq!(src.descriptor().full_name(), dst.descriptor().full_name());
+    merge_tree(src, dst, &FieldMaskTree::new(mask), options)
+}
+
+fn merge_tree(src: &Message, dst: &mut Message, tree: &FieldMaskTree, options: &MergeOptions) {
+    let d = src.descriptor();
+    for (name, subtree) in &tree.children {
+        let field = match d.fields().iter().find(|f| f.name() == &name[..]) {
+            Some(field) => field,
+            None => continue,
+        };
+
+        if !subtree.is_leaf() {
+            if is_singular_message(field) && (field.has_field(src) || field.has_field(dst)) {
+                match field.get_reflect(src) {
+                    ReflectFieldRef::Optional(Some(ProtobufValueRef::Message(m))) => {
+                        merge_tree(m, field.mut_message(dst), subtree, options);
+                    }
+                    ReflectFieldRef::Wrapper(Some(m)) => {
+                        field.modify_message(dst, |dst| merge_tree(&*m, dst, subtree, options));
+                    }
+                    _ => {
+                        // masked subfields are cleared
+                        let empty = field.message_descriptor().new_instance();
+                        field.modify_message(dst, |dst| merge_tree(&*empty, dst, subtree, options));
+                    }
+                }
+            }
+            continue;
+        }
+
+        match field.get_reflect(src) {
+            ReflectFieldRef::Repeated(repeated) => {
+                if options.replace_repeated_fields {
+                    field.clear_field(dst);
+                }
+                for v in repeated {
+                    field.push_repeated(dst, value_to_box(v.as_ref()));
+                }
+            }
+            ReflectFieldRef::Map(map) => {
+                if options.replace_repeated_fields {
+                    field.clear_field(dst);
+                }
+                for (k, v) in map {
+                    field.insert_map(dst, value_to_box(k.as_ref()), value_to_box(v.as_ref()));
+                }
+            }
+            ReflectFieldRef::Optional(Some(ProtobufValueRef::Message(m))) => {
+                if options.replace_message_fields {
+                    field.clear_field(dst);
+                }
+                merge_message(field.mut_message(dst), m);
+            }
+            ReflectFieldRef::Wrapper(Some(m)) => {
+                if options.replace_message_fields {
+                    field.clear_field(dst);
+                }
+                field.modify_message(dst, |dst| merge_message(dst, &*m));
+            }
+            ReflectFieldRef::Optional(Some(v)) => {
+                field.set_singular_field(dst, value_to_box(v));
+            }
+            ReflectFieldRef::Optional(None) |
+            ReflectFieldRef::Wrapper(None) => {
+                if !is_singular_message(field) || options.replace_message_fields {
+                    field.clear_field(dst);
+                }
+            }
+        }
+    }
+}
+
+/// Merge `src` into `dst` of the same type, like `Message::merge`
+fn merge_message(dst: &mut Message, src: &Message) {
+    let mut bytes = Vec::new();
+    src.compute_size();
+    src.write_to_with_cached_sizes(&mut CodedOutputStream::vec(&mut bytes))
+        .expect("write to vec");
+    dst.merge_from_bytes(&bytes).expect("merge message of the same type");
+}
+
+fn value_to_box(value: ProtobufValueRef) -> ProtobufValueBox {
+    match value {
+        ProtobufValueRef::U32(v) => ProtobufValueBox::U32(v),
+        ProtobufValueRef::U64(v) => ProtobufValueBox::U64(v),
+        ProtobufValueRef::I32(v) => ProtobufValueBox::I32(v),
+        ProtobufValueRef::I64(v) => ProtobufValueBox::I64(v),
+        ProtobufValueRef::F32(v) => ProtobufValueBox::F32(v),
+        ProtobufValueRef::F64(v) => ProtobufValueBox::F64(v),
+        ProtobufValueRef::Bool(v) => ProtobufValueBox::Bool(v),
+        ProtobufValueRef::String(v) => ProtobufValueBox::String(v.to_owned()),
+        ProtobufValueRef::Bytes(v) => ProtobufValueBox::Bytes(v.to_owned()),
+        ProtobufValueRef::Enum(v) => ProtobufValueBox::Enum(v),
+        ProtobufValueRef::Message(m) => {
+            let mut r = m.descriptor().new_instance();
+            merge_message(&mut *r, m);
+            ProtobufValueBox::Message(r)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn mask(paths: &[&str]) -> FieldMask {
+        FieldMask::from_paths(paths.iter().cloned())
+    }
+
+    #[test]
+    fn normalize() {
+        let m = mask(&["bc", "a.x", "b.c", "a", "b.c.d", "a"]);
+        assert_eq!(mask(&["a", "b.c", "bc"]), m.normalize());
+        assert_eq!(mask(&[]), mask(&[]).normalize());
+    }
+
+    #[test]
+    fn union_intersection() {
+        let a = mask(&["a.b", "c", "e"]);
+        let b = mask(&["a", "c.d", "f"]);
+        assert_eq!(mask(&["a", "c", "e", "f"]), a.union(&b));
+        assert_eq!(mask(&["a.b", "c.d"]), a.intersection(&b));
+        assert_eq!(mask(&[]), a.intersection(&mask(&["ab", "cd"])));
+    }
+
+    #[test]
+    fn validate() {
+        let d = FieldMask::descriptor_static(None);
+        assert!(mask(&["paths"]).validate(d).is_ok());
+        assert!(mask(&["paths.x"]).validate(d).is_err());
+        assert!(mask(&["path"]).validate(d).is_err());
+        assert!(mask(&[""]).validate(d).is_err());
+    }
+}
@@ -0,0 +1,56 @@
+//! Conversions and utilities for well-known types.
+//!
+//! Types themselves are generated into `well_known_types`,
+//! this module contains implementations written by hand.
+//! `Timestamp` and `Duration` conversions are in `well_known_types` next to the types.
+
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+#[cfg(feature = "std")]
+use std::error::Error;
+use std::fmt;
+
+mod any;
+mod field_mask;
+mod struct_value;
+mod wrappers;
+
+pub use self::any::TYPE_URL_PREFIX;
+pub use self::any::find_type_by_full_name;
+pub use self::any::find_type_by_url;
+pub use self::any::register_message;
+pub use self::any::register_type;
+pub use self::any::type_url_full_name;
+pub use self::field_mask::MergeOptions;
+pub use self::field_mask::merge_with_mask;
+pub use self::field_mask::trim;
+pub use self::struct_value::Kind;
+pub use self::wrappers::WrapperMessage;
+
+/// `FieldMask` path does not refer to a field of the message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldMaskError {
+    pub message: String,
+}
+
+impl FieldMaskError {
+    fn new<S : Into<String>>(message: S) -> FieldMaskError {
+        FieldMaskError { message: message.into() }
+    }
+}
+
+impl fmt::Display for FieldMaskError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for FieldMaskError {
+    fn description(&self) -> &str {
+        &self.message
+    }
+}
+
+pub type FieldMaskResult<T> = Result<T, FieldMaskError>;
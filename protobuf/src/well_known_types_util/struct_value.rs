@@ -0,0 +1,395 @@
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::*;
+
+use std::collections::HashMap;
+use std::collections::hash_map;
+use std::iter::FromIterator;
+use std::ops::Index;
+use std::slice;
+use std::vec;
+
+#[cfg(feature = "serde_json")]
+use serde_json;
+
+use enum_or_unknown::ProtobufEnumOrUnknown;
+use well_known_types::ListValue;
+use well_known_types::NullValue;
+use well_known_types::Struct;
+use well_known_types::Value;
+use well_known_types::Value_oneof_kind;
+
+/// Content of `Value`, borrowed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind<'a> {
+    Null,
+    Number(f64),
+    String(&'a str),
+    Bool(bool),
+    Struct(&'a Struct),
+    List(&'a ListValue),
+}
+
+impl Value {
+    /// `null` value.
+    pub fn null() -> Value {
+        Value::from_kind(Value_oneof_kind::null_value(ProtobufEnumOrUnknown::new(
+            NullValue::NULL_VALUE,
+        )))
+    }
+
+    fn from_kind(kind: Value_oneof_kind) -> Value {
+        let mut r = Value::new();
+        r.kind = Some(kind);
+        r
+    }
+
+    /// Content of the value, `Kind::Null` if kind is not set.
+    pub fn kind(&self) -> Kind {
+        match self.kind {
+            None | Some(Value_oneof_kind::null_value(..)) => Kind::Null,
+            Some(Value_oneof_kind::number_value(v)) => Kind::Number(v),
+            Some(Value_oneof_kind::string_value(ref v)) => Kind::String(v),
+            Some(Value_oneof_kind::bool_value(v)) => Kind::Bool(v),
+            Some(Value_oneof_kind::struct_value(ref v)) => Kind::Struct(v),
+            Some(Value_oneof_kind::list_value(ref v)) => Kind::List(v),
+        }
+    }
+
+    /// Value is `null` or kind is not set.
+    pub fn is_null(&self) -> bool {
+        self.kind() == Kind::Null
+    }
+
+    /// Number value, `None` if the value is not a number.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self.kind() {
+            Kind::Number(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// String value, `None` if the value is not a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self.kind() {
+            Kind::String(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Bool value, `None` if the value is not a bool.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.kind() {
+            Kind::Bool(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Struct value, `None` if the value is not a struct.
+    pub fn as_struct(&self) -> Option<&Struct> {
+        match self.kind() {
+            Kind::Struct(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// List value, `None` if the value is not a list.
+    pub fn as_list(&self) -> Option<&ListValue> {
+        match self.kind() {
+            Kind::List(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl From<f64> for Value {
+    fn from(v: f64) -> Value {
+        Value::from_kind(Value_oneof_kind::number_value(v))
+    }
+}
+
+impl From<bool> for Value {
+    fn from(v: bool) -> Value {
+        Value::from_kind(Value_oneof_kind::bool_value(v))
+    }
+}
+
+impl From<String> for Value {
+    fn from(v: String) -> Value {
+        Value::from_kind(Value_oneof_kind::string_value(v))
+    }
+}
+
+impl<'a> From<&'a str> for Value {
+    fn from(v: &'a str) -> Value {
+        Value::from(v.to_owned())
+    }
+}
+
+impl From<Struct> for Value {
+    fn from(v: Struct) -> Value {
+        Value::from_kind(Value_oneof_kind::struct_value(v))
+    }
+}
+
+impl From<HashMap<String, Value>> for Value {
+    fn from(v: HashMap<String, Value>) -> Value {
+        Value::from(Struct::from(v))
+    }
+}
+
+impl From<ListValue> for Value {
+    fn from(v: ListValue) -> Value {
+        Value::from_kind(Value_oneof_kind::list_value(v))
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(v: Vec<Value>) -> Value {
+        Value::from(ListValue::from(v))
+    }
+}
+
+/// Collect values into list value.
+impl<V : Into<Value>> FromIterator<V> for Value {
+    fn from_iter<I : IntoIterator<Item = V>>(iter: I) -> Value {
+        Value::from(ListValue::from_iter(iter))
+    }
+}
+
+impl Struct {
+    /// Field value by key.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.fields.get(key)
+    }
+
+    /// Insert field, return previous value of the field.
+    pub fn insert<K : Into<String>, V : Into<Value>>(&mut self, key: K, value: V) -> Option<Value> {
+        self.fields.insert(key.into(), value.into())
+    }
+
+    /// Remove field, return its value.
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        self.fields.remove(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Iterate over fields in arbitrary order.
+    pub fn iter(&self) -> hash_map::Iter<String, Value> {
+        self.fields.iter()
+    }
+}
+
+/// Field value by key, `null` if there is no such field.
+impl<'a> Index<&'a str> for Struct {
+    type Output = Value;
+
+    fn index(&self, key: &str) -> &Value {
+        self.get(key).unwrap_or(Value::default_instance())
+    }
+}
+
+impl From<HashMap<String, Value>> for Struct {
+    fn from(fields: HashMap<String, Value>) -> Struct {
+        let mut r = Struct::new();
+        r.fields = fields;
+        r
+    }
+}
+
+impl<K : Into<String>, V : Into<Value>> FromIterator<(K, V)> for Struct {
+    fn from_iter<I : IntoIterator<Item = (K, V)>>(iter: I) -> Struct {
+        let fields: HashMap<String, Value> =
+            iter.into_iter().map(|(k, v)| (k.into(), v.into())).collect();
+        Struct::from(fields)
+    }
+}
+
+impl IntoIterator for Struct {
+    type Item = (String, Value);
+    type IntoIter = hash_map::IntoIter<String, Value>;
+
+    fn into_iter(self) -> hash_map::IntoIter<String, Value> {
+        self.fields.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Struct {
+    type Item = (&'a String, &'a Value);
+    type IntoIter = hash_map::Iter<'a, String, Value>;
+
+    fn into_iter(self) -> hash_map::Iter<'a, String, Value> {
+        self.fields.iter()
+    }
+}
+
+impl ListValue {
+    /// Append value to the list.
+    pub fn push<V : Into<Value>>(&mut self, value: V) {
+        self.values.push(value.into())
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn iter(&self) -> slice::Iter<Value> {
+        self.values.iter()
+    }
+}
+
+/// Element by index, panics if index is out of bounds.
+impl Index<usize> for ListValue {
+    type Output = Value;
+
+    fn index(&self, index: usize) -> &Value {
+        &self.values[index]
+    }
+}
+
+impl From<Vec<Value>> for ListValue {
+    fn from(values: Vec<Value>) -> ListValue {
+        let mut r = ListValue::new();
+        r.values = values;
+        r
+    }
+}
+
+impl<V : Into<Value>> FromIterator<V> for ListValue {
+    fn from_iter<I : IntoIterator<Item = V>>(iter: I) -> ListValue {
+        ListValue::from(iter.into_iter().map(Into::into).collect::<Vec<Value>>())
+    }
+}
+
+impl IntoIterator for ListValue {
+    type Item = Value;
+    type IntoIter = vec::IntoIter<Value>;
+
+    fn into_iter(self) -> vec::IntoIter<Value> {
+        self.values.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ListValue {
+    type Item = &'a Value;
+    type IntoIter = slice::Iter<'a, Value>;
+
+    fn into_iter(self) -> slice::Iter<'a, Value> {
+        self.values.iter()
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl From<serde_json::Value> for Value {
+    fn from(json: serde_json::Value) -> Value {
+        match json {
+            serde_json::Value::Null => Value::null(),
+            serde_json::Value::Bool(v) => Value::from(v),
+            // integers which do not fit into `f64` lose precision
+            serde_json::Value::Number(v) => Value::from(v.as_f64().unwrap_or(0.0)),
+            serde_json::Value::String(v) => Value::from(v),
+            serde_json::Value::Array(v) => Value::from_iter(v),
+            serde_json::Value::Object(v) => Value::from(Struct::from(v)),
+        }
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl From<serde_json::Map<String, serde_json::Value>> for Struct {
+    fn from(json: serde_json::Map<String, serde_json::Value>) -> Struct {
+        Struct::from_iter(json)
+    }
+}
+
+/// Infinite and NaN numbers are converted to `null`,
+/// like `serde_json` does for `f64`.
+#[cfg(feature = "serde_json")]
+impl From<Value> for serde_json::Value {
+    fn from(value: Value) -> serde_json::Value {
+        match value.kind {
+            None | Some(Value_oneof_kind::null_value(..)) => serde_json::Value::Null,
+            Some(Value_oneof_kind::number_value(v)) => serde_json::Value::from(v),
+            Some(Value_oneof_kind::string_value(v)) => serde_json::Value::String(v),
+            Some(Value_oneof_kind::bool_value(v)) => serde_json::Value::Bool(v),
+            Some(Value_oneof_kind::struct_value(v)) => serde_json::Value::Object(v.into()),
+            Some(Value_oneof_kind::list_value(v)) => {
+                serde_json::Value::Array(v.into_iter().map(Into::into).collect())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl From<Struct> for serde_json::Map<String, serde_json::Value> {
+    fn from(value: Struct) -> serde_json::Map<String, serde_json::Value> {
+        value.into_iter().map(|(k, v)| (k, v.into())).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn value_from() {
+        assert_eq!(Kind::Number(1.5), Value::from(1.5).kind());
+        assert_eq!(Kind::Bool(true), Value::from(true).kind());
+        assert_eq!(Kind::String("a"), Value::from("a").kind());
+        assert_eq!(Kind::Null, Value::null().kind());
+        assert_eq!(Kind::Null, Value::new().kind());
+        assert_eq!(Some("a"), Value::from("a".to_owned()).as_str());
+        assert_eq!(None, Value::from("a").as_f64());
+
+        let list = Value::from(vec![Value::from(1.0), Value::null()]);
+        assert_eq!(2, list.as_list().unwrap().len());
+        assert_eq!(list, vec![Value::from(1.0), Value::null()].into_iter().collect());
+    }
+
+    #[test]
+    fn struct_index() {
+        let mut s: Struct = vec![("a", 1.0), ("b", 2.0)].into_iter().collect();
+        assert_eq!(Some(1.0), s["a"].as_f64());
+        assert!(s["c"].is_null());
+        assert_eq!(None, s.get("c"));
+        assert_eq!(Some(Value::from(2.0)), s.insert("b", "x"));
+        assert_eq!(Some("x"), s["b"].as_str());
+
+        let value = Value::from(s.clone());
+        assert_eq!(Some(&s), value.as_struct());
+        let mut keys: Vec<&String> = s.iter().map(|(k, _)| k).collect();
+        keys.sort();
+        assert_eq!(vec!["a", "b"], keys);
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn serde_json() {
+        let json: serde_json::Value =
+            serde_json::from_str(r#"{"a": [1, "x", true, null], "b": {"c": 2.5}}"#).unwrap();
+        let value = Value::from(json.clone());
+        let s = value.as_struct().unwrap();
+        let a = s["a"].as_list().unwrap();
+        assert_eq!(Some(1.0), a[0].as_f64());
+        assert_eq!(Some("x"), a[1].as_str());
+        assert_eq!(Some(true), a[2].as_bool());
+        assert!(a[3].is_null());
+        assert_eq!(Some(2.5), s["b"].as_struct().unwrap()["c"].as_f64());
+
+        let expected = r#"{"a": [1.0, "x", true, null], "b": {"c": 2.5}}"#;
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(expected).unwrap(),
+            serde_json::Value::from(value)
+        );
+        assert_eq!(serde_json::Value::Null, serde_json::Value::from(Value::from(::std::f64::NAN)));
+    }
+}
@@ -0,0 +1,47 @@
+use message::Message;
+use types::*;
+use well_known_types::*;
+
+/// Wrapper well-known type like `Int32Value`: message with single field `value`.
+///
+/// Fields of wrapper types are generated as `Option` of the wrapped value
+/// when `rustproto.wrappers_as_option` option is enabled,
+/// and this trait is used to convert between the two representations.
+pub trait WrapperMessage : Message + Clone + Default {
+    /// Type of the `value` field
+    type ValueType : ProtobufType;
+
+    /// Construct a wrapper containing given value.
+    fn from_value(value: <Self::ValueType as ProtobufType>::Value) -> Self;
+
+    /// Take the wrapped value.
+    fn into_value(self) -> <Self::ValueType as ProtobufType>::Value;
+}
+
+macro_rules! wrapper_message {
+    ($message:ty, $value_type:ty) => {
+        impl WrapperMessage for $message {
+            type ValueType = $value_type;
+
+            fn from_value(value: <$value_type as ProtobufType>::Value) -> $message {
+                let mut r = <$message>::new();
+                r.value = value;
+                r
+            }
+
+            fn into_value(self) -> <$value_type as ProtobufType>::Value {
+                self.value
+            }
+        }
+    };
+}
+
+wrapper_message!(DoubleValue, ProtobufTypeDouble);
+wrapper_message!(FloatValue, ProtobufTypeFloat);
+wrapper_message!(Int64Value, ProtobufTypeInt64);
+wrapper_message!(UInt64Value, ProtobufTypeUint64);
+wrapper_message!(Int32Value, ProtobufTypeInt32);
+wrapper_message!(UInt32Value, ProtobufTypeUint32);
+wrapper_message!(BoolValue, ProtobufTypeBool);
+wrapper_message!(StringValue, ProtobufTypeString);
+wrapper_message!(BytesValue, ProtobufTypeBytes);
@@ -8,6 +8,11 @@ use std::io;
 use std::io::Read;
 use std::io::Write;
 use std::fs;
+use std::path::Path;
+
+use protobuf::descriptor::FileDescriptorSet;
+use protobuf::descriptor::FileOptions;
+use protobuf::rustproto;
 
 pub use protoc::Error;
 pub use protoc::Result;
@@ -21,6 +26,36 @@ pub struct Args<'a> {
     pub includes: &'a [&'a str],
     /// List of .proto files to compile
     pub input: &'a [&'a str],
+    /// Generate rustdoc from comments in .proto files
+    pub include_source_info: bool,
+    /// Extra derives for all messages, same as `rustproto.message_derive_all` option
+    pub message_derive: &'a [&'a str],
+    /// Extra attributes for all messages, same as `rustproto.message_attr_all` option
+    pub message_attr: &'a [&'a str],
+    /// Extra derives for all enums, same as `rustproto.enum_derive_all` option
+    pub enum_derive: &'a [&'a str],
+    /// Extra attributes for all enums, same as `rustproto.enum_attr_all` option
+    pub enum_attr: &'a [&'a str],
+    /// Generate code for `no_std` crates, same as `rustproto.no_std_all` option
+    pub no_std: bool,
+}
+
+// pass options from args to codegen as rustproto file options
+fn add_file_options(options: &mut FileOptions, args: &Args) {
+    let exts = [
+        (rustproto::exts::message_derive_all, args.message_derive),
+        (rustproto::exts::message_attr_all, args.message_attr),
+        (rustproto::exts::enum_derive_all, args.enum_derive),
+        (rustproto::exts::enum_attr_all, args.enum_attr),
+    ];
+    for &(ref ext, values) in &exts {
+        for value in values {
+            ext.push(options, (*value).to_owned());
+        }
+    }
+    if args.no_std {
+        rustproto::exts::no_std_all.set(options, true);
+    }
 }
 
 /// Like `protoc --rust_out=...` but without requiring `protoc-gen-rust` command in `$PATH`.
@@ -37,6 +72,7 @@ pub fn run(args: Args) -> Result<()> {
         includes: args.includes,
         input: args.input,
         include_imports: true,
+        include_source_info: args.include_source_info,
     })?;
 
     let mut fds = Vec::new();
@@ -46,7 +82,7 @@ pub fn run(args: Args) -> Result<()> {
     drop(file);
     drop(temp_dir);
 
-    let fds: protobuf::descriptor::FileDescriptorSet = protobuf::parse_from_bytes(&fds)
+    let mut fds: FileDescriptorSet = protobuf::parse_from_bytes(&fds)
         .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
 
     let mut includes = args.includes;
@@ -74,11 +110,21 @@ pub fn run(args: Args) -> Result<()> {
         ));
     }
 
+    for file in fds.mut_file().iter_mut() {
+        if files_to_generate.iter().any(|f| f == file.get_name()) {
+            add_file_options(file.mut_options(), &args);
+        }
+    }
+
     let gen_result = protobuf_codegen::gen(fds.get_file(), &files_to_generate);
 
     for r in gen_result {
         let r: protobuf::compiler_plugin::GenResult = r;
         let file = format!("{}/{}", args.out_dir, r.name);
+        if let Some(dir) = Path::new(&file).parent() {
+            // files are generated into subdirectories in module tree mode
+            fs::create_dir_all(dir)?;
+        }
         let mut file = fs::File::create(&file)?;
         file.write_all(&r.content)?;
         file.flush()?;
@@ -151,4 +197,29 @@ mod test {
         assert_eq!(None, super::remove_path_prefix("xxx/abc.proto", "yyy"));
         assert_eq!(None, super::remove_path_prefix("xxx/abc.proto", "yyy/"));
     }
+
+    #[test]
+    fn add_file_options() {
+        use protobuf::descriptor::FileOptions;
+        use protobuf::rustproto;
+
+        let mut options = FileOptions::new();
+        rustproto::exts::message_derive_all.push(&mut options, "Eq".to_owned());
+        super::add_file_options(&mut options, &super::Args {
+            message_derive: &["Hash"],
+            enum_attr: &["repr(i32)"],
+            no_std: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            vec!["Eq".to_owned(), "Hash".to_owned()],
+            rustproto::exts::message_derive_all.get(&options)
+        );
+        assert_eq!(
+            vec!["repr(i32)".to_owned()],
+            rustproto::exts::enum_attr_all.get(&options)
+        );
+        assert!(rustproto::exts::message_attr_all.get(&options).is_empty());
+        assert_eq!(Some(true), rustproto::exts::no_std_all.get(&options));
+    }
 }
@@ -40,6 +40,8 @@ pub struct DescriptorSetOutArgs<'a> {
     pub input: &'a [&'a str],
     /// `--include_imports`
     pub include_imports: bool,
+    /// `--include_source_info`
+    pub include_source_info: bool,
 }
 
 
@@ -170,6 +172,10 @@ impl Protoc {
             cmd_args.push("--include_imports".to_owned());
         }
 
+        if args.include_source_info {
+            cmd_args.push("--include_source_info".to_owned());
+        }
+
         if args.input.is_empty() {
             return err_other("input is empty");
         }